@@ -96,6 +96,10 @@ pub enum BackupError {
     #[error("File collection failed: {0}")]
     CollectFailed(String),
 
+    /// Writing the checksum manifest failed
+    #[error("Checksum manifest failed: {0}")]
+    Manifest(String),
+
     /// JSON serialization or deserialization error
     #[error("Serialization error: {0}")]
     Serde(#[from] serde_json::Error),
@@ -108,6 +112,14 @@ pub enum BackupError {
     #[error("Backup job not found")]
     JobNotFound,
 
+    /// Running backup was cancelled before it finished
+    #[error("Backup cancelled")]
+    Cancelled,
+
+    /// Destination free space (in bytes) fell below the safety margin mid-backup
+    #[error("Insufficient disk space: {0} bytes free on the destination")]
+    InsufficientSpace(u64),
+
     /// Tried to cancel a non-pending job
     #[error("Can only cancel pending backups")]
     NotPending,
@@ -141,6 +153,30 @@ pub enum DeliveryError {
     /// Path prefix strip failed
     #[error("Path error: {0}")]
     PathError(String),
+
+    /// Writing the delivery `.zip` failed
+    #[error("Zip error: {0}")]
+    Zip(#[from] zip::result::ZipError),
+
+    /// Rewriting image metadata failed
+    #[error("Metadata error: {0}")]
+    Metadata(String),
+
+    /// Resizing or re-encoding an image for export failed
+    #[error("Export error: {0}")]
+    Export(String),
+
+    /// Running delivery was cancelled before it finished
+    #[error("Delivery cancelled")]
+    Cancelled,
+
+    /// Writing a file through the delivery's storage backend failed
+    #[error("Storage error: {0}")]
+    Storage(String),
+
+    /// JSON serialization or deserialization error
+    #[error("Serialization error: {0}")]
+    Serde(#[from] serde_json::Error),
 }
 
 impl From<DeliveryError> for String {
@@ -171,6 +207,14 @@ pub enum ImportError {
     /// Underlying file copy returned an error
     #[error("File copy failed: {0}")]
     CopyFailed(String),
+
+    /// Source file doesn't exist, e.g. the card was removed or the file deleted
+    #[error("Source file not found")]
+    SourceNotFound,
+
+    /// Copied file's checksum differs from its source, e.g. after a card read error
+    #[error("Checksum mismatch after copy")]
+    ChecksumMismatch,
 }
 
 impl From<ImportError> for String {
@@ -202,6 +246,10 @@ pub enum GoogleDriveError {
     #[error("Encryption error: {0}")]
     Crypto(String),
 
+    /// OS keychain unavailable or rejected the request
+    #[error("Keychain error: {0}")]
+    Keychain(String),
+
     /// Mutex lock acquisition failed
     #[error("Failed to acquire lock")]
     LockFailed,
@@ -274,6 +322,10 @@ mod tests {
             BackupError::InProgress.to_string(),
             "Cannot remove in-progress backup"
         );
+        assert_eq!(
+            BackupError::InsufficientSpace(1024).to_string(),
+            "Insufficient disk space: 1024 bytes free on the destination"
+        );
     }
 
     #[test]
@@ -9,14 +9,18 @@ mod utils;
 
 // Re-export _impl functions for testing
 #[doc(hidden)]
-pub use modules::archive::{create_archive_impl, get_archive_queue_impl, remove_archive_job_impl};
+pub use modules::archive::{
+    cancel_archive_impl, create_archive_impl, get_archive_queue_impl, remove_archive_job_impl,
+    ArchiveOptions,
+};
 #[doc(hidden)]
 pub use modules::backup::{
     cancel_backup_impl, get_backup_queue_impl, queue_backup_impl, remove_backup_job_impl,
+    start_backup_blocking_impl,
 };
 #[doc(hidden)]
 pub use modules::delivery::{
-    create_delivery_impl, get_delivery_queue_impl, remove_delivery_job_impl,
+    create_delivery_impl, get_delivery_queue_impl, remove_delivery_job_impl, DeliveryOptions,
 };
 #[doc(hidden)]
 pub use modules::file_copy::cancel_import_impl;
@@ -24,37 +28,66 @@ pub use modules::file_copy::cancel_import_impl;
 /// Result type for application-level operations
 pub type AppResult = Result<(), Box<dyn std::error::Error>>;
 
-use modules::archive::{create_archive, get_archive_queue, remove_archive_job, start_archive};
+/// How long running jobs get to stop after being cancelled on exit.
+const SHUTDOWN_GRACE: std::time::Duration = std::time::Duration::from_secs(3);
+
+use modules::archive::{
+    cancel_archive, create_archive, get_archive_queue, persist_archive_queue, remove_archive_job,
+    restore_archive, restore_archive_queue, start_archive,
+};
 use modules::backup::{
-    cancel_backup, get_backup_history, get_backup_queue, get_project_backup_history, queue_backup,
-    remove_backup_job, start_backup,
+    cancel_backup, cancel_backup_group, get_backup_group, get_backup_history, get_backup_queue,
+    get_project_backup_history, queue_backup, queue_backup_multi, remove_backup_job, start_backup,
+    start_backup_blocking, verify_backup,
 };
+use modules::checksums::verify_manifest;
 use modules::client::{
     create_client, delete_client, get_client, list_clients, migrate_clients_from_projects,
     run_client_migration, search_clients, update_client, update_client_status,
 };
+use modules::contact_sheet::create_contact_sheet;
+use modules::db::{backup_database, restore_database};
 use modules::delivery::{
-    create_delivery, get_delivery_queue, list_project_files, remove_delivery_job, start_delivery,
+    create_delivery, get_delivery_history, get_delivery_queue, get_project_delivery_history,
+    list_project_files, persist_delivery_queue, remove_delivery_job, restore_delivery_queue,
+    retry_delivery, select_files_by_pattern, start_delivery, validate_naming_template,
 };
-use modules::file_copy::{cancel_import, copy_files};
+use modules::disk::{compute_folder_size, get_storage_report, list_volumes};
+use modules::duplicates::find_duplicate_files;
+use modules::file_copy::{cancel_import, copy_files, get_supported_extensions};
 use modules::file_system::{
-    open_in_aftershoot, open_in_davinci_resolve, open_in_final_cut_pro, open_in_lightroom,
-    reveal_in_finder,
+    is_application_installed, open_in_aftershoot, open_in_application, open_in_davinci_resolve,
+    open_in_final_cut_pro, open_in_lightroom, reveal_in_finder, reveal_project_folder,
 };
 use modules::file_utils::get_home_directory;
 use modules::google_drive::{
-    complete_google_drive_auth, get_google_drive_account, remove_google_drive_account,
-    set_drive_parent_folder, start_google_drive_auth, test_google_drive_connection,
-    upload_to_google_drive,
+    cancel_drive_upload, complete_google_drive_auth, deliver_to_google_drive,
+    get_drive_upload_queue, get_google_drive_account, list_drive_folders, pause_drive_upload,
+    remove_google_drive_account, resume_drive_upload, set_drive_parent_folder,
+    start_google_drive_auth, test_google_drive_connection, upload_to_google_drive,
 };
 use modules::import_history::{
-    get_import_history, get_project_import_history, save_import_history,
+    delete_import_history_entry, export_import_history_csv, get_import_history,
+    get_import_statistics, get_project_import_history, save_import_history,
 };
+use modules::job_log::get_job_log;
+use modules::metadata::read_exif;
 use modules::project::{
-    create_project, delete_project, get_project, list_projects, refresh_projects,
-    update_project_deadline, update_project_status,
+    add_project_tag, create_project, delete_project, get_project, get_project_by_folder,
+    list_project_tags, list_projects, move_project, refresh_projects, remove_project_tag,
+    search_projects, update_project_client, update_project_deadline, update_project_status,
+    update_projects_status,
+};
+use modules::queue::{get_pending_workload, get_queue_summary};
+use modules::sd_card::{
+    diff_sd_card_against, eject_sd_card, list_sd_card_files, scan_sd_cards, verify_sd_card,
+};
+use modules::settings::{
+    add_shoot_type, get_app_config, get_default_conflict_mode, get_setting, list_shoot_types,
+    remove_shoot_type, set_default_conflict_mode, set_setting,
 };
-use modules::sd_card::{eject_sd_card, list_sd_card_files, scan_sd_cards};
+use modules::thumbnail::generate_thumbnail;
+use tauri::Manager;
 
 /// Run the Tauri application
 ///
@@ -83,6 +116,15 @@ pub fn run() -> AppResult {
     // Initialize application state
     let app_state = state::AppState::default();
 
+    // Reload deliveries and archives from the previous session; interrupted ones are
+    // marked failed
+    if let Err(e) = restore_delivery_queue(&app_state.delivery_queue) {
+        log::warn!("Failed to restore delivery queue: {e}");
+    }
+    if let Err(e) = restore_archive_queue(&app_state.archive_queue) {
+        log::warn!("Failed to restore archive queue: {e}");
+    }
+
     tauri::Builder::default()
         .manage(db)
         .manage(app_state)
@@ -92,9 +134,15 @@ pub fn run() -> AppResult {
         .invoke_handler(tauri::generate_handler![
             scan_sd_cards,
             list_sd_card_files,
+            diff_sd_card_against,
+            read_exif,
+            generate_thumbnail,
+            create_contact_sheet,
+            verify_sd_card,
             eject_sd_card,
             copy_files,
             cancel_import,
+            get_supported_extensions,
             create_client,
             list_clients,
             get_client,
@@ -106,44 +154,113 @@ pub fn run() -> AppResult {
             create_project,
             list_projects,
             get_project,
+            get_project_by_folder,
+            move_project,
+            find_duplicate_files,
             refresh_projects,
             update_project_status,
+            update_projects_status,
+            update_project_client,
             update_project_deadline,
             delete_project,
+            search_projects,
+            add_project_tag,
+            remove_project_tag,
+            list_project_tags,
             save_import_history,
             get_import_history,
             get_project_import_history,
+            delete_import_history_entry,
+            export_import_history_csv,
+            get_import_statistics,
             queue_backup,
+            queue_backup_multi,
             get_backup_queue,
+            get_backup_group,
             start_backup,
+            start_backup_blocking,
             cancel_backup,
+            cancel_backup_group,
             remove_backup_job,
             get_backup_history,
             get_project_backup_history,
+            verify_backup,
+            verify_manifest,
             list_project_files,
+            select_files_by_pattern,
             create_delivery,
             start_delivery,
             get_delivery_queue,
+            get_delivery_history,
+            get_project_delivery_history,
+            validate_naming_template,
             remove_delivery_job,
+            retry_delivery,
             create_archive,
             start_archive,
             get_archive_queue,
+            get_queue_summary,
+            get_pending_workload,
+            get_job_log,
             remove_archive_job,
+            cancel_archive,
+            restore_archive,
+            get_storage_report,
+            list_volumes,
+            compute_folder_size,
             reveal_in_finder,
+            reveal_project_folder,
+            is_application_installed,
+            open_in_application,
             open_in_lightroom,
             open_in_aftershoot,
             open_in_davinci_resolve,
             open_in_final_cut_pro,
             get_home_directory,
+            get_setting,
+            set_setting,
+            list_shoot_types,
+            add_shoot_type,
+            remove_shoot_type,
+            get_app_config,
+            get_default_conflict_mode,
+            set_default_conflict_mode,
+            backup_database,
+            restore_database,
             start_google_drive_auth,
             complete_google_drive_auth,
             get_google_drive_account,
             set_drive_parent_folder,
+            list_drive_folders,
             remove_google_drive_account,
             test_google_drive_connection,
             upload_to_google_drive,
+            deliver_to_google_drive,
+            get_drive_upload_queue,
+            cancel_drive_upload,
+            pause_drive_upload,
+            resume_drive_upload,
         ])
-        .run(tauri::generate_context!())?;
+        .build(tauri::generate_context!())?
+        .run(|app_handle, event| {
+            if let tauri::RunEvent::Exit = event {
+                shut_down_jobs(app_handle);
+            }
+        });
 
     Ok(())
 }
+
+/// Cancel running jobs before the app exits so no copy is abandoned mid-file, then
+/// save the queues with those jobs marked cancelled.
+fn shut_down_jobs(app_handle: &tauri::AppHandle) {
+    let state = app_handle.state::<state::AppState>();
+    tauri::async_runtime::block_on(async {
+        let cancelled = state.cancel_all_jobs(SHUTDOWN_GRACE).await;
+        if cancelled > 0 {
+            log::info!("Cancelled {cancelled} running jobs on exit");
+        }
+        persist_delivery_queue(&state.delivery_queue).await;
+        persist_archive_queue(&state.archive_queue).await;
+    });
+}
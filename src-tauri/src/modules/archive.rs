@@ -1,16 +1,66 @@
 //! Archive module for moving completed projects to long-term storage.
 //!
 //! Provides job queue management and background processing for archiving project
-//! directories. Supports optional compression (planned) and emits progress events
-//! via Tauri for real-time UI updates.
-
-use crate::modules::file_utils::{count_files_and_size, get_timestamp};
+//! directories, either moved as-is or compressed into a single (optionally
+//! encrypted) file. Emits progress events via Tauri for real-time UI updates.
+
+use crate::error::AppError;
+use crate::modules::checksums::{
+    manifest_files, manifest_line, parse_manifest, write_manifest, HashingReader, ManifestEntries,
+    MANIFEST_FILE_NAME,
+};
+use crate::modules::db::Database;
+use crate::modules::file_system::move_to_trash;
+use crate::modules::file_utils::{
+    count_files_and_size, get_home_dir, get_timestamp, read_json_with_backup, write_json_atomic,
+    TransferRate,
+};
+use crate::modules::notifications::{archive_notification, notify_job_finished};
+use crate::modules::project::{
+    get_project_by_id, update_projects_status_in_db, Project, ProjectStatus,
+};
+use crate::utils::crypto;
+use rand::RngExt;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::fs;
-use std::path::Path;
-use tauri::Emitter;
+use std::io::{BufReader, BufWriter, Read, Write};
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use tauri::{Emitter, Manager};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 use walkdir::WalkDir;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
+
+/// Bytes read from a file between two `archive-progress` events.
+const PROGRESS_INTERVAL: u64 = 4 * 1024 * 1024;
+/// zstd level used for `.tar.zst` archives (the library default).
+const ZSTD_LEVEL: i32 = 3;
+/// File under `~/CreatorOps` the archive queue is persisted to.
+const QUEUE_FILE_NAME: &str = "archive_queue.json";
+/// Error recorded on jobs that were running when the app quit.
+const INTERRUPTED_MESSAGE: &str = "Interrupted by app restart";
+/// Error recorded on encrypted jobs restored without their password.
+const PASSWORD_LOST_MESSAGE: &str =
+    "Archive password is not kept across restarts; create the archive again";
+/// Error a cancelled job stops with.
+const CANCELLED_MESSAGE: &str = "Archive cancelled";
+/// Entry holding the archived project's database row, read back on restore.
+const PROJECT_FILE_NAME: &str = "project.json";
+/// Suffix appended to password-protected archives, after the format extension.
+const ENCRYPTED_SUFFIX: &str = ".enc";
+/// Leading bytes of a password-protected archive (format version 1).
+const ENCRYPTED_MAGIC: &[u8; 8] = b"COARCHV1";
+/// Length of the random salt stored after the magic bytes.
+const SALT_LEN: usize = 16;
+/// Plaintext bytes sealed per chunk of a password-protected archive.
+const ENCRYPTED_CHUNK_SIZE: usize = 4 * 1024 * 1024;
+/// Per-chunk plaintext header: chunk index (u64) and a last-chunk flag.
+const CHUNK_HEADER_LEN: usize = 9;
+/// AES-GCM nonce and tag added to every sealed chunk.
+const SEAL_OVERHEAD: usize = 12 + 16;
 
 /// Represents a queued or running archive operation for a project.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,7 +72,15 @@ pub struct ArchiveJob {
     pub source_path: String,
     pub archive_path: String,
     pub compress: bool,
-    pub compression_format: Option<String>,
+    /// Artifact format when `compress` is set; zip when not given.
+    pub compression_format: Option<ArchiveFormat>,
+    /// Password the compressed archive is encrypted with; never sent to the UI.
+    #[serde(skip)]
+    pub password: Option<String>,
+    /// Move the project folder to the Trash and mark the project archived once the
+    /// archive is verified.
+    #[serde(default)]
+    pub and_remove_source: bool,
     pub status: ArchiveStatus,
     pub total_files: usize,
     pub files_archived: usize,
@@ -34,6 +92,55 @@ pub struct ArchiveJob {
     pub error_message: Option<String>,
 }
 
+/// Compressed artifact written by a compressed archive job.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ArchiveFormat {
+    #[default]
+    Zip,
+    TarGz,
+    TarZst,
+}
+
+impl ArchiveFormat {
+    /// File extension of the artifact, without the leading dot.
+    pub const fn extension(self) -> &'static str {
+        match self {
+            Self::Zip => "zip",
+            Self::TarGz => "tar.gz",
+            Self::TarZst => "tar.zst",
+        }
+    }
+
+    /// Detect the format of an existing archive from its file name, looking
+    /// through the `.enc` suffix of password-protected archives.
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_string_lossy().to_lowercase();
+        let name = name.strip_suffix(ENCRYPTED_SUFFIX).unwrap_or(&name);
+        ARCHIVE_SUFFIXES
+            .iter()
+            .find(|(suffix, _)| name.ends_with(suffix))
+            .map(|&(_, format)| format)
+    }
+}
+
+/// File name suffixes recognised when restoring, with their formats.
+const ARCHIVE_SUFFIXES: [(&str, ArchiveFormat); 4] = [
+    (".zip", ArchiveFormat::Zip),
+    (".tar.gz", ArchiveFormat::TarGz),
+    (".tgz", ArchiveFormat::TarGz),
+    (".tar.zst", ArchiveFormat::TarZst),
+];
+
+/// Whether `path` names a password-protected archive.
+fn is_encrypted_archive(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| {
+        name.to_string_lossy()
+            .to_lowercase()
+            .ends_with(ENCRYPTED_SUFFIX)
+    })
+}
+
 /// Lifecycle state of an archive job.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "lowercase")]
@@ -42,6 +149,7 @@ pub enum ArchiveStatus {
     InProgress,
     Completed,
     Failed,
+    Cancelled,
 }
 
 /// Per-file progress payload emitted as the `archive-progress` Tauri event.
@@ -54,30 +162,96 @@ pub struct ArchiveProgress {
     pub total_files: usize,
     pub bytes_transferred: u64,
     pub total_bytes: u64,
+    /// Average throughput in bytes per second.
+    pub speed: f64,
+    /// Estimated seconds remaining.
+    pub eta: u64,
+}
+
+/// Per-file progress payload emitted as the `archive-restore-progress` Tauri event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreProgress {
+    pub archive_path: String,
+    pub file_name: String,
+    pub files_restored: usize,
+    pub bytes_restored: u64,
+}
+
+/// Outcome of restoring an archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RestoreResult {
+    pub destination: String,
+    pub files_restored: usize,
+    pub bytes_restored: u64,
+    /// Project recreated from a `project.json` found in the archive.
+    pub project: Option<Project>,
+}
+
+/// How an archive job stores the project.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ArchiveOptions {
+    /// Write a single compressed file next to the archive location instead of
+    /// moving the project folder there.
+    #[serde(default)]
+    pub compress: bool,
+    /// Format of a compressed archive; zip when unset.
+    #[serde(default)]
+    pub compression_format: Option<ArchiveFormat>,
+    /// Password to encrypt a compressed archive with.
+    #[serde(default)]
+    pub password: Option<String>,
+    /// Move the project folder to the Trash and mark the project archived once
+    /// the archive has been verified.
+    #[serde(default)]
+    pub and_remove_source: bool,
 }
 
 /// Core logic for creating an archive job (testable)
 ///
 /// # Errors
 ///
-/// Returns error if source path doesn't exist or job creation fails
+/// Returns error if source path doesn't exist, job creation fails, or a password
+/// is given for an uncompressed archive
 pub async fn create_archive_impl(
     archive_queue: &crate::state::ArchiveQueue,
     project_id: String,
     project_name: String,
     source_path: String,
     archive_location: String,
-    compress: bool,
-    compression_format: Option<String>,
+    options: ArchiveOptions,
 ) -> Result<ArchiveJob, String> {
+    let ArchiveOptions {
+        compress,
+        compression_format,
+        password,
+        and_remove_source,
+    } = options;
+    let password = password.filter(|p| !p.is_empty());
+    if password.is_some() && !compress {
+        return Err("Password protection requires a compressed archive".to_owned());
+    }
+
     let id = Uuid::new_v4().to_string();
     let now = get_timestamp();
 
     // Calculate total size and count files
     let (total_files, total_bytes) = count_files_and_size(&source_path)?;
 
-    // Create archive path
-    let archive_path = Path::new(&archive_location).join(&project_name);
+    // Create archive path: a folder for moved projects, a file for compressed ones
+    let archive_path = if compress {
+        let format = compression_format.unwrap_or_default();
+        let suffix = if password.is_some() {
+            ENCRYPTED_SUFFIX
+        } else {
+            ""
+        };
+        Path::new(&archive_location).join(format!("{project_name}.{}{suffix}", format.extension()))
+    } else {
+        Path::new(&archive_location).join(&project_name)
+    };
 
     let job = ArchiveJob {
         id: id.clone(),
@@ -87,6 +261,8 @@ pub async fn create_archive_impl(
         archive_path: archive_path.to_string_lossy().to_string(),
         compress,
         compression_format,
+        password,
+        and_remove_source,
         status: ArchiveStatus::Pending,
         total_files,
         files_archived: 0,
@@ -107,7 +283,10 @@ pub async fn create_archive_impl(
     Ok(job)
 }
 
-/// Create an archive job
+/// Create an archive job, encrypting the compressed archive when a password is given.
+///
+/// With `and_remove_source`, the project folder is moved to the Trash and the
+/// project marked archived after the archive has been verified.
 #[tauri::command]
 pub async fn create_archive(
     state: tauri::State<'_, crate::state::AppState>,
@@ -115,28 +294,39 @@ pub async fn create_archive(
     project_name: String,
     source_path: String,
     archive_location: String,
-    compress: bool,
-    compression_format: Option<String>,
+    options: ArchiveOptions,
 ) -> Result<ArchiveJob, String> {
-    create_archive_impl(
+    let job = create_archive_impl(
         &state.archive_queue,
         project_id,
         project_name,
         source_path,
         archive_location,
-        compress,
-        compression_format,
+        options,
     )
-    .await
+    .await?;
+    persist_archive_queue(&state.archive_queue).await;
+    Ok(job)
 }
 
 /// Start an archive job
 #[tauri::command]
 pub async fn start_archive(
+    db: tauri::State<'_, Database>,
     state: tauri::State<'_, crate::state::AppState>,
     job_id: String,
     app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
+    let project_id = state
+        .archive_queue
+        .lock()
+        .await
+        .get(&job_id)
+        .ok_or("Job not found")?
+        .project_id
+        .clone();
+    let project = archived_project(&db, &project_id)?;
+
     // Get job from queue
     let job = {
         let mut queue = state.archive_queue.lock().await;
@@ -152,66 +342,225 @@ pub async fn start_archive(
         drop(queue);
         job_clone
     };
+    persist_archive_queue(&state.archive_queue).await;
+
+    let cancel = CancellationToken::new();
+    state
+        .archive_tokens
+        .lock()
+        .await
+        .insert(job_id.clone(), cancel.clone());
 
     // Spawn background task
     let archive_queue = state.archive_queue.clone();
+    let archive_tokens = state.archive_tokens.clone();
     tokio::spawn(async move {
-        let result = process_archive(job.clone(), &app_handle, archive_queue.clone()).await;
-
-        // Update job status
-        let mut queue = archive_queue.lock().await;
-        if let Some(job) = queue.get_mut(&job_id) {
-            match result {
-                Ok(()) => {
-                    job.status = ArchiveStatus::Completed;
-                    job.completed_at = Some(get_timestamp());
-                }
-                Err(e) => {
-                    job.status = ArchiveStatus::Failed;
-                    job.error_message = Some(e);
-                    job.completed_at = Some(get_timestamp());
-                }
+        let mark_archived = job.and_remove_source.then(|| job.project_id.clone());
+        let emitter = app_handle.clone();
+        let emit: ProgressEmitter = Arc::new(move |progress| {
+            let _ = emitter.emit("archive-progress", progress);
+        });
+        let trash: SourceTrasher = Arc::new(move_to_trash);
+        let status =
+            run_archive_job(job, project, archive_queue.clone(), cancel, emit, trash).await;
+
+        if let (ArchiveStatus::Completed, Some(project_id)) = (status, mark_archived) {
+            let db = app_handle.state::<Database>();
+            if let Err(e) =
+                update_projects_status_in_db(&db, &[project_id], &ProjectStatus::Archived)
+            {
+                log::warn!("Failed to mark archived project: {e}");
             }
         }
+
+        archive_tokens.lock().await.remove(&job_id);
+        persist_archive_queue(&archive_queue).await;
+
+        let notification = archive_queue
+            .lock()
+            .await
+            .get(&job_id)
+            .and_then(archive_notification);
+        notify_job_finished(&app_handle, notification);
     });
 
     Ok(())
 }
 
+/// The database row of the project being archived, or `None` when the job
+/// archives a folder that isn't a known project.
+///
+/// # Errors
+///
+/// Returns error if the database query fails
+fn archived_project(db: &Database, project_id: &str) -> Result<Option<Project>, String> {
+    match get_project_by_id(db, project_id) {
+        Ok(project) => Ok(Some(project)),
+        Err(AppError::ProjectNotFound { .. }) => Ok(None),
+        Err(e) => Err(e.into()),
+    }
+}
+
+/// Sink for `archive-progress` payloads.
+type ProgressEmitter = Arc<dyn Fn(&ArchiveProgress) + Send + Sync>;
+
+/// Moves a verified project folder out of the way for `and_remove_source` jobs.
+type SourceTrasher = Arc<dyn Fn(&Path) -> Result<(), String> + Send + Sync>;
+
+/// Run an archive job and record its final status in `archive_queue`.
+///
+/// A compressed archive carries `project`, when given, as its `project.json`.
+/// Cancelling `cancel` stops the job before its next file and removes the partial
+/// artifact; the job then ends as [`ArchiveStatus::Cancelled`].
+async fn run_archive_job(
+    job: ArchiveJob,
+    project: Option<Project>,
+    archive_queue: crate::state::ArchiveQueue,
+    cancel: CancellationToken,
+    emit: ProgressEmitter,
+    trash: SourceTrasher,
+) -> ArchiveStatus {
+    let job_id = job.id.clone();
+    let result = process_archive(job, project, archive_queue.clone(), &cancel, emit, trash).await;
+
+    let mut queue = archive_queue.lock().await;
+    let Some(job) = queue.get_mut(&job_id) else {
+        return ArchiveStatus::Cancelled;
+    };
+    match result {
+        _ if cancel.is_cancelled() => job.status = ArchiveStatus::Cancelled,
+        Ok(()) => job.status = ArchiveStatus::Completed,
+        Err(e) => {
+            job.status = ArchiveStatus::Failed;
+            job.error_message = Some(e);
+        }
+    }
+    if job.completed_at.is_none() {
+        job.completed_at = Some(get_timestamp());
+    }
+    let status = job.status.clone();
+    drop(queue);
+    status
+}
+
+/// Archive a job's project: uncompressed jobs move the folder to the archive
+/// location, compressed ones write a single `.zip`, `.tar.gz` or `.tar.zst` (with
+/// an extra `.enc` suffix when encrypted) and leave the source in place. Either way
+/// the archived files get a `checksums.sha256` manifest.
+///
+/// With `and_remove_source`, the finished archive is verified against its manifest
+/// before the source goes to the Trash and the project is marked `Archived`; a
+/// failed job never touches the source.
 async fn process_archive(
     mut job: ArchiveJob,
-    app_handle: &tauri::AppHandle,
+    project: Option<Project>,
     archive_queue: crate::state::ArchiveQueue,
+    cancel: &CancellationToken,
+    emit: ProgressEmitter,
+    trash: SourceTrasher,
 ) -> Result<(), String> {
-    let source_path_str = job.source_path.clone();
-    let archive_path_str = job.archive_path.clone();
-    let source_path = Path::new(&source_path_str);
-    let archive_path = Path::new(&archive_path_str);
+    let source_path = PathBuf::from(&job.source_path);
+    let archive_path = PathBuf::from(&job.archive_path);
+    let mut rate = TransferRate::new();
 
     if job.compress {
-        // Compression not implemented in MVP - just move files
-        // TODO: Implement zip/tar compression in future phase
-        return Err("Compression not yet implemented".to_owned());
+        let format = job.compression_format.unwrap_or_default();
+        let password = job.password.clone();
+        let cancel = cancel.clone();
+
+        return tokio::task::spawn_blocking(move || {
+            let on_progress = |file_name: &str, current_file, bytes| {
+                job.files_archived = current_file;
+                job.bytes_transferred = bytes;
+
+                if let Some(q_job) = archive_queue.blocking_lock().get_mut(&job.id) {
+                    q_job.files_archived = job.files_archived;
+                    q_job.bytes_transferred = job.bytes_transferred;
+                }
+
+                emit(&archive_progress(&job, file_name, &mut rate));
+            };
+
+            let result = match &password {
+                Some(password) => write_encrypted_archive(
+                    &source_path,
+                    &archive_path,
+                    format,
+                    project.as_ref(),
+                    password,
+                    &cancel,
+                    on_progress,
+                ),
+                None => write_archive(
+                    &source_path,
+                    &archive_path,
+                    format,
+                    project.as_ref(),
+                    &cancel,
+                    on_progress,
+                ),
+            };
+            if result.is_err() {
+                let _ = fs::remove_file(&archive_path);
+            }
+            result?;
+
+            if job.and_remove_source {
+                verify_archive(&archive_path, password.as_deref()).map_err(|e| {
+                    format!("Archive verification failed; the project folder was kept: {e}")
+                })?;
+                trash(&source_path).map_err(|e| {
+                    format!("Archive created, but the project folder could not be moved to the Trash: {e}")
+                })?;
+            }
+            Ok(())
+        })
+        .await
+        .map_err(|e| format!("Archive task failed: {e}"))?;
     }
-    // Move entire directory to archive location
-    move_directory_recursive(
-        source_path,
-        archive_path,
+
+    // Move entire directory to archive location, dropping a partial copy we created
+    let existed = archive_path.exists();
+    let result = move_directory_recursive(
+        &source_path,
+        &archive_path,
         &mut job,
-        app_handle,
         &archive_queue,
+        cancel,
+        &emit,
+        &mut rate,
     )
-    .await?;
+    .await;
+    if result.is_err() && !existed {
+        let _ = fs::remove_dir_all(&archive_path);
+    }
 
-    Ok(())
+    result
+}
+
+/// Progress payload for the job's current counters.
+fn archive_progress(job: &ArchiveJob, file_name: &str, rate: &mut TransferRate) -> ArchiveProgress {
+    let (speed, eta) = rate.update(job.bytes_transferred, job.total_bytes);
+    ArchiveProgress {
+        job_id: job.id.clone(),
+        file_name: file_name.to_owned(),
+        current_file: job.files_archived,
+        total_files: job.total_files,
+        bytes_transferred: job.bytes_transferred,
+        total_bytes: job.total_bytes,
+        speed,
+        eta,
+    }
 }
 
 async fn move_directory_recursive(
     source: &Path,
     dest: &Path,
     job: &mut ArchiveJob,
-    app_handle: &tauri::AppHandle,
     archive_queue: &crate::state::ArchiveQueue,
+    cancel: &CancellationToken,
+    emit: &ProgressEmitter,
+    rate: &mut TransferRate,
 ) -> Result<(), String> {
     // Create destination directory
     fs::create_dir_all(dest).map_err(|e| e.to_string())?;
@@ -228,6 +577,10 @@ async fn move_directory_recursive(
         if entry.file_type().is_dir() {
             fs::create_dir_all(&dest_path).map_err(|e| e.to_string())?;
         } else if entry.file_type().is_file() {
+            if cancel.is_cancelled() {
+                return Err(CANCELLED_MESSAGE.to_owned());
+            }
+
             // Copy file
             fs::copy(path, &dest_path).map_err(|e| e.to_string())?;
 
@@ -248,28 +601,658 @@ async fn move_directory_recursive(
             let file_name = path
                 .file_name()
                 .and_then(|n| n.to_str())
-                .unwrap_or("unknown")
-                .to_owned();
-
-            let progress = ArchiveProgress {
-                job_id: job.id.clone(),
-                file_name,
-                current_file: job.files_archived,
-                total_files: job.total_files,
-                bytes_transferred: job.bytes_transferred,
-                total_bytes: job.total_bytes,
-            };
-
-            let _ = app_handle.emit("archive-progress", &progress);
+                .unwrap_or("unknown");
+            emit(&archive_progress(job, file_name, rate));
         }
     }
 
+    // Record digests of the copy before the original goes away
+    let manifest_root = dest.to_path_buf();
+    tokio::task::spawn_blocking(move || write_manifest(&manifest_root))
+        .await
+        .map_err(|e| e.to_string())??;
+
     // After successful copy, remove source directory
     fs::remove_dir_all(source).map_err(|e| e.to_string())?;
 
     Ok(())
 }
 
+/// Open archive being written in one of the supported formats.
+enum ArchiveWriter {
+    Zip(ZipWriter<fs::File>),
+    TarGz(tar::Builder<flate2::write::GzEncoder<fs::File>>),
+    TarZst(tar::Builder<zstd::Encoder<'static, fs::File>>),
+}
+
+impl ArchiveWriter {
+    fn new(file: fs::File, format: ArchiveFormat) -> std::io::Result<Self> {
+        Ok(match format {
+            ArchiveFormat::Zip => Self::Zip(ZipWriter::new(file)),
+            ArchiveFormat::TarGz => Self::TarGz(tar::Builder::new(flate2::write::GzEncoder::new(
+                file,
+                flate2::Compression::default(),
+            ))),
+            ArchiveFormat::TarZst => {
+                Self::TarZst(tar::Builder::new(zstd::Encoder::new(file, ZSTD_LEVEL)?))
+            }
+        })
+    }
+
+    /// Add a file of `size` bytes read from `reader` as `name`.
+    fn append(&mut self, name: &str, size: u64, reader: &mut impl Read) -> Result<(), String> {
+        match self {
+            Self::Zip(zip) => {
+                let options = SimpleFileOptions::default()
+                    .compression_method(CompressionMethod::Deflated)
+                    .large_file(size >= u64::from(u32::MAX));
+                zip.start_file(name, options).map_err(|e| e.to_string())?;
+                std::io::copy(reader, zip).map_err(|e| e.to_string())?;
+            }
+            Self::TarGz(builder) => append_tar_entry(builder, name, size, reader)?,
+            Self::TarZst(builder) => append_tar_entry(builder, name, size, reader)?,
+        }
+        Ok(())
+    }
+
+    /// Write the archive trailer and flush everything to disk.
+    fn finish(self) -> Result<(), String> {
+        match self {
+            Self::Zip(zip) => zip
+                .finish()
+                .map_err(|e| e.to_string())?
+                .sync_all()
+                .map_err(|e| e.to_string()),
+            Self::TarGz(builder) => builder
+                .into_inner()
+                .and_then(flate2::write::GzEncoder::finish)
+                .and_then(|file| file.sync_all())
+                .map_err(|e| e.to_string()),
+            Self::TarZst(builder) => builder
+                .into_inner()
+                .and_then(zstd::Encoder::finish)
+                .and_then(|file| file.sync_all())
+                .map_err(|e| e.to_string()),
+        }
+    }
+}
+
+fn append_tar_entry<W: Write>(
+    builder: &mut tar::Builder<W>,
+    name: &str,
+    size: u64,
+    reader: &mut impl Read,
+) -> Result<(), String> {
+    let mut header = tar::Header::new_gnu();
+    header.set_size(size);
+    header.set_mode(0o644);
+    header.set_mtime(
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs()),
+    );
+    builder
+        .append_data(&mut header, name, reader)
+        .map_err(|e| e.to_string())
+}
+
+/// Reader that reports every read to a callback.
+struct ProgressReader<R, F> {
+    inner: R,
+    on_read: F,
+}
+
+impl<R: Read, F: FnMut(usize)> Read for ProgressReader<R, F> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        (self.on_read)(bytes_read);
+        Ok(bytes_read)
+    }
+}
+
+/// Write every file under `source` into a compressed archive at `dest`, with
+/// entries under a top-level folder named after `source` and a `checksums.sha256`
+/// manifest of their digests next to them. When `project` is given its database
+/// row goes in as `project.json`, in place of any such file in `source`, so the
+/// restore can register the project again.
+///
+/// `on_progress` receives the current file name, its 1-based position and the
+/// bytes processed so far across the whole archive, at least once per file.
+/// Once `cancel` is cancelled, stops with an error before the next file.
+fn write_archive<F>(
+    source: &Path,
+    dest: &Path,
+    format: ArchiveFormat,
+    project: Option<&Project>,
+    cancel: &CancellationToken,
+    mut on_progress: F,
+) -> Result<(), String>
+where
+    F: FnMut(&str, usize, u64),
+{
+    let root = source
+        .file_name()
+        .map_or_else(|| "archive".to_owned(), |n| n.to_string_lossy().to_string());
+
+    let project_file = source.join(PROJECT_FILE_NAME);
+    let mut files = manifest_files(source);
+    if project.is_some() {
+        files.retain(|path| *path != project_file);
+    }
+
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut writer = ArchiveWriter::new(fs::File::create(dest).map_err(|e| e.to_string())?, format)
+        .map_err(|e| e.to_string())?;
+
+    let mut bytes_processed = 0;
+    let mut manifest = String::new();
+    for (index, path) in files.iter().enumerate() {
+        if cancel.is_cancelled() {
+            return Err(CANCELLED_MESSAGE.to_owned());
+        }
+
+        let relative = path.strip_prefix(source).map_err(|e| e.to_string())?;
+        let entry_name = Path::new(&root)
+            .join(relative)
+            .to_string_lossy()
+            .replace('\\', "/");
+        let file_name = path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown")
+            .to_owned();
+
+        let file = fs::File::open(path).map_err(|e| e.to_string())?;
+        let size = file.metadata().map_err(|e| e.to_string())?.len();
+
+        let mut last_reported = bytes_processed;
+        let mut reader = ProgressReader {
+            inner: HashingReader::new(file),
+            on_read: |bytes_read: usize| {
+                bytes_processed += bytes_read as u64;
+                if bytes_processed - last_reported >= PROGRESS_INTERVAL {
+                    last_reported = bytes_processed;
+                    on_progress(&file_name, index + 1, bytes_processed);
+                }
+            },
+        };
+        writer.append(&entry_name, size, &mut reader)?;
+        manifest.push_str(&manifest_line(&reader.inner.finish(), relative));
+
+        on_progress(&file_name, index + 1, bytes_processed);
+    }
+
+    if let Some(project) = project {
+        let json = serde_json::to_vec_pretty(project).map_err(|e| e.to_string())?;
+        let mut reader = HashingReader::new(json.as_slice());
+        writer.append(
+            &format!("{root}/{PROJECT_FILE_NAME}"),
+            json.len() as u64,
+            &mut reader,
+        )?;
+        manifest.push_str(&manifest_line(
+            &reader.finish(),
+            Path::new(PROJECT_FILE_NAME),
+        ));
+    }
+
+    let manifest_entry = format!("{root}/{MANIFEST_FILE_NAME}");
+    writer.append(
+        &manifest_entry,
+        manifest.len() as u64,
+        &mut manifest.as_bytes(),
+    )?;
+    writer.finish()
+}
+
+/// Write the archive to a temporary file next to `dest`, then seal it into `dest`
+/// with a key derived from `password`. The unencrypted copy is always removed.
+fn write_encrypted_archive<F>(
+    source: &Path,
+    dest: &Path,
+    format: ArchiveFormat,
+    project: Option<&Project>,
+    password: &str,
+    cancel: &CancellationToken,
+    on_progress: F,
+) -> Result<(), String>
+where
+    F: FnMut(&str, usize, u64),
+{
+    let plain = dest.with_file_name(format!(".{}.{}", Uuid::new_v4(), format.extension()));
+
+    let result = write_archive(source, &plain, format, project, cancel, on_progress)
+        .and_then(|()| encrypt_archive_file(&plain, dest, password));
+    let _ = fs::remove_file(&plain);
+
+    if result.is_err() {
+        let _ = fs::remove_file(dest);
+    }
+    result
+}
+
+/// Encrypt `plain` into `dest`: the magic bytes and salt, then one
+/// length-prefixed sealed chunk per [`ENCRYPTED_CHUNK_SIZE`] bytes of input.
+///
+/// Chunks are sealed with AES-256-GCM under a key derived from the password with
+/// Argon2id, and each carries its index so reordered or truncated files fail to
+/// decrypt instead of yielding partial projects.
+fn encrypt_archive_file(plain: &Path, dest: &Path, password: &str) -> Result<(), String> {
+    let salt: [u8; SALT_LEN] = rand::rng().random();
+    let key = crypto::derive_key_from_password(password, &salt)?;
+
+    let mut input = fs::File::open(plain).map_err(|e| e.to_string())?;
+    let total = input.metadata().map_err(|e| e.to_string())?.len();
+    let chunk_count = total.div_ceil(ENCRYPTED_CHUNK_SIZE as u64).max(1);
+
+    let mut output = BufWriter::new(fs::File::create(dest).map_err(|e| e.to_string())?);
+    output
+        .write_all(ENCRYPTED_MAGIC)
+        .and_then(|()| output.write_all(&salt))
+        .map_err(|e| e.to_string())?;
+
+    for index in 0..chunk_count {
+        let mut chunk = index.to_be_bytes().to_vec();
+        chunk.push(u8::from(index + 1 == chunk_count));
+        (&mut input)
+            .take(ENCRYPTED_CHUNK_SIZE as u64)
+            .read_to_end(&mut chunk)
+            .map_err(|e| e.to_string())?;
+
+        let sealed = crypto::encrypt_data(&chunk, &key)?;
+        let sealed_len = u32::try_from(sealed.len()).map_err(|e| e.to_string())?;
+        output
+            .write_all(&sealed_len.to_be_bytes())
+            .and_then(|()| output.write_all(&sealed))
+            .map_err(|e| e.to_string())?;
+    }
+
+    output
+        .into_inner()
+        .map_err(|e| e.to_string())?
+        .sync_all()
+        .map_err(|e| e.to_string())
+}
+
+/// Decrypt a password-protected archive written by [`encrypt_archive_file`].
+///
+/// A first chunk that fails to authenticate means the password is wrong; later
+/// failures, reordered chunks or a missing last chunk mean the file is damaged.
+fn decrypt_archive_file(encrypted: &Path, dest: &Path, password: &str) -> Result<(), String> {
+    const TRUNCATED: &str = "Encrypted archive is truncated";
+    const CORRUPTED: &str = "Encrypted archive is corrupted";
+
+    let mut input = BufReader::new(fs::File::open(encrypted).map_err(|e| e.to_string())?);
+
+    let mut magic = [0_u8; ENCRYPTED_MAGIC.len()];
+    input
+        .read_exact(&mut magic)
+        .map_err(|_| "Not a password-protected archive".to_owned())?;
+    if magic != *ENCRYPTED_MAGIC {
+        return Err("Not a password-protected archive".to_owned());
+    }
+    let mut salt = [0_u8; SALT_LEN];
+    input
+        .read_exact(&mut salt)
+        .map_err(|_| TRUNCATED.to_owned())?;
+    let key = crypto::derive_key_from_password(password, &salt)?;
+
+    let mut output = BufWriter::new(fs::File::create(dest).map_err(|e| e.to_string())?);
+    let max_sealed_len = CHUNK_HEADER_LEN + SEAL_OVERHEAD + ENCRYPTED_CHUNK_SIZE;
+    let mut index = 0_u64;
+
+    loop {
+        let mut len_bytes = [0_u8; 4];
+        input
+            .read_exact(&mut len_bytes)
+            .map_err(|_| TRUNCATED.to_owned())?;
+        let sealed_len = u32::from_be_bytes(len_bytes) as usize;
+        if sealed_len > max_sealed_len {
+            return Err(CORRUPTED.to_owned());
+        }
+
+        let mut sealed = vec![0_u8; sealed_len];
+        input
+            .read_exact(&mut sealed)
+            .map_err(|_| TRUNCATED.to_owned())?;
+
+        let chunk = crypto::decrypt_data(&sealed, &key).map_err(|_| {
+            if index == 0 {
+                "Incorrect archive password".to_owned()
+            } else {
+                CORRUPTED.to_owned()
+            }
+        })?;
+        if chunk.len() < CHUNK_HEADER_LEN || chunk[..8] != index.to_be_bytes() {
+            return Err(CORRUPTED.to_owned());
+        }
+
+        output
+            .write_all(&chunk[CHUNK_HEADER_LEN..])
+            .map_err(|e| e.to_string())?;
+
+        if chunk[8] == 1 {
+            break;
+        }
+        index += 1;
+    }
+
+    // Nothing may follow the last chunk
+    if input.read(&mut [0_u8; 1]).map_err(|e| e.to_string())? != 0 {
+        return Err(CORRUPTED.to_owned());
+    }
+
+    output.flush().map_err(|e| e.to_string())
+}
+
+/// Join an archive entry name onto `destination`, refusing absolute paths and
+/// `..` components that would place the file outside it.
+fn safe_entry_path(destination: &Path, entry_name: &Path) -> Result<PathBuf, String> {
+    let mut path = destination.to_path_buf();
+
+    for component in entry_name.components() {
+        match component {
+            Component::Normal(part) => path.push(part),
+            Component::CurDir => {}
+            Component::ParentDir | Component::RootDir | Component::Prefix(_) => {
+                return Err(format!(
+                    "Refusing unsafe archive entry: {}",
+                    entry_name.display()
+                ));
+            }
+        }
+    }
+
+    Ok(path)
+}
+
+/// Write one extracted file, creating its parent folders.
+fn write_entry(dest: &Path, reader: &mut dyn Read) -> Result<u64, String> {
+    if let Some(parent) = dest.parent() {
+        fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let mut file = fs::File::create(dest).map_err(|e| e.to_string())?;
+    std::io::copy(reader, &mut file).map_err(|e| e.to_string())
+}
+
+/// Files and bytes restored from an archive.
+type RestoredTotals = (usize, u64);
+
+/// Call `visit` with the path of every entry of a zip, tar.gz or tar.zst archive
+/// and a reader over its contents, or `None` for folders. Links and other special
+/// tar entries are skipped.
+fn visit_entries<F>(archive_path: &Path, mut visit: F) -> Result<(), String>
+where
+    F: FnMut(&Path, Option<&mut dyn Read>) -> Result<(), String>,
+{
+    let format = ArchiveFormat::from_path(archive_path)
+        .ok_or_else(|| format!("Unsupported archive: {}", archive_path.display()))?;
+    let file = fs::File::open(archive_path).map_err(|e| e.to_string())?;
+
+    match format {
+        ArchiveFormat::Zip => {
+            let mut archive = zip::ZipArchive::new(file).map_err(|e| e.to_string())?;
+            for index in 0..archive.len() {
+                let mut entry = archive.by_index(index).map_err(|e| e.to_string())?;
+                let entry_path = PathBuf::from(entry.name());
+
+                if entry.is_dir() {
+                    visit(&entry_path, None)?;
+                } else {
+                    visit(&entry_path, Some(&mut entry))?;
+                }
+            }
+        }
+        ArchiveFormat::TarGz | ArchiveFormat::TarZst => {
+            let reader: Box<dyn Read> = if format == ArchiveFormat::TarGz {
+                Box::new(flate2::read::GzDecoder::new(file))
+            } else {
+                Box::new(zstd::Decoder::new(file).map_err(|e| e.to_string())?)
+            };
+            let mut archive = tar::Archive::new(reader);
+
+            for entry in archive.entries().map_err(|e| e.to_string())? {
+                let mut entry = entry.map_err(|e| e.to_string())?;
+                let entry_path = entry.path().map_err(|e| e.to_string())?.into_owned();
+
+                match entry.header().entry_type() {
+                    tar::EntryType::Directory => visit(&entry_path, None)?,
+                    tar::EntryType::Regular | tar::EntryType::Continuous => {
+                        visit(&entry_path, Some(&mut entry))?;
+                    }
+                    _ => log::warn!("Skipping special archive entry {}", entry_path.display()),
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Extract a zip, tar.gz or tar.zst archive into `destination`.
+///
+/// `on_progress` receives each file's name, the files and the bytes restored so
+/// far. Returns the number of files and bytes restored.
+fn extract_archive<F>(
+    archive_path: &Path,
+    destination: &Path,
+    mut on_progress: F,
+) -> Result<RestoredTotals, String>
+where
+    F: FnMut(&str, usize, u64),
+{
+    fs::create_dir_all(destination).map_err(|e| e.to_string())?;
+
+    let mut files_restored = 0;
+    let mut bytes_restored = 0;
+    visit_entries(archive_path, |entry_path, contents| {
+        let dest = safe_entry_path(destination, entry_path)?;
+        let Some(reader) = contents else {
+            return fs::create_dir_all(&dest).map_err(|e| e.to_string());
+        };
+
+        bytes_restored += write_entry(&dest, reader)?;
+        files_restored += 1;
+        let file_name = dest
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or("unknown");
+        on_progress(file_name, files_restored, bytes_restored);
+        Ok(())
+    })?;
+
+    Ok((files_restored, bytes_restored))
+}
+
+/// Re-read a finished archive and check every file in it against the
+/// `checksums.sha256` manifest stored alongside them, decrypting it to a
+/// temporary file first when a password is given.
+fn verify_archive(archive_path: &Path, password: Option<&str>) -> Result<(), String> {
+    let Some(password) = password else {
+        return verify_archive_entries(archive_path);
+    };
+
+    let format = ArchiveFormat::from_path(archive_path)
+        .ok_or_else(|| format!("Unsupported archive: {}", archive_path.display()))?;
+    let plain = archive_path.with_file_name(format!(".{}.{}", Uuid::new_v4(), format.extension()));
+    let result = decrypt_archive_file(archive_path, &plain, password)
+        .and_then(|()| verify_archive_entries(&plain));
+    let _ = fs::remove_file(&plain);
+
+    result
+}
+
+/// Hash the files of an unencrypted archive and compare them with its manifest.
+fn verify_archive_entries(archive_path: &Path) -> Result<(), String> {
+    let mut digests = ManifestEntries::new();
+    let mut manifest = None;
+
+    visit_entries(archive_path, |entry_path, contents| {
+        let Some(reader) = contents else {
+            return Ok(());
+        };
+        // Entries sit under the project's top-level folder
+        let relative: PathBuf = entry_path.components().skip(1).collect();
+
+        if relative == Path::new(MANIFEST_FILE_NAME) {
+            let mut text = String::new();
+            reader
+                .read_to_string(&mut text)
+                .map_err(|e| e.to_string())?;
+            manifest = Some(text);
+        } else {
+            let mut hashing = HashingReader::new(reader);
+            std::io::copy(&mut hashing, &mut std::io::sink()).map_err(|e| e.to_string())?;
+            digests.insert(
+                relative.to_string_lossy().replace('\\', "/"),
+                hashing.finish(),
+            );
+        }
+        Ok(())
+    })?;
+
+    let manifest = manifest.ok_or("Archive has no checksum manifest")?;
+    if parse_manifest(&manifest)? != digests {
+        return Err("Archive contents don't match its checksum manifest".to_owned());
+    }
+    Ok(())
+}
+
+/// Extract `archive_path` into `destination`, decrypting it to a temporary file
+/// first when it is password protected.
+fn restore_files<F>(
+    archive_path: &Path,
+    destination: &Path,
+    password: Option<&str>,
+    on_progress: F,
+) -> Result<RestoredTotals, String>
+where
+    F: FnMut(&str, usize, u64),
+{
+    if !is_encrypted_archive(archive_path) {
+        return extract_archive(archive_path, destination, on_progress);
+    }
+
+    let password = password
+        .filter(|p| !p.is_empty())
+        .ok_or("This archive is password protected; enter its password to restore it")?;
+    let format = ArchiveFormat::from_path(archive_path)
+        .ok_or_else(|| format!("Unsupported archive: {}", archive_path.display()))?;
+    fs::create_dir_all(destination).map_err(|e| e.to_string())?;
+
+    let plain = destination.join(format!(".{}.{}", Uuid::new_v4(), format.extension()));
+    let result = decrypt_archive_file(archive_path, &plain, password)
+        .and_then(|()| extract_archive(&plain, destination, on_progress));
+    let _ = fs::remove_file(&plain);
+
+    result
+}
+
+/// Recreate (or repoint) the database row of a project restored into
+/// `destination`, if its archive carried a `project.json`.
+///
+/// # Errors
+///
+/// Returns error if the `project.json` is unreadable or the database write fails
+pub fn register_restored_project(
+    db: &Database,
+    destination: &Path,
+) -> Result<Option<Project>, String> {
+    let Some(project_json) = WalkDir::new(destination)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(Result::ok)
+        .find(|entry| entry.file_type().is_file() && entry.file_name() == PROJECT_FILE_NAME)
+    else {
+        return Ok(None);
+    };
+
+    let json_data = fs::read_to_string(project_json.path()).map_err(|e| e.to_string())?;
+    let mut project: Project = serde_json::from_str(&json_data)
+        .map_err(|e| format!("Invalid project.json in archive: {e}"))?;
+    project.folder_path = project_json
+        .path()
+        .parent()
+        .unwrap_or(destination)
+        .to_string_lossy()
+        .to_string();
+    project.updated_at = chrono::Utc::now().to_rfc3339();
+
+    db.execute(|conn| {
+        conn.execute(
+            "INSERT INTO projects (id, name, client_name, date, shoot_type, status, folder_path,
+              created_at, updated_at, deadline, client_id, client_email, client_phone)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+             ON CONFLICT(id) DO UPDATE SET folder_path = excluded.folder_path,
+              updated_at = excluded.updated_at",
+            rusqlite::params![
+                &project.id,
+                &project.name,
+                &project.client_name,
+                &project.date,
+                &project.shoot_type,
+                project.status.to_string(),
+                &project.folder_path,
+                &project.created_at,
+                &project.updated_at,
+                &project.deadline,
+                &project.client_id,
+                &project.client_email,
+                &project.client_phone,
+            ],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| format!("Failed to restore project: {e}"))?;
+
+    Ok(Some(project))
+}
+
+/// Extract a compressed archive back into `destination`, re-registering the
+/// project when the archive contains its `project.json`. Password-protected
+/// archives need the password they were created with.
+#[tauri::command]
+pub async fn restore_archive(
+    db: tauri::State<'_, Database>,
+    app_handle: tauri::AppHandle,
+    archive_path: String,
+    destination: String,
+    password: Option<String>,
+) -> Result<RestoreResult, String> {
+    let source = PathBuf::from(&archive_path);
+    let dest = PathBuf::from(&destination);
+
+    let (files_restored, bytes_restored) = tokio::task::spawn_blocking(move || {
+        restore_files(
+            &source,
+            &dest,
+            password.as_deref(),
+            |file_name, files_restored, bytes_restored| {
+                let progress = RestoreProgress {
+                    archive_path: archive_path.clone(),
+                    file_name: file_name.to_owned(),
+                    files_restored,
+                    bytes_restored,
+                };
+                let _ = app_handle.emit("archive-restore-progress", &progress);
+            },
+        )
+    })
+    .await
+    .map_err(|e| format!("Restore task failed: {e}"))??;
+
+    let project = register_restored_project(&db, Path::new(&destination))?;
+
+    Ok(RestoreResult {
+        destination,
+        files_restored,
+        bytes_restored,
+        project,
+    })
+}
+
 /// Core logic for getting archive queue (testable)
 ///
 /// # Errors
@@ -309,12 +1292,121 @@ pub async fn remove_archive_job(
     state: tauri::State<'_, crate::state::AppState>,
     job_id: String,
 ) -> Result<(), String> {
-    remove_archive_job_impl(&state.archive_queue, job_id).await
+    remove_archive_job_impl(&state.archive_queue, job_id).await?;
+    persist_archive_queue(&state.archive_queue).await;
+    Ok(())
+}
+
+/// Core logic for cancelling an archive job (testable)
+///
+/// Pending jobs are cancelled outright; running jobs stop before their next file
+/// and remove the partial archive.
+///
+/// # Errors
+///
+/// Returns error if the job doesn't exist or has already finished
+pub async fn cancel_archive_impl(
+    archive_queue: &crate::state::ArchiveQueue,
+    archive_tokens: &crate::state::ArchiveTokens,
+    job_id: String,
+) -> Result<(), String> {
+    let mut queue = archive_queue.lock().await;
+    let job = queue.get_mut(&job_id).ok_or("Archive job not found")?;
+
+    if !matches!(
+        job.status,
+        ArchiveStatus::Pending | ArchiveStatus::InProgress
+    ) {
+        return Err("Can only cancel pending or running archives".to_owned());
+    }
+    job.status = ArchiveStatus::Cancelled;
+    job.completed_at = Some(get_timestamp());
+    drop(queue);
+
+    if let Some(token) = archive_tokens.lock().await.get(&job_id) {
+        token.cancel();
+    }
+    Ok(())
+}
+
+/// Cancel a pending or running archive job
+#[tauri::command]
+pub async fn cancel_archive(
+    state: tauri::State<'_, crate::state::AppState>,
+    job_id: String,
+) -> Result<(), String> {
+    cancel_archive_impl(&state.archive_queue, &state.archive_tokens, job_id).await?;
+    persist_archive_queue(&state.archive_queue).await;
+    Ok(())
+}
+
+/// Location of the persisted archive queue.
+fn archive_queue_path() -> Result<PathBuf, String> {
+    Ok(get_home_dir()?.join("CreatorOps").join(QUEUE_FILE_NAME))
+}
+
+/// Archive jobs keyed by id, as held in the queue and its saved file.
+type ArchiveJobs = HashMap<String, ArchiveJob>;
+
+/// Write a snapshot of the queue to `path`.
+fn save_archive_queue(jobs: &ArchiveJobs, path: &Path) -> Result<(), String> {
+    write_json_atomic(path, jobs).map_err(|e| e.to_string())
+}
+
+/// Read a persisted queue, marking jobs that were still running as failed.
+///
+/// Passwords are never saved, so pending encrypted jobs fail as well rather than
+/// producing an unencrypted archive. A missing file yields an empty queue; a
+/// corrupt one is recovered from its backup.
+fn load_archive_queue(path: &Path) -> Result<ArchiveJobs, String> {
+    let mut jobs: ArchiveJobs = read_json_with_backup(path)
+        .map_err(|e| e.to_string())?
+        .unwrap_or_default();
+    for job in jobs.values_mut() {
+        let message = match job.status {
+            ArchiveStatus::InProgress => INTERRUPTED_MESSAGE,
+            ArchiveStatus::Pending if is_encrypted_archive(Path::new(&job.archive_path)) => {
+                PASSWORD_LOST_MESSAGE
+            }
+            _ => continue,
+        };
+        job.status = ArchiveStatus::Failed;
+        job.error_message = Some(message.to_owned());
+    }
+
+    Ok(jobs)
+}
+
+/// Save the current queue to disk. Failures are logged rather than returned so an
+/// archive never fails because its queue entry could not be persisted.
+pub async fn persist_archive_queue(archive_queue: &crate::state::ArchiveQueue) {
+    let jobs = archive_queue.lock().await.clone();
+    let result = archive_queue_path().and_then(|path| save_archive_queue(&jobs, &path));
+
+    if let Err(e) = result {
+        log::warn!("Failed to persist archive queue: {e}");
+    }
+}
+
+/// Load the archive queue saved by a previous session into `archive_queue`.
+///
+/// Must be called before the async runtime starts. Returns the number of
+/// restored jobs.
+///
+/// # Errors
+///
+/// Returns error if the saved queue cannot be read or parsed
+pub fn restore_archive_queue(archive_queue: &crate::state::ArchiveQueue) -> Result<usize, String> {
+    let jobs = load_archive_queue(&archive_queue_path()?)?;
+    let count = jobs.len();
+    archive_queue.blocking_lock().extend(jobs);
+    Ok(count)
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
 
     #[test]
     fn test_archive_status_serialization() {
@@ -334,6 +1426,10 @@ mod tests {
             serde_json::to_string(&ArchiveStatus::Failed).unwrap(),
             r#""failed""#
         );
+        assert_eq!(
+            serde_json::to_string(&ArchiveStatus::Cancelled).unwrap(),
+            r#""cancelled""#
+        );
     }
 
     #[test]
@@ -346,6 +1442,8 @@ mod tests {
             archive_path: "/archive/project".to_owned(),
             compress: false,
             compression_format: None,
+            password: None,
+            and_remove_source: false,
             status: ArchiveStatus::Pending,
             total_files: 100,
             files_archived: 0,
@@ -372,7 +1470,9 @@ mod tests {
             source_path: "/source".to_owned(),
             archive_path: "/archive".to_owned(),
             compress: true,
-            compression_format: Some("zip".to_owned()),
+            compression_format: Some(ArchiveFormat::Zip),
+            password: None,
+            and_remove_source: false,
             status: ArchiveStatus::Pending,
             total_files: 50,
             files_archived: 0,
@@ -385,7 +1485,7 @@ mod tests {
         };
 
         assert!(job.compress);
-        assert_eq!(job.compression_format, Some("zip".to_owned()));
+        assert_eq!(job.compression_format, Some(ArchiveFormat::Zip));
     }
 
     #[test]
@@ -397,6 +1497,8 @@ mod tests {
             total_files: 100,
             bytes_transferred: 256_000,
             total_bytes: 1_024_000,
+            speed: 0.0,
+            eta: 0,
         };
 
         let json = serde_json::to_string(&progress).unwrap();
@@ -424,8 +1526,7 @@ mod tests {
             "Archive Test".to_owned(),
             source.to_string_lossy().to_string(),
             archive_location.to_string_lossy().to_string(),
-            false,
-            None,
+            ArchiveOptions::default(),
         )
         .await;
 
@@ -461,15 +1562,18 @@ mod tests {
             "Compressed Archive".to_owned(),
             source.to_string_lossy().to_string(),
             archive_location.to_string_lossy().to_string(),
-            true,
-            Some("zip".to_owned()),
+            ArchiveOptions {
+                compress: true,
+                compression_format: Some(ArchiveFormat::Zip),
+                ..ArchiveOptions::default()
+            },
         )
         .await;
 
         assert!(result.is_ok());
         let job = result.unwrap();
         assert!(job.compress);
-        assert_eq!(job.compression_format, Some("zip".to_owned()));
+        assert_eq!(job.compression_format, Some(ArchiveFormat::Zip));
 
         let _ = remove_archive_job_impl(&state.archive_queue, job.id).await;
     }
@@ -493,8 +1597,7 @@ mod tests {
             "Queue Test".to_owned(),
             source.to_string_lossy().to_string(),
             archive_location.to_string_lossy().to_string(),
-            false,
-            None,
+            ArchiveOptions::default(),
         )
         .await
         .unwrap();
@@ -524,8 +1627,7 @@ mod tests {
             "Remove Test".to_owned(),
             source.to_string_lossy().to_string(),
             archive_location.to_string_lossy().to_string(),
-            false,
-            None,
+            ArchiveOptions::default(),
         )
         .await
         .unwrap();
@@ -561,8 +1663,7 @@ mod tests {
             "Size Test".to_owned(),
             source.to_string_lossy().to_string(),
             archive_location.to_string_lossy().to_string(),
-            false,
-            None,
+            ArchiveOptions::default(),
         )
         .await
         .unwrap();
@@ -592,8 +1693,7 @@ mod tests {
             "Multi File Test".to_owned(),
             source.to_string_lossy().to_string(),
             archive_location.to_string_lossy().to_string(),
-            false,
-            None,
+            ArchiveOptions::default(),
         )
         .await
         .unwrap();
@@ -620,6 +1720,8 @@ mod tests {
                 archive_path: "/arch".to_owned(),
                 compress: false,
                 compression_format: None,
+                password: None,
+                and_remove_source: false,
                 status: status.clone(),
                 total_files: 0,
                 files_archived: 0,
@@ -664,6 +1766,8 @@ mod tests {
             archive_path: "/archive".to_owned(),
             compress: false,
             compression_format: None,
+            password: None,
+            and_remove_source: false,
             status: ArchiveStatus::Failed,
             total_files: 10,
             files_archived: 5,
@@ -689,6 +1793,8 @@ mod tests {
             total_files: 100,
             bytes_transferred: 512_000,
             total_bytes: 1_024_000,
+            speed: 0.0,
+            eta: 0,
         };
         let progress_percent = (progress.current_file as f64 / progress.total_files as f64) * 100.0;
         assert!((progress_percent - 50.0).abs() < f64::EPSILON);
@@ -718,8 +1824,7 @@ mod tests {
             "Subdir Test".to_owned(),
             source.to_string_lossy().to_string(),
             archive_location.to_string_lossy().to_string(),
-            false,
-            None,
+            ArchiveOptions::default(),
         )
         .await
         .unwrap();
@@ -738,4 +1843,917 @@ mod tests {
             remove_archive_job_impl(&state.archive_queue, "nonexistent-id".to_owned()).await;
         assert!(result.is_ok());
     }
+
+    /// Build a small project tree and return its root.
+    fn create_test_tree(temp_dir: &tempfile::TempDir) -> PathBuf {
+        let source = temp_dir.path().join("Smith Wedding");
+        std::fs::create_dir_all(source.join("RAW")).unwrap();
+        std::fs::write(source.join("notes.txt"), "ceremony at 3pm").unwrap();
+        std::fs::write(source.join("RAW").join("IMG_0001.CR3"), vec![1_u8; 4096]).unwrap();
+        source
+    }
+
+    /// Entry names and their contents.
+    type ArchiveEntries = Vec<(String, Vec<u8>)>;
+
+    /// Entry names and contents of a written archive.
+    fn read_archive(path: &Path, format: ArchiveFormat) -> ArchiveEntries {
+        let file = std::fs::File::open(path).unwrap();
+        let mut entries = Vec::new();
+
+        match format {
+            ArchiveFormat::Zip => {
+                let mut archive = zip::ZipArchive::new(file).unwrap();
+                for index in 0..archive.len() {
+                    let mut entry = archive.by_index(index).unwrap();
+                    let mut contents = Vec::new();
+                    entry.read_to_end(&mut contents).unwrap();
+                    entries.push((entry.name().to_owned(), contents));
+                }
+            }
+            ArchiveFormat::TarGz | ArchiveFormat::TarZst => {
+                let reader: Box<dyn Read> = if format == ArchiveFormat::TarGz {
+                    Box::new(flate2::read::GzDecoder::new(file))
+                } else {
+                    Box::new(zstd::Decoder::new(file).unwrap())
+                };
+                let mut archive = tar::Archive::new(reader);
+                for entry in archive.entries().unwrap() {
+                    let mut entry = entry.unwrap();
+                    let name = entry.path().unwrap().to_string_lossy().to_string();
+                    let mut contents = Vec::new();
+                    entry.read_to_end(&mut contents).unwrap();
+                    entries.push((name, contents));
+                }
+            }
+        }
+
+        entries.sort();
+        entries
+    }
+
+    #[test]
+    fn test_write_archive_each_format() {
+        for format in [
+            ArchiveFormat::Zip,
+            ArchiveFormat::TarGz,
+            ArchiveFormat::TarZst,
+        ] {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let source = create_test_tree(&temp_dir);
+            let dest = temp_dir
+                .path()
+                .join("archives")
+                .join(format!("Smith Wedding.{}", format.extension()));
+
+            write_archive(
+                &source,
+                &dest,
+                format,
+                None,
+                &CancellationToken::new(),
+                |_, _, _| {},
+            )
+            .unwrap();
+
+            let mut entries = read_archive(&dest, format);
+            let manifest = entries.remove(1);
+            assert_eq!(manifest.0, "Smith Wedding/checksums.sha256");
+            let manifest = String::from_utf8(manifest.1).unwrap();
+            assert!(manifest.contains("  RAW/IMG_0001.CR3\n"), "{format:?}");
+            assert!(manifest.contains("  notes.txt\n"), "{format:?}");
+            assert_eq!(
+                entries,
+                vec![
+                    (
+                        "Smith Wedding/RAW/IMG_0001.CR3".to_owned(),
+                        vec![1_u8; 4096]
+                    ),
+                    (
+                        "Smith Wedding/notes.txt".to_owned(),
+                        b"ceremony at 3pm".to_vec()
+                    ),
+                ],
+                "{format:?}"
+            );
+
+            // Compressed archives leave the project in place
+            assert!(source.join("notes.txt").exists());
+        }
+    }
+
+    #[test]
+    fn test_write_archive_reports_bytes_processed() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = create_test_tree(&temp_dir);
+        let dest = temp_dir.path().join("out.tar.gz");
+
+        let mut events = Vec::new();
+        write_archive(
+            &source,
+            &dest,
+            ArchiveFormat::TarGz,
+            None,
+            &CancellationToken::new(),
+            |name, current, bytes| {
+                events.push((name.to_owned(), current, bytes));
+            },
+        )
+        .unwrap();
+
+        assert_eq!(
+            events,
+            vec![
+                ("IMG_0001.CR3".to_owned(), 1, 4096),
+                ("notes.txt".to_owned(), 2, 4096 + 15),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_create_archive_compressed_path_uses_format_extension() {
+        let state = crate::state::AppState::default();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = create_test_tree(&temp_dir);
+        let archive_location = temp_dir.path().join("archives");
+
+        let job = create_archive_impl(
+            &state.archive_queue,
+            "proj-zst".to_owned(),
+            "Smith Wedding".to_owned(),
+            source.to_string_lossy().to_string(),
+            archive_location.to_string_lossy().to_string(),
+            ArchiveOptions {
+                compress: true,
+                compression_format: Some(ArchiveFormat::TarZst),
+                ..ArchiveOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            Path::new(&job.archive_path),
+            archive_location.join("Smith Wedding.tar.zst")
+        );
+        let _ = remove_archive_job_impl(&state.archive_queue, job.id).await;
+    }
+
+    #[test]
+    fn test_archive_format_serialization() {
+        assert_eq!(
+            serde_json::to_string(&ArchiveFormat::TarGz).unwrap(),
+            r#""tar_gz""#
+        );
+        assert_eq!(
+            serde_json::from_str::<ArchiveFormat>(r#""tar_zst""#).unwrap(),
+            ArchiveFormat::TarZst
+        );
+        assert_eq!(ArchiveFormat::default(), ArchiveFormat::Zip);
+    }
+
+    #[test]
+    fn test_restore_round_trip_each_format() {
+        for format in [
+            ArchiveFormat::Zip,
+            ArchiveFormat::TarGz,
+            ArchiveFormat::TarZst,
+        ] {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let source = create_test_tree(&temp_dir);
+            let archive = temp_dir
+                .path()
+                .join(format!("Smith Wedding.{}", format.extension()));
+            write_archive(
+                &source,
+                &archive,
+                format,
+                None,
+                &CancellationToken::new(),
+                |_, _, _| {},
+            )
+            .unwrap();
+
+            let restore_dir = temp_dir.path().join("restored");
+            let mut events = Vec::new();
+            let (files, bytes) = extract_archive(&archive, &restore_dir, |name, count, bytes| {
+                events.push((name.to_owned(), count, bytes));
+            })
+            .unwrap();
+
+            let restored = restore_dir.join("Smith Wedding");
+            let manifest = restored.join(MANIFEST_FILE_NAME);
+            let expected_bytes = 4096 + 15 + std::fs::metadata(&manifest).unwrap().len();
+            assert_eq!(files, 3, "{format:?}");
+            assert_eq!(bytes, expected_bytes, "{format:?}");
+            assert_eq!(events.last().map(|e| (e.1, e.2)), Some((3, expected_bytes)));
+            assert!(
+                crate::modules::checksums::verify_manifest_at(&restored, &manifest)
+                    .unwrap()
+                    .passed,
+                "{format:?}"
+            );
+
+            for relative in ["notes.txt", "RAW/IMG_0001.CR3"] {
+                assert_eq!(
+                    std::fs::read(restored.join(relative)).unwrap(),
+                    std::fs::read(source.join(relative)).unwrap(),
+                    "{format:?} {relative}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_project_archive_restores_into_a_new_database() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("studio.db")).unwrap();
+        let created = crate::modules::project::create_project_impl(
+            &db,
+            &temp_dir.path().join("Projects"),
+            crate::modules::project::NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                deadline: Some("2024-07-01".to_owned()),
+                ..crate::modules::project::NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+        let source = PathBuf::from(&created.folder_path);
+        std::fs::write(source.join("Selects").join("IMG_0001.jpg"), b"jpeg").unwrap();
+        // A stale copy in the folder is replaced by the database row
+        std::fs::write(source.join(PROJECT_FILE_NAME), b"{}").unwrap();
+
+        let project = archived_project(&db, &created.id).unwrap();
+        assert!(archived_project(&db, "missing").unwrap().is_none());
+        let archive = temp_dir
+            .path()
+            .join("archives")
+            .join("Smith Wedding.zip.enc");
+        write_encrypted_archive(
+            &source,
+            &archive,
+            ArchiveFormat::Zip,
+            project.as_ref(),
+            "hunter2",
+            &CancellationToken::new(),
+            |_, _, _| {},
+        )
+        .unwrap();
+        verify_archive(&archive, Some("hunter2")).unwrap();
+
+        let other_db = Database::new_with_path(&temp_dir.path().join("other.db")).unwrap();
+        let destination = temp_dir.path().join("restored");
+        restore_files(&archive, &destination, Some("hunter2"), |_, _, _| {}).unwrap();
+        let restored = register_restored_project(&other_db, &destination)
+            .unwrap()
+            .unwrap();
+
+        let folder = destination.join(source.file_name().unwrap());
+        assert_eq!(Path::new(&restored.folder_path), folder);
+        assert_eq!(
+            std::fs::read(folder.join("Selects").join("IMG_0001.jpg")).unwrap(),
+            b"jpeg"
+        );
+        let stored = get_project_by_id(&other_db, &created.id).unwrap();
+        assert_eq!(stored.name, "Smith Wedding");
+        assert_eq!(stored.client_name, "Jane Smith");
+        assert_eq!(stored.deadline.as_deref(), Some("2024-07-01"));
+        assert_eq!(Path::new(&stored.folder_path), folder);
+    }
+
+    #[test]
+    fn test_restore_rejects_parent_dir_entry_in_zip() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let archive = temp_dir.path().join("evil.zip");
+        let mut zip = ZipWriter::new(std::fs::File::create(&archive).unwrap());
+        zip.start_file("../evil.txt", SimpleFileOptions::default())
+            .unwrap();
+        zip.write_all(b"owned").unwrap();
+        zip.finish().unwrap();
+
+        let restore_dir = temp_dir.path().join("restored");
+        let result = extract_archive(&archive, &restore_dir, |_, _, _| {});
+
+        assert!(result.unwrap_err().contains("unsafe archive entry"));
+        assert!(!temp_dir.path().join("evil.txt").exists());
+    }
+
+    #[test]
+    fn test_restore_rejects_parent_dir_entry_in_tar() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let archive = temp_dir.path().join("evil.tar.gz");
+        let encoder = flate2::write::GzEncoder::new(
+            std::fs::File::create(&archive).unwrap(),
+            flate2::Compression::default(),
+        );
+        let mut builder = tar::Builder::new(encoder);
+
+        // tar's path setters refuse "..", so write the raw name field
+        let mut header = tar::Header::new_old();
+        let name = b"../evil.txt";
+        header.as_old_mut().name[..name.len()].copy_from_slice(name);
+        header.set_size(5);
+        header.set_entry_type(tar::EntryType::Regular);
+        header.set_cksum();
+        builder.append(&header, &b"owned"[..]).unwrap();
+        builder.into_inner().unwrap().finish().unwrap();
+
+        let restore_dir = temp_dir.path().join("restored");
+        let result = extract_archive(&archive, &restore_dir, |_, _, _| {});
+
+        assert!(result.unwrap_err().contains("unsafe archive entry"));
+        assert!(!temp_dir.path().join("evil.txt").exists());
+    }
+
+    #[test]
+    fn test_safe_entry_path() {
+        let dest = Path::new("/restore");
+        assert_eq!(
+            safe_entry_path(dest, Path::new("Project/./RAW/a.jpg")).unwrap(),
+            Path::new("/restore/Project/RAW/a.jpg")
+        );
+        assert!(safe_entry_path(dest, Path::new("/etc/passwd")).is_err());
+        assert!(safe_entry_path(dest, Path::new("Project/../../x")).is_err());
+    }
+
+    #[test]
+    fn test_restore_unsupported_archive() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let archive = temp_dir.path().join("project.rar");
+        std::fs::write(&archive, b"rar").unwrap();
+
+        let result = extract_archive(&archive, temp_dir.path(), |_, _, _| {});
+        assert!(result.unwrap_err().contains("Unsupported archive"));
+    }
+
+    #[test]
+    fn test_encrypted_restore_round_trip_each_format() {
+        for format in [
+            ArchiveFormat::Zip,
+            ArchiveFormat::TarGz,
+            ArchiveFormat::TarZst,
+        ] {
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let source = create_test_tree(&temp_dir);
+            let archive = temp_dir
+                .path()
+                .join(format!("Smith Wedding.{}.enc", format.extension()));
+            write_encrypted_archive(
+                &source,
+                &archive,
+                format,
+                None,
+                "hunter2",
+                &CancellationToken::new(),
+                |_, _, _| {},
+            )
+            .unwrap();
+
+            // Only the sealed archive is left behind
+            let leftovers: Vec<_> = std::fs::read_dir(temp_dir.path())
+                .unwrap()
+                .filter_map(Result::ok)
+                .filter(|entry| entry.file_name().to_string_lossy().starts_with('.'))
+                .collect();
+            assert!(leftovers.is_empty(), "{format:?}");
+            assert_eq!(
+                &std::fs::read(&archive).unwrap()[..ENCRYPTED_MAGIC.len()],
+                ENCRYPTED_MAGIC
+            );
+            assert_eq!(ArchiveFormat::from_path(&archive), Some(format));
+
+            let restore_dir = temp_dir.path().join("restored");
+            let (files, bytes) =
+                restore_files(&archive, &restore_dir, Some("hunter2"), |_, _, _| {}).unwrap();
+            let restored = restore_dir.join("Smith Wedding");
+            let manifest_len = std::fs::metadata(restored.join(MANIFEST_FILE_NAME))
+                .unwrap()
+                .len();
+            assert_eq!((files, bytes), (3, 4096 + 15 + manifest_len), "{format:?}");
+
+            for relative in ["notes.txt", "RAW/IMG_0001.CR3"] {
+                assert_eq!(
+                    std::fs::read(restored.join(relative)).unwrap(),
+                    std::fs::read(source.join(relative)).unwrap(),
+                    "{format:?} {relative}"
+                );
+            }
+            assert_eq!(std::fs::read_dir(&restore_dir).unwrap().count(), 1);
+        }
+    }
+
+    #[test]
+    fn test_encrypted_restore_wrong_password() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = create_test_tree(&temp_dir);
+        let archive = temp_dir.path().join("Smith Wedding.zip.enc");
+        write_encrypted_archive(
+            &source,
+            &archive,
+            ArchiveFormat::Zip,
+            None,
+            "hunter2",
+            &CancellationToken::new(),
+            |_, _, _| {},
+        )
+        .unwrap();
+
+        let restore_dir = temp_dir.path().join("restored");
+        let result = restore_files(&archive, &restore_dir, Some("hunter3"), |_, _, _| {});
+        assert_eq!(result.unwrap_err(), "Incorrect archive password");
+        assert_eq!(std::fs::read_dir(&restore_dir).unwrap().count(), 0);
+
+        let result = restore_files(&archive, &restore_dir, None, |_, _, _| {});
+        assert!(result.unwrap_err().contains("password protected"));
+    }
+
+    #[test]
+    fn test_encrypted_archive_spans_chunks_and_detects_truncation() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let plain = temp_dir.path().join("plain.bin");
+        let data: Vec<u8> = (0..ENCRYPTED_CHUNK_SIZE * 2 + 100)
+            .map(|i| (i % 251) as u8)
+            .collect();
+        std::fs::write(&plain, &data).unwrap();
+
+        let sealed = temp_dir.path().join("plain.bin.enc");
+        encrypt_archive_file(&plain, &sealed, "pw").unwrap();
+
+        let decrypted = temp_dir.path().join("decrypted.bin");
+        decrypt_archive_file(&sealed, &decrypted, "pw").unwrap();
+        assert_eq!(std::fs::read(&decrypted).unwrap(), data);
+
+        // Dropping the last chunk must not pass as a shorter archive
+        let bytes = std::fs::read(&sealed).unwrap();
+        let last_chunk_len = 4 + CHUNK_HEADER_LEN + SEAL_OVERHEAD + 100;
+        std::fs::write(&sealed, &bytes[..bytes.len() - last_chunk_len]).unwrap();
+        assert!(decrypt_archive_file(&sealed, &decrypted, "pw")
+            .unwrap_err()
+            .contains("truncated"));
+    }
+
+    #[tokio::test]
+    async fn test_create_archive_with_password() {
+        let state = crate::state::AppState::default();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = create_test_tree(&temp_dir);
+        let archive_location = temp_dir.path().join("archives");
+
+        let job = create_archive_impl(
+            &state.archive_queue,
+            "proj-enc".to_owned(),
+            "Smith Wedding".to_owned(),
+            source.to_string_lossy().to_string(),
+            archive_location.to_string_lossy().to_string(),
+            ArchiveOptions {
+                compress: true,
+                compression_format: Some(ArchiveFormat::TarGz),
+                password: Some("hunter2".to_owned()),
+                ..ArchiveOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(
+            Path::new(&job.archive_path),
+            archive_location.join("Smith Wedding.tar.gz.enc")
+        );
+        assert!(!serde_json::to_string(&job).unwrap().contains("hunter2"));
+
+        let result = create_archive_impl(
+            &state.archive_queue,
+            "proj-enc".to_owned(),
+            "Smith Wedding".to_owned(),
+            source.to_string_lossy().to_string(),
+            archive_location.to_string_lossy().to_string(),
+            ArchiveOptions {
+                password: Some("hunter2".to_owned()),
+                ..ArchiveOptions::default()
+            },
+        )
+        .await;
+        assert!(result
+            .unwrap_err()
+            .contains("requires a compressed archive"));
+    }
+
+    /// Queue a job for `source` and mark it running, as `start_archive` does.
+    async fn start_test_job(
+        state: &crate::state::AppState,
+        source: &Path,
+        archive_location: &Path,
+        compress: bool,
+    ) -> (ArchiveJob, CancellationToken) {
+        let mut job = create_archive_impl(
+            &state.archive_queue,
+            "proj-cancel".to_owned(),
+            "Smith Wedding".to_owned(),
+            source.to_string_lossy().to_string(),
+            archive_location.to_string_lossy().to_string(),
+            ArchiveOptions {
+                compress,
+                ..ArchiveOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+        job.status = ArchiveStatus::InProgress;
+        state
+            .archive_queue
+            .lock()
+            .await
+            .insert(job.id.clone(), job.clone());
+
+        let cancel = CancellationToken::new();
+        state
+            .archive_tokens
+            .lock()
+            .await
+            .insert(job.id.clone(), cancel.clone());
+        (job, cancel)
+    }
+
+    #[tokio::test]
+    async fn test_cancel_compressed_archive_mid_way() {
+        use std::sync::mpsc;
+
+        let state = crate::state::AppState::default();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = create_test_tree(&temp_dir);
+        let archive_location = temp_dir.path().join("archives");
+        let (job, cancel) = start_test_job(&state, &source, &archive_location, true).await;
+
+        // Hold the writer after its first file until the job has been cancelled
+        let (started_tx, started_rx) = mpsc::channel();
+        let (release_tx, release_rx) = mpsc::channel::<()>();
+        let release_rx = std::sync::Mutex::new(release_rx);
+        let emit: ProgressEmitter = Arc::new(move |progress| {
+            if progress.current_file == 1 {
+                started_tx.send(progress.clone()).unwrap();
+                release_rx.lock().unwrap().recv().unwrap();
+            }
+        });
+        let run = tokio::spawn(run_archive_job(
+            job.clone(),
+            None,
+            state.archive_queue.clone(),
+            cancel,
+            emit,
+            Arc::new(move_to_trash),
+        ));
+
+        let first = tokio::task::spawn_blocking(move || started_rx.recv().unwrap())
+            .await
+            .unwrap();
+        assert_eq!(first.total_files, 2);
+        assert!(first.bytes_transferred > 0);
+        cancel_archive_impl(&state.archive_queue, &state.archive_tokens, job.id.clone())
+            .await
+            .unwrap();
+        release_tx.send(()).unwrap();
+
+        assert_eq!(run.await.unwrap(), ArchiveStatus::Cancelled);
+        let stored = state.archive_queue.lock().await[&job.id].clone();
+        assert_eq!(stored.status, ArchiveStatus::Cancelled);
+        assert_eq!(stored.files_archived, 1);
+        assert!(stored.completed_at.is_some());
+        assert!(!Path::new(&job.archive_path).exists());
+        assert!(source.join("notes.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_moved_archive_removes_partial_copy() {
+        let state = crate::state::AppState::default();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = create_test_tree(&temp_dir);
+        let archive_location = temp_dir.path().join("archives");
+        let (job, cancel) = start_test_job(&state, &source, &archive_location, false).await;
+
+        let token = cancel.clone();
+        let emit: ProgressEmitter = Arc::new(move |_| token.cancel());
+        let status = run_archive_job(
+            job.clone(),
+            None,
+            state.archive_queue.clone(),
+            cancel,
+            emit,
+            Arc::new(move_to_trash),
+        )
+        .await;
+
+        assert_eq!(status, ArchiveStatus::Cancelled);
+        assert!(!Path::new(&job.archive_path).exists());
+        assert_eq!(
+            std::fs::read(source.join("RAW").join("IMG_0001.CR3")).unwrap(),
+            vec![1_u8; 4096]
+        );
+        assert!(source.join("notes.txt").exists());
+    }
+
+    #[tokio::test]
+    async fn test_run_archive_job_completes_and_reports_speed() {
+        let state = crate::state::AppState::default();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = create_test_tree(&temp_dir);
+        let archive_location = temp_dir.path().join("archives");
+        let (job, cancel) = start_test_job(&state, &source, &archive_location, true).await;
+
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+        let emit: ProgressEmitter = Arc::new(move |progress| {
+            sink.lock().unwrap().push(progress.clone());
+        });
+        let status = run_archive_job(
+            job.clone(),
+            None,
+            state.archive_queue.clone(),
+            cancel,
+            emit,
+            Arc::new(move_to_trash),
+        )
+        .await;
+
+        assert_eq!(status, ArchiveStatus::Completed);
+        assert!(Path::new(&job.archive_path).exists());
+        let last = events.lock().unwrap().last().cloned().unwrap();
+        assert_eq!((last.current_file, last.bytes_transferred), (2, 4096 + 15));
+        assert!(last.speed > 0.0);
+        assert_eq!(last.eta, 0);
+    }
+
+    #[tokio::test]
+    async fn test_moved_archive_writes_checksum_manifest() {
+        let state = crate::state::AppState::default();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = create_test_tree(&temp_dir);
+        let archive_location = temp_dir.path().join("archives");
+        let (job, cancel) = start_test_job(&state, &source, &archive_location, false).await;
+
+        let status = run_archive_job(
+            job.clone(),
+            None,
+            state.archive_queue.clone(),
+            cancel,
+            Arc::new(|_| {}),
+            Arc::new(move_to_trash),
+        )
+        .await;
+
+        assert_eq!(status, ArchiveStatus::Completed);
+        assert!(!source.exists());
+        let archived = Path::new(&job.archive_path);
+        let report = crate::modules::checksums::verify_manifest_at(
+            archived,
+            &archived.join(MANIFEST_FILE_NAME),
+        )
+        .unwrap();
+        assert!(report.passed);
+        assert_eq!(report.verified, 2);
+    }
+
+    /// Trasher that moves the folder into `trash_dir` and records the call.
+    fn test_trasher(trash_dir: &Path) -> (SourceTrasher, Arc<AtomicUsize>) {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let trash_dir = trash_dir.to_path_buf();
+        let trash: SourceTrasher = Arc::new(move |path| {
+            counter.fetch_add(1, Ordering::SeqCst);
+            std::fs::create_dir_all(&trash_dir).unwrap();
+            std::fs::rename(path, trash_dir.join(path.file_name().unwrap()))
+                .map_err(|e| e.to_string())
+        });
+        (trash, calls)
+    }
+
+    #[tokio::test]
+    async fn test_archive_and_remove_source_trashes_after_success() {
+        for password in [None, Some("hunter2")] {
+            let state = crate::state::AppState::default();
+            let temp_dir = tempfile::TempDir::new().unwrap();
+            let source = create_test_tree(&temp_dir);
+            let archive_location = temp_dir.path().join("archives");
+            let (mut job, cancel) = start_test_job(&state, &source, &archive_location, true).await;
+            job.and_remove_source = true;
+            if let Some(password) = password {
+                job.password = Some(password.to_owned());
+                job.archive_path.push_str(ENCRYPTED_SUFFIX);
+            }
+
+            let trash_dir = temp_dir.path().join("Trash");
+            let (trash, calls) = test_trasher(&trash_dir);
+            let status = run_archive_job(
+                job.clone(),
+                None,
+                state.archive_queue.clone(),
+                cancel,
+                Arc::new(|_| {}),
+                trash,
+            )
+            .await;
+
+            assert_eq!(status, ArchiveStatus::Completed);
+            assert_eq!(calls.load(Ordering::SeqCst), 1);
+            assert!(!source.exists());
+            assert!(trash_dir.join("Smith Wedding").join("notes.txt").exists());
+            assert!(Path::new(&job.archive_path).exists());
+        }
+    }
+
+    #[tokio::test]
+    async fn test_archive_and_remove_source_keeps_source_on_failure() {
+        let state = crate::state::AppState::default();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = create_test_tree(&temp_dir);
+        // A file where the archive folder should be makes writing the archive fail
+        let archive_location = temp_dir.path().join("archives");
+        std::fs::write(&archive_location, "not a folder").unwrap();
+        let (mut job, cancel) = start_test_job(&state, &source, &archive_location, true).await;
+        job.and_remove_source = true;
+
+        let trash_dir = temp_dir.path().join("Trash");
+        let (trash, calls) = test_trasher(&trash_dir);
+        let status = run_archive_job(
+            job.clone(),
+            None,
+            state.archive_queue.clone(),
+            cancel,
+            Arc::new(|_| {}),
+            trash,
+        )
+        .await;
+
+        assert_eq!(status, ArchiveStatus::Failed);
+        assert_eq!(calls.load(Ordering::SeqCst), 0);
+        assert!(!trash_dir.exists());
+        assert!(source.join("notes.txt").exists());
+        assert!(source.join("RAW").join("IMG_0001.CR3").exists());
+    }
+
+    #[test]
+    fn test_verify_archive_detects_mismatched_manifest() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let archive = temp_dir.path().join("Smith Wedding.zip");
+        let mut writer =
+            ArchiveWriter::new(fs::File::create(&archive).unwrap(), ArchiveFormat::Zip).unwrap();
+        writer
+            .append("Smith Wedding/notes.txt", 3, &mut &b"abc"[..])
+            .unwrap();
+        let manifest = manifest_line(&"0".repeat(64), Path::new("notes.txt"));
+        writer
+            .append(
+                &format!("Smith Wedding/{MANIFEST_FILE_NAME}"),
+                manifest.len() as u64,
+                &mut manifest.as_bytes(),
+            )
+            .unwrap();
+        writer.finish().unwrap();
+
+        assert!(verify_archive(&archive, None)
+            .unwrap_err()
+            .contains("don't match"));
+    }
+
+    #[tokio::test]
+    async fn test_cancel_archive_pending_and_finished() {
+        let state = crate::state::AppState::default();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = create_test_tree(&temp_dir);
+
+        let job = create_archive_impl(
+            &state.archive_queue,
+            "proj-pending".to_owned(),
+            "Smith Wedding".to_owned(),
+            source.to_string_lossy().to_string(),
+            temp_dir
+                .path()
+                .join("archives")
+                .to_string_lossy()
+                .to_string(),
+            ArchiveOptions {
+                compress: true,
+                ..ArchiveOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        cancel_archive_impl(&state.archive_queue, &state.archive_tokens, job.id.clone())
+            .await
+            .unwrap();
+        let stored = state.archive_queue.lock().await[&job.id].clone();
+        assert_eq!(stored.status, ArchiveStatus::Cancelled);
+
+        let again = cancel_archive_impl(&state.archive_queue, &state.archive_tokens, job.id).await;
+        assert!(again.unwrap_err().contains("pending or running"));
+
+        let missing = cancel_archive_impl(
+            &state.archive_queue,
+            &state.archive_tokens,
+            "nope".to_owned(),
+        )
+        .await;
+        assert!(missing.unwrap_err().contains("not found"));
+    }
+
+    #[test]
+    fn test_archive_queue_persistence() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(QUEUE_FILE_NAME);
+        let job = |id: &str, archive_path: &str, status: ArchiveStatus| ArchiveJob {
+            id: id.to_owned(),
+            project_id: "proj".to_owned(),
+            project_name: "Test".to_owned(),
+            source_path: "/src".to_owned(),
+            archive_path: archive_path.to_owned(),
+            compress: true,
+            compression_format: None,
+            password: Some("secret".to_owned()),
+            and_remove_source: false,
+            status,
+            total_files: 0,
+            files_archived: 0,
+            total_bytes: 0,
+            bytes_transferred: 0,
+            created_at: "2024-01-01".to_owned(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+        };
+
+        let jobs: ArchiveJobs = [
+            job("running", "/arch/a.zip", ArchiveStatus::InProgress),
+            job("pending", "/arch/b.zip", ArchiveStatus::Pending),
+            job("locked", "/arch/c.zip.enc", ArchiveStatus::Pending),
+            job("done", "/arch/d.zip", ArchiveStatus::Completed),
+        ]
+        .into_iter()
+        .map(|job| (job.id.clone(), job))
+        .collect();
+        save_archive_queue(&jobs, &path).unwrap();
+        assert!(!std::fs::read_to_string(&path).unwrap().contains("secret"));
+
+        let loaded = load_archive_queue(&path).unwrap();
+        assert_eq!(loaded.len(), 4);
+        assert_eq!(loaded["running"].status, ArchiveStatus::Failed);
+        assert_eq!(
+            loaded["running"].error_message.as_deref(),
+            Some(INTERRUPTED_MESSAGE)
+        );
+        assert_eq!(loaded["pending"].status, ArchiveStatus::Pending);
+        assert_eq!(loaded["locked"].status, ArchiveStatus::Failed);
+        assert_eq!(
+            loaded["locked"].error_message.as_deref(),
+            Some(PASSWORD_LOST_MESSAGE)
+        );
+        assert_eq!(loaded["done"].status, ArchiveStatus::Completed);
+        assert!(loaded.values().all(|job| job.password.is_none()));
+
+        let missing = load_archive_queue(&temp_dir.path().join("missing.json")).unwrap();
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn test_register_restored_project() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+
+        let project_dir = temp_dir.path().join("restored").join("2024-06-01_Smith");
+        std::fs::create_dir_all(&project_dir).unwrap();
+        let project = serde_json::json!({
+            "id": "proj-restored",
+            "name": "Smith Wedding",
+            "clientName": "Smith",
+            "date": "2024-06-01",
+            "shootType": "Wedding",
+            "status": "Archived",
+            "folderPath": "/old/location",
+            "createdAt": "2024-06-01T00:00:00Z",
+            "updatedAt": "2024-06-01T00:00:00Z"
+        });
+        std::fs::write(project_dir.join("project.json"), project.to_string()).unwrap();
+
+        let restored = register_restored_project(&db, &temp_dir.path().join("restored"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(restored.id, "proj-restored");
+        assert_eq!(Path::new(&restored.folder_path), project_dir);
+
+        let stored = crate::modules::project::get_project_by_id(&db, "proj-restored").unwrap();
+        assert_eq!(stored.name, "Smith Wedding");
+        assert_eq!(Path::new(&stored.folder_path), project_dir);
+    }
+
+    #[test]
+    fn test_register_restored_project_without_project_json() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+
+        assert!(register_restored_project(&db, temp_dir.path())
+            .unwrap()
+            .is_none());
+    }
 }
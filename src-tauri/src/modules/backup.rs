@@ -2,26 +2,42 @@
 //!
 //! Manages an in-memory job queue, performs chunked file copies with SHA-256
 //! checksum verification and exponential-backoff retries, and persists a
-//! completion record to `~/CreatorOps/backup_history.json`.
+//! completion record to `~/CreatorOps/backup_history.json`. The source digest is
+//! computed while the copy streams, so each file is read from the source once.
+//! An existing backup can later be re-checked against its source without copying.
+//! A running backup stops early if the destination runs low on free space.
+//! Files matched by a `.creatoropsignore` in the source folder are left out.
 
 use crate::error::BackupError;
+use crate::modules::checksums::{hash_file, write_manifest};
+use crate::modules::disk::{get_disk_for_path, get_free_space_for_path};
 use crate::modules::file_utils::{
-    collect_files_recursive, count_files_and_size, get_home_dir, get_timestamp, verify_checksum,
+    calculate_file_hash, collect_unignored_files, count_unignored_files_and_size,
+    ensure_writable_dir, get_home_dir, get_timestamp, hex_encode, read_json_with_backup,
+    write_json_atomic, TransferRate,
 };
+use crate::modules::job_log::JobLog;
+use crate::modules::notifications::{backup_notification, notify_job_finished};
 use crate::utils::file_ops;
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
 use std::fs;
-use std::io::Write;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
-use tauri::Emitter;
+use tauri::{Emitter, Manager};
 use tokio::io::{AsyncReadExt, AsyncWriteExt};
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use tokio_retry::Retry;
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
 
 const CHUNK_SIZE: usize = 4 * 1024 * 1024; // 4MB chunks
 const MAX_RETRY_ATTEMPTS: usize = 3;
+/// Files copied between checks of the destination's free space.
+const SPACE_CHECK_INTERVAL: usize = 25;
+/// Free space below which a running backup stops rather than filling the disk.
+const MIN_FREE_SPACE: u64 = 512 * 1024 * 1024; // 512MB
 
 /// Represents a queued or running backup operation for a project.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,6 +60,21 @@ pub struct BackupJob {
     pub started_at: Option<String>,
     pub completed_at: Option<String>,
     pub error_message: Option<String>,
+    /// Source and destination are on the same disk, so the backup won't survive
+    /// that disk failing.
+    #[serde(default)]
+    pub same_volume_warning: bool,
+    /// Shared by the jobs of one multi-destination backup.
+    pub group_id: Option<String>,
+}
+
+/// Drive or folder a multi-destination backup copies to.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BackupDestination {
+    pub id: String,
+    pub name: String,
+    pub path: String,
 }
 
 /// Lifecycle state of a backup job.
@@ -71,6 +102,29 @@ pub struct BackupProgress {
     pub eta: u64,
 }
 
+/// Result of re-checking an existing backup against its source.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyReport {
+    /// Source files whose backup copy has the same contents.
+    pub matched: usize,
+    /// Source files whose backup copy differs, as paths relative to the source.
+    pub mismatched: Vec<String>,
+    /// Source files with no copy in the backup.
+    pub missing: Vec<String>,
+    /// Whether every source file has an identical copy.
+    pub passed: bool,
+}
+
+/// Per-file progress payload emitted as the `backup-verify-progress` Tauri event.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyProgress {
+    pub file_name: String,
+    pub current_file: usize,
+    pub total_files: usize,
+}
+
 /// Completed backup record persisted to `~/CreatorOps/backup_history.json`.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -87,6 +141,28 @@ pub struct BackupHistory {
     pub completed_at: String,
     pub status: BackupStatus,
     pub error_message: Option<String>,
+    /// Seconds between start and completion; `None` if either time is unknown.
+    pub duration_secs: Option<f64>,
+    /// Bytes copied per second over the whole run; `None` without a duration.
+    pub avg_bytes_per_sec: Option<u64>,
+}
+
+/// Duration in seconds and average bytes per second of a backup run.
+type Throughput = (Option<f64>, Option<u64>);
+
+/// Throughput of a run between two [`get_timestamp`] values (Unix seconds). A
+/// run shorter than a second has a duration but no rate.
+fn backup_throughput(started_at: &str, completed_at: &str, bytes: u64) -> Throughput {
+    let parse = |s: &str| s.parse::<u64>().ok();
+    let (Some(start), Some(end)) = (parse(started_at), parse(completed_at)) else {
+        return (None, None);
+    };
+    let Some(secs) = end.checked_sub(start) else {
+        return (None, None);
+    };
+
+    let rate = (secs > 0).then(|| bytes / secs);
+    (Some(secs as f64), rate)
 }
 
 /// Core logic for queuing a backup job (testable)
@@ -107,7 +183,8 @@ pub async fn queue_backup_impl(
     let now = get_timestamp();
 
     // Count files and calculate total size
-    let (total_files, total_bytes) = count_files_and_size(&source_path)?;
+    let (total_files, total_bytes) = count_unignored_files_and_size(&source_path)?;
+    let same_volume_warning = is_same_volume(&source_path, &destination_path);
 
     let job = BackupJob {
         id: id.clone(),
@@ -127,6 +204,8 @@ pub async fn queue_backup_impl(
         started_at: None,
         completed_at: None,
         error_message: None,
+        same_volume_warning,
+        group_id: None,
     };
 
     {
@@ -137,6 +216,18 @@ pub async fn queue_backup_impl(
     Ok(job)
 }
 
+/// Whether `source` and `destination` are on the same disk. Paths whose disk
+/// can't be determined are treated as different.
+fn is_same_volume(source: &str, destination: &str) -> bool {
+    match (
+        get_disk_for_path(Path::new(source)),
+        get_disk_for_path(Path::new(destination)),
+    ) {
+        (Ok(source_disk), Ok(destination_disk)) => source_disk == destination_disk,
+        _ => false,
+    }
+}
+
 /// Queue a backup job for the given project source and destination.
 #[tauri::command]
 pub async fn queue_backup(
@@ -148,6 +239,8 @@ pub async fn queue_backup(
     destination_name: String,
     destination_path: String,
 ) -> Result<BackupJob, String> {
+    ensure_writable_dir(Path::new(&destination_path))?;
+
     queue_backup_impl(
         &state.backup_queue,
         project_id,
@@ -160,6 +253,126 @@ pub async fn queue_backup(
     .await
 }
 
+/// Core logic for queuing one backup job per destination (testable)
+///
+/// The jobs share a `group_id` so they can be started and tracked as a set.
+///
+/// # Errors
+///
+/// Returns error if no destinations are given, a destination is listed twice,
+/// or the source path doesn't exist
+pub async fn queue_backup_multi_impl(
+    backup_queue: &crate::state::BackupQueue,
+    project_id: String,
+    project_name: String,
+    source_path: String,
+    destinations: Vec<BackupDestination>,
+) -> Result<Vec<BackupJob>, String> {
+    if destinations.is_empty() {
+        return Err("No backup destinations given".to_owned());
+    }
+    let mut seen = HashSet::new();
+    for destination in &destinations {
+        if !seen.insert(destination.path.as_str()) {
+            return Err(format!("Destination listed twice: {}", destination.path));
+        }
+    }
+
+    let group_id = Uuid::new_v4().to_string();
+    let now = get_timestamp();
+    let (total_files, total_bytes) = count_unignored_files_and_size(&source_path)?;
+
+    let jobs: Vec<BackupJob> = destinations
+        .into_iter()
+        .map(|destination| BackupJob {
+            id: Uuid::new_v4().to_string(),
+            project_id: project_id.clone(),
+            project_name: project_name.clone(),
+            source_path: source_path.clone(),
+            same_volume_warning: is_same_volume(&source_path, &destination.path),
+            destination_id: destination.id,
+            destination_name: destination.name,
+            destination_path: destination.path,
+            status: BackupStatus::Pending,
+            total_files,
+            files_copied: 0,
+            files_skipped: 0,
+            total_bytes,
+            bytes_transferred: 0,
+            created_at: now.clone(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+            group_id: Some(group_id.clone()),
+        })
+        .collect();
+
+    let mut queue = backup_queue.lock().await;
+    for job in &jobs {
+        queue.insert(job.id.clone(), job.clone());
+    }
+    drop(queue);
+
+    Ok(jobs)
+}
+
+/// Queue a backup of one source to several destinations at once.
+#[tauri::command]
+pub async fn queue_backup_multi(
+    state: tauri::State<'_, crate::state::AppState>,
+    project_id: String,
+    project_name: String,
+    source_path: String,
+    destinations: Vec<BackupDestination>,
+) -> Result<Vec<BackupJob>, String> {
+    for destination in &destinations {
+        ensure_writable_dir(Path::new(&destination.path))?;
+    }
+
+    queue_backup_multi_impl(
+        &state.backup_queue,
+        project_id,
+        project_name,
+        source_path,
+        destinations,
+    )
+    .await
+}
+
+/// Core logic for getting the jobs of a backup group (testable)
+///
+/// # Errors
+///
+/// Returns error if no job belongs to the group
+pub async fn get_backup_group_impl(
+    backup_queue: &crate::state::BackupQueue,
+    group_id: &str,
+) -> Result<Vec<BackupJob>, String> {
+    let mut jobs: Vec<BackupJob> = {
+        let queue = backup_queue.lock().await;
+        queue
+            .values()
+            .filter(|job| job.group_id.as_deref() == Some(group_id))
+            .cloned()
+            .collect()
+    };
+    if jobs.is_empty() {
+        return Err("Backup group not found".to_owned());
+    }
+    jobs.sort_by(|a, b| a.destination_name.cmp(&b.destination_name));
+
+    Ok(jobs)
+}
+
+/// Get the jobs queued together by `queue_backup_multi`
+#[tauri::command]
+pub async fn get_backup_group(
+    state: tauri::State<'_, crate::state::AppState>,
+    group_id: String,
+) -> Result<Vec<BackupJob>, String> {
+    get_backup_group_impl(&state.backup_queue, &group_id).await
+}
+
 /// Core logic for getting backup queue (testable)
 ///
 /// # Errors
@@ -185,78 +398,154 @@ pub async fn get_backup_queue(
     get_backup_queue_impl(&state.backup_queue).await
 }
 
-/// Start a backup job
+/// Start a backup job in the background, returning it with in-progress status.
 #[tauri::command]
 pub async fn start_backup(
     state: tauri::State<'_, crate::state::AppState>,
     window: tauri::Window,
     job_id: String,
 ) -> Result<BackupJob, String> {
-    // Get job from queue
-    let job = {
-        let mut queue = state.backup_queue.lock().await;
-        let job = queue
-            .get_mut(&job_id)
-            .ok_or("Backup job not found")?
-            .clone();
-
-        if job.status != BackupStatus::Pending {
-            return Err("Backup job is not pending".to_owned());
-        }
+    let (job, cancel) = begin_backup(&state.backup_queue, &state.backup_tokens, &job_id).await?;
+    let started = job.clone();
 
-        let job_clone = job;
-        drop(queue);
-        job_clone
-    };
+    let backup_queue = state.backup_queue.clone();
+    let backup_tokens = state.backup_tokens.clone();
+    tokio::spawn(async move {
+        let emit = progress_emitter(&window);
+        let finished = run_backup(&backup_queue, &backup_tokens, job, cancel, emit).await;
+        announce_backup_finished(&window, finished.as_ref());
+    });
 
-    // Update status to in-progress
-    {
-        let mut queue = state.backup_queue.lock().await;
-        if let Some(j) = queue.get_mut(&job_id) {
-            j.status = BackupStatus::InProgress;
-            j.started_at = Some(get_timestamp());
-        }
+    Ok(started)
+}
+
+/// Run a backup job and wait for it to finish, returning it with its final
+/// status. Suited to scripts, which would otherwise have to poll the queue.
+#[tauri::command]
+pub async fn start_backup_blocking(
+    state: tauri::State<'_, crate::state::AppState>,
+    window: tauri::Window,
+    job_id: String,
+) -> Result<BackupJob, String> {
+    let job = start_backup_blocking_impl(
+        &state.backup_queue,
+        &state.backup_tokens,
+        &job_id,
+        progress_emitter(&window),
+    )
+    .await?;
+    announce_backup_finished(&window, Some(&job));
+    Ok(job)
+}
+
+/// Core logic for running a backup to completion (testable)
+///
+/// # Errors
+///
+/// Returns error if the job is not found or not pending
+pub async fn start_backup_blocking_impl(
+    backup_queue: &crate::state::BackupQueue,
+    backup_tokens: &crate::state::BackupTokens,
+    job_id: &str,
+    emit: impl Fn(BackupProgress),
+) -> Result<BackupJob, String> {
+    let (job, cancel) = begin_backup(backup_queue, backup_tokens, job_id).await?;
+    run_backup(backup_queue, backup_tokens, job, cancel, emit)
+        .await
+        .ok_or_else(|| "Backup job not found".to_owned())
+}
+
+/// Mark a pending job in progress and register its cancellation token.
+async fn begin_backup(
+    backup_queue: &crate::state::BackupQueue,
+    backup_tokens: &crate::state::BackupTokens,
+    job_id: &str,
+) -> Result<(BackupJob, CancellationToken), String> {
+    let mut queue = backup_queue.lock().await;
+    let job = queue.get_mut(job_id).ok_or("Backup job not found")?;
+    if job.status != BackupStatus::Pending {
+        return Err("Backup job is not pending".to_owned());
     }
+    job.status = BackupStatus::InProgress;
+    job.started_at = Some(get_timestamp());
+    let job = job.clone();
+    drop(queue);
 
-    // Perform backup in background
-    let job_id_clone = job_id.clone();
-    let window_clone = window;
-    let backup_queue = state.backup_queue.clone();
-    tokio::spawn(async move {
-        let result = perform_backup(&window_clone, &job_id_clone, &job).await;
+    let cancel = CancellationToken::new();
+    backup_tokens
+        .lock()
+        .await
+        .insert(job_id.to_owned(), cancel.clone());
+    Ok((job, cancel))
+}
 
-        // Update job status
-        let mut queue = backup_queue.lock().await;
-        if let Some(j) = queue.get_mut(&job_id_clone) {
-            match result {
-                Ok((files_copied, files_skipped, bytes_transferred)) => {
-                    j.status = BackupStatus::Completed;
-                    j.files_copied = files_copied;
-                    j.files_skipped = files_skipped;
-                    j.bytes_transferred = bytes_transferred;
-                    j.completed_at = Some(get_timestamp());
-
-                    // Save to history
-                    let _ = save_backup_to_history(j);
-                }
-                Err(e) => {
-                    j.status = BackupStatus::Failed;
-                    j.error_message = Some(e.to_string());
-                    j.completed_at = Some(get_timestamp());
-                }
-            }
+/// Copy a started job's files and record the outcome in the queue and the
+/// backup history. Returns the finished job, or `None` if it left the queue.
+async fn run_backup(
+    backup_queue: &crate::state::BackupQueue,
+    backup_tokens: &crate::state::BackupTokens,
+    job: BackupJob,
+    cancel: CancellationToken,
+    emit: impl Fn(BackupProgress),
+) -> Option<BackupJob> {
+    let log = JobLog::open(&job.id);
+    log.write(&format!(
+        "Backup of {} to {}",
+        job.source_path, job.destination_path
+    ));
+    let result = perform_backup(&job.id, &job, &cancel, &log, emit, get_free_space_for_path).await;
+    backup_tokens.lock().await.remove(&job.id);
+    match &result {
+        Ok((copied, skipped, _)) => {
+            log.write(&format!("Completed: {copied} copied, {skipped} skipped"));
+        }
+        Err(e) => log.write(&format!("Stopped: {e}")),
+    }
 
-            // Emit job update
-            let _ = window_clone.emit("backup-job-updated", j.clone());
+    // Update job status
+    let mut queue = backup_queue.lock().await;
+    let j = queue.get_mut(&job.id)?;
+    match result {
+        Ok((files_copied, files_skipped, bytes_transferred)) => {
+            j.status = BackupStatus::Completed;
+            j.files_copied = files_copied;
+            j.files_skipped = files_skipped;
+            j.bytes_transferred = bytes_transferred;
+            j.completed_at = Some(get_timestamp());
+
+            // Save to history
+            let _ = save_backup_to_history(j);
         }
-    });
+        Err(BackupError::Cancelled) => {
+            j.status = BackupStatus::Cancelled;
+            j.completed_at = Some(get_timestamp());
+        }
+        Err(e) => {
+            j.status = BackupStatus::Failed;
+            j.error_message = Some(e.to_string());
+            j.completed_at = Some(get_timestamp());
+        }
+    }
+    let finished = j.clone();
+    drop(queue);
+    Some(finished)
+}
 
-    // Return immediately with in-progress status
-    let queue = state.backup_queue.lock().await;
-    queue
-        .get(&job_id)
-        .cloned()
-        .ok_or_else(|| "Backup job not found".to_owned())
+/// Sink for `backup-progress` events on `window`.
+fn progress_emitter(window: &tauri::Window) -> impl Fn(BackupProgress) {
+    let window = window.clone();
+    move |progress| {
+        let _ = window.emit("backup-progress", progress);
+    }
+}
+
+/// Tell the frontend and the desktop that a backup job has finished.
+fn announce_backup_finished(window: &tauri::Window, job: Option<&BackupJob>) {
+    let Some(job) = job else {
+        return;
+    };
+    let _ = window.emit("backup-job-updated", job);
+    notify_job_finished(window.app_handle(), backup_notification(job));
 }
 
 /// Core logic for canceling a backup job (testable)
@@ -292,6 +581,65 @@ pub async fn cancel_backup(
     cancel_backup_impl(&state.backup_queue, job_id).await
 }
 
+/// Core logic for canceling every unfinished job of a backup group (testable)
+///
+/// Pending jobs are cancelled at once; running ones are signalled to stop and
+/// are marked cancelled when their copy loop notices. Returns how many jobs
+/// were affected.
+///
+/// # Errors
+///
+/// Returns error if no job belongs to the group
+pub async fn cancel_backup_group_impl(
+    backup_queue: &crate::state::BackupQueue,
+    backup_tokens: &crate::state::BackupTokens,
+    group_id: &str,
+) -> Result<usize, String> {
+    let mut queue = backup_queue.lock().await;
+    let mut in_group = 0;
+    let mut cancelled = 0;
+    let mut running = Vec::new();
+    for job in queue
+        .values_mut()
+        .filter(|job| job.group_id.as_deref() == Some(group_id))
+    {
+        in_group += 1;
+        match job.status {
+            BackupStatus::Pending => {
+                job.status = BackupStatus::Cancelled;
+                job.completed_at = Some(get_timestamp());
+                cancelled += 1;
+            }
+            BackupStatus::InProgress => running.push(job.id.clone()),
+            BackupStatus::Completed | BackupStatus::Failed | BackupStatus::Cancelled => {}
+        }
+    }
+    drop(queue);
+    if in_group == 0 {
+        return Err("Backup group not found".to_owned());
+    }
+
+    let tokens = backup_tokens.lock().await;
+    for id in &running {
+        if let Some(token) = tokens.get(id) {
+            token.cancel();
+            cancelled += 1;
+        }
+    }
+    drop(tokens);
+
+    Ok(cancelled)
+}
+
+/// Cancel every pending or running job of a backup group, returning how many
+#[tauri::command]
+pub async fn cancel_backup_group(
+    state: tauri::State<'_, crate::state::AppState>,
+    group_id: String,
+) -> Result<usize, String> {
+    cancel_backup_group_impl(&state.backup_queue, &state.backup_tokens, &group_id).await
+}
+
 /// Core logic for removing a backup job (testable)
 ///
 /// # Errors
@@ -329,12 +677,9 @@ pub async fn get_backup_history() -> Result<Vec<BackupHistory>, String> {
     let home_dir = get_home_dir()?;
     let history_path = home_dir.join("CreatorOps").join("backup_history.json");
 
-    if !history_path.exists() {
-        return Ok(Vec::new());
-    }
-
-    let data = fs::read_to_string(&history_path).map_err(|e| e.to_string())?;
-    let mut history: Vec<BackupHistory> = serde_json::from_str(&data).unwrap_or_default();
+    let mut history: Vec<BackupHistory> = read_json_with_backup(&history_path)
+        .map_err(|e| format!("Failed to read backup history: {e}"))?
+        .unwrap_or_default();
 
     // Sort by completed_at descending
     history.sort_by(|a, b| b.completed_at.cmp(&a.completed_at));
@@ -352,32 +697,132 @@ pub async fn get_project_backup_history(project_id: String) -> Result<Vec<Backup
         .collect())
 }
 
+/// Compare every file under `source` with its copy in the backup of it at
+/// `destination`, without changing either side.
+///
+/// # Errors
+///
+/// Returns error if the source can't be listed or a file can't be read
+pub fn verify_backup_files<F>(
+    source: &Path,
+    destination: &Path,
+    mut on_progress: F,
+) -> Result<VerifyReport, BackupError>
+where
+    F: FnMut(&VerifyProgress),
+{
+    let backup_path = backup_folder(source, destination)?;
+    let mut files =
+        collect_unignored_files(source).map_err(|e| BackupError::CollectFailed(e.to_string()))?;
+    files.sort();
+
+    let total_files = files.len();
+    let mut report = VerifyReport::default();
+
+    for (index, source_file) in files.iter().enumerate() {
+        let relative_path = source_file
+            .strip_prefix(source)
+            .map_err(|e| BackupError::PathError(e.to_string()))?;
+        let backup_file = backup_path.join(relative_path);
+        let relative = relative_path.to_string_lossy().replace('\\', "/");
+
+        if !backup_file.is_file() {
+            report.missing.push(relative);
+        } else if hash_file(source_file).map_err(BackupError::ChecksumFailed)?
+            == hash_file(&backup_file).map_err(BackupError::ChecksumFailed)?
+        {
+            report.matched += 1;
+        } else {
+            report.mismatched.push(relative);
+        }
+
+        on_progress(&VerifyProgress {
+            file_name: source_file
+                .file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .to_string(),
+            current_file: index + 1,
+            total_files,
+        });
+    }
+    report.passed = report.mismatched.is_empty() && report.missing.is_empty();
+
+    Ok(report)
+}
+
+/// Re-check an existing backup of `source_path` at `destination_path` by
+/// checksum, reporting matched, mismatched and missing files.
+#[tauri::command]
+pub async fn verify_backup(
+    window: tauri::Window,
+    source_path: String,
+    destination_path: String,
+) -> Result<VerifyReport, String> {
+    tokio::task::spawn_blocking(move || {
+        verify_backup_files(
+            Path::new(&source_path),
+            Path::new(&destination_path),
+            |progress| {
+                let _ = window.emit("backup-verify-progress", progress);
+            },
+        )
+    })
+    .await
+    .map_err(|e| format!("Verification task failed: {e}"))?
+    .map_err(String::from)
+}
+
 // Helper functions
 
+/// Folder a backup of `source` to `destination` is written to: the source's
+/// own folder name under the destination.
+fn backup_folder(source: &Path, destination: &Path) -> Result<PathBuf, BackupError> {
+    let project_folder_name = source.file_name().ok_or(BackupError::InvalidPath)?;
+    Ok(destination.join(project_folder_name))
+}
+
+/// Copy a job's files into its backup folder, writing each file's outcome to
+/// `log` and its progress to `emit`.
+///
+/// Every [`SPACE_CHECK_INTERVAL`] files the destination's free space is read
+/// through `free_space`, and the backup stops with
+/// [`BackupError::InsufficientSpace`] once it drops below [`MIN_FREE_SPACE`],
+/// or below the bytes still to copy when that is less, so a small backup still
+/// fits on a nearly full drive.
 async fn perform_backup(
-    window: &tauri::Window,
     job_id: &str,
     job: &BackupJob,
+    cancel: &CancellationToken,
+    log: &JobLog,
+    emit: impl Fn(BackupProgress),
+    free_space: impl Fn(&Path) -> Result<u64, String>,
 ) -> Result<(usize, usize, u64), BackupError> {
     let src_path = Path::new(&job.source_path);
-    let dest_base = Path::new(&job.destination_path);
-
-    let project_folder_name = src_path
-        .file_name()
-        .ok_or(BackupError::InvalidPath)?
-        .to_string_lossy();
-    let dest_path = dest_base.join(project_folder_name.as_ref());
+    let dest_path = backup_folder(src_path, Path::new(&job.destination_path))?;
 
     let files_to_copy =
-        collect_files_recursive(src_path).map_err(|e| BackupError::CollectFailed(e.to_string()))?;
+        collect_unignored_files(src_path).map_err(|e| BackupError::CollectFailed(e.to_string()))?;
 
     let total_files = files_to_copy.len();
-    let start_time = std::time::Instant::now();
+    let mut rate = TransferRate::new();
     let mut bytes_transferred = 0_u64;
     let mut files_copied = 0;
     let mut files_skipped = 0;
 
     for (index, src_file) in files_to_copy.iter().enumerate() {
+        if cancel.is_cancelled() {
+            return Err(BackupError::Cancelled);
+        }
+        if index % SPACE_CHECK_INTERVAL == 0 {
+            let remaining = job.total_bytes.saturating_sub(bytes_transferred);
+            let threshold = MIN_FREE_SPACE.min(remaining);
+            if let Err(e) = check_free_space(&free_space, &dest_path, threshold) {
+                log.write(&format!("Stopped before {}: {e}", src_file.display()));
+                return Err(e);
+            }
+        }
+
         let relative_path = src_file
             .strip_prefix(src_path)
             .map_err(|e| BackupError::PathError(e.to_string()))?;
@@ -394,33 +839,21 @@ async fn perform_backup(
             .to_string();
 
         // Attempt copy with retries
-        match copy_file_with_retry(src_file, &dest_file).await {
+        match copy_file_with_retry(src_file, &dest_file, log).await {
             Ok(size) => {
                 bytes_transferred += size;
                 files_copied += 1;
+                log.write(&format!("Copied {} ({size} bytes)", src_file.display()));
             }
-            Err(_e) => {
+            Err(e) => {
                 // Copy failed after retries - skip file
                 files_skipped += 1;
+                log.write(&format!("Skipped {}: {e}", src_file.display()));
             }
         }
 
         // Emit progress
-        let elapsed = start_time.elapsed().as_secs_f64();
-        // Safe cast: bytes_transferred and remaining_bytes used for progress calculation
-        // Precision loss acceptable for display purposes
-        let speed = if elapsed > 0.0 {
-            bytes_transferred as f64 / elapsed
-        } else {
-            0.0
-        };
-
-        let remaining_bytes = job.total_bytes - bytes_transferred;
-        let eta = if speed > 0.0 {
-            (remaining_bytes as f64 / speed) as u64
-        } else {
-            0
-        };
+        let (speed, eta) = rate.update(bytes_transferred, job.total_bytes);
 
         let progress = BackupProgress {
             job_id: job_id.to_owned(),
@@ -433,40 +866,82 @@ async fn perform_backup(
             eta,
         };
 
-        let _ = window.emit("backup-progress", progress);
+        emit(progress);
+    }
+
+    if dest_path.is_dir() {
+        tokio::task::spawn_blocking(move || write_manifest(&dest_path))
+            .await
+            .map_err(|e| BackupError::Manifest(e.to_string()))?
+            .map_err(BackupError::Manifest)?;
     }
 
     Ok((files_copied, files_skipped, bytes_transferred))
 }
 
-async fn copy_file_with_retry(src: &Path, dest: &Path) -> Result<u64, BackupError> {
+/// Fail with [`BackupError::InsufficientSpace`] if the volume holding `dest` has
+/// less than `threshold` bytes free. A volume that can't be queried passes, so
+/// the check never stops a backup that could otherwise finish.
+fn check_free_space(
+    free_space: impl Fn(&Path) -> Result<u64, String>,
+    dest: &Path,
+    threshold: u64,
+) -> Result<(), BackupError> {
+    match free_space(dest) {
+        Ok(free) if free < threshold => Err(BackupError::InsufficientSpace(free)),
+        Ok(_) => Ok(()),
+        Err(e) => {
+            log::warn!("Failed to check free space for {}: {e}", dest.display());
+            Ok(())
+        }
+    }
+}
+
+async fn copy_file_with_retry(src: &Path, dest: &Path, log: &JobLog) -> Result<u64, BackupError> {
     let retry_strategy = ExponentialBackoff::from_millis(10)
         .map(jitter)
         .take(MAX_RETRY_ATTEMPTS);
+    let attempt = std::sync::atomic::AtomicUsize::new(0);
 
     Retry::spawn(retry_strategy, || async {
-        let size = copy_file(src, dest).await?;
-
-        match verify_checksum(src, dest).await {
-            Ok(true) => Ok(size),
-            Ok(false) => {
-                let _ = file_ops::remove_file(dest).await;
-                Err(BackupError::ChecksumMismatch)
-            }
-            Err(e) => {
-                let _ = file_ops::remove_file(dest).await;
-                Err(BackupError::ChecksumFailed(e.to_string()))
-            }
+        let attempt = attempt.fetch_add(1, std::sync::atomic::Ordering::Relaxed) + 1;
+        let result = async {
+            let (size, source_digest) = copy_file(src, dest).await?;
+            verify_copy(dest, &source_digest).await?;
+            Ok(size)
         }
+        .await;
+        if let Err(e) = &result {
+            log.write(&format!(
+                "Attempt {attempt} failed for {}: {e}",
+                src.display()
+            ));
+        }
+        result
     })
     .await
 }
 
-async fn copy_file(src: &Path, dest: &Path) -> Result<u64, BackupError> {
+/// Check `dest` against the digest of the source it was copied from, removing it
+/// on a mismatch so the retry starts clean.
+async fn verify_copy(dest: &Path, source_digest: &str) -> Result<(), BackupError> {
+    let error = match calculate_file_hash(dest).await {
+        Ok(digest) if digest == source_digest => return Ok(()),
+        Ok(_) => BackupError::ChecksumMismatch,
+        Err(e) => BackupError::ChecksumFailed(e.to_string()),
+    };
+    let _ = file_ops::remove_file(dest).await;
+    Err(error)
+}
+
+/// Copy `src` to `dest`, returning the bytes written and the SHA-256 digest of
+/// the source as it was read.
+async fn copy_file(src: &Path, dest: &Path) -> Result<(u64, String), BackupError> {
     let mut src_file = tokio::fs::File::open(src).await?;
     let mut dest_file = tokio::fs::File::create(dest).await?;
 
     let mut buffer = vec![0_u8; CHUNK_SIZE];
+    let mut hasher = Sha256::new();
     let mut total_bytes = 0_u64;
 
     loop {
@@ -476,6 +951,7 @@ async fn copy_file(src: &Path, dest: &Path) -> Result<u64, BackupError> {
             break;
         }
 
+        hasher.update(&buffer[..bytes_read]);
         dest_file.write_all(&buffer[..bytes_read]).await?;
 
         total_bytes += bytes_read as u64;
@@ -483,7 +959,7 @@ async fn copy_file(src: &Path, dest: &Path) -> Result<u64, BackupError> {
 
     dest_file.sync_all().await?;
 
-    Ok(total_bytes)
+    Ok((total_bytes, hex_encode(&hasher.finalize())))
 }
 
 // Global mutex for backup history file access
@@ -501,12 +977,12 @@ fn save_backup_to_history(job: &BackupJob) -> Result<(), BackupError> {
 
     let history_path = history_dir.join("backup_history.json");
 
-    let mut history: Vec<BackupHistory> = if history_path.exists() {
-        let data = fs::read_to_string(&history_path)?;
-        serde_json::from_str(&data)?
-    } else {
-        Vec::new()
-    };
+    let mut history: Vec<BackupHistory> = read_json_with_backup(&history_path)?.unwrap_or_default();
+
+    let started_at = job.started_at.clone().unwrap_or_default();
+    let completed_at = job.completed_at.clone().unwrap_or_default();
+    let (duration_secs, avg_bytes_per_sec) =
+        backup_throughput(&started_at, &completed_at, job.bytes_transferred);
 
     let entry = BackupHistory {
         id: job.id.clone(),
@@ -517,19 +993,17 @@ fn save_backup_to_history(job: &BackupJob) -> Result<(), BackupError> {
         files_copied: job.files_copied,
         files_skipped: job.files_skipped,
         total_bytes: job.bytes_transferred,
-        started_at: job.started_at.clone().unwrap_or_default(),
-        completed_at: job.completed_at.clone().unwrap_or_default(),
+        started_at,
+        completed_at,
         status: job.status.clone(),
         error_message: job.error_message.clone(),
+        duration_secs,
+        avg_bytes_per_sec,
     };
 
     history.push(entry);
 
-    let json_data = serde_json::to_string_pretty(&history)?;
-
-    let mut file = fs::File::create(&history_path)?;
-    file.write_all(json_data.as_bytes())?;
-    file.sync_all()?;
+    write_json_atomic(&history_path, &history)?;
 
     Ok(())
 }
@@ -537,6 +1011,7 @@ fn save_backup_to_history(job: &BackupJob) -> Result<(), BackupError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::modules::file_utils::collect_files_recursive;
 
     // Global mutex to serialize tests that manipulate HOME environment variable
     lazy_static::lazy_static! {
@@ -587,6 +1062,8 @@ mod tests {
             started_at: None,
             completed_at: None,
             error_message: None,
+            same_volume_warning: false,
+            group_id: None,
         };
 
         let json = serde_json::to_string(&job).unwrap();
@@ -629,6 +1106,8 @@ mod tests {
             completed_at: "2024-01-01T11:00:00Z".to_owned(),
             status: BackupStatus::Completed,
             error_message: None,
+            duration_secs: None,
+            avg_bytes_per_sec: None,
         };
 
         let json = serde_json::to_string(&history).unwrap();
@@ -656,6 +1135,8 @@ mod tests {
             started_at: Some("2024-01-01T10:00:00Z".to_owned()),
             completed_at: Some("2024-01-01T10:30:00Z".to_owned()),
             error_message: Some("Disk full".to_owned()),
+            same_volume_warning: false,
+            group_id: None,
         };
 
         let json = serde_json::to_string(&job).unwrap();
@@ -695,6 +1176,203 @@ mod tests {
         let _ = remove_backup_job_impl(&state.backup_queue, job.id).await;
     }
 
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn test_queue_backup_warns_about_same_volume() {
+        use tempfile::TempDir;
+
+        let state = crate::state::AppState::default();
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("project");
+        std::fs::create_dir(&source).unwrap();
+        std::fs::write(source.join("file1.txt"), "test data").unwrap();
+
+        let job = queue_backup_impl(
+            &state.backup_queue,
+            "proj-123".to_owned(),
+            "Backup Test".to_owned(),
+            source.to_string_lossy().to_string(),
+            "dest-456".to_owned(),
+            "Same Disk".to_owned(),
+            temp_dir
+                .path()
+                .join("Backups")
+                .to_string_lossy()
+                .to_string(),
+        )
+        .await
+        .unwrap();
+
+        assert!(job.same_volume_warning);
+        assert!(state.backup_queue.lock().await[&job.id].same_volume_warning);
+    }
+
+    #[tokio::test]
+    async fn test_queue_backup_multi_links_jobs() {
+        use tempfile::TempDir;
+
+        let state = crate::state::AppState::default();
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("project");
+        std::fs::create_dir(&source).unwrap();
+        std::fs::write(source.join("file1.txt"), "test data").unwrap();
+
+        let destinations: Vec<BackupDestination> = ["Drive A", "Drive B", "Cloud Sync"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| BackupDestination {
+                id: format!("dest-{i}"),
+                name: (*name).to_owned(),
+                path: format!("/backups/{i}"),
+            })
+            .collect();
+
+        let jobs = queue_backup_multi_impl(
+            &state.backup_queue,
+            "proj-123".to_owned(),
+            "Backup Test".to_owned(),
+            source.to_string_lossy().to_string(),
+            destinations,
+        )
+        .await
+        .unwrap();
+
+        assert_eq!(jobs.len(), 3);
+        let group_id = jobs[0].group_id.clone().unwrap();
+        assert!(jobs
+            .iter()
+            .all(|job| job.group_id.as_ref() == Some(&group_id)
+                && job.status == BackupStatus::Pending
+                && job.total_files == 1));
+        assert_eq!(state.backup_queue.lock().await.len(), 3);
+
+        let group = get_backup_group_impl(&state.backup_queue, &group_id)
+            .await
+            .unwrap();
+        let names: Vec<&str> = group.iter().map(|j| j.destination_name.as_str()).collect();
+        assert_eq!(names, ["Cloud Sync", "Drive A", "Drive B"]);
+
+        assert!(get_backup_group_impl(&state.backup_queue, "missing")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_backup_group_cancels_all_jobs() {
+        use tempfile::TempDir;
+
+        let state = crate::state::AppState::default();
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("project");
+        std::fs::create_dir(&source).unwrap();
+        std::fs::write(source.join("file1.txt"), "test data").unwrap();
+        let destinations: Vec<BackupDestination> = ["Drive A", "Drive B", "Cloud Sync"]
+            .iter()
+            .enumerate()
+            .map(|(i, name)| BackupDestination {
+                id: format!("dest-{i}"),
+                name: (*name).to_owned(),
+                path: format!("/backups/{i}"),
+            })
+            .collect();
+        let jobs = queue_backup_multi_impl(
+            &state.backup_queue,
+            "proj-123".to_owned(),
+            "Backup Test".to_owned(),
+            source.to_string_lossy().to_string(),
+            destinations,
+        )
+        .await
+        .unwrap();
+        let group_id = jobs[0].group_id.clone().unwrap();
+        let other = queue_backup_impl(
+            &state.backup_queue,
+            "proj-456".to_owned(),
+            "Other".to_owned(),
+            source.to_string_lossy().to_string(),
+            "dest-x".to_owned(),
+            "Drive X".to_owned(),
+            "/backups/x".to_owned(),
+        )
+        .await
+        .unwrap();
+
+        // One sibling is already copying
+        let running = jobs[0].id.clone();
+        if let Some(job) = state.backup_queue.lock().await.get_mut(&running) {
+            job.status = BackupStatus::InProgress;
+        }
+        let token = CancellationToken::new();
+        state
+            .backup_tokens
+            .lock()
+            .await
+            .insert(running.clone(), token.clone());
+
+        let cancelled =
+            cancel_backup_group_impl(&state.backup_queue, &state.backup_tokens, &group_id)
+                .await
+                .unwrap();
+
+        assert_eq!(cancelled, 3);
+        assert!(token.is_cancelled());
+        let queue = state.backup_queue.lock().await;
+        assert!(jobs[1..]
+            .iter()
+            .all(|job| queue[&job.id].status == BackupStatus::Cancelled));
+        assert_eq!(queue[&other.id].status, BackupStatus::Pending);
+        drop(queue);
+
+        assert!(
+            cancel_backup_group_impl(&state.backup_queue, &state.backup_tokens, "missing")
+                .await
+                .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_queue_backup_multi_rejects_bad_destinations() {
+        use tempfile::TempDir;
+
+        let state = crate::state::AppState::default();
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().to_string_lossy().to_string();
+        let destination = BackupDestination {
+            id: "dest-1".to_owned(),
+            name: "Drive A".to_owned(),
+            path: "/backups/a".to_owned(),
+        };
+
+        let empty = queue_backup_multi_impl(
+            &state.backup_queue,
+            "proj-123".to_owned(),
+            "Backup Test".to_owned(),
+            source.clone(),
+            Vec::new(),
+        )
+        .await;
+        assert!(empty.is_err());
+
+        let duplicate = queue_backup_multi_impl(
+            &state.backup_queue,
+            "proj-123".to_owned(),
+            "Backup Test".to_owned(),
+            source,
+            vec![destination.clone(), destination],
+        )
+        .await;
+        assert_eq!(
+            duplicate.unwrap_err(),
+            "Destination listed twice: /backups/a"
+        );
+        assert!(state.backup_queue.lock().await.is_empty());
+    }
+
+    #[test]
+    fn test_unresolvable_paths_are_not_same_volume() {
+        assert!(!is_same_volume("", ""));
+    }
+
     #[tokio::test]
     async fn test_get_backup_queue() {
         use tempfile::TempDir;
@@ -916,6 +1594,8 @@ mod tests {
                 started_at: None,
                 completed_at: None,
                 error_message: None,
+                same_volume_warning: false,
+                group_id: None,
             };
             assert_eq!(job.status, status);
         }
@@ -1029,6 +1709,8 @@ mod tests {
             started_at: Some("2024-01-01T10:00:00Z".to_owned()),
             completed_at: Some("2024-01-01T10:10:00Z".to_owned()),
             error_message: Some("2 files skipped".to_owned()),
+            same_volume_warning: false,
+            group_id: None,
         };
 
         assert_eq!(job.files_copied + job.files_skipped, job.total_files);
@@ -1098,6 +1780,8 @@ mod tests {
             started_at: None,
             completed_at: None,
             error_message: None,
+            same_volume_warning: false,
+            group_id: None,
         };
 
         let job2 = BackupJob {
@@ -1118,6 +1802,8 @@ mod tests {
             started_at: None,
             completed_at: None,
             error_message: None,
+            same_volume_warning: false,
+            group_id: None,
         };
 
         // IDs should be unique
@@ -1141,7 +1827,33 @@ mod tests {
 
         let content = std::fs::read(&dest).unwrap();
         assert_eq!(content, test_data);
-        assert_eq!(result.unwrap(), test_data.len() as u64);
+        let (size, digest) = result.unwrap();
+        assert_eq!(size, test_data.len() as u64);
+        assert_eq!(digest, calculate_file_hash(&src).await.unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_verify_copy_catches_corrupted_destination() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("source.cr3");
+        let dest = temp_dir.path().join("dest.cr3");
+        std::fs::write(&src, vec![7_u8; 64 * 1024]).unwrap();
+
+        let (_, digest) = copy_file(&src, &dest).await.unwrap();
+        verify_copy(&dest, &digest).await.unwrap();
+
+        // Flip a byte in the copy, as a failing drive might
+        let mut corrupted = std::fs::read(&dest).unwrap();
+        corrupted[1000] ^= 0xFF;
+        std::fs::write(&dest, corrupted).unwrap();
+
+        let result = verify_copy(&dest, &digest).await;
+        assert!(matches!(result, Err(BackupError::ChecksumMismatch)));
+        assert!(
+            !dest.exists(),
+            "corrupted copy should be removed before retrying"
+        );
     }
 
     #[tokio::test]
@@ -1158,7 +1870,7 @@ mod tests {
         let result = copy_file(&src, &dest).await;
         assert!(result.is_ok());
         let expected_size = data.len() as u64;
-        assert_eq!(result.unwrap(), expected_size);
+        assert_eq!(result.unwrap().0, expected_size);
 
         let dest_size = std::fs::metadata(&dest).unwrap().len();
         assert_eq!(dest_size, expected_size);
@@ -1177,7 +1889,7 @@ mod tests {
 
         std::fs::write(&src, b"photo data").unwrap();
 
-        let result = copy_file_with_retry(&src, &dest).await;
+        let result = copy_file_with_retry(&src, &dest, &JobLog::disabled()).await;
         assert!(result.is_ok());
         assert!(dest.exists());
 
@@ -1197,7 +1909,7 @@ mod tests {
         let content = b"checksum test data 12345";
         std::fs::write(&src, content).unwrap();
 
-        let result = copy_file_with_retry(&src, &dest).await;
+        let result = copy_file_with_retry(&src, &dest, &JobLog::disabled()).await;
         assert!(result.is_ok());
 
         // Verify checksum matches
@@ -1206,6 +1918,298 @@ mod tests {
         assert_eq!(src_data, dest_data);
     }
 
+    #[tokio::test]
+    async fn test_completed_backup_writes_job_log() {
+        use crate::modules::job_log::read_job_log;
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("Wedding");
+        std::fs::create_dir_all(source.join("RAW")).unwrap();
+        std::fs::write(source.join("RAW").join("IMG_0001.CR3"), b"raw").unwrap();
+        std::fs::write(source.join("notes.txt"), b"notes").unwrap();
+        let logs = temp_dir.path().join("logs");
+        let queue = crate::state::BackupQueue::default();
+        let job = queue_backup_impl(
+            &queue,
+            "proj-1".to_owned(),
+            "Wedding".to_owned(),
+            source.to_string_lossy().to_string(),
+            "drive-1".to_owned(),
+            "Drive".to_owned(),
+            temp_dir.path().join("Drive").to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        let log = JobLog::open_in(&logs, &job.id).unwrap();
+        let result = perform_backup(
+            &job.id,
+            &job,
+            &CancellationToken::new(),
+            &log,
+            |_| {},
+            |_: &Path| Ok(u64::MAX),
+        )
+        .await;
+
+        assert_eq!(result.unwrap().0, 2);
+        let contents = read_job_log(&logs, &job.id).unwrap();
+        let copied: Vec<_> = contents
+            .lines()
+            .filter(|line| line.contains(" Copied "))
+            .collect();
+        assert_eq!(copied.len(), 2);
+        assert!(copied.iter().any(|line| line.contains("IMG_0001.CR3")));
+        assert!(copied.iter().any(|line| line.contains("notes.txt")));
+    }
+
+    #[tokio::test]
+    async fn test_perform_backup_skips_ignored_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("Wedding");
+        std::fs::create_dir_all(source.join("RAW")).unwrap();
+        std::fs::create_dir_all(source.join("Cache").join("previews")).unwrap();
+        std::fs::write(source.join("RAW").join("IMG_0001.CR3"), b"raw").unwrap();
+        std::fs::write(source.join("RAW").join("IMG_0001.tmp"), b"scratch").unwrap();
+        std::fs::write(source.join("export.tmp"), b"scratch").unwrap();
+        std::fs::write(
+            source.join("Cache").join("previews").join("p1.jpg"),
+            b"preview",
+        )
+        .unwrap();
+        std::fs::write(
+            source.join(".creatoropsignore"),
+            "# scratch files\n*.tmp\nCache/\n",
+        )
+        .unwrap();
+        let destination = temp_dir.path().join("Drive");
+        let queue = crate::state::BackupQueue::default();
+        let job = queue_backup_impl(
+            &queue,
+            "proj-1".to_owned(),
+            "Wedding".to_owned(),
+            source.to_string_lossy().to_string(),
+            "drive-1".to_owned(),
+            "Drive".to_owned(),
+            destination.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        // Totals only count the raw file and the ignore file itself
+        assert_eq!(job.total_files, 2);
+        assert_eq!(
+            job.total_bytes,
+            3 + "# scratch files\n*.tmp\nCache/\n".len() as u64
+        );
+
+        let log = JobLog::open_in(&temp_dir.path().join("logs"), &job.id).unwrap();
+        let (copied, skipped, _) = perform_backup(
+            &job.id,
+            &job,
+            &CancellationToken::new(),
+            &log,
+            |_| {},
+            |_: &Path| Ok(u64::MAX),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!((copied, skipped), (2, 0));
+        let backup = destination.join("Wedding");
+        assert!(backup.join("RAW").join("IMG_0001.CR3").is_file());
+        assert!(!backup.join("RAW").join("IMG_0001.tmp").exists());
+        assert!(!backup.join("export.tmp").exists());
+        assert!(!backup.join("Cache").exists());
+
+        // Ignored files aren't reported missing when the backup is verified
+        let report = verify_backup_files(&source, &destination, |_| {}).unwrap();
+        assert!(report.passed);
+        assert_eq!(report.matched, 2);
+    }
+
+    #[test]
+    fn test_start_backup_blocking_completes() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("Wedding");
+        std::fs::create_dir_all(source.join("RAW")).unwrap();
+        std::fs::write(source.join("RAW").join("IMG_0001.CR3"), b"raw photo").unwrap();
+        std::fs::write(source.join("notes.txt"), b"notes").unwrap();
+        let destination = temp_dir.path().join("Drive");
+        let state = crate::state::AppState::default();
+
+        // History and the job log are written under HOME
+        let _lock = HOME_TEST_MUTEX.lock().unwrap();
+        let original_home = std::env::var_os("HOME");
+        std::env::set_var("HOME", temp_dir.path());
+
+        let runtime = tokio::runtime::Runtime::new().unwrap();
+        let (queued, finished) = runtime.block_on(async {
+            let queued = queue_backup_impl(
+                &state.backup_queue,
+                "proj-1".to_owned(),
+                "Wedding".to_owned(),
+                source.to_string_lossy().to_string(),
+                "drive-1".to_owned(),
+                "Drive".to_owned(),
+                destination.to_string_lossy().to_string(),
+            )
+            .await
+            .unwrap();
+            let finished = start_backup_blocking_impl(
+                &state.backup_queue,
+                &state.backup_tokens,
+                &queued.id,
+                |_| {},
+            )
+            .await;
+            (queued, finished)
+        });
+
+        if let Some(home) = original_home {
+            std::env::set_var("HOME", home);
+        } else {
+            std::env::remove_var("HOME");
+        }
+
+        let job = finished.unwrap();
+        assert_eq!(job.id, queued.id);
+        assert_eq!(job.status, BackupStatus::Completed);
+        assert_eq!(job.files_copied, 2);
+        assert_eq!(job.files_skipped, 0);
+        assert_eq!(job.bytes_transferred, 14);
+        assert!(job.completed_at.is_some());
+        assert!(destination.join("Wedding/RAW/IMG_0001.CR3").is_file());
+        assert!(temp_dir
+            .path()
+            .join("CreatorOps/logs")
+            .join(format!("{}.log", job.id))
+            .is_file());
+
+        // A finished job can't be started again
+        let again = runtime.block_on(start_backup_blocking_impl(
+            &state.backup_queue,
+            &state.backup_tokens,
+            &job.id,
+            |_| {},
+        ));
+        assert_eq!(again.unwrap_err(), "Backup job is not pending");
+    }
+
+    #[test]
+    fn test_check_free_space_threshold() {
+        let dest = Path::new("/backups");
+
+        assert!(check_free_space(|_: &Path| Ok(100), dest, 100).is_ok());
+        assert!(matches!(
+            check_free_space(|_: &Path| Ok(99), dest, 100),
+            Err(BackupError::InsufficientSpace(99))
+        ));
+        // A volume that can't be queried doesn't stop the backup
+        assert!(check_free_space(|_: &Path| Err("unsupported".to_owned()), dest, 100).is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_backup_stops_when_free_space_runs_low() {
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("Wedding");
+        std::fs::create_dir_all(&source).unwrap();
+        for i in 0..SPACE_CHECK_INTERVAL + 5 {
+            std::fs::write(source.join(format!("IMG_{i:04}.JPG")), b"photo").unwrap();
+        }
+        let destination = temp_dir.path().join("Drive");
+        let queue = crate::state::BackupQueue::default();
+        let job = queue_backup_impl(
+            &queue,
+            "proj-1".to_owned(),
+            "Wedding".to_owned(),
+            source.to_string_lossy().to_string(),
+            "drive-1".to_owned(),
+            "Drive".to_owned(),
+            destination.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        // Plenty of space at the first check, then less than the 25 bytes left
+        let checks = std::sync::atomic::AtomicUsize::new(0);
+        let free_space = |_: &Path| {
+            let check = checks.fetch_add(1, std::sync::atomic::Ordering::Relaxed);
+            Ok(if check == 0 { u64::MAX } else { 10 })
+        };
+        let result = perform_backup(
+            &job.id,
+            &job,
+            &CancellationToken::new(),
+            &JobLog::disabled(),
+            |_| {},
+            free_space,
+        )
+        .await;
+
+        assert!(matches!(result, Err(BackupError::InsufficientSpace(10))));
+        let copied = collect_files_recursive(&destination).unwrap();
+        assert_eq!(copied.len(), SPACE_CHECK_INTERVAL);
+    }
+
+    #[tokio::test]
+    async fn test_small_backup_fits_on_nearly_full_drive() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("Wedding");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join("IMG_0001.JPG"), vec![0_u8; 4096]).unwrap();
+        let destination = temp_dir.path().join("Drive");
+        let queue = crate::state::BackupQueue::default();
+        let job = queue_backup_impl(
+            &queue,
+            "proj-1".to_owned(),
+            "Wedding".to_owned(),
+            source.to_string_lossy().to_string(),
+            "drive-1".to_owned(),
+            "Drive".to_owned(),
+            destination.to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        // Far below MIN_FREE_SPACE, but more than the backup needs
+        let result = perform_backup(
+            &job.id,
+            &job,
+            &CancellationToken::new(),
+            &JobLog::disabled(),
+            |_| {},
+            |_: &Path| Ok(8192),
+        )
+        .await;
+
+        assert_eq!(result.unwrap().0, 1);
+        assert!(destination.join("Wedding").join("IMG_0001.JPG").is_file());
+    }
+
+    #[tokio::test]
+    async fn test_copy_failures_are_logged_per_attempt() {
+        use crate::modules::job_log::read_job_log;
+        use tempfile::TempDir;
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("missing.jpg");
+        let dest = temp_dir.path().join("dest.jpg");
+        let log = JobLog::open_in(temp_dir.path(), "job-1").unwrap();
+
+        assert!(copy_file_with_retry(&src, &dest, &log).await.is_err());
+
+        let contents = read_job_log(temp_dir.path(), "job-1").unwrap();
+        assert_eq!(
+            contents
+                .lines()
+                .filter(|line| line.contains("failed for") && line.contains("missing.jpg"))
+                .count(),
+            MAX_RETRY_ATTEMPTS + 1
+        );
+    }
+
     #[tokio::test]
     async fn test_save_backup_to_history() {
         use tempfile::TempDir;
@@ -1236,6 +2240,8 @@ mod tests {
             started_at: Some("2024-01-01T00:01:00Z".to_owned()),
             completed_at: Some("2024-01-01T00:02:00Z".to_owned()),
             error_message: None,
+            same_volume_warning: false,
+            group_id: None,
         };
 
         let result = save_backup_to_history(&job);
@@ -1291,6 +2297,8 @@ mod tests {
                 started_at: Some("2024-01-01T00:01:00Z".to_owned()),
                 completed_at: Some("2024-01-01T00:02:00Z".to_owned()),
                 error_message: None,
+                same_volume_warning: false,
+                group_id: None,
             };
 
             save_backup_to_history(&job1).unwrap();
@@ -1314,6 +2322,8 @@ mod tests {
                 started_at: Some("2024-01-02T00:01:00Z".to_owned()),
                 completed_at: Some("2024-01-02T00:02:00Z".to_owned()),
                 error_message: None,
+                same_volume_warning: false,
+                group_id: None,
             };
 
             save_backup_to_history(&job2).unwrap();
@@ -1401,6 +2411,8 @@ mod tests {
             completed_at: "2024-01-01T00:01:00Z".to_owned(),
             status: BackupStatus::Completed,
             error_message: None,
+            duration_secs: None,
+            avg_bytes_per_sec: None,
         };
 
         assert_eq!(history.id, "hist-1");
@@ -1413,6 +2425,30 @@ mod tests {
         assert!(history.error_message.is_none());
     }
 
+    #[test]
+    fn test_backup_throughput() {
+        let (duration, rate) = backup_throughput("1704103200", "1704103300", 500_000_000);
+        assert_eq!(duration, Some(100.0));
+        assert_eq!(rate, Some(5_000_000));
+
+        // Timestamps are whole seconds, as written by `get_timestamp`
+        let now = get_timestamp();
+        let later = (now.parse::<u64>().unwrap() + 4).to_string();
+        assert_eq!(
+            backup_throughput(&now, &later, 1000),
+            (Some(4.0), Some(250))
+        );
+
+        // Sub-second runs have no rate; unknown or reversed times have neither
+        assert_eq!(backup_throughput(&now, &now, 1000), (Some(0.0), None));
+        assert_eq!(backup_throughput("", &now, 1000), (None, None));
+        assert_eq!(
+            backup_throughput("2024-01-01T10:00:00Z", "2024-01-01T10:01:40Z", 1000),
+            (None, None)
+        );
+        assert_eq!(backup_throughput(&later, &now, 1000), (None, None));
+    }
+
     #[tokio::test]
     async fn test_backup_job_timestamps() {
         use tempfile::TempDir;
@@ -1545,4 +2581,59 @@ mod tests {
         assert_eq!(job.project_id, "proj-1");
         assert!(matches!(job.status, BackupStatus::Pending));
     }
+
+    /// Source tree with two files and an exact copy laid out the way a backup
+    /// to `Backups` writes it.
+    fn backed_up_project(temp_dir: &tempfile::TempDir) -> (PathBuf, PathBuf) {
+        let source = temp_dir.path().join("Smith");
+        let destination = temp_dir.path().join("Backups");
+        for root in [source.clone(), destination.join("Smith")] {
+            std::fs::create_dir_all(root.join("RAW")).unwrap();
+            std::fs::write(root.join("notes.txt"), "shot list").unwrap();
+            std::fs::write(root.join("RAW/IMG_0001.CR3"), "raw data").unwrap();
+        }
+        (source, destination)
+    }
+
+    #[test]
+    fn test_verify_backup_passes_for_matching_tree() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (source, destination) = backed_up_project(&temp_dir);
+
+        let mut progress = Vec::new();
+        let report = verify_backup_files(&source, &destination, |p| {
+            progress.push((p.current_file, p.total_files));
+        })
+        .unwrap();
+
+        assert_eq!(
+            report,
+            VerifyReport {
+                matched: 2,
+                passed: true,
+                ..VerifyReport::default()
+            }
+        );
+        assert_eq!(progress, [(1, 2), (2, 2)]);
+    }
+
+    #[test]
+    fn test_verify_backup_reports_missing_and_corrupted_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (source, destination) = backed_up_project(&temp_dir);
+        std::fs::write(destination.join("Smith/RAW/IMG_0001.CR3"), "bit rot").unwrap();
+        std::fs::remove_file(destination.join("Smith/notes.txt")).unwrap();
+
+        let report = verify_backup_files(&source, &destination, |_| {}).unwrap();
+
+        assert_eq!(report.matched, 0);
+        assert_eq!(report.mismatched, ["RAW/IMG_0001.CR3"]);
+        assert_eq!(report.missing, ["notes.txt"]);
+        assert!(!report.passed);
+        // Verification never touches the backup
+        assert_eq!(
+            std::fs::read_to_string(destination.join("Smith/RAW/IMG_0001.CR3")).unwrap(),
+            "bit rot"
+        );
+    }
 }
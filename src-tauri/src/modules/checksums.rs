@@ -0,0 +1,408 @@
+//! SHA-256 checksum manifests for long-term integrity checks.
+//!
+//! A manifest is a `checksums.sha256` file at the root of a backup or archive with
+//! one `HEX  relative/path` line per file — the format `sha256sum -c` reads — so
+//! the copy can be checked years later, with or without `CreatorOps`.
+//!
+//! Digests can also be remembered in the database's checksum cache, keyed by
+//! absolute path, size and modification time, so scans that revisit unchanged
+//! files don't read them again.
+
+use crate::modules::db::Database;
+use crate::modules::file_utils::hex_encode;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// File name of the manifest written at the root of a backup or archive.
+pub const MANIFEST_FILE_NAME: &str = "checksums.sha256";
+
+/// Result of checking a tree against its manifest.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestReport {
+    /// Files whose digest matched.
+    pub verified: usize,
+    /// Files whose contents no longer match the manifest.
+    pub mismatched: Vec<String>,
+    /// Files listed in the manifest but absent from the tree.
+    pub missing: Vec<String>,
+    /// Files in the tree the manifest doesn't list.
+    pub extra: Vec<String>,
+    /// Whether every file matched and nothing is missing or extra.
+    pub passed: bool,
+}
+
+/// Reader that feeds every byte it returns into a SHA-256 hasher.
+pub struct HashingReader<R> {
+    inner: R,
+    hasher: Sha256,
+}
+
+impl<R: Read> HashingReader<R> {
+    /// Wrap `inner`, hashing what is read through it.
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            hasher: Sha256::new(),
+        }
+    }
+
+    /// Hex digest of everything read so far.
+    pub fn finish(self) -> String {
+        hex_encode(&self.hasher.finalize())
+    }
+}
+
+impl<R: Read> Read for HashingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        self.hasher.update(&buf[..bytes_read]);
+        Ok(bytes_read)
+    }
+}
+
+/// Hex SHA-256 digest of a file.
+///
+/// # Errors
+///
+/// Returns error if the file cannot be read
+pub fn hash_file(path: &Path) -> Result<String, String> {
+    let file = fs::File::open(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let mut reader = HashingReader::new(file);
+    std::io::copy(&mut reader, &mut std::io::sink())
+        .map_err(|e| format!("{}: {e}", path.display()))?;
+    Ok(reader.finish())
+}
+
+/// Hex SHA-256 digest of a file, taken from the checksum cache while the
+/// file's size and modification time match the cached entry.
+///
+/// # Errors
+///
+/// Returns error if the file cannot be read
+pub fn cached_hash_file(db: &Database, path: &Path) -> Result<String, String> {
+    cached_hash_with(db, path, hash_file)
+}
+
+/// [`cached_hash_file`] computing cache misses with `hash`.
+///
+/// The cache only saves work: if it can't be read or written the digest is
+/// still computed and returned.
+///
+/// # Errors
+///
+/// Returns error if the file's metadata cannot be read or `hash` fails
+pub fn cached_hash_with<F>(db: &Database, path: &Path, hash: F) -> Result<String, String>
+where
+    F: FnOnce(&Path) -> Result<String, String>,
+{
+    let metadata = fs::metadata(path).map_err(|e| format!("{}: {e}", path.display()))?;
+    let key = fs::canonicalize(path)
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .to_string();
+    let size = i64::try_from(metadata.len()).unwrap_or(i64::MAX);
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|time| time.duration_since(std::time::UNIX_EPOCH).ok())
+        .map_or(0, |since| {
+            i64::try_from(since.as_nanos()).unwrap_or(i64::MAX)
+        });
+
+    let cached = db.execute(|conn| {
+        Ok(conn
+            .query_row(
+                "SELECT checksum FROM checksum_cache WHERE path = ?1 AND size = ?2 AND modified = ?3",
+                params![key, size, modified],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?)
+    });
+    match cached {
+        Ok(Some(checksum)) => return Ok(checksum),
+        Ok(None) => {}
+        Err(e) => log::warn!("Checksum cache lookup failed: {e}"),
+    }
+
+    let checksum = hash(path)?;
+    let stored = db.execute(|conn| {
+        conn.execute(
+            "INSERT INTO checksum_cache (path, size, modified, checksum) VALUES (?1, ?2, ?3, ?4)
+             ON CONFLICT(path) DO UPDATE SET size = excluded.size,
+              modified = excluded.modified, checksum = excluded.checksum",
+            params![key, size, modified, checksum],
+        )?;
+        Ok(())
+    });
+    if let Err(e) = stored {
+        log::warn!("Failed to cache checksum of {}: {e}", path.display());
+    }
+
+    Ok(checksum)
+}
+
+/// Manifest path of `relative`, always with `/` separators.
+fn manifest_path_of(relative: &Path) -> String {
+    relative.to_string_lossy().replace('\\', "/")
+}
+
+/// One manifest line for a file with the given digest.
+pub fn manifest_line(digest: &str, relative: &Path) -> String {
+    format!("{digest}  {}\n", manifest_path_of(relative))
+}
+
+/// Files under `root` in a stable order, leaving out a manifest at its top level.
+pub fn manifest_files(root: &Path) -> Vec<PathBuf> {
+    let own_manifest = root.join(MANIFEST_FILE_NAME);
+    let mut files: Vec<PathBuf> = WalkDir::new(root)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .map(walkdir::DirEntry::into_path)
+        .filter(|path| *path != own_manifest)
+        .collect();
+    files.sort();
+    files
+}
+
+/// Hash every file under `root` and write `root/checksums.sha256`.
+///
+/// # Errors
+///
+/// Returns error if a file cannot be read or the manifest cannot be written
+pub fn write_manifest(root: &Path) -> Result<PathBuf, String> {
+    let mut manifest = String::new();
+    for path in manifest_files(root) {
+        let relative = path.strip_prefix(root).map_err(|e| e.to_string())?;
+        manifest.push_str(&manifest_line(&hash_file(&path)?, relative));
+    }
+
+    let manifest_path = root.join(MANIFEST_FILE_NAME);
+    fs::write(&manifest_path, manifest)
+        .map_err(|e| format!("Failed to write {}: {e}", manifest_path.display()))?;
+    Ok(manifest_path)
+}
+
+/// Expected digests keyed by manifest path.
+pub type ManifestEntries = BTreeMap<String, String>;
+
+/// Parse manifest text into relative path → digest.
+///
+/// Accepts the `*` binary-mode marker `sha256sum -b` writes before the path.
+///
+/// # Errors
+///
+/// Returns error on a line that isn't a digest followed by a path
+pub fn parse_manifest(contents: &str) -> Result<ManifestEntries, String> {
+    let mut entries = BTreeMap::new();
+
+    for (index, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let (digest, path) = line
+            .split_once(' ')
+            .filter(|(digest, _)| {
+                digest.len() == 64 && digest.chars().all(|c| c.is_ascii_hexdigit())
+            })
+            .ok_or_else(|| format!("Invalid manifest line {}: {line}", index + 1))?;
+        let path = path
+            .strip_prefix(' ')
+            .or_else(|| path.strip_prefix('*'))
+            .unwrap_or(path);
+
+        entries.insert(path.to_owned(), digest.to_ascii_lowercase());
+    }
+
+    Ok(entries)
+}
+
+/// Check every file under `root` against the manifest at `manifest_path`.
+///
+/// # Errors
+///
+/// Returns error if the manifest cannot be read or parsed
+pub fn verify_manifest_at(root: &Path, manifest_path: &Path) -> Result<ManifestReport, String> {
+    let contents = fs::read_to_string(manifest_path)
+        .map_err(|e| format!("Failed to read {}: {e}", manifest_path.display()))?;
+    let mut expected = parse_manifest(&contents)?;
+    let mut report = ManifestReport::default();
+
+    for path in manifest_files(root) {
+        if path == manifest_path {
+            continue;
+        }
+        let relative = manifest_path_of(path.strip_prefix(root).map_err(|e| e.to_string())?);
+
+        match expected.remove(&relative) {
+            Some(digest) if hash_file(&path)? == digest => report.verified += 1,
+            Some(_) => report.mismatched.push(relative),
+            None => report.extra.push(relative),
+        }
+    }
+    report.missing = expected.into_keys().collect();
+    report.passed =
+        report.mismatched.is_empty() && report.missing.is_empty() && report.extra.is_empty();
+
+    Ok(report)
+}
+
+/// Recompute the digests of the files under `root` and compare them with a
+/// `checksums.sha256` manifest.
+#[tauri::command]
+pub async fn verify_manifest(
+    root: String,
+    manifest_path: String,
+) -> Result<ManifestReport, String> {
+    tokio::task::spawn_blocking(move || {
+        verify_manifest_at(Path::new(&root), Path::new(&manifest_path))
+    })
+    .await
+    .map_err(|e| format!("Verification task failed: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn create_tree(temp_dir: &TempDir) -> PathBuf {
+        let root = temp_dir.path().join("Smith Wedding");
+        fs::create_dir_all(root.join("RAW")).unwrap();
+        fs::write(root.join("notes.txt"), "ceremony at 3pm").unwrap();
+        fs::write(root.join("RAW").join("IMG_0001.CR3"), vec![1_u8; 4096]).unwrap();
+        root
+    }
+
+    #[test]
+    fn test_write_manifest_format() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = create_tree(&temp_dir);
+
+        let manifest_path = write_manifest(&root).unwrap();
+        assert_eq!(manifest_path, root.join(MANIFEST_FILE_NAME));
+
+        let contents = fs::read_to_string(&manifest_path).unwrap();
+        let lines: Vec<&str> = contents.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("  RAW/IMG_0001.CR3"));
+        assert!(lines[1].ends_with("  notes.txt"));
+        assert_eq!(
+            lines[1].split_once("  ").unwrap().0,
+            hex_encode(&Sha256::digest(b"ceremony at 3pm"))
+        );
+    }
+
+    #[test]
+    fn test_verify_unchanged_tree_passes() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = create_tree(&temp_dir);
+        let manifest_path = write_manifest(&root).unwrap();
+
+        let report = verify_manifest_at(&root, &manifest_path).unwrap();
+        assert!(report.passed);
+        assert_eq!(report.verified, 2);
+    }
+
+    #[test]
+    fn test_verify_tampered_tree_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = create_tree(&temp_dir);
+        let manifest_path = write_manifest(&root).unwrap();
+
+        fs::write(root.join("RAW").join("IMG_0001.CR3"), vec![2_u8; 4096]).unwrap();
+        fs::remove_file(root.join("notes.txt")).unwrap();
+        fs::write(root.join("RAW").join("IMG_0002.CR3"), b"new").unwrap();
+
+        let report = verify_manifest_at(&root, &manifest_path).unwrap();
+        assert!(!report.passed);
+        assert_eq!(report.verified, 0);
+        assert_eq!(report.mismatched, vec!["RAW/IMG_0001.CR3"]);
+        assert_eq!(report.missing, vec!["notes.txt"]);
+        assert_eq!(report.extra, vec!["RAW/IMG_0002.CR3"]);
+    }
+
+    #[test]
+    fn test_verify_manifest_outside_root() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = create_tree(&temp_dir);
+        let manifest_path = write_manifest(&root).unwrap();
+        let elsewhere = temp_dir.path().join("smith.sha256");
+        fs::rename(&manifest_path, &elsewhere).unwrap();
+
+        assert!(verify_manifest_at(&root, &elsewhere).unwrap().passed);
+    }
+
+    #[test]
+    fn test_parse_manifest() {
+        let digest = "a".repeat(64);
+        let entries = parse_manifest(&format!("{digest}  a b.jpg\n{digest} *raw.cr3\n\n")).unwrap();
+        assert_eq!(entries.get("a b.jpg"), Some(&digest));
+        assert_eq!(entries.get("raw.cr3"), Some(&digest));
+
+        assert!(parse_manifest("not-a-digest  file.jpg")
+            .unwrap_err()
+            .contains("line 1"));
+    }
+
+    /// Hash `path` through the cache, counting how often the file is read.
+    fn counted_hash(db: &Database, path: &Path, reads: &std::cell::Cell<usize>) -> String {
+        cached_hash_with(db, path, |path| {
+            reads.set(reads.get() + 1);
+            hash_file(path)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_cached_hash_skips_unchanged_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+        let root = create_tree(&temp_dir);
+        let notes = root.join("notes.txt");
+        let reads = std::cell::Cell::new(0);
+
+        let first = counted_hash(&db, &notes, &reads);
+        assert_eq!(first, hash_file(&notes).unwrap());
+        assert_eq!(counted_hash(&db, &notes, &reads), first);
+        assert_eq!(cached_hash_file(&db, &notes).unwrap(), first);
+        assert_eq!(reads.get(), 1);
+    }
+
+    #[test]
+    fn test_modified_file_busts_checksum_cache() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+        let root = create_tree(&temp_dir);
+        let notes = root.join("notes.txt");
+        let reads = std::cell::Cell::new(0);
+        let original = counted_hash(&db, &notes, &reads);
+
+        // A different size is a miss
+        fs::write(&notes, "ceremony at 4pm, dinner at 7").unwrap();
+        let resized = counted_hash(&db, &notes, &reads);
+        assert_ne!(resized, original);
+        assert_eq!(reads.get(), 2);
+
+        // So is the same size with a new modification time
+        fs::write(&notes, "ceremony at 5pm, dinner at 8").unwrap();
+        let later = std::time::SystemTime::now() + std::time::Duration::from_secs(60);
+        fs::File::options()
+            .write(true)
+            .open(&notes)
+            .unwrap()
+            .set_modified(later)
+            .unwrap();
+        let rewritten = counted_hash(&db, &notes, &reads);
+        assert_eq!(rewritten, hash_file(&notes).unwrap());
+        assert_ne!(rewritten, resized);
+        assert_eq!(reads.get(), 3);
+    }
+}
@@ -9,7 +9,7 @@ use uuid::Uuid;
 
 use crate::error::AppError;
 use crate::modules::db::Database;
-use crate::modules::project::{map_project_row, Project};
+use crate::modules::project::{attach_project_tags, map_project_row, Project};
 
 /// Client status — active or soft-deleted via archival.
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
@@ -77,7 +77,7 @@ pub struct ClientWithProjects {
 
 /// Validate email — checks for exactly one @, non-empty local/domain parts,
 /// and at least one dot in the domain that isn't at the start or end.
-fn validate_email(email: &str) -> bool {
+pub fn validate_email(email: &str) -> bool {
     let at_idx = match email.find('@') {
         Some(i) if !email[i + 1..].contains('@') => i,
         _ => return false,
@@ -238,12 +238,13 @@ pub async fn get_client(
         .execute(|conn| {
             let mut stmt = conn.prepare(
                 "SELECT id, name, client_name, date, shoot_type, status, folder_path,
-                        created_at, updated_at, deadline, client_id
+                        created_at, updated_at, deadline, client_id, client_email, client_phone
                  FROM projects WHERE client_id = ?1 ORDER BY updated_at DESC",
             )?;
-            let rows = stmt
+            let mut rows = stmt
                 .query_map(params![client_id], map_project_row)?
                 .collect::<Result<Vec<_>, _>>()?;
+            attach_project_tags(conn, &mut rows)?;
             Ok(rows)
         })
         .map_err(|e| format!("Failed to load client projects: {e}"))?;
@@ -0,0 +1,434 @@
+//! Contact sheet (proof sheet) PDF generation.
+//!
+//! Lays out image thumbnails in a grid with their filenames, one page per
+//! `columns × rows` block, using the built-in Helvetica font so no font data
+//! has to be embedded. Progress is emitted as the `contact-sheet-progress`
+//! Tauri event.
+
+use pdf_writer::{Content, Filter, Finish, Name, Pdf, Rect, Ref, Str};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Emitter;
+
+use crate::modules::db::Database;
+use crate::modules::project::get_project_by_id;
+use crate::modules::thumbnail::{encode_thumbnail_jpeg, load_thumbnail_image};
+
+/// Blank border around the grid, in points.
+const PAGE_MARGIN: f32 = 36.0;
+/// Space reserved above the grid for the sheet title.
+const HEADER_HEIGHT: f32 = 28.0;
+/// Space reserved below each thumbnail for its filename.
+const CAPTION_HEIGHT: f32 = 14.0;
+/// Gap between a thumbnail and its cell border.
+const CELL_PADDING: f32 = 4.0;
+/// Narrowest cell that still leaves a recognisable thumbnail.
+const MIN_CELL_WIDTH: f32 = 48.0;
+const TITLE_FONT_SIZE: f32 = 14.0;
+const CAPTION_FONT_SIZE: f32 = 7.0;
+/// Average Helvetica glyph width as a fraction of the font size, used to truncate captions.
+const AVERAGE_GLYPH_WIDTH: f32 = 0.5;
+/// Thumbnails are rendered at twice their printed size (~144 dpi).
+const THUMBNAIL_SCALE: f32 = 2.0;
+
+/// Paper size of the generated PDF.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PageSize {
+    #[default]
+    A4,
+    Letter,
+}
+
+impl PageSize {
+    /// Page width and height in points.
+    const fn dimensions(self) -> (f32, f32) {
+        match self {
+            Self::A4 => (595.0, 842.0),
+            Self::Letter => (612.0, 792.0),
+        }
+    }
+}
+
+/// Progress event emitted after each image is placed on the sheet.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactSheetProgress {
+    pub files_processed: usize,
+    pub total_files: usize,
+    pub current_file: String,
+}
+
+/// Summary of a generated contact sheet.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ContactSheet {
+    pub output_path: String,
+    pub page_count: usize,
+    /// Files that could not be decoded; their cells show only the filename.
+    pub skipped_files: Vec<String>,
+}
+
+/// Cell grid for one page size and column count.
+#[derive(Debug, Clone, Copy, PartialEq)]
+struct GridLayout {
+    columns: u32,
+    rows: u32,
+    cell_width: f32,
+    cell_height: f32,
+}
+
+impl GridLayout {
+    const fn cells_per_page(&self) -> usize {
+        (self.columns * self.rows) as usize
+    }
+}
+
+/// Render a PDF proof sheet of `files` for a project.
+#[tauri::command]
+pub async fn create_contact_sheet(
+    app: tauri::AppHandle,
+    db: tauri::State<'_, Database>,
+    project_id: String,
+    files: Vec<String>,
+    output_path: String,
+    columns: u32,
+    page_size: Option<PageSize>,
+) -> Result<ContactSheet, String> {
+    let project = get_project_by_id(&db, &project_id)?;
+    let files: Vec<PathBuf> = files.into_iter().map(PathBuf::from).collect();
+
+    tokio::task::spawn_blocking(move || {
+        write_contact_sheet(
+            &project.name,
+            &files,
+            Path::new(&output_path),
+            columns,
+            page_size.unwrap_or_default(),
+            |progress| {
+                let _ = app.emit("contact-sheet-progress", progress);
+            },
+        )
+    })
+    .await
+    .map_err(|e| format!("Contact sheet task failed: {e}"))?
+}
+
+/// Compute how many rows of `columns` cells fit on a page.
+fn grid_layout(columns: u32, page_size: PageSize) -> Result<GridLayout, String> {
+    if columns == 0 {
+        return Err("Columns must be at least 1".to_owned());
+    }
+
+    let (page_width, page_height) = page_size.dimensions();
+    let cell_width = 2.0_f32.mul_add(-PAGE_MARGIN, page_width) / columns as f32;
+    if cell_width < MIN_CELL_WIDTH {
+        return Err(format!("Too many columns ({columns}) for the page size"));
+    }
+
+    let cell_height = cell_width + CAPTION_HEIGHT;
+    let grid_height = 2.0_f32.mul_add(-PAGE_MARGIN, page_height) - HEADER_HEIGHT;
+    let rows = ((grid_height / cell_height).floor() as u32).max(1);
+
+    Ok(GridLayout {
+        columns,
+        rows,
+        cell_width,
+        cell_height,
+    })
+}
+
+/// Lay out `files` into a PDF at `output_path`, reporting progress after each file.
+fn write_contact_sheet<F>(
+    title: &str,
+    files: &[PathBuf],
+    output_path: &Path,
+    columns: u32,
+    page_size: PageSize,
+    on_progress: F,
+) -> Result<ContactSheet, String>
+where
+    F: Fn(ContactSheetProgress),
+{
+    if files.is_empty() {
+        return Err("No files selected for contact sheet".to_owned());
+    }
+
+    let layout = grid_layout(columns, page_size)?;
+    let (page_width, page_height) = page_size.dimensions();
+    let image_box = 2.0_f32.mul_add(-CELL_PADDING, layout.cell_width);
+    let thumbnail_edge = (image_box * THUMBNAIL_SCALE).ceil() as u32;
+    let page_count = files.len().div_ceil(layout.cells_per_page());
+
+    let mut pdf = Pdf::new();
+    let catalog_id = Ref::new(1);
+    let page_tree_id = Ref::new(2);
+    let font_id = Ref::new(3);
+    let mut next_ref = Ref::new(4);
+    let font_name = Name(b"F1");
+
+    let mut page_ids = Vec::with_capacity(page_count);
+    let mut skipped_files = Vec::new();
+
+    for (page_index, page_files) in files.chunks(layout.cells_per_page()).enumerate() {
+        let page_id = next_ref.bump();
+        let content_id = next_ref.bump();
+        page_ids.push(page_id);
+
+        let mut content = Content::new();
+        content
+            .begin_text()
+            .set_font(font_name, TITLE_FONT_SIZE)
+            .next_line(PAGE_MARGIN, page_height - PAGE_MARGIN - TITLE_FONT_SIZE)
+            .show(Str(pdf_text(&format!(
+                "{title} - Page {} of {page_count}",
+                page_index + 1
+            ))
+            .as_bytes()))
+            .end_text();
+
+        let mut images = Vec::new();
+        for (slot, path) in page_files.iter().enumerate() {
+            let column = (slot as u32 % layout.columns) as f32;
+            let row = (slot as u32 / layout.columns) as f32;
+            let cell_x = column.mul_add(layout.cell_width, PAGE_MARGIN);
+            let cell_top = row.mul_add(
+                -layout.cell_height,
+                page_height - PAGE_MARGIN - HEADER_HEIGHT,
+            );
+            let file_name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default();
+
+            let thumbnail = load_thumbnail_image(path, thumbnail_edge).and_then(|image| {
+                encode_thumbnail_jpeg(&image).map(|jpeg| (image.width(), image.height(), jpeg))
+            });
+            match thumbnail {
+                Ok((width, height, jpeg)) => {
+                    let image_id = next_ref.bump();
+                    let image_name = format!("Im{}", images.len() + 1);
+
+                    let mut xobject = pdf.image_xobject(image_id, &jpeg);
+                    xobject.filter(Filter::DctDecode);
+                    xobject.width(i32::try_from(width).unwrap_or(i32::MAX));
+                    xobject.height(i32::try_from(height).unwrap_or(i32::MAX));
+                    xobject.color_space().device_rgb();
+                    xobject.bits_per_component(8);
+                    xobject.finish();
+
+                    // Fit the image inside the square box, centred horizontally and bottom-aligned
+                    // so captions line up across the row
+                    let scale = image_box / width.max(height) as f32;
+                    let draw_width = width as f32 * scale;
+                    let draw_height = height as f32 * scale;
+                    let x = cell_x + CELL_PADDING + (image_box - draw_width) / 2.0;
+                    let y = cell_top - CELL_PADDING - image_box;
+                    content
+                        .save_state()
+                        .transform([draw_width, 0.0, 0.0, draw_height, x, y])
+                        .x_object(Name(image_name.as_bytes()))
+                        .restore_state();
+
+                    images.push((image_name, image_id));
+                }
+                Err(e) => {
+                    log::warn!("Skipping {} in contact sheet: {e}", path.display());
+                    skipped_files.push(path.to_string_lossy().to_string());
+                }
+            }
+
+            let caption = truncate_caption(&pdf_text(&file_name), image_box);
+            content
+                .begin_text()
+                .set_font(font_name, CAPTION_FONT_SIZE)
+                .next_line(
+                    cell_x + CELL_PADDING,
+                    cell_top - layout.cell_width - CAPTION_FONT_SIZE,
+                )
+                .show(Str(caption.as_bytes()))
+                .end_text();
+
+            on_progress(ContactSheetProgress {
+                files_processed: page_index * layout.cells_per_page() + slot + 1,
+                total_files: files.len(),
+                current_file: file_name,
+            });
+        }
+
+        pdf.stream(content_id, &content.finish());
+
+        let mut page = pdf.page(page_id);
+        page.media_box(Rect::new(0.0, 0.0, page_width, page_height));
+        page.parent(page_tree_id);
+        page.contents(content_id);
+        let mut resources = page.resources();
+        resources.fonts().pair(font_name, font_id);
+        resources
+            .x_objects()
+            .pairs(images.iter().map(|(name, id)| (Name(name.as_bytes()), *id)));
+        resources.finish();
+        page.finish();
+    }
+
+    pdf.catalog(catalog_id).pages(page_tree_id);
+    pdf.pages(page_tree_id)
+        .kids(page_ids.iter().copied())
+        .count(i32::try_from(page_ids.len()).unwrap_or(i32::MAX));
+    pdf.type1_font(font_id).base_font(Name(b"Helvetica"));
+
+    if let Some(parent) = output_path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create {}: {e}", parent.display()))?;
+    }
+    fs::write(output_path, pdf.finish())
+        .map_err(|e| format!("Failed to write {}: {e}", output_path.display()))?;
+
+    Ok(ContactSheet {
+        output_path: output_path.to_string_lossy().to_string(),
+        page_count,
+        skipped_files,
+    })
+}
+
+/// Restrict text to the printable ASCII range Helvetica's standard encoding covers.
+fn pdf_text(text: &str) -> String {
+    text.chars()
+        .map(|c| {
+            if c.is_ascii_graphic() || c == ' ' {
+                c
+            } else {
+                '?'
+            }
+        })
+        .collect()
+}
+
+/// Shorten a caption so it fits within `width` points at the caption font size.
+fn truncate_caption(caption: &str, width: f32) -> String {
+    let max_chars = (width / (CAPTION_FONT_SIZE * AVERAGE_GLYPH_WIDTH)) as usize;
+    if caption.len() <= max_chars {
+        return caption.to_owned();
+    }
+
+    let keep = max_chars.saturating_sub(3);
+    format!("{}...", &caption[..keep])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::RgbImage;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tempfile::TempDir;
+
+    fn write_test_images(dir: &Path, count: usize) -> Vec<PathBuf> {
+        (0..count)
+            .map(|i| {
+                let path = dir.join(format!("IMG_{i:04}.png"));
+                RgbImage::from_fn(60, 40, |x, _| image::Rgb([(x * 4) as u8, 80, 160]))
+                    .save(&path)
+                    .unwrap();
+                path
+            })
+            .collect()
+    }
+
+    /// Count page objects, excluding the `/Type /Pages` tree node.
+    fn count_pages(pdf: &[u8]) -> usize {
+        let needle = b"/Type /Page";
+        pdf.windows(needle.len() + 1)
+            .filter(|w| w.starts_with(needle) && w[needle.len()] != b's')
+            .count()
+    }
+
+    #[test]
+    fn test_grid_layout() {
+        let layout = grid_layout(2, PageSize::A4).unwrap();
+        assert_eq!((layout.columns, layout.rows), (2, 2));
+
+        let layout = grid_layout(1, PageSize::Letter).unwrap();
+        assert_eq!((layout.columns, layout.rows), (1, 1));
+
+        let layout = grid_layout(4, PageSize::A4).unwrap();
+        assert_eq!((layout.columns, layout.rows), (4, 5));
+
+        assert!(grid_layout(0, PageSize::A4).is_err());
+        assert!(grid_layout(20, PageSize::A4).is_err());
+    }
+
+    #[test]
+    fn test_write_contact_sheet_page_count() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = write_test_images(temp_dir.path(), 5);
+
+        // 2 columns x 2 rows on A4: 5 images need 2 pages
+        let output = temp_dir.path().join("sheets").join("proof.pdf");
+        let progress_events = AtomicUsize::new(0);
+        let sheet = write_contact_sheet("Smith Wedding", &files, &output, 2, PageSize::A4, |_| {
+            progress_events.fetch_add(1, Ordering::SeqCst);
+        })
+        .unwrap();
+
+        assert!(output.exists());
+        assert_eq!(sheet.page_count, 2);
+        assert!(sheet.skipped_files.is_empty());
+        assert_eq!(progress_events.load(Ordering::SeqCst), 5);
+        let pdf = fs::read(&output).unwrap();
+        assert!(pdf.starts_with(b"%PDF-"));
+        assert_eq!(count_pages(&pdf), 2);
+
+        // 1 column x 1 row: one image per page
+        let output = temp_dir.path().join("single.pdf");
+        let sheet = write_contact_sheet(
+            "Smith Wedding",
+            &files,
+            &output,
+            1,
+            PageSize::Letter,
+            |_| {},
+        )
+        .unwrap();
+        assert_eq!(sheet.page_count, 5);
+        assert_eq!(count_pages(&fs::read(&output).unwrap()), 5);
+    }
+
+    #[test]
+    fn test_write_contact_sheet_skips_undecodable_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut files = write_test_images(temp_dir.path(), 1);
+        let broken = temp_dir.path().join("broken.jpg");
+        fs::write(&broken, b"not an image").unwrap();
+        files.push(broken.clone());
+
+        let output = temp_dir.path().join("proof.pdf");
+        let sheet = write_contact_sheet("Shoot", &files, &output, 4, PageSize::A4, |_| {}).unwrap();
+
+        assert_eq!(sheet.page_count, 1);
+        assert_eq!(
+            sheet.skipped_files,
+            vec![broken.to_string_lossy().to_string()]
+        );
+    }
+
+    #[test]
+    fn test_write_contact_sheet_requires_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let output = temp_dir.path().join("proof.pdf");
+
+        let result = write_contact_sheet("Shoot", &[], &output, 4, PageSize::A4, |_| {});
+        assert_eq!(result.unwrap_err(), "No files selected for contact sheet");
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn test_truncate_caption() {
+        assert_eq!(truncate_caption("IMG_0001.CR3", 100.0), "IMG_0001.CR3");
+        assert_eq!(
+            truncate_caption("a_very_long_file_name.jpg", 35.0),
+            "a_very_..."
+        );
+        assert_eq!(pdf_text("café.jpg"), "caf?.jpg");
+    }
+}
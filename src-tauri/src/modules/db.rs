@@ -1,11 +1,31 @@
 use crate::error::AppError;
-use rusqlite::Connection;
+use rusqlite::{Connection, OpenFlags};
 use std::path::{Path, PathBuf};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, TryLockError};
+use std::time::Duration;
+
+/// Connections kept open to the database file.
+const POOL_SIZE: usize = 4;
+/// How long a statement waits for another connection's write lock before failing.
+const BUSY_TIMEOUT: Duration = Duration::from_secs(5);
+/// Suffix of a validated backup waiting to replace the database on next open.
+const PENDING_RESTORE_SUFFIX: &str = "restore";
+/// Suffix the replaced database is kept under after a restore.
+const REPLACED_SUFFIX: &str = "before-restore";
+/// Suffix a backup is written under until it's complete.
+const PARTIAL_BACKUP_SUFFIX: &str = "partial";
+/// Suffixes of the database file and its WAL-mode companions.
+const DATABASE_FILE_SUFFIXES: [&str; 3] = ["", "-wal", "-shm"];
 
 /// Database wrapper for dependency injection
+///
+/// Holds a small pool of connections to one file in WAL mode, so reads from one
+/// command don't wait behind another command's write.
 pub struct Database {
-    conn: Mutex<Connection>,
+    path: PathBuf,
+    conns: Vec<Mutex<Connection>>,
+    next: AtomicUsize,
 }
 
 impl Database {
@@ -22,116 +42,136 @@ impl Database {
             std::fs::create_dir_all(parent)?;
         }
 
-        let conn = Connection::open(db_path)?;
+        Self::apply_pending_restore(db_path)?;
+
+        let conn = Self::open_connection(db_path)?;
+
+        // Bring the schema up to date before any other connection sees it
+        Self::run_migrations(&conn)?;
 
-        // Initialize schema
-        Self::init_schema(&conn)?;
+        let mut conns = vec![Mutex::new(conn)];
+        for _ in 1..POOL_SIZE {
+            conns.push(Mutex::new(Self::open_connection(db_path)?));
+        }
 
         Ok(Self {
-            conn: Mutex::new(conn),
+            path: db_path.to_path_buf(),
+            conns,
+            next: AtomicUsize::new(0),
         })
     }
 
-    /// Initialize database schema
-    fn init_schema(conn: &Connection) -> Result<(), AppError> {
-        // Create clients table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS clients (
-                id TEXT PRIMARY KEY,
-                name TEXT UNIQUE NOT NULL,
-                email TEXT,
-                phone TEXT,
-                notes TEXT,
-                status TEXT NOT NULL DEFAULT 'active',
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL
-            )",
-            [],
-        )?;
-
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_clients_name ON clients(name)",
-            [],
-        )?;
-
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_clients_status ON clients(status)",
-            [],
-        )?;
+    /// Open a connection in WAL mode that waits for locks instead of failing.
+    fn open_connection(db_path: &Path) -> Result<Connection, AppError> {
+        let conn = Connection::open(db_path)?;
+        conn.busy_timeout(BUSY_TIMEOUT)?;
+        let _mode: String =
+            conn.pragma_update_and_check(None, "journal_mode", "WAL", |row| row.get(0))?;
+        Ok(conn)
+    }
 
-        // Create projects table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS projects (
-                id TEXT PRIMARY KEY,
-                name TEXT NOT NULL,
-                client_name TEXT NOT NULL,
-                date TEXT NOT NULL,
-                shoot_type TEXT NOT NULL,
-                status TEXT NOT NULL,
-                folder_path TEXT NOT NULL,
-                created_at TEXT NOT NULL,
-                updated_at TEXT NOT NULL,
-                deadline TEXT
-            )",
-            [],
-        )?;
+    /// Apply every migration newer than the database's `user_version`, in order.
+    ///
+    /// Each migration runs in its own transaction together with the version bump,
+    /// so an interrupted upgrade resumes from the last completed step.
+    fn run_migrations(conn: &Connection) -> Result<(), AppError> {
+        let current = schema_version(conn)?;
+        let latest = MIGRATIONS.last().map_or(0, |m| m.version);
+        if current > latest {
+            log::warn!(
+                "Database schema version {current} is newer than this app supports ({latest})"
+            );
+            return Ok(());
+        }
 
-        // Migration: add client_id column to existing projects tables
-        let add_client_id = conn.execute(
-            "ALTER TABLE projects ADD COLUMN client_id TEXT REFERENCES clients(id)",
-            [],
-        );
-        if let Err(e) = add_client_id {
-            if !e.to_string().contains("duplicate column") {
-                return Err(e.into());
-            }
+        for migration in MIGRATIONS.iter().filter(|m| m.version > current) {
+            let tx = conn.unchecked_transaction()?;
+            (migration.apply)(&tx)?;
+            tx.pragma_update(None, "user_version", migration.version)?;
+            tx.commit()?;
+            log::info!(
+                "Applied database migration {}: {}",
+                migration.version,
+                migration.description
+            );
         }
 
-        // Create indexes for common queries
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_projects_status ON projects(status)",
-            [],
-        )?;
+        Ok(())
+    }
 
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_projects_updated_at ON projects(updated_at DESC)",
-            [],
-        )?;
+    /// Write a consistent copy of the live database to `output_path`.
+    ///
+    /// Uses `VACUUM INTO`, which reads inside one transaction, so writes made
+    /// while the backup runs are either fully in it or not at all. The copy is
+    /// written next to `output_path` and renamed over it once complete, so a
+    /// failed backup leaves an existing file at `output_path` untouched.
+    pub fn backup_to(&self, output_path: &Path) -> Result<(), AppError> {
+        if is_database_file(&self.path, output_path) {
+            return Err(AppError::InvalidData(
+                "Backup path is the live database".to_owned(),
+            ));
+        }
+        let partial = sibling_path(output_path, PARTIAL_BACKUP_SUFFIX);
+        let partial_str = partial
+            .to_str()
+            .ok_or_else(|| AppError::InvalidData("Backup path is not valid UTF-8".to_owned()))?;
+        if partial.exists() {
+            std::fs::remove_file(&partial)?;
+        }
 
-        // Create google_drive_accounts table
-        conn.execute(
-            "CREATE TABLE IF NOT EXISTS google_drive_accounts (
-                id TEXT PRIMARY KEY,
-                email TEXT UNIQUE NOT NULL,
-                display_name TEXT NOT NULL,
-                parent_folder_id TEXT,
-                enabled INTEGER NOT NULL DEFAULT 1,
-                created_at TEXT NOT NULL,
-                last_authenticated TEXT NOT NULL
-            )",
-            [],
-        )?;
+        let result = self
+            .execute(|conn| {
+                conn.execute("VACUUM INTO ?1", [partial_str])?;
+                Ok(())
+            })
+            .and_then(|()| Ok(std::fs::rename(&partial, output_path)?));
+        if result.is_err() {
+            let _ = std::fs::remove_file(&partial);
+        }
+        result
+    }
 
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_drive_accounts_email ON google_drive_accounts(email)",
-            [],
-        )?;
+    /// Validate a backup and stage it to replace this database on next open.
+    pub fn restore_from(&self, input_path: &Path) -> Result<(), AppError> {
+        Self::stage_restore(&self.path, input_path)
+    }
 
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_projects_date ON projects(date)",
-            [],
-        )?;
+    /// Validate the backup at `input_path` and copy it next to `db_path`, where
+    /// the next [`Database::new_with_path`] swaps it in.
+    ///
+    /// The swap can't happen while connections are open, so the restore takes
+    /// effect after a restart.
+    fn stage_restore(db_path: &Path, input_path: &Path) -> Result<(), AppError> {
+        validate_backup(input_path)?;
+        std::fs::copy(input_path, sibling_path(db_path, PENDING_RESTORE_SUFFIX))?;
+        Ok(())
+    }
 
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_projects_client_name ON projects(client_name)",
-            [],
-        )?;
+    /// Swap a staged backup in for the database file, keeping the old file (and
+    /// its WAL) under the `before-restore` name.
+    fn apply_pending_restore(db_path: &Path) -> Result<(), AppError> {
+        let pending = sibling_path(db_path, PENDING_RESTORE_SUFFIX);
+        if !pending.exists() {
+            return Ok(());
+        }
 
-        conn.execute(
-            "CREATE INDEX IF NOT EXISTS idx_projects_client_id ON projects(client_id)",
-            [],
-        )?;
+        let replaced = sibling_path(db_path, REPLACED_SUFFIX);
+        for suffix in DATABASE_FILE_SUFFIXES {
+            let live = PathBuf::from(format!("{}{suffix}", db_path.display()));
+            let kept = PathBuf::from(format!("{}{suffix}", replaced.display()));
+            if kept.exists() {
+                std::fs::remove_file(&kept)?;
+            }
+            if live.exists() {
+                std::fs::rename(&live, &kept)?;
+            }
+        }
+        std::fs::rename(&pending, db_path)?;
 
+        log::info!(
+            "Restored database from backup; previous database kept at {}",
+            replaced.display()
+        );
         Ok(())
     }
 
@@ -141,21 +181,36 @@ impl Database {
         Ok(home_dir.join("CreatorOps").join("creatorops.db"))
     }
 
-    /// Execute a query with the database connection
+    /// Execute a query with a free connection from the pool
     pub fn execute<F, R>(&self, f: F) -> Result<R, AppError>
     where
         F: FnOnce(&Connection) -> Result<R, AppError>,
     {
-        let conn = self.conn.lock().map_err(|_| AppError::LockFailed)?;
+        let conn = self.acquire()?;
         f(&conn)
     }
 
+    /// Take the first idle connection, starting from a rotating position, or wait
+    /// for one if all are busy.
+    fn acquire(&self) -> Result<std::sync::MutexGuard<'_, Connection>, AppError> {
+        let start = self.next.fetch_add(1, Ordering::Relaxed) % self.conns.len();
+
+        for offset in 0..self.conns.len() {
+            match self.conns[(start + offset) % self.conns.len()].try_lock() {
+                Ok(conn) => return Ok(conn),
+                Err(TryLockError::WouldBlock | TryLockError::Poisoned(_)) => {}
+            }
+        }
+
+        self.conns[start].lock().map_err(|_| AppError::LockFailed)
+    }
+
     #[cfg(test)]
     pub fn transaction<F, R>(&self, f: F) -> Result<R, AppError>
     where
         F: FnOnce(&rusqlite::Transaction) -> Result<R, AppError>,
     {
-        let mut conn = self.conn.lock().map_err(|_| AppError::LockFailed)?;
+        let mut conn = self.acquire()?;
         let tx = conn.transaction()?;
         let result = f(&tx)?;
         tx.commit()?;
@@ -164,6 +219,311 @@ impl Database {
     }
 }
 
+/// `db_path` with `.suffix` appended to its file name.
+fn sibling_path(db_path: &Path, suffix: &str) -> PathBuf {
+    let mut name = db_path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    db_path.with_file_name(name)
+}
+
+/// Whether `path` is the database file at `db_path` or one of its `-wal` and
+/// `-shm` files, comparing the resolved folders so other spellings match too.
+fn is_database_file(db_path: &Path, path: &Path) -> bool {
+    let resolve = |p: &Path| {
+        let folder = p.parent().filter(|d| !d.as_os_str().is_empty());
+        let folder = folder
+            .unwrap_or_else(|| Path::new("."))
+            .canonicalize()
+            .ok()?;
+        Some(folder.join(p.file_name()?))
+    };
+    let (Some(db_path), Some(path)) = (resolve(db_path), resolve(path)) else {
+        return false;
+    };
+    DATABASE_FILE_SUFFIXES
+        .iter()
+        .any(|suffix| format!("{}{suffix}", db_path.display()) == path)
+}
+
+/// Check that `path` is an intact `CreatorOps` database this app can open.
+fn validate_backup(path: &Path) -> Result<(), AppError> {
+    let invalid = |reason: String| {
+        AppError::InvalidData(format!(
+            "{} is not a valid backup: {reason}",
+            path.display()
+        ))
+    };
+
+    let conn = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)
+        .map_err(|e| invalid(e.to_string()))?;
+    let integrity: String = conn
+        .query_row("PRAGMA integrity_check", [], |row| row.get(0))
+        .map_err(|e| invalid(e.to_string()))?;
+    if integrity != "ok" {
+        return Err(invalid(integrity));
+    }
+    if !has_column(&conn, "projects", "id")? {
+        return Err(invalid("no projects table".to_owned()));
+    }
+    let latest = MIGRATIONS.last().map_or(0, |m| m.version);
+    if schema_version(&conn)? > latest {
+        return Err(invalid("made by a newer version of CreatorOps".to_owned()));
+    }
+    Ok(())
+}
+
+/// Save a copy of the application database to `output_path`.
+#[tauri::command]
+pub async fn backup_database(
+    db: tauri::State<'_, Database>,
+    output_path: String,
+) -> Result<(), String> {
+    db.backup_to(Path::new(&output_path)).map_err(String::from)
+}
+
+/// Validate a database backup and restore it the next time the app starts.
+#[tauri::command]
+pub async fn restore_database(
+    db: tauri::State<'_, Database>,
+    input_path: String,
+) -> Result<(), String> {
+    db.restore_from(Path::new(&input_path))
+        .map_err(String::from)
+}
+
+/// Schema change applied once, in order, to bring a database up to date.
+///
+/// Migrations must be idempotent: databases created before versioning was
+/// introduced report version 0 but may already contain some of these changes.
+struct Migration {
+    version: u32,
+    description: &'static str,
+    apply: MigrationFn,
+}
+
+/// Function that applies one migration.
+type MigrationFn = fn(&Connection) -> Result<(), AppError>;
+
+/// Every schema migration, oldest first. Append new ones; never edit old ones.
+const MIGRATIONS: &[Migration] = &[
+    Migration {
+        version: 1,
+        description: "clients, projects and Google Drive account tables",
+        apply: create_base_schema,
+    },
+    Migration {
+        version: 2,
+        description: "project deadline and client link",
+        apply: add_project_deadline_and_client,
+    },
+    Migration {
+        version: 3,
+        description: "settings table",
+        apply: create_settings_table,
+    },
+    Migration {
+        version: 4,
+        description: "project client contact details",
+        apply: add_project_client_contact,
+    },
+    Migration {
+        version: 5,
+        description: "checksum cache",
+        apply: create_checksum_cache_table,
+    },
+    Migration {
+        version: 6,
+        description: "project tags",
+        apply: create_tags_table,
+    },
+];
+
+/// Schema version recorded in the database's `user_version` pragma.
+fn schema_version(conn: &Connection) -> Result<u32, AppError> {
+    Ok(conn.query_row("PRAGMA user_version", [], |row| row.get(0))?)
+}
+
+/// Whether `table` already has a column named `column`.
+fn has_column(conn: &Connection, table: &str, column: &str) -> Result<bool, AppError> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
+    let names = stmt.query_map([], |row| row.get::<_, String>(1))?;
+    for name in names {
+        if name? == column {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+/// Add a column unless an earlier, unversioned schema already has it.
+fn add_column_if_missing(
+    conn: &Connection,
+    table: &str,
+    column: &str,
+    definition: &str,
+) -> Result<(), AppError> {
+    if !has_column(conn, table, column)? {
+        conn.execute(
+            &format!("ALTER TABLE {table} ADD COLUMN {column} {definition}"),
+            [],
+        )?;
+    }
+    Ok(())
+}
+
+/// Migration 1: the tables and indexes of the first release.
+fn create_base_schema(conn: &Connection) -> Result<(), AppError> {
+    // Create clients table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS clients (
+            id TEXT PRIMARY KEY,
+            name TEXT UNIQUE NOT NULL,
+            email TEXT,
+            phone TEXT,
+            notes TEXT,
+            status TEXT NOT NULL DEFAULT 'active',
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_clients_name ON clients(name)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_clients_status ON clients(status)",
+        [],
+    )?;
+
+    // Create projects table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS projects (
+            id TEXT PRIMARY KEY,
+            name TEXT NOT NULL,
+            client_name TEXT NOT NULL,
+            date TEXT NOT NULL,
+            shoot_type TEXT NOT NULL,
+            status TEXT NOT NULL,
+            folder_path TEXT NOT NULL,
+            created_at TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    // Create indexes for common queries
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_projects_status ON projects(status)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_projects_updated_at ON projects(updated_at DESC)",
+        [],
+    )?;
+
+    // Create google_drive_accounts table
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS google_drive_accounts (
+            id TEXT PRIMARY KEY,
+            email TEXT UNIQUE NOT NULL,
+            display_name TEXT NOT NULL,
+            parent_folder_id TEXT,
+            enabled INTEGER NOT NULL DEFAULT 1,
+            created_at TEXT NOT NULL,
+            last_authenticated TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_drive_accounts_email ON google_drive_accounts(email)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_projects_date ON projects(date)",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_projects_client_name ON projects(client_name)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Migration 2: delivery deadlines and the link from projects to client records.
+fn add_project_deadline_and_client(conn: &Connection) -> Result<(), AppError> {
+    add_column_if_missing(conn, "projects", "deadline", "TEXT")?;
+    add_column_if_missing(conn, "projects", "client_id", "TEXT REFERENCES clients(id)")?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_projects_client_id ON projects(client_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Migration 3: key/value application settings.
+fn create_settings_table(conn: &Connection) -> Result<(), AppError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS settings (
+            key TEXT PRIMARY KEY,
+            value TEXT NOT NULL,
+            updated_at TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Migration 4: client email and phone kept on the project itself.
+fn add_project_client_contact(conn: &Connection) -> Result<(), AppError> {
+    add_column_if_missing(conn, "projects", "client_email", "TEXT")?;
+    add_column_if_missing(conn, "projects", "client_phone", "TEXT")?;
+
+    Ok(())
+}
+
+/// Migration 5: file digests remembered by path, size and modification time.
+fn create_checksum_cache_table(conn: &Connection) -> Result<(), AppError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS checksum_cache (
+            path TEXT PRIMARY KEY,
+            size INTEGER NOT NULL,
+            modified INTEGER NOT NULL,
+            checksum TEXT NOT NULL
+        )",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Migration 6: free-form tags on projects, compared ignoring case.
+fn create_tags_table(conn: &Connection) -> Result<(), AppError> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS tags (
+            project_id TEXT NOT NULL REFERENCES projects(id),
+            tag TEXT NOT NULL COLLATE NOCASE,
+            PRIMARY KEY (project_id, tag)
+        )",
+        [],
+    )?;
+
+    conn.execute("CREATE INDEX IF NOT EXISTS idx_tags_tag ON tags(tag)", [])?;
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -401,4 +761,265 @@ mod tests {
 
         assert_eq!(count, 0);
     }
+
+    #[test]
+    fn test_new_database_is_at_latest_schema_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+
+        let version = db.execute(schema_version).unwrap();
+        assert_eq!(version, MIGRATIONS.last().unwrap().version);
+    }
+
+    /// Name, status, deadline and client id of a project row.
+    type ProjectRow = (String, String, Option<String>, Option<String>);
+
+    #[test]
+    fn test_migrates_old_schema_without_data_loss() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("old.db");
+
+        // A database from before deadlines, clients and versioning existed
+        {
+            let conn = Connection::open(&db_path).unwrap();
+            conn.execute_batch(
+                "CREATE TABLE projects (
+                    id TEXT PRIMARY KEY,
+                    name TEXT NOT NULL,
+                    client_name TEXT NOT NULL,
+                    date TEXT NOT NULL,
+                    shoot_type TEXT NOT NULL,
+                    status TEXT NOT NULL,
+                    folder_path TEXT NOT NULL,
+                    created_at TEXT NOT NULL,
+                    updated_at TEXT NOT NULL
+                );
+                INSERT INTO projects VALUES ('old-1', 'Smith Wedding', 'Smith', '2023-06-01',
+                    'Wedding', 'Editing', '/Projects/Smith', '2023-06-01T00:00:00Z',
+                    '2023-06-02T00:00:00Z');",
+            )
+            .unwrap();
+        }
+
+        let db = Database::new_with_path(&db_path).unwrap();
+
+        let (name, status, deadline, client_id): ProjectRow = db
+            .execute(|conn| {
+                Ok(conn.query_row(
+                    "SELECT name, status, deadline, client_id FROM projects WHERE id = 'old-1'",
+                    [],
+                    |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+                )?)
+            })
+            .unwrap();
+        assert_eq!(name, "Smith Wedding");
+        assert_eq!(status, "Editing");
+        assert!(deadline.is_none());
+        assert!(client_id.is_none());
+
+        let version = db.execute(schema_version).unwrap();
+        assert_eq!(version, MIGRATIONS.last().unwrap().version);
+        assert!(db
+            .execute(|conn| has_column(conn, "clients", "email"))
+            .unwrap());
+        assert!(db
+            .execute(|conn| has_column(conn, "projects", "client_email"))
+            .unwrap());
+    }
+
+    #[test]
+    fn test_migrations_are_idempotent_on_unversioned_databases() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        drop(Database::new_with_path(&db_path).unwrap());
+
+        // Databases written before versioning have the full schema at version 0
+        Connection::open(&db_path)
+            .unwrap()
+            .pragma_update(None, "user_version", 0)
+            .unwrap();
+
+        let db = Database::new_with_path(&db_path).unwrap();
+        assert_eq!(
+            db.execute(schema_version).unwrap(),
+            MIGRATIONS.last().unwrap().version
+        );
+    }
+
+    #[test]
+    fn test_connections_use_wal() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+
+        for _ in 0..POOL_SIZE {
+            let mode: String = db
+                .execute(|conn| Ok(conn.query_row("PRAGMA journal_mode", [], |row| row.get(0))?))
+                .unwrap();
+            assert_eq!(mode, "wal");
+        }
+    }
+
+    #[test]
+    fn test_parallel_reads_and_writes_do_not_lock() {
+        let temp_dir = TempDir::new().unwrap();
+        let db =
+            std::sync::Arc::new(Database::new_with_path(&temp_dir.path().join("test.db")).unwrap());
+
+        let handles: Vec<_> = (0..16)
+            .map(|thread| {
+                let db = db.clone();
+                std::thread::spawn(move || {
+                    for i in 0..25 {
+                        let id = format!("p-{thread}-{i}");
+                        db.execute(|conn| {
+                            conn.execute(
+                                "INSERT INTO projects (id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at)
+                                 VALUES (?1, 'Name', 'Client', '2024-01-01', 'Wedding', 'New', '/path', 'now', 'now')",
+                                rusqlite::params![id],
+                            )?;
+                            Ok(())
+                        })
+                        .unwrap();
+                        let _count: i64 = db
+                            .execute(|conn| {
+                                Ok(conn.query_row("SELECT COUNT(*) FROM projects", [], |row| {
+                                    row.get(0)
+                                })?)
+                            })
+                            .unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        let count: i64 = db
+            .execute(|conn| {
+                Ok(conn.query_row("SELECT COUNT(*) FROM projects", [], |row| row.get(0))?)
+            })
+            .unwrap();
+        assert_eq!(count, 16 * 25);
+    }
+
+    fn insert_project(db: &Database, id: &str) {
+        db.execute(|conn| {
+            conn.execute(
+                "INSERT INTO projects (id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at)
+                 VALUES (?1, 'Name', 'Client', '2024-01-01', 'Wedding', 'New', '/path', 'now', 'now')",
+                rusqlite::params![id],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    fn project_ids(db: &Database) -> Vec<String> {
+        db.execute(|conn| {
+            let mut stmt = conn.prepare("SELECT id FROM projects ORDER BY id")?;
+            let ids = stmt
+                .query_map([], |row| row.get(0))?
+                .collect::<Result<Vec<String>, _>>()?;
+            Ok(ids)
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_backup_and_restore_after_corruption() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("creatorops.db");
+        let backup_path = temp_dir.path().join("backup.db");
+
+        let db = Database::new_with_path(&db_path).unwrap();
+        insert_project(&db, "a");
+        insert_project(&db, "b");
+        db.backup_to(&backup_path).unwrap();
+
+        // Changes after the backup are not part of it
+        insert_project(&db, "c");
+        db.restore_from(&backup_path).unwrap();
+        drop(db);
+
+        std::fs::write(&db_path, b"garbage, not a database").unwrap();
+
+        let db = Database::new_with_path(&db_path).unwrap();
+        assert_eq!(project_ids(&db), vec!["a", "b"]);
+        assert!(sibling_path(&db_path, REPLACED_SUFFIX).exists());
+        assert!(!sibling_path(&db_path, PENDING_RESTORE_SUFFIX).exists());
+    }
+
+    #[test]
+    fn test_backup_overwrites_existing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let backup_path = temp_dir.path().join("backup.db");
+        std::fs::write(&backup_path, b"old").unwrap();
+
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+        insert_project(&db, "a");
+        db.backup_to(&backup_path).unwrap();
+
+        assert!(validate_backup(&backup_path).is_ok());
+        assert!(!sibling_path(&backup_path, PARTIAL_BACKUP_SUFFIX).exists());
+    }
+
+    #[test]
+    fn test_backup_refuses_to_overwrite_the_live_database() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Database::new_with_path(&db_path).unwrap();
+        insert_project(&db, "a");
+
+        let respelled = temp_dir.path().join("sub").join("..").join("test.db");
+        std::fs::create_dir(temp_dir.path().join("sub")).unwrap();
+        for target in [
+            db_path,
+            respelled,
+            temp_dir.path().join("test.db-wal"),
+            temp_dir.path().join("test.db-shm"),
+        ] {
+            let err = db.backup_to(&target).unwrap_err().to_string();
+            assert!(err.contains("live database"), "{}: {err}", target.display());
+        }
+
+        assert_eq!(project_ids(&db), vec!["a"]);
+    }
+
+    #[test]
+    fn test_failed_backup_keeps_the_existing_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+        let backup_path = temp_dir.path().join("backup.db");
+        std::fs::write(&backup_path, b"previous backup").unwrap();
+        // A folder where the partial copy goes makes the backup fail
+        std::fs::create_dir(sibling_path(&backup_path, PARTIAL_BACKUP_SUFFIX)).unwrap();
+
+        assert!(db.backup_to(&backup_path).is_err());
+        assert_eq!(std::fs::read(&backup_path).unwrap(), b"previous backup");
+    }
+
+    #[test]
+    fn test_restore_rejects_invalid_backups() {
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let db = Database::new_with_path(&db_path).unwrap();
+
+        let garbage = temp_dir.path().join("garbage.db");
+        std::fs::write(&garbage, vec![7_u8; 8192]).unwrap();
+        assert!(db.restore_from(&garbage).is_err());
+
+        let unrelated = temp_dir.path().join("unrelated.db");
+        Connection::open(&unrelated)
+            .unwrap()
+            .execute("CREATE TABLE notes (id TEXT)", [])
+            .unwrap();
+        let err = db.restore_from(&unrelated).unwrap_err().to_string();
+        assert!(err.contains("no projects table"));
+
+        assert!(db
+            .restore_from(&temp_dir.path().join("missing.db"))
+            .is_err());
+        assert!(!sibling_path(&db_path, PENDING_RESTORE_SUFFIX).exists());
+    }
 }
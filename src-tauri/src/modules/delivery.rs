@@ -1,20 +1,42 @@
 //! Delivery module for copying selected project files to a client handoff folder.
 //!
-//! Supports optional naming templates (`{index}`, `{name}`, `{ext}`) and generates
-//! a `delivery_manifest.txt` summarising the operation. Progress is emitted as
-//! the `delivery-progress` Tauri event.
-
-use crate::error::DeliveryError;
-use crate::modules::file_utils::{get_home_dir, get_timestamp};
-use crate::modules::project::Project;
+//! Supports optional naming templates (`{index}`, `{name}`, `{ext}`, `{date}`,
+//! `{project}`, `{client}`) and generates a `delivery_manifest.txt` summarising the
+//! operation. Progress is emitted as the `delivery-progress` Tauri event.
+
+use crate::error::{AppError, DeliveryError};
+use crate::modules::checksums::hash_file;
+use crate::modules::db::Database;
+use crate::modules::file_utils::{
+    ensure_writable_dir, get_home_dir, get_timestamp, glob_matches, keep_backup_name,
+    read_json_with_backup, write_json_atomic, TransferRate,
+};
+use crate::modules::metadata::rewrite_jpeg_metadata;
+use crate::modules::notifications::{delivery_notification, notify_job_finished};
+use crate::modules::project::{get_project_by_id, Project};
+use crate::modules::settings::{
+    index_padding, resolve_conflict_mode, CONFLICT_MODES, DEFAULT_INDEX_PADDING,
+    FALLBACK_CONFLICT_MODE, MAX_INDEX_PADDING,
+};
+use crate::modules::storage::{LocalStorage, StorageProvider};
+use crate::modules::thumbnail::{encode_jpeg, load_thumbnail_image};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fs;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use tauri::Emitter;
-use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio_util::sync::CancellationToken;
 use uuid::Uuid;
+use zip::write::SimpleFileOptions;
+use zip::{CompressionMethod, ZipWriter};
 
 const CHUNK_SIZE: usize = 4 * 1024 * 1024; // 4MB chunks
+const MANIFEST_FILE_NAME: &str = "delivery_manifest.txt";
+const QUEUE_FILE_NAME: &str = "delivery_queue.json";
+const HISTORY_FILE_NAME: &str = "delivery_history.json";
+/// Error recorded on deliveries that were running when the app last exited.
+const INTERRUPTED_MESSAGE: &str = "Interrupted by app restart";
 
 /// Represents a queued or running delivery operation for a set of project files.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -36,6 +58,142 @@ pub struct DeliveryJob {
     pub completed_at: Option<String>,
     pub error_message: Option<String>,
     pub manifest_path: Option<String>,
+    /// Delivery folder for loose deliveries, or the `.zip` file when packaged.
+    pub output_path: Option<String>,
+    #[serde(flatten)]
+    pub options: DeliveryOptions,
+    /// Files left undelivered because their name was taken and `conflict_mode`
+    /// is `skip`.
+    #[serde(default)]
+    pub files_skipped: usize,
+    /// Files not copied because an identical copy was already delivered.
+    #[serde(default)]
+    pub files_unchanged: usize,
+}
+
+/// How a delivery lands its files in the delivery folder.
+///
+/// Serialized flat alongside the other [`DeliveryJob`] fields.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryOptions {
+    /// Package the files into a single `.zip` instead of copying them loose.
+    #[serde(default)]
+    pub package_as_zip: bool,
+    /// Recreate each file's subfolders relative to the project root.
+    #[serde(default)]
+    pub preserve_structure: bool,
+    /// What to do when a file's name is already taken in the delivery folder:
+    /// `skip`, `rename` (add a ` (n)` counter), `overwrite`, or
+    /// `overwrite_keep_backup` (move the old file to `name.bak-<timestamp>` first).
+    #[serde(default = "default_conflict_mode")]
+    pub conflict_mode: String,
+    /// Leave files the delivery folder already holds an identical copy of, so
+    /// re-running an interrupted delivery only copies what's missing.
+    #[serde(default)]
+    pub skip_existing_identical: bool,
+    #[serde(flatten)]
+    pub images: ImageOptions,
+}
+
+impl Default for DeliveryOptions {
+    fn default() -> Self {
+        Self {
+            package_as_zip: false,
+            preserve_structure: false,
+            conflict_mode: default_conflict_mode(),
+            skip_existing_identical: false,
+            images: ImageOptions::default(),
+        }
+    }
+}
+
+/// What a delivery does to images on their way out. Videos and other files are
+/// always delivered byte for byte.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImageOptions {
+    /// Remove GPS, maker notes and serial numbers from delivered JPEGs.
+    #[serde(default)]
+    pub strip_exif: bool,
+    /// Copyright notice embedded (EXIF + IPTC) in delivered JPEGs.
+    pub copyright: Option<String>,
+    /// Resize and re-encode delivered images instead of copying originals.
+    pub export_preset: Option<ExportPreset>,
+}
+
+/// Completed delivery record persisted to `~/CreatorOps/delivery_history.json`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeliveryHistory {
+    pub id: String,
+    pub project_id: String,
+    pub project_name: String,
+    pub delivery_path: String,
+    /// Delivery folder for loose deliveries, or the `.zip` file when packaged.
+    pub output_path: Option<String>,
+    pub manifest_path: Option<String>,
+    pub files_copied: usize,
+    pub files_skipped: usize,
+    pub total_bytes: u64,
+    pub started_at: String,
+    pub completed_at: String,
+}
+
+impl From<&DeliveryJob> for DeliveryHistory {
+    fn from(job: &DeliveryJob) -> Self {
+        Self {
+            id: job.id.clone(),
+            project_id: job.project_id.clone(),
+            project_name: job.project_name.clone(),
+            delivery_path: job.delivery_path.clone(),
+            output_path: job.output_path.clone(),
+            manifest_path: job.manifest_path.clone(),
+            files_copied: job.files_copied,
+            files_skipped: job.files_skipped,
+            total_bytes: job.bytes_transferred,
+            started_at: job.started_at.clone().unwrap_or_default(),
+            completed_at: job.completed_at.clone().unwrap_or_default(),
+        }
+    }
+}
+
+/// Conflict mode of deliveries saved before it could be chosen, which were
+/// always renamed.
+fn default_conflict_mode() -> String {
+    FALLBACK_CONFLICT_MODE.to_owned()
+}
+
+/// Size and encoding applied to images exported during a delivery.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportPreset {
+    /// Longest edge in pixels; smaller images are never upscaled.
+    pub max_long_edge: u32,
+    /// JPEG quality from 1 to 100 (ignored for PNG).
+    pub quality: u8,
+    /// Output image format.
+    #[serde(default)]
+    pub format: ExportFormat,
+}
+
+/// Image format produced by an [`ExportPreset`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    #[default]
+    Jpeg,
+    Png,
+}
+
+impl ExportFormat {
+    /// File extension used for exported files.
+    const fn extension(self) -> &'static str {
+        match self {
+            Self::Jpeg => "jpg",
+            Self::Png => "png",
+        }
+    }
 }
 
 /// Lifecycle state of a delivery job.
@@ -46,9 +204,14 @@ pub enum DeliveryStatus {
     InProgress,
     Completed,
     Failed,
+    Cancelled,
 }
 
-/// Per-file progress payload emitted as the `delivery-progress` Tauri event.
+/// Progress payload emitted as the `delivery-progress` Tauri event.
+///
+/// `current_file` is the file being transferred; `files_copied` counts files that
+/// have finished. The last event of a successful job reports every file copied and
+/// a `job_percent` of 100 once the manifest is written.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct DeliveryProgress {
@@ -56,12 +219,67 @@ pub struct DeliveryProgress {
     pub file_name: String,
     pub current_file: usize,
     pub total_files: usize,
+    pub files_copied: usize,
     pub bytes_transferred: u64,
     pub total_bytes: u64,
+    /// Whole-job completion from 0 to 100.
+    pub job_percent: f64,
     pub speed: f64,
     pub eta: u64,
 }
 
+/// Project details resolved when a delivery starts.
+#[derive(Debug, Clone, Default)]
+struct DeliveryContext {
+    /// Project folder; subfolders are kept relative to it when `preserve_structure` is set.
+    project_root: Option<PathBuf>,
+    naming: NamingContext,
+    /// Stops the delivery before its next file once cancelled.
+    cancel: CancellationToken,
+}
+
+/// Values for the project-level naming template tokens.
+#[derive(Debug, Clone)]
+pub struct NamingContext {
+    /// `{project}`: project name.
+    project: String,
+    /// `{client}`: client name.
+    client: String,
+    /// `{date}`: delivery date as `YYYY-MM-DD`.
+    date: String,
+    /// Digits a bare `{index}` is zero-padded to.
+    index_padding: usize,
+}
+
+impl Default for NamingContext {
+    fn default() -> Self {
+        Self {
+            project: String::new(),
+            client: String::new(),
+            date: String::new(),
+            index_padding: DEFAULT_INDEX_PADDING,
+        }
+    }
+}
+
+impl NamingContext {
+    /// Token values for delivering `project_name` today, with the client taken
+    /// from the project record when it was found and a bare `{index}` padded to
+    /// `index_padding` digits.
+    pub fn for_project(
+        project_name: &str,
+        project: Option<&Project>,
+        index_padding: usize,
+    ) -> Self {
+        Self {
+            project: project_name.to_owned(),
+            client: project.map(|p| p.client_name.clone()).unwrap_or_default(),
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            index_padding,
+        }
+    }
+}
+
 /// Metadata for a single file within a project directory.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
@@ -74,41 +292,148 @@ pub struct ProjectFile {
     pub relative_path: String,
 }
 
+/// Field used to order [`list_project_files`] results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SortKey {
+    Name,
+    Size,
+    Modified,
+}
+
 /// List all files in a project directory
+///
+/// `file_type` keeps only files with that extension (case-insensitive, leading
+/// `.` optional). `sort_by` orders the results ascending unless `descending` is
+/// set; without it files are returned in filesystem order.
 #[tauri::command]
-pub async fn list_project_files(project_id: String) -> Result<Vec<ProjectFile>, String> {
-    // Load project to get folder path
-    let home_dir = get_home_dir()?;
-    let projects_path = home_dir.join("CreatorOps").join("Projects");
-
-    // Find project by scanning directories
-    let mut project_path: Option<PathBuf> = None;
-    if let Ok(entries) = fs::read_dir(&projects_path) {
-        for entry in entries.filter_map(Result::ok) {
-            let path = entry.path();
-            let metadata_path = path.join("project.json");
-            if metadata_path.exists() {
-                if let Ok(json_data) = fs::read_to_string(&metadata_path) {
-                    if let Ok(project) = serde_json::from_str::<Project>(&json_data) {
-                        if project.id == project_id {
-                            project_path = Some(path);
-                            break;
-                        }
-                    }
-                }
-            }
-        }
-    }
+pub async fn list_project_files(
+    db: tauri::State<'_, Database>,
+    project_id: String,
+    file_type: Option<String>,
+    sort_by: Option<SortKey>,
+    descending: Option<bool>,
+) -> Result<Vec<ProjectFile>, String> {
+    list_project_files_impl(
+        &db,
+        &project_id,
+        file_type.as_deref(),
+        sort_by,
+        descending.unwrap_or(false),
+    )
+}
 
-    let project_path = project_path.ok_or("Project not found")?;
+/// Core logic for listing a project's files (testable)
+///
+/// The project's folder comes from its database record.
+///
+/// # Errors
+///
+/// Returns error if the project doesn't exist or its folder can't be read
+pub fn list_project_files_impl(
+    db: &Database,
+    project_id: &str,
+    file_type: Option<&str>,
+    sort_by: Option<SortKey>,
+    descending: bool,
+) -> Result<Vec<ProjectFile>, String> {
+    let project = get_project_by_id(db, project_id)?;
+    let project_path = PathBuf::from(project.folder_path);
 
     // Recursively list all files
     let mut files = Vec::new();
     collect_project_files(&project_path, &project_path, &mut files)?;
 
+    if let Some(file_type) = file_type {
+        filter_by_file_type(&mut files, file_type);
+    }
+    if let Some(sort_by) = sort_by {
+        sort_project_files(&mut files, sort_by, descending);
+    }
+
     Ok(files)
 }
 
+/// Keep only files whose extension matches `file_type`.
+fn filter_by_file_type(files: &mut Vec<ProjectFile>, file_type: &str) {
+    let wanted = file_type.trim().trim_start_matches('.').to_uppercase();
+    files.retain(|file| file.file_type == wanted);
+}
+
+/// Order files by `key`, breaking ties by relative path so the order is stable.
+fn sort_project_files(files: &mut [ProjectFile], key: SortKey, descending: bool) {
+    files.sort_by(|a, b| {
+        let ordering = match key {
+            SortKey::Name => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+            SortKey::Size => a.size.cmp(&b.size),
+            SortKey::Modified => modified_secs(a).cmp(&modified_secs(b)),
+        }
+        .then_with(|| a.relative_path.cmp(&b.relative_path));
+
+        if descending {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+}
+
+/// Modification time of a listed file in Unix seconds.
+fn modified_secs(file: &ProjectFile) -> u64 {
+    file.modified.parse().unwrap_or(0)
+}
+
+/// Select project files for delivery by a glob over their paths relative to the
+/// project folder, e.g. `Selects/**/*.jpg`, returning absolute paths.
+///
+/// `*` and `?` match within one folder level and `**` matches any number of
+/// levels. File extensions match regardless of case.
+#[tauri::command]
+pub async fn select_files_by_pattern(
+    db: tauri::State<'_, Database>,
+    project_id: String,
+    pattern: String,
+) -> Result<Vec<String>, String> {
+    select_files_by_pattern_impl(&db, &project_id, &pattern)
+}
+
+/// Core logic for selecting project files by glob (testable)
+///
+/// # Errors
+///
+/// Returns error if the pattern is empty, the project doesn't exist or its
+/// folder can't be read
+pub fn select_files_by_pattern_impl(
+    db: &Database,
+    project_id: &str,
+    pattern: &str,
+) -> Result<Vec<String>, String> {
+    let pattern: Vec<&str> = pattern
+        .trim()
+        .split(['/', '\\'])
+        .filter(|segment| !segment.is_empty() && *segment != ".")
+        .collect();
+    if pattern.is_empty() {
+        return Err("Pattern is empty".to_owned());
+    }
+
+    let project = get_project_by_id(db, project_id)?;
+    let project_path = PathBuf::from(project.folder_path);
+    let mut files = Vec::new();
+    collect_project_files(&project_path, &project_path, &mut files)?;
+
+    let mut selected: Vec<String> = files
+        .into_iter()
+        .filter(|file| {
+            let segments: Vec<&str> = file.relative_path.split(['/', '\\']).collect();
+            glob_matches(&pattern, &segments)
+        })
+        .map(|file| file.path)
+        .collect();
+    selected.sort();
+    Ok(selected)
+}
+
 fn collect_project_files(
     base_path: &Path,
     current_path: &Path,
@@ -177,7 +502,22 @@ pub async fn create_delivery_impl(
     selected_files: Vec<String>,
     delivery_path: String,
     naming_template: Option<String>,
+    mut options: DeliveryOptions,
 ) -> Result<DeliveryJob, String> {
+    if !CONFLICT_MODES.contains(&options.conflict_mode.as_str()) {
+        return Err(format!("Invalid conflict mode: {}", options.conflict_mode));
+    }
+    if let Some(preset) = options.images.export_preset {
+        if preset.max_long_edge == 0 || !(1..=100).contains(&preset.quality) {
+            return Err(
+                "Export preset needs a long edge above 0 and a quality between 1 and 100"
+                    .to_owned(),
+            );
+        }
+    }
+
+    options.images.copyright = options.images.copyright.filter(|c| !c.trim().is_empty());
+
     let id = Uuid::new_v4().to_string();
     let now = get_timestamp();
 
@@ -206,6 +546,10 @@ pub async fn create_delivery_impl(
         completed_at: None,
         error_message: None,
         manifest_path: None,
+        output_path: None,
+        options,
+        files_skipped: 0,
+        files_unchanged: 0,
     };
 
     // Add to queue
@@ -218,33 +562,98 @@ pub async fn create_delivery_impl(
 }
 
 /// Create a delivery job from a set of selected project files.
+///
+/// Without a `conflict_mode`, the `default_conflict_mode` setting applies. With
+/// `skip_existing_identical`, files already delivered unchanged aren't copied again.
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub async fn create_delivery(
+    db: tauri::State<'_, Database>,
     state: tauri::State<'_, crate::state::AppState>,
     project_id: String,
     project_name: String,
     selected_files: Vec<String>,
     delivery_path: String,
     naming_template: Option<String>,
+    package_as_zip: Option<bool>,
+    preserve_structure: Option<bool>,
+    strip_exif: Option<bool>,
+    copyright: Option<String>,
+    export_preset: Option<ExportPreset>,
+    conflict_mode: Option<String>,
+    skip_existing_identical: Option<bool>,
 ) -> Result<DeliveryJob, String> {
-    create_delivery_impl(
+    ensure_writable_dir(Path::new(&delivery_path))?;
+    let conflict_mode = resolve_conflict_mode(&db, conflict_mode)?;
+
+    let job = create_delivery_impl(
         &state.delivery_queue,
         project_id,
         project_name,
         selected_files,
         delivery_path,
         naming_template,
+        DeliveryOptions {
+            package_as_zip: package_as_zip.unwrap_or(false),
+            preserve_structure: preserve_structure.unwrap_or(false),
+            conflict_mode,
+            skip_existing_identical: skip_existing_identical.unwrap_or(false),
+            images: ImageOptions {
+                strip_exif: strip_exif.unwrap_or(false),
+                copyright,
+                export_preset,
+            },
+        },
     )
-    .await
+    .await?;
+
+    persist_delivery_queue(&state.delivery_queue).await;
+    Ok(job)
+}
+
+/// Naming tokens and project folder for `job`, read from its project's database row.
+///
+/// A project deleted since the job was queued leaves the project tokens blank.
+///
+/// # Errors
+///
+/// Returns error if the database query fails, or if the project is gone and the
+/// job preserves structure, which needs the project folder to keep subfolders
+fn delivery_context(
+    db: &Database,
+    job: &DeliveryJob,
+    cancel: CancellationToken,
+) -> Result<DeliveryContext, String> {
+    let project = match get_project_by_id(db, &job.project_id) {
+        Ok(project) => Some(project),
+        Err(AppError::ProjectNotFound { .. }) if !job.options.preserve_structure => None,
+        Err(e) => return Err(e.into()),
+    };
+
+    Ok(DeliveryContext {
+        naming: NamingContext::for_project(&job.project_name, project.as_ref(), index_padding(db)?),
+        project_root: project.map(|p| PathBuf::from(p.folder_path)),
+        cancel,
+    })
 }
 
 /// Start a delivery job
 #[tauri::command]
 pub async fn start_delivery(
+    db: tauri::State<'_, Database>,
     state: tauri::State<'_, crate::state::AppState>,
     job_id: String,
     app_handle: tauri::AppHandle,
 ) -> Result<(), String> {
+    let queued = state
+        .delivery_queue
+        .lock()
+        .await
+        .get(&job_id)
+        .cloned()
+        .ok_or("Job not found")?;
+    let context = delivery_context(&db, &queued, CancellationToken::new())?;
+
     // Get job from queue
     let job = {
         let mut queue = state.delivery_queue.lock().await;
@@ -260,20 +669,42 @@ pub async fn start_delivery(
         drop(queue);
         job_clone
     };
+    persist_delivery_queue(&state.delivery_queue).await;
+
+    state
+        .delivery_tokens
+        .lock()
+        .await
+        .insert(job_id.clone(), context.cancel.clone());
 
     // Spawn background task
     let delivery_queue = state.delivery_queue.clone();
+    let delivery_tokens = state.delivery_tokens.clone();
+    let notifier = app_handle.clone();
     tokio::spawn(async move {
-        let result =
-            process_delivery(job.clone(), app_handle.clone(), delivery_queue.clone()).await;
+        let result = process_delivery(
+            job.clone(),
+            context,
+            delivery_queue.clone(),
+            move |progress| {
+                let _ = app_handle.emit("delivery-progress", progress);
+            },
+        )
+        .await;
+        delivery_tokens.lock().await.remove(&job_id);
 
         // Update job status
-        let mut queue = delivery_queue.lock().await;
-        if let Some(job) = queue.get_mut(&job_id) {
+        let mut completed = None;
+        if let Some(job) = delivery_queue.lock().await.get_mut(&job_id) {
             match result {
                 Ok(()) => {
                     job.status = DeliveryStatus::Completed;
                     job.completed_at = Some(get_timestamp());
+                    completed = Some(DeliveryHistory::from(&*job));
+                }
+                Err(DeliveryError::Cancelled) => {
+                    job.status = DeliveryStatus::Cancelled;
+                    job.completed_at = Some(get_timestamp());
                 }
                 Err(e) => {
                     job.status = DeliveryStatus::Failed;
@@ -282,56 +713,175 @@ pub async fn start_delivery(
                 }
             }
         }
+        persist_delivery_queue(&delivery_queue).await;
+        if let Some(entry) = completed {
+            record_delivery_history(entry).await;
+        }
+
+        let notification = delivery_queue
+            .lock()
+            .await
+            .get(&job_id)
+            .and_then(delivery_notification);
+        notify_job_finished(&notifier, notification);
     });
 
     Ok(())
 }
 
-async fn process_delivery(
+/// Copy (or zip) a job's files into its delivery path.
+///
+/// The context's `project_root` is only consulted when `preserve_structure` is set;
+/// files outside it fall back to the delivery root.
+async fn process_delivery<F>(
     mut job: DeliveryJob,
-    app_handle: tauri::AppHandle,
+    context: DeliveryContext,
     delivery_queue: crate::state::DeliveryQueue,
-) -> Result<(), DeliveryError> {
-    let delivery_path = Path::new(&job.delivery_path);
-    fs::create_dir_all(delivery_path)?;
+    on_progress: F,
+) -> Result<(), DeliveryError>
+where
+    F: Fn(&DeliveryProgress) + Send + Sync + 'static,
+{
+    let delivery_path = PathBuf::from(&job.delivery_path);
+    fs::create_dir_all(&delivery_path)?;
+
+    if job.options.package_as_zip {
+        let zip_path = delivery_path.join(zip_file_name(&job.project_name));
+        let progress_queue = delivery_queue.clone();
+        let output_path = zip_path.to_string_lossy().to_string();
+
+        let job = tokio::task::spawn_blocking(move || {
+            write_delivery_zip(&mut job, &zip_path, &context, |progress| {
+                on_progress(progress);
+                if let Some(q_job) = progress_queue.blocking_lock().get_mut(&progress.job_id) {
+                    q_job.bytes_transferred = progress.bytes_transferred;
+                }
+            })
+            .map(|()| job)
+        })
+        .await
+        .map_err(|e| DeliveryError::PathError(format!("Zip task failed: {e}")))??;
+
+        if let Some(q_job) = delivery_queue.lock().await.get_mut(&job.id) {
+            q_job.files_copied = job.files_copied;
+            q_job.bytes_transferred = job.bytes_transferred;
+            q_job.output_path = Some(output_path);
+        }
+        return Ok(());
+    }
 
-    let start_time = std::time::Instant::now();
+    let storage = LocalStorage::new(&delivery_path);
+    let mut rate = TransferRate::new();
     let mut manifest_entries = Vec::new();
+    let mut delivered = HashSet::new();
+    // Cloned so the loop can hand the whole job to the copy helper
+    let selected_files = job.selected_files.clone();
+
+    for (index, source_file) in selected_files.iter().enumerate() {
+        if context.cancel.is_cancelled() {
+            return Err(DeliveryError::Cancelled);
+        }
 
-    for (index, source_file) in job.selected_files.iter().enumerate() {
         let source_path = Path::new(source_file);
-        let file_name = source_path
-            .file_name()
-            .ok_or(DeliveryError::InvalidFileName)?
-            .to_string_lossy()
-            .to_string();
+        let rendered = if job.options.skip_existing_identical {
+            // Checked under the name the file was first delivered as, before
+            // `rename` would pick a fresh one
+            let dest_name = delivery_entry_name(&job, index, source_path, &context, |name| {
+                delivered.contains(name)
+            })?;
+            let (contents, unchanged) = render_unless_delivered(
+                &job.options.images,
+                source_path,
+                &delivery_path.join(&dest_name),
+            )
+            .await?;
+            if unchanged {
+                log::info!("Already delivered: {dest_name}");
+                job.files_unchanged += 1;
+                let file_name = source_path
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy();
+                let file_size = fs::metadata(source_path)?.len();
+                manifest_entries.push(manifest_entry(&file_name, &dest_name, file_size, None));
+                delivered.insert(dest_name);
+                if let Some(q_job) = delivery_queue.lock().await.get_mut(&job.id) {
+                    q_job.files_unchanged = job.files_unchanged;
+                }
+                continue;
+            }
+            Some(contents)
+        } else {
+            None
+        };
 
-        let dest_name = job.naming_template.as_ref().map_or_else(
-            || file_name.clone(),
-            |template| apply_naming_template(template, &file_name, index),
-        );
+        // Files of this delivery never overwrite each other, whatever the conflict mode
+        let rename_existing = job.options.conflict_mode == "rename";
+        let dest_name = delivery_entry_name(&job, index, source_path, &context, |name| {
+            delivered.contains(name) || (rename_existing && delivery_path.join(name).exists())
+        })?;
+        if job.options.conflict_mode == "skip"
+            && storage
+                .exists(&job.delivery_path, &dest_name)
+                .await
+                .map_err(DeliveryError::Storage)?
+        {
+            log::info!("Skipping existing delivery file: {dest_name}");
+            job.files_skipped += 1;
+            if let Some(q_job) = delivery_queue.lock().await.get_mut(&job.id) {
+                q_job.files_skipped = job.files_skipped;
+            }
+            continue;
+        }
+        delivered.insert(dest_name.clone());
 
         let dest_path = delivery_path.join(&dest_name);
+        if let Some(parent) = dest_path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        if job.options.conflict_mode == "overwrite_keep_backup" && dest_path.is_file() {
+            let kept = keep_existing_file(&dest_path)?;
+            log::info!("Kept previous {dest_name} as {}", kept.display());
+        }
 
+        let file_name = source_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
         let file_size = fs::metadata(source_path)?.len();
 
-        copy_file_with_progress(
-            source_path,
-            &dest_path,
-            &job.id,
-            index + 1,
-            job.total_files,
-            &mut job.bytes_transferred,
-            job.total_bytes,
-            start_time,
-            &app_handle,
-        )
-        .await?;
+        let rendered = match rendered {
+            Some(contents) => contents,
+            None => render_off_runtime(&job.options.images, source_path).await?,
+        };
+        let rendered_size = rendered.as_ref().map(Vec::len);
+        if let Some(contents) = rendered {
+            tokio::fs::write(&dest_path, contents).await?;
+            job.bytes_transferred += file_size;
+            on_progress(&delivery_progress(&job, &file_name, index + 1, &mut rate));
+        } else {
+            copy_file_with_progress(
+                &storage,
+                source_path,
+                &dest_name,
+                &mut job,
+                index + 1,
+                &mut rate,
+                &on_progress,
+            )
+            .await?;
+        }
 
         job.files_copied += 1;
 
         // Add to manifest
-        manifest_entries.push(format!("{file_name} -> {dest_name} ({file_size})"));
+        manifest_entries.push(manifest_entry(
+            &file_name,
+            &dest_name,
+            file_size,
+            rendered_size,
+        ));
 
         // Update queue
         {
@@ -344,8 +894,88 @@ async fn process_delivery(
     }
 
     // Generate manifest file
-    let manifest_path = delivery_path.join("delivery_manifest.txt");
-    let manifest_content = format!(
+    let manifest_path = delivery_path.join(MANIFEST_FILE_NAME);
+    fs::write(&manifest_path, build_manifest(&job, &manifest_entries))?;
+    on_progress(&completion_progress(&job, &mut rate));
+
+    // Update job with manifest path
+    {
+        let mut queue = delivery_queue.lock().await;
+        if let Some(q_job) = queue.get_mut(&job.id) {
+            q_job.manifest_path = Some(manifest_path.to_string_lossy().to_string());
+            q_job.output_path = Some(job.delivery_path.clone());
+        }
+    }
+
+    Ok(())
+}
+
+/// Move the file at `path` aside to its `.bak-<timestamp>` name so a new
+/// delivery can take its place, adding a counter if that name is taken too.
+fn keep_existing_file(path: &Path) -> Result<PathBuf, DeliveryError> {
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let backup_name = keep_backup_name(&file_name, &get_timestamp());
+    let backup_path = (1..=usize::MAX)
+        .map(|attempt| {
+            if attempt == 1 {
+                path.with_file_name(&backup_name)
+            } else {
+                path.with_file_name(format!("{backup_name}-{attempt}"))
+            }
+        })
+        .find(|candidate| !candidate.exists())
+        .unwrap_or_default();
+    fs::rename(path, &backup_path)?;
+    Ok(backup_path)
+}
+
+/// Render `source` once on a blocking thread and check whether `dest` already
+/// holds exactly that, so an unchanged file is neither rendered twice nor copied.
+async fn render_unless_delivered(
+    images: &ImageOptions,
+    source: &Path,
+    dest: &Path,
+) -> Result<(RenderedFile, bool), DeliveryError> {
+    let images = images.clone();
+    let source = source.to_path_buf();
+    let dest = dest.to_path_buf();
+    tokio::task::spawn_blocking(move || {
+        let rendered = render_delivered_file(&images, &source)?;
+        let unchanged = is_already_delivered(&source, rendered.as_deref(), &dest)?;
+        Ok((rendered, unchanged))
+    })
+    .await
+    .map_err(|e| DeliveryError::Export(format!("Render task failed: {e}")))?
+}
+
+/// Whether `dest` already holds exactly what delivering `source` would write:
+/// the same size, then the same contents. `rendered` holds the delivered
+/// contents when the job transforms the file.
+fn is_already_delivered(
+    source: &Path,
+    rendered: Option<&[u8]>,
+    dest: &Path,
+) -> Result<bool, DeliveryError> {
+    let Ok(existing) = fs::metadata(dest) else {
+        return Ok(false);
+    };
+    if !existing.is_file() {
+        return Ok(false);
+    }
+
+    if let Some(rendered) = rendered {
+        return Ok(existing.len() == rendered.len() as u64 && fs::read(dest)? == rendered);
+    }
+    if existing.len() != fs::metadata(source)?.len() {
+        return Ok(false);
+    }
+    let digest = |path| hash_file(path).map_err(std::io::Error::other);
+    Ok(digest(source)? == digest(dest)?)
+}
+
+/// Render the `delivery_manifest.txt` contents for a job.
+fn build_manifest(job: &DeliveryJob, entries: &[String]) -> String {
+    format!(
         "Delivery Manifest\n\
          Project: {}\n\
          Date: {}\n\
@@ -357,113 +987,555 @@ async fn process_delivery(
         get_timestamp(),
         job.total_files,
         job.total_bytes,
-        manifest_entries.join("\n")
-    );
-
-    fs::write(&manifest_path, manifest_content)?;
+        entries.join("\n")
+    )
+}
 
-    // Update job with manifest path
-    {
-        let mut queue = delivery_queue.lock().await;
-        if let Some(q_job) = queue.get_mut(&job.id) {
-            q_job.manifest_path = Some(manifest_path.to_string_lossy().to_string());
-        }
-    }
+/// Manifest line for one delivered file, with the original and delivered sizes
+/// when the file was re-encoded.
+fn manifest_entry(
+    file_name: &str,
+    dest_name: &str,
+    file_size: u64,
+    rendered_size: Option<usize>,
+) -> String {
+    rendered_size.map_or_else(
+        || format!("{file_name} -> {dest_name} ({file_size})"),
+        |size| format!("{file_name} -> {dest_name} ({file_size} -> {size})"),
+    )
+}
 
-    Ok(())
+/// Replace path separators so a value can be used inside a single file name.
+fn sanitize_file_component(value: &str) -> String {
+    value
+        .chars()
+        .map(|c| {
+            if matches!(c, '/' | '\\' | ':') {
+                '_'
+            } else {
+                c
+            }
+        })
+        .collect()
 }
 
-/// Copy a single file and emit live progress events to the frontend.
-///
-/// Argument count exceeds the lint default because progress tracking requires
-/// independent counters (`current_file`, `total_files`), a shared byte accumulator
-/// (`bytes_transferred`), a total for percentage (`total_bytes`), `start_time` for
-/// speed and ETA, and `app_handle` for event emission. Callers already own each value
-/// independently so grouping them into a struct would not reduce coupling.
-#[allow(clippy::too_many_arguments)]
-async fn copy_file_with_progress(
-    source: &Path,
-    dest: &Path,
-    job_id: &str,
-    current_file: usize,
-    total_files: usize,
-    bytes_transferred: &mut u64,
-    total_bytes: u64,
-    start_time: std::time::Instant,
-    app_handle: &tauri::AppHandle,
-) -> Result<(), DeliveryError> {
-    let mut source_file = tokio::fs::File::open(source).await?;
-    let mut dest_file = tokio::fs::File::create(dest).await?;
+/// File name of the packaged delivery, derived from the project name.
+fn zip_file_name(project_name: &str) -> String {
+    let stem = sanitize_file_component(project_name);
+    let stem = stem.trim();
 
-    let mut buffer = vec![0_u8; CHUNK_SIZE];
-    let file_name = source
+    if stem.is_empty() {
+        "delivery.zip".to_owned()
+    } else {
+        format!("{stem}.zip")
+    }
+}
+
+/// Relative destination of a selected file within the delivery, renamed with a
+/// ` (n)` counter while `is_taken` reports a collision.
+fn delivery_entry_name<F>(
+    job: &DeliveryJob,
+    index: usize,
+    source_path: &Path,
+    context: &DeliveryContext,
+    is_taken: F,
+) -> Result<String, DeliveryError>
+where
+    F: Fn(&str) -> bool,
+{
+    let file_name = source_path
         .file_name()
-        .unwrap_or_default()
+        .ok_or(DeliveryError::InvalidFileName)?
         .to_string_lossy()
         .to_string();
 
-    loop {
-        let bytes_read = source_file.read(&mut buffer).await?;
+    let dest_name = job.naming_template.as_ref().map_or_else(
+        || file_name.clone(),
+        |template| apply_naming_template(template, &file_name, index, &context.naming),
+    );
+    let dest_name = match export_preset_for(&job.options.images, source_path) {
+        Some(preset) => Path::new(&dest_name)
+            .with_extension(preset.format.extension())
+            .to_string_lossy()
+            .to_string(),
+        None => dest_name,
+    };
+
+    // Zip entries always use `/`, which is also accepted as a separator on Windows
+    let subfolder = context
+        .project_root
+        .as_deref()
+        .filter(|_| job.options.preserve_structure)
+        .and_then(|root| source_path.parent()?.strip_prefix(root).ok())
+        .map(|relative| {
+            relative
+                .components()
+                .map(|c| c.as_os_str().to_string_lossy())
+                .collect::<Vec<_>>()
+                .join("/")
+        })
+        .filter(|relative| !relative.is_empty());
+    let with_subfolder = |name: &str| {
+        subfolder
+            .as_ref()
+            .map_or_else(|| name.to_owned(), |dir| format!("{dir}/{name}"))
+    };
 
-        if bytes_read == 0 {
-            break;
-        }
+    let candidate = with_subfolder(&dest_name);
+    if !is_taken(&candidate) {
+        return Ok(candidate);
+    }
 
-        dest_file.write_all(&buffer[..bytes_read]).await?;
+    let dest = Path::new(&dest_name);
+    let stem = dest.file_stem().unwrap_or_default().to_string_lossy();
+    let extension = dest
+        .extension()
+        .map(|e| format!(".{}", e.to_string_lossy()))
+        .unwrap_or_default();
+
+    (1..=usize::MAX)
+        .map(|counter| with_subfolder(&format!("{stem} ({counter}){extension}")))
+        .find(|candidate| !is_taken(candidate))
+        .ok_or(DeliveryError::InvalidFileName)
+}
 
-        *bytes_transferred += bytes_read as u64;
+/// Package every selected file into `zip_path`, with the manifest as the last entry.
+///
+/// Media is stored uncompressed since photo and video formats are already compressed;
+/// progress is reported as bytes are added to the archive.
+fn write_delivery_zip<F>(
+    job: &mut DeliveryJob,
+    zip_path: &Path,
+    context: &DeliveryContext,
+    mut on_progress: F,
+) -> Result<(), DeliveryError>
+where
+    F: FnMut(&DeliveryProgress),
+{
+    let mut zip = ZipWriter::new(fs::File::create(zip_path)?);
+    let mut rate = TransferRate::new();
+    let mut manifest_entries = Vec::new();
+    let mut entries = HashSet::new();
+    let mut buffer = vec![0_u8; CHUNK_SIZE];
 
-        // Calculate speed and ETA
-        let elapsed = start_time.elapsed().as_secs_f64();
-        let speed = if elapsed > 0.0 {
-            *bytes_transferred as f64 / elapsed
-        } else {
-            0.0
-        };
+    for (index, source_file) in job.selected_files.iter().enumerate() {
+        if context.cancel.is_cancelled() {
+            drop(zip);
+            fs::remove_file(zip_path)?;
+            return Err(DeliveryError::Cancelled);
+        }
 
-        let remaining_bytes = total_bytes.saturating_sub(*bytes_transferred);
-        let eta = if speed > 0.0 {
-            (remaining_bytes as f64 / speed) as u64
-        } else {
-            0
-        };
+        let source_path = Path::new(source_file);
+        let entry_name = delivery_entry_name(job, index, source_path, context, |name| {
+            entries.contains(name)
+        })?;
+        entries.insert(entry_name.clone());
 
-        // Emit progress event
-        let progress = DeliveryProgress {
-            job_id: job_id.to_owned(),
-            file_name: file_name.clone(),
-            current_file,
-            total_files,
-            bytes_transferred: *bytes_transferred,
-            total_bytes,
-            speed,
-            eta,
-        };
+        let file_name = source_path
+            .file_name()
+            .unwrap_or_default()
+            .to_string_lossy()
+            .to_string();
 
-        let _ = app_handle.emit("delivery-progress", &progress);
+        let mut source = fs::File::open(source_path)?;
+        let file_size = source.metadata()?.len();
+        let options = SimpleFileOptions::default()
+            .compression_method(CompressionMethod::Stored)
+            .large_file(file_size >= u64::from(u32::MAX));
+        zip.start_file(entry_name.as_str(), options)?;
+
+        let rendered = render_delivered_file(&job.options.images, source_path)?;
+        let rendered_size = rendered.as_ref().map(Vec::len);
+        if let Some(contents) = rendered {
+            zip.write_all(&contents)?;
+            job.bytes_transferred += file_size;
+            on_progress(&delivery_progress(job, &file_name, index + 1, &mut rate));
+        } else {
+            loop {
+                let bytes_read = source.read(&mut buffer)?;
+                if bytes_read == 0 {
+                    break;
+                }
+                zip.write_all(&buffer[..bytes_read])?;
+                job.bytes_transferred += bytes_read as u64;
+                on_progress(&delivery_progress(job, &file_name, index + 1, &mut rate));
+            }
+        }
+
+        job.files_copied += 1;
+        manifest_entries.push(manifest_entry(
+            &file_name,
+            &entry_name,
+            file_size,
+            rendered_size,
+        ));
     }
 
-    dest_file.flush().await?;
+    zip.start_file(
+        MANIFEST_FILE_NAME,
+        SimpleFileOptions::default().compression_method(CompressionMethod::Deflated),
+    )?;
+    zip.write_all(build_manifest(job, &manifest_entries).as_bytes())?;
+    zip.finish()?;
+    on_progress(&completion_progress(job, &mut rate));
 
     Ok(())
 }
 
-const PLACEHOLDER_INDEX: &str = "{index}";
-const PLACEHOLDER_NAME: &str = "{name}";
-const PLACEHOLDER_EXT: &str = "{ext}";
+/// File extensions whose metadata can be rewritten on delivery.
+const JPEG_EXTENSIONS: [&str; 2] = ["jpg", "jpeg"];
+
+/// Image extensions an export preset re-encodes; RAW files use their embedded preview.
+const EXPORTABLE_EXTENSIONS: [&str; 13] = [
+    "jpg", "jpeg", "png", "tif", "tiff", "webp", "bmp", "raw", "cr2", "cr3", "nef", "arw", "dng",
+];
+
+/// Rewritten file contents, or `None` to copy the source unchanged.
+type RenderedFile = Option<Vec<u8>>;
+
+/// Produce the delivered contents of a file when the job transforms it.
+///
+/// Returns `None` when the file should be copied byte for byte: the file is not
+/// exported by the preset and either no metadata options are set or it is not a
+/// JPEG (video and other files always pass through untouched).
+fn render_delivered_file(
+    images: &ImageOptions,
+    source_path: &Path,
+) -> Result<RenderedFile, DeliveryError> {
+    let preset = export_preset_for(images, source_path);
+    let exported = preset
+        .map(|preset| export_image(source_path, preset))
+        .transpose()?;
+
+    let outputs_jpeg = preset.map_or_else(
+        || has_extension(source_path, &JPEG_EXTENSIONS),
+        |preset| preset.format == ExportFormat::Jpeg,
+    );
+    if !outputs_jpeg || (!images.strip_exif && images.copyright.is_none()) {
+        return Ok(exported);
+    }
+
+    let jpeg = match exported {
+        Some(jpeg) => jpeg,
+        None => fs::read(source_path)?,
+    };
+    rewrite_jpeg_metadata(&jpeg, images.strip_exif, images.copyright.as_deref())
+        .map(Some)
+        .map_err(|e| DeliveryError::Metadata(format!("{}: {e}", source_path.display())))
+}
+
+/// [`render_delivered_file`] on a blocking thread, so decoding and re-encoding
+/// large images doesn't stall the async runtime.
+async fn render_off_runtime(
+    images: &ImageOptions,
+    source_path: &Path,
+) -> Result<RenderedFile, DeliveryError> {
+    let images = images.clone();
+    let source_path = source_path.to_path_buf();
+    tokio::task::spawn_blocking(move || render_delivered_file(&images, &source_path))
+        .await
+        .map_err(|e| DeliveryError::Export(format!("Render task failed: {e}")))?
+}
+
+/// The job's export preset, if it applies to this file.
+fn export_preset_for(images: &ImageOptions, source_path: &Path) -> Option<ExportPreset> {
+    images
+        .export_preset
+        .filter(|_| has_extension(source_path, &EXPORTABLE_EXTENSIONS))
+}
+
+/// Whether the file's extension (case-insensitive) is one of `extensions`.
+fn has_extension(path: &Path, extensions: &[&str]) -> bool {
+    path.extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|e| extensions.contains(&e.to_lowercase().as_str()))
+}
+
+/// Decode, orient and downscale an image, then encode it per the preset.
+fn export_image(source_path: &Path, preset: ExportPreset) -> Result<Vec<u8>, DeliveryError> {
+    let image =
+        load_thumbnail_image(source_path, preset.max_long_edge).map_err(DeliveryError::Export)?;
+
+    match preset.format {
+        ExportFormat::Jpeg => encode_jpeg(&image, preset.quality).map_err(DeliveryError::Export),
+        ExportFormat::Png => {
+            let mut bytes = std::io::Cursor::new(Vec::new());
+            image
+                .write_to(&mut bytes, image::ImageFormat::Png)
+                .map_err(|e| DeliveryError::Export(format!("{}: {e}", source_path.display())))?;
+            Ok(bytes.into_inner())
+        }
+    }
+}
+
+/// Progress snapshot for the job's current byte count.
+fn delivery_progress(
+    job: &DeliveryJob,
+    file_name: &str,
+    current_file: usize,
+    rate: &mut TransferRate,
+) -> DeliveryProgress {
+    let (speed, eta) = rate.update(job.bytes_transferred, job.total_bytes);
+    DeliveryProgress {
+        job_id: job.id.clone(),
+        file_name: file_name.to_owned(),
+        current_file,
+        total_files: job.total_files,
+        files_copied: job.files_copied,
+        bytes_transferred: job.bytes_transferred,
+        total_bytes: job.total_bytes,
+        job_percent: job_percent(job),
+        speed,
+        eta,
+    }
+}
+
+/// Terminal progress event, sent once every file and the manifest are written.
+fn completion_progress(job: &DeliveryJob, rate: &mut TransferRate) -> DeliveryProgress {
+    DeliveryProgress {
+        job_percent: 100.0,
+        ..delivery_progress(job, MANIFEST_FILE_NAME, job.total_files, rate)
+    }
+}
+
+/// Whole-job completion by bytes, or by file count when the job has no bytes.
+///
+/// Capped below 100 so only [`completion_progress`] reports a finished job.
+fn job_percent(job: &DeliveryJob) -> f64 {
+    let percent = if job.total_bytes > 0 {
+        job.bytes_transferred as f64 / job.total_bytes as f64 * 100.0
+    } else if job.total_files > 0 {
+        job.files_copied as f64 / job.total_files as f64 * 100.0
+    } else {
+        0.0
+    };
+
+    percent.min(99.9)
+}
+
+/// Copy a single file into the delivery folder through `storage` as
+/// `dest_name`, adding to the job's byte count and reporting live progress to
+/// `on_progress`.
+async fn copy_file_with_progress<F>(
+    storage: &LocalStorage,
+    source: &Path,
+    dest_name: &str,
+    job: &mut DeliveryJob,
+    current_file: usize,
+    rate: &mut TransferRate,
+    on_progress: &F,
+) -> Result<(), DeliveryError>
+where
+    F: Fn(&DeliveryProgress) + Sync,
+{
+    let file_name = source
+        .file_name()
+        .unwrap_or_default()
+        .to_string_lossy()
+        .to_string();
+    let folder = job.delivery_path.clone();
+    let bytes_before = job.bytes_transferred;
+    // The storage callback is shared, so the job and rate it updates sit behind a lock
+    let progress = std::sync::Mutex::new((job, rate));
+
+    storage
+        .upload_file_with_progress(source, &folder, dest_name, &|_, copied, _| {
+            let Ok(mut guard) = progress.lock() else {
+                return;
+            };
+            let (job, rate) = &mut *guard;
+            job.bytes_transferred = bytes_before + copied;
+            on_progress(&delivery_progress(job, &file_name, current_file, rate));
+        })
+        .await
+        .map_err(DeliveryError::Storage)
+}
 
-fn apply_naming_template(template: &str, original_name: &str, index: usize) -> String {
+/// Opening of the `{index}` / `{index:N}` token.
+const PLACEHOLDER_INDEX_PREFIX: &str = "{index";
+const PLACEHOLDER_NAME: &str = "{name}";
+const PLACEHOLDER_EXT: &str = "{ext}";
+const PLACEHOLDER_DATE: &str = "{date}";
+const PLACEHOLDER_PROJECT: &str = "{project}";
+const PLACEHOLDER_CLIENT: &str = "{client}";
+
+/// Build a delivered file name from `template`, substituting the `{index}`,
+/// `{name}`, `{ext}`, `{date}`, `{project}` and `{client}` tokens.
+pub fn apply_naming_template(
+    template: &str,
+    original_name: &str,
+    index: usize,
+    context: &NamingContext,
+) -> String {
     // Simple template replacement
-    // Supports: {index}, {name}, {ext}
+    // Supports: {index}, {index:N}, {name}, {ext}, {date}, {project}, {client};
+    // unknown tokens are kept
     let path = Path::new(original_name);
     let name_without_ext = path.file_stem().unwrap_or_default().to_string_lossy();
     let ext = path.extension().unwrap_or_default().to_string_lossy();
-    let index_str = format!("{:03}", index + 1);
 
-    template
-        .replace(PLACEHOLDER_INDEX, &index_str)
+    replace_index_tokens(template, index + 1, context.index_padding)
         .replace(PLACEHOLDER_NAME, &name_without_ext)
         .replace(PLACEHOLDER_EXT, &ext)
+        .replace(PLACEHOLDER_DATE, &context.date)
+        .replace(
+            PLACEHOLDER_PROJECT,
+            &sanitize_file_component(&context.project),
+        )
+        .replace(
+            PLACEHOLDER_CLIENT,
+            &sanitize_file_component(&context.client),
+        )
+}
+
+/// Replace every `{index}` / `{index:N}` token with `number` zero-padded to `N`
+/// digits, or to `default_padding` digits for a bare `{index}`.
+///
+/// Tokens [`index_token_padding`] rejects are kept verbatim, just as
+/// [`validate_template`] reports them.
+fn replace_index_tokens(template: &str, number: usize, default_padding: usize) -> String {
+    use std::fmt::Write as _;
+
+    let mut result = String::with_capacity(template.len());
+    let mut rest = template;
+
+    while let Some(start) = rest.find(PLACEHOLDER_INDEX_PREFIX) {
+        let Some(end) = rest[start..].find('}').map(|offset| start + offset) else {
+            break;
+        };
+
+        if let Some(Ok(width)) = index_token_padding(&rest[start + 1..end], default_padding) {
+            result.push_str(&rest[..start]);
+            let _ = write!(result, "{number:0width$}");
+            rest = &rest[end + 1..];
+        } else {
+            // Something like `{indexed}` or `{index:x}`: not a usable token, keep it
+            result.push_str(&rest[..=start]);
+            rest = &rest[start + 1..];
+        }
+    }
+
+    result.push_str(rest);
+    result
+}
+
+/// Zero-padding width of an index token, given the text between its braces.
+///
+/// A bare `{index}` takes `default_padding`. `None` means the token isn't an
+/// index token at all; an `Err` describes a malformed or too wide `{index:N}`.
+fn index_token_padding(token: &str, default_padding: usize) -> Option<Result<usize, String>> {
+    let spec = token.strip_prefix("index")?;
+    if spec.is_empty() {
+        return Some(Ok(default_padding));
+    }
+    let width = spec.strip_prefix(':')?;
+    Some(
+        width
+            .trim()
+            .parse::<usize>()
+            .ok()
+            .filter(|width| *width <= MAX_INDEX_PADDING)
+            .ok_or_else(|| {
+                format!("Invalid padding in {{{token}}}: expected 0 to {MAX_INDEX_PADDING}")
+            }),
+    )
+}
+
+/// Tokens [`apply_naming_template`] substitutes, besides `{index:N}`.
+const NAMING_TOKENS: &[&str] = &["index", "name", "ext", "date", "project", "client"];
+/// File a template's sample output is rendered for.
+const SAMPLE_FILE_NAME: &str = "IMG_0001.jpg";
+
+/// Outcome of checking a delivery naming template.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TemplateValidation {
+    pub valid: bool,
+    /// Recognized tokens the template uses, by name, in order of first use.
+    pub tokens: Vec<String>,
+    /// Unknown tokens and unbalanced braces, in the order they appear.
+    pub errors: Vec<String>,
+    /// The template applied to a sample file, when it is valid.
+    pub sample: Option<String>,
+}
+
+/// Check `template` for unknown tokens and unbalanced braces, and render it for a
+/// sample file with a bare `{index}` padded to `index_padding` digits.
+pub fn validate_template(template: &str, index_padding: usize) -> TemplateValidation {
+    let mut tokens: Vec<String> = Vec::new();
+    let mut errors = Vec::new();
+    if template.trim().is_empty() {
+        errors.push("Template is empty".to_owned());
+    }
+
+    let mut chars = template.char_indices().peekable();
+    let mut position = 0;
+    while let Some((start, c)) = chars.next() {
+        position += 1;
+        match c {
+            '{' => {
+                let end = template[start + 1..]
+                    .find(['{', '}'])
+                    .map(|offset| start + 1 + offset)
+                    .filter(|&end| template[end..].starts_with('}'));
+                let Some(end) = end else {
+                    errors.push(format!("Unclosed '{{' at position {position}"));
+                    continue;
+                };
+                let token = &template[start + 1..end];
+                position += token.chars().count() + 1;
+                while chars.next_if(|&(i, _)| i <= end).is_some() {}
+
+                match naming_token_name(token) {
+                    Ok(name) if !tokens.iter().any(|t| t == name) => tokens.push(name.to_owned()),
+                    Ok(_) => {}
+                    Err(error) => errors.push(error),
+                }
+            }
+            '}' => errors.push(format!("Unmatched '}}' at position {position}")),
+            _ => {}
+        }
+    }
+
+    let valid = errors.is_empty();
+    let sample = valid.then(|| {
+        let context = NamingContext {
+            project: "Sample Project".to_owned(),
+            client: "Sample Client".to_owned(),
+            date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            index_padding,
+        };
+        apply_naming_template(template, SAMPLE_FILE_NAME, 0, &context)
+    });
+
+    TemplateValidation {
+        valid,
+        tokens,
+        errors,
+        sample,
+    }
+}
+
+/// Name of a recognized token given the text between its braces, with index
+/// tokens checked by the same [`index_token_padding`] the renderer uses.
+fn naming_token_name(token: &str) -> Result<&'static str, String> {
+    if let Some(padding) = index_token_padding(token, DEFAULT_INDEX_PADDING) {
+        return padding.map(|_| "index");
+    }
+    NAMING_TOKENS
+        .iter()
+        .copied()
+        .find(|name| *name == token)
+        .ok_or_else(|| format!("Unknown token {{{token}}}"))
+}
+
+/// Check a delivery naming template and preview it for a sample file.
+#[tauri::command]
+pub async fn validate_naming_template(
+    db: tauri::State<'_, Database>,
+    template: String,
+) -> Result<TemplateValidation, String> {
+    Ok(validate_template(&template, index_padding(&db)?))
 }
 
 /// Core logic for getting delivery queue (testable)
@@ -486,6 +1558,50 @@ pub async fn get_delivery_queue(
     get_delivery_queue_impl(&state.delivery_queue).await
 }
 
+/// Core logic for putting a failed delivery back in the queue (testable)
+///
+/// The job returns to `Pending` with its error, timestamps and progress
+/// cleared, so `start_delivery` can run it again.
+///
+/// # Errors
+///
+/// Returns error if the job doesn't exist or hasn't failed
+pub async fn retry_delivery_impl(
+    delivery_queue: &crate::state::DeliveryQueue,
+    job_id: &str,
+) -> Result<DeliveryJob, String> {
+    let mut queue = delivery_queue.lock().await;
+    let job = queue.get_mut(job_id).ok_or("Job not found")?;
+    if job.status != DeliveryStatus::Failed {
+        return Err("Only failed deliveries can be retried".to_owned());
+    }
+
+    job.status = DeliveryStatus::Pending;
+    job.error_message = None;
+    job.started_at = None;
+    job.completed_at = None;
+    job.files_copied = 0;
+    job.files_skipped = 0;
+    job.files_unchanged = 0;
+    job.bytes_transferred = 0;
+    job.output_path = None;
+    job.manifest_path = None;
+    let job = job.clone();
+    drop(queue);
+    Ok(job)
+}
+
+/// Reset a failed delivery to pending so it can be started again
+#[tauri::command]
+pub async fn retry_delivery(
+    state: tauri::State<'_, crate::state::AppState>,
+    job_id: String,
+) -> Result<DeliveryJob, String> {
+    let job = retry_delivery_impl(&state.delivery_queue, &job_id).await?;
+    persist_delivery_queue(&state.delivery_queue).await;
+    Ok(job)
+}
+
 /// Core logic for removing a delivery job (testable)
 ///
 /// # Errors
@@ -505,7 +1621,142 @@ pub async fn remove_delivery_job(
     state: tauri::State<'_, crate::state::AppState>,
     job_id: String,
 ) -> Result<(), String> {
-    remove_delivery_job_impl(&state.delivery_queue, job_id).await
+    remove_delivery_job_impl(&state.delivery_queue, job_id).await?;
+    persist_delivery_queue(&state.delivery_queue).await;
+    Ok(())
+}
+
+/// Location of the persisted delivery queue, saved whenever a command changes it
+/// and reloaded on startup.
+fn delivery_queue_path() -> Result<PathBuf, String> {
+    Ok(get_home_dir()?.join("CreatorOps").join(QUEUE_FILE_NAME))
+}
+
+/// Delivery jobs keyed by id, as held in the queue and its saved file.
+type DeliveryJobs = HashMap<String, DeliveryJob>;
+
+/// Write a snapshot of the queue to `path`.
+fn save_delivery_queue(jobs: &DeliveryJobs, path: &Path) -> Result<(), DeliveryError> {
+    write_json_atomic(path, jobs)?;
+    Ok(())
+}
+
+/// Read a persisted queue, marking jobs that were still running as failed.
+///
+/// A missing file yields an empty queue; a corrupt one is recovered from its backup.
+fn load_delivery_queue(path: &Path) -> Result<DeliveryJobs, DeliveryError> {
+    let mut jobs: DeliveryJobs = read_json_with_backup(path)?.unwrap_or_default();
+    for job in jobs.values_mut() {
+        if job.status == DeliveryStatus::InProgress {
+            job.status = DeliveryStatus::Failed;
+            job.error_message = Some(INTERRUPTED_MESSAGE.to_owned());
+        }
+    }
+
+    Ok(jobs)
+}
+
+/// Save the current queue to disk. Failures are logged rather than returned so a
+/// delivery never fails because its queue entry could not be persisted.
+pub async fn persist_delivery_queue(delivery_queue: &crate::state::DeliveryQueue) {
+    let jobs = delivery_queue.lock().await.clone();
+    let result = delivery_queue_path()
+        .and_then(|path| save_delivery_queue(&jobs, &path).map_err(String::from));
+
+    if let Err(e) = result {
+        log::warn!("Failed to persist delivery queue: {e}");
+    }
+}
+
+/// Load the delivery queue saved by a previous session into `delivery_queue`.
+///
+/// Must be called before the async runtime starts. Returns the number of
+/// restored jobs.
+///
+/// # Errors
+///
+/// Returns error if the saved queue cannot be read or parsed
+pub fn restore_delivery_queue(
+    delivery_queue: &crate::state::DeliveryQueue,
+) -> Result<usize, String> {
+    restore_delivery_queue_from(delivery_queue, &delivery_queue_path()?)
+}
+
+/// Load the delivery queue persisted at `path` into `delivery_queue`.
+fn restore_delivery_queue_from(
+    delivery_queue: &crate::state::DeliveryQueue,
+    path: &Path,
+) -> Result<usize, String> {
+    let jobs = load_delivery_queue(path)?;
+    let count = jobs.len();
+    delivery_queue.blocking_lock().extend(jobs);
+    Ok(count)
+}
+
+/// Location of the persisted delivery history.
+fn delivery_history_path() -> Result<PathBuf, String> {
+    Ok(get_home_dir()?.join("CreatorOps").join(HISTORY_FILE_NAME))
+}
+
+/// Serializes read-modify-write cycles of the history file.
+static HISTORY_LOCK: tokio::sync::Mutex<()> = tokio::sync::Mutex::const_new(());
+
+/// Append `entry` to the history file at `path`.
+async fn save_delivery_history(entry: DeliveryHistory, path: &Path) -> Result<(), DeliveryError> {
+    let _lock = HISTORY_LOCK.lock().await;
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+
+    let mut history: Vec<DeliveryHistory> = read_json_with_backup(path)?.unwrap_or_default();
+    history.push(entry);
+    write_json_atomic(path, &history)?;
+    Ok(())
+}
+
+/// Read the history file at `path`, newest delivery first, keeping only
+/// `project_id`'s deliveries when given. A missing file is an empty history.
+fn load_delivery_history(
+    path: &Path,
+    project_id: Option<&str>,
+) -> Result<Vec<DeliveryHistory>, DeliveryError> {
+    let mut history: Vec<DeliveryHistory> = read_json_with_backup(path)?.unwrap_or_default();
+    if let Some(project_id) = project_id {
+        history.retain(|h| h.project_id == project_id);
+    }
+    history.sort_by(|a, b| b.completed_at.cmp(&a.completed_at));
+    Ok(history)
+}
+
+/// Add a completed delivery to the history. Failures are logged rather than
+/// returned, as the delivery itself has already succeeded.
+async fn record_delivery_history(entry: DeliveryHistory) {
+    let result = match delivery_history_path() {
+        Ok(path) => save_delivery_history(entry, &path)
+            .await
+            .map_err(String::from),
+        Err(e) => Err(e),
+    };
+
+    if let Err(e) = result {
+        log::warn!("Failed to record delivery history: {e}");
+    }
+}
+
+/// Get the history of completed deliveries, newest first.
+#[tauri::command]
+pub async fn get_delivery_history() -> Result<Vec<DeliveryHistory>, String> {
+    load_delivery_history(&delivery_history_path()?, None)
+        .map_err(|e| format!("Failed to read delivery history: {e}"))
+}
+
+/// Get the history of completed deliveries for a specific project, newest first.
+#[tauri::command]
+pub async fn get_project_delivery_history(
+    project_id: String,
+) -> Result<Vec<DeliveryHistory>, String> {
+    load_delivery_history(&delivery_history_path()?, Some(&project_id))
+        .map_err(|e| format!("Failed to read delivery history: {e}"))
 }
 
 #[cfg(test)]
@@ -551,12 +1802,20 @@ mod tests {
             completed_at: None,
             error_message: None,
             manifest_path: None,
+            output_path: None,
+            options: DeliveryOptions::default(),
+            files_skipped: 0,
+            files_unchanged: 0,
         };
 
         let json = serde_json::to_string(&job).unwrap();
         assert!(json.contains("del-123"));
         assert!(json.contains("Delivery Test"));
         assert!(json.contains("pending"));
+        // Options are stored alongside the job's own fields, not nested
+        assert!(json.contains(r#""conflictMode":"rename""#));
+        assert!(json.contains(r#""stripExif":false"#));
+        assert!(!json.contains("options"));
     }
 
     #[test]
@@ -583,8 +1842,10 @@ mod tests {
             file_name: "photo.jpg".to_owned(),
             current_file: 1,
             total_files: 5,
+            files_copied: 0,
             bytes_transferred: 512,
             total_bytes: 2560,
+            job_percent: 20.0,
             speed: 100.5,
             eta: 20,
         };
@@ -597,19 +1858,39 @@ mod tests {
     #[test]
     fn test_apply_naming_template() {
         assert_eq!(
-            apply_naming_template("{index}_{name}.{ext}", "photo.jpg", 0),
+            apply_naming_template(
+                "{index}_{name}.{ext}",
+                "photo.jpg",
+                0,
+                &NamingContext::default()
+            ),
             "001_photo.jpg"
         );
         assert_eq!(
-            apply_naming_template("{index}_{name}.{ext}", "photo.jpg", 9),
+            apply_naming_template(
+                "{index}_{name}.{ext}",
+                "photo.jpg",
+                9,
+                &NamingContext::default()
+            ),
             "010_photo.jpg"
         );
         assert_eq!(
-            apply_naming_template("{name}_final.{ext}", "document.pdf", 0),
+            apply_naming_template(
+                "{name}_final.{ext}",
+                "document.pdf",
+                0,
+                &NamingContext::default()
+            ),
             "document_final.pdf"
         );
         assert_eq!(
-            apply_naming_template("image_{index}.{ext}", "test.png", 99),
+            apply_naming_template(
+                "image_{index}.{ext}",
+                "test.png",
+                99,
+                &NamingContext::default()
+            ),
             "image_100.png"
         );
     }
@@ -617,7 +1898,7 @@ mod tests {
     #[test]
     fn test_apply_naming_template_no_extension() {
         assert_eq!(
-            apply_naming_template("{index}_{name}", "file", 5),
+            apply_naming_template("{index}_{name}", "file", 5, &NamingContext::default()),
             "006_file"
         );
     }
@@ -648,6 +1929,7 @@ mod tests {
             ],
             "/delivery".to_owned(),
             Some("{index}_{name}.{ext}".to_owned()),
+            DeliveryOptions::default(),
         )
         .await;
 
@@ -680,6 +1962,7 @@ mod tests {
             vec![file1.to_string_lossy().to_string()],
             "/delivery".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -713,6 +1996,7 @@ mod tests {
             vec![file1.to_string_lossy().to_string()],
             "/delivery".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -745,6 +2029,7 @@ mod tests {
             vec![file1.to_string_lossy().to_string()],
             "/delivery".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -799,6 +2084,7 @@ mod tests {
             DeliveryStatus::InProgress,
             DeliveryStatus::Completed,
             DeliveryStatus::Failed,
+            DeliveryStatus::Cancelled,
         ];
 
         for status in statuses {
@@ -819,6 +2105,10 @@ mod tests {
                 completed_at: None,
                 error_message: None,
                 manifest_path: None,
+                output_path: None,
+                options: DeliveryOptions::default(),
+                files_skipped: 0,
+                files_unchanged: 0,
             };
             assert_eq!(job.status, status);
         }
@@ -851,8 +2141,10 @@ mod tests {
             file_name: "image.jpg".to_owned(),
             current_file: 30,
             total_files: 100,
+            files_copied: 29,
             bytes_transferred: 307_200,
             total_bytes: 1_024_000,
+            job_percent: 30.0,
             speed: 102_400.0,
             eta: 7,
         };
@@ -886,6 +2178,7 @@ mod tests {
             vec![file.to_string_lossy().to_string()],
             delivery_path.to_string_lossy().to_string(),
             None,
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -926,14 +2219,14 @@ mod tests {
     fn test_apply_naming_template_with_index() {
         let template = "{name}_{index}";
         // index is 1-based and zero-padded to 3 digits: index 5 becomes "006"
-        let result = apply_naming_template(template, "photo.jpg", 5);
+        let result = apply_naming_template(template, "photo.jpg", 5, &NamingContext::default());
         assert_eq!(result, "photo_006");
     }
 
     #[test]
     fn test_apply_naming_template_with_ext() {
         let template = "{name}.{ext}";
-        let result = apply_naming_template(template, "photo.jpg", 1);
+        let result = apply_naming_template(template, "photo.jpg", 1, &NamingContext::default());
         assert_eq!(result, "photo.jpg");
     }
 
@@ -958,6 +2251,7 @@ mod tests {
             vec![file.to_string_lossy().to_string()],
             delivery_path.to_string_lossy().to_string(),
             Some("{name}_{index}".to_owned()),
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -993,6 +2287,7 @@ mod tests {
         assert_eq!(job.id, "del-123");
         assert_eq!(job.project_id, "proj-456");
         assert_eq!(job.total_files, 2);
+        assert_eq!(job.options, DeliveryOptions::default());
     }
 
     #[test]
@@ -1022,13 +2317,14 @@ mod tests {
     async fn test_apply_naming_template_in_workflow() {
         let template = "{name}_{index}.{ext}";
 
-        let result1 = apply_naming_template(template, "photo.jpg", 0);
+        let result1 = apply_naming_template(template, "photo.jpg", 0, &NamingContext::default());
         assert_eq!(result1, "photo_001.jpg");
 
-        let result2 = apply_naming_template(template, "video.mp4", 9);
+        let result2 = apply_naming_template(template, "video.mp4", 9, &NamingContext::default());
         assert_eq!(result2, "video_010.mp4");
 
-        let result3 = apply_naming_template(template, "document.pdf", 99);
+        let result3 =
+            apply_naming_template(template, "document.pdf", 99, &NamingContext::default());
         assert_eq!(result3, "document_100.pdf");
     }
 
@@ -1063,6 +2359,7 @@ mod tests {
             ],
             "/delivery".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -1125,6 +2422,7 @@ mod tests {
             vec![file1.to_string_lossy().to_string()],
             "/delivery".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -1163,6 +2461,7 @@ mod tests {
             vec![file1.to_string_lossy().to_string()],
             "/del1".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -1174,6 +2473,7 @@ mod tests {
             vec![file2.to_string_lossy().to_string()],
             "/del2".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -1203,19 +2503,25 @@ mod tests {
     #[test]
     fn test_apply_naming_template_edge_cases() {
         // Empty template
-        let result = apply_naming_template("", "file.jpg", 0);
+        let result = apply_naming_template("", "file.jpg", 0, &NamingContext::default());
         assert_eq!(result, "");
 
         // No placeholders
-        let result = apply_naming_template("static_name.jpg", "original.png", 5);
+        let result = apply_naming_template(
+            "static_name.jpg",
+            "original.png",
+            5,
+            &NamingContext::default(),
+        );
         assert_eq!(result, "static_name.jpg");
 
         // Only index
-        let result = apply_naming_template("{index}", "file.jpg", 42);
+        let result = apply_naming_template("{index}", "file.jpg", 42, &NamingContext::default());
         assert_eq!(result, "043");
 
         // File without extension
-        let result = apply_naming_template("{name}_{index}", "README", 10);
+        let result =
+            apply_naming_template("{name}_{index}", "README", 10, &NamingContext::default());
         assert_eq!(result, "README_011");
     }
 
@@ -1251,6 +2557,7 @@ mod tests {
             vec!["/nonexistent/file.jpg".to_owned()],
             "/delivery".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await;
 
@@ -1276,4 +2583,1398 @@ mod tests {
         assert!(result.is_ok());
         assert_eq!(files.len(), 0);
     }
+
+    /// Collect a temp project of files with distinct names, sizes and mtimes.
+    fn sortable_project_files(base: &Path) -> Vec<ProjectFile> {
+        std::fs::create_dir(base.join("Selects")).unwrap();
+        for (relative_path, contents, mtime) in [
+            ("b.jpg", &b"bb"[..], 300),
+            ("Selects/a.JPG", b"a", 100),
+            ("c.mp4", b"cccc", 200),
+            ("d.cr3", b"ddd", 400),
+        ] {
+            let path = base.join(relative_path);
+            std::fs::write(&path, contents).unwrap();
+            std::fs::File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(std::time::UNIX_EPOCH + std::time::Duration::from_secs(mtime))
+                .unwrap();
+        }
+
+        let mut files = Vec::new();
+        collect_project_files(base, base, &mut files).unwrap();
+        files
+    }
+
+    fn names(files: &[ProjectFile]) -> Vec<&str> {
+        files.iter().map(|f| f.name.as_str()).collect()
+    }
+
+    #[test]
+    fn test_filter_by_file_type() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut files = sortable_project_files(temp_dir.path());
+
+        filter_by_file_type(&mut files, ".jpg");
+        sort_project_files(&mut files, SortKey::Name, false);
+        assert_eq!(names(&files), ["a.JPG", "b.jpg"]);
+
+        filter_by_file_type(&mut files, "mp4");
+        assert!(files.is_empty());
+    }
+
+    #[test]
+    fn test_sort_project_files_by_each_key() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let mut files = sortable_project_files(temp_dir.path());
+
+        sort_project_files(&mut files, SortKey::Name, false);
+        assert_eq!(names(&files), ["a.JPG", "b.jpg", "c.mp4", "d.cr3"]);
+        sort_project_files(&mut files, SortKey::Name, true);
+        assert_eq!(names(&files), ["d.cr3", "c.mp4", "b.jpg", "a.JPG"]);
+
+        sort_project_files(&mut files, SortKey::Size, false);
+        assert_eq!(names(&files), ["a.JPG", "b.jpg", "d.cr3", "c.mp4"]);
+        sort_project_files(&mut files, SortKey::Size, true);
+        assert_eq!(names(&files), ["c.mp4", "d.cr3", "b.jpg", "a.JPG"]);
+
+        sort_project_files(&mut files, SortKey::Modified, false);
+        assert_eq!(names(&files), ["a.JPG", "c.mp4", "b.jpg", "d.cr3"]);
+        sort_project_files(&mut files, SortKey::Modified, true);
+        assert_eq!(names(&files), ["d.cr3", "b.jpg", "c.mp4", "a.JPG"]);
+    }
+
+    #[test]
+    fn test_list_project_files_resolves_folder_from_database() {
+        use crate::modules::project::{create_project_impl, NewProject};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+        let project = create_project_impl(
+            &db,
+            &temp_dir.path().join("Projects"),
+            NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                shoot_type: "Wedding".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+        let folder = Path::new(&project.folder_path);
+        std::fs::write(folder.join("RAW/Photos/IMG_0001.CR3"), "raw").unwrap();
+        std::fs::write(folder.join("Selects/IMG_0001.jpg"), "jpeg").unwrap();
+
+        let files =
+            list_project_files_impl(&db, &project.id, None, Some(SortKey::Name), false).unwrap();
+        assert_eq!(names(&files), ["IMG_0001.CR3", "IMG_0001.jpg"]);
+        assert_eq!(files[1].relative_path, "Selects/IMG_0001.jpg");
+
+        let jpegs = list_project_files_impl(&db, &project.id, Some("jpg"), None, false).unwrap();
+        assert_eq!(names(&jpegs), ["IMG_0001.jpg"]);
+
+        let missing = list_project_files_impl(&db, "missing", None, None, false).unwrap_err();
+        assert!(missing.contains("Project not found"));
+    }
+
+    #[tokio::test]
+    async fn test_delivery_context_reads_project_from_database() {
+        use crate::modules::project::{create_project_impl, NewProject};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+        let project = create_project_impl(
+            &db,
+            &temp_dir.path().join("Projects"),
+            NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                shoot_type: "Wedding".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+        let state = crate::state::AppState::default();
+        let job = create_delivery_impl(
+            &state.delivery_queue,
+            project.id.clone(),
+            project.name.clone(),
+            vec![],
+            temp_dir
+                .path()
+                .join("Delivery")
+                .to_string_lossy()
+                .to_string(),
+            None,
+            DeliveryOptions::default(),
+        )
+        .await
+        .unwrap();
+
+        let context = delivery_context(&db, &job, CancellationToken::new()).unwrap();
+        assert_eq!(context.naming.project, "Smith Wedding");
+        assert_eq!(context.naming.client, "Jane Smith");
+        assert_eq!(
+            context.project_root,
+            Some(PathBuf::from(&project.folder_path))
+        );
+
+        let orphan = DeliveryJob {
+            project_id: "missing".to_owned(),
+            ..job
+        };
+        let context = delivery_context(&db, &orphan, CancellationToken::new()).unwrap();
+        assert!(context.naming.client.is_empty());
+        assert!(context.project_root.is_none());
+
+        // Without its project folder a structured delivery would flatten, so it is refused
+        let mut structured = orphan;
+        structured.options.preserve_structure = true;
+        let err = delivery_context(&db, &structured, CancellationToken::new()).unwrap_err();
+        assert!(err.contains("missing"), "{err}");
+    }
+
+    #[test]
+    fn test_select_files_by_pattern() {
+        use crate::modules::project::{create_project_impl, NewProject};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+        let project = create_project_impl(
+            &db,
+            &temp_dir.path().join("Projects"),
+            NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                shoot_type: "Wedding".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+        let folder = Path::new(&project.folder_path);
+        std::fs::create_dir_all(folder.join("Selects/Ceremony")).unwrap();
+        for file in [
+            "Selects/IMG_0001.jpg",
+            "Selects/IMG_0002.JPG",
+            "Selects/Ceremony/IMG_0003.jpg",
+            "Selects/notes.txt",
+            "RAW/Photos/IMG_0004.jpg",
+        ] {
+            std::fs::write(folder.join(file), "data").unwrap();
+        }
+        let select = |pattern: &str| {
+            select_files_by_pattern_impl(&db, &project.id, pattern)
+                .unwrap()
+                .into_iter()
+                .map(|path| {
+                    Path::new(&path)
+                        .strip_prefix(folder)
+                        .unwrap()
+                        .to_string_lossy()
+                        .replace('\\', "/")
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            select("Selects/**/*.jpg"),
+            [
+                "Selects/Ceremony/IMG_0003.jpg",
+                "Selects/IMG_0001.jpg",
+                "Selects/IMG_0002.JPG",
+            ]
+        );
+        assert_eq!(
+            select("Selects/*.jpg"),
+            ["Selects/IMG_0001.jpg", "Selects/IMG_0002.JPG"]
+        );
+        assert_eq!(
+            select("**/IMG_000?.jpg").len(),
+            4,
+            "** also matches files at any depth"
+        );
+        assert!(select("selects/*.jpg").is_empty());
+        assert!(select_files_by_pattern_impl(&db, &project.id, " ").is_err());
+        assert!(select_files_by_pattern_impl(&db, "missing", "*.jpg").is_err());
+    }
+
+    #[test]
+    fn test_sort_key_deserialization() {
+        assert_eq!(
+            serde_json::from_str::<SortKey>(r#""modified""#).unwrap(),
+            SortKey::Modified
+        );
+        assert!(serde_json::from_str::<SortKey>(r#""date""#).is_err());
+    }
+
+    #[test]
+    fn test_write_delivery_zip_entries() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let mut selected_files = Vec::new();
+        for (file_name, contents) in [
+            ("a.jpg", &b"first"[..]),
+            ("b.jpg", b"second"),
+            ("c.mp4", b"third!"),
+        ] {
+            let path = temp_dir.path().join(file_name);
+            std::fs::write(&path, contents).unwrap();
+            selected_files.push(path.to_string_lossy().to_string());
+        }
+
+        let mut job = DeliveryJob {
+            id: "del-zip".to_owned(),
+            project_id: "proj-zip".to_owned(),
+            project_name: "Smith Wedding".to_owned(),
+            total_files: selected_files.len(),
+            selected_files,
+            delivery_path: temp_dir.path().to_string_lossy().to_string(),
+            naming_template: Some("{index}_{name}.{ext}".to_owned()),
+            status: DeliveryStatus::InProgress,
+            files_copied: 0,
+            total_bytes: 17,
+            bytes_transferred: 0,
+            created_at: "2024-01-01".to_owned(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+            manifest_path: None,
+            output_path: None,
+            options: DeliveryOptions {
+                package_as_zip: true,
+                ..DeliveryOptions::default()
+            },
+            files_skipped: 0,
+            files_unchanged: 0,
+        };
+
+        let zip_path = temp_dir.path().join(zip_file_name(&job.project_name));
+        let mut last_progress = None;
+        write_delivery_zip(
+            &mut job,
+            &zip_path,
+            &DeliveryContext::default(),
+            |progress| {
+                last_progress = Some(progress.clone());
+            },
+        )
+        .unwrap();
+
+        assert_eq!(job.files_copied, 3);
+        assert_eq!(job.bytes_transferred, 17);
+        let last_progress = last_progress.unwrap();
+        assert_eq!(last_progress.bytes_transferred, 17);
+        assert_eq!(last_progress.files_copied, 3);
+        assert!((last_progress.job_percent - 100.0).abs() < f64::EPSILON);
+
+        let mut archive = zip::ZipArchive::new(std::fs::File::open(&zip_path).unwrap()).unwrap();
+        let names: Vec<_> = archive.file_names().map(str::to_owned).collect();
+        assert_eq!(names.len(), 4);
+        for entry in ["001_a.jpg", "002_b.jpg", "003_c.mp4", MANIFEST_FILE_NAME] {
+            assert!(names.iter().any(|n| n == entry), "missing entry {entry}");
+        }
+
+        let mut contents = String::new();
+        archive
+            .by_name("002_b.jpg")
+            .unwrap()
+            .read_to_string(&mut contents)
+            .unwrap();
+        assert_eq!(contents, "second");
+
+        let mut manifest = String::new();
+        archive
+            .by_name(MANIFEST_FILE_NAME)
+            .unwrap()
+            .read_to_string(&mut manifest)
+            .unwrap();
+        assert!(manifest.contains("Project: Smith Wedding"));
+        assert!(manifest.contains("c.mp4 -> 003_c.mp4 (6)"));
+    }
+
+    #[test]
+    fn test_zip_file_name() {
+        assert_eq!(zip_file_name("Smith Wedding"), "Smith Wedding.zip");
+        assert_eq!(zip_file_name("A/B: C"), "A_B_ C.zip");
+        assert_eq!(zip_file_name("  "), "delivery.zip");
+    }
+
+    /// Two `photo.jpg` files from different project subfolders, queued as a delivery job.
+    async fn queue_same_named_files(
+        temp_dir: &tempfile::TempDir,
+        state: &crate::state::AppState,
+        preserve_structure: bool,
+    ) -> DeliveryJob {
+        let project = temp_dir.path().join("project");
+        for (subfolder, contents) in [("Selects", "selects"), ("Edited", "edited")] {
+            std::fs::create_dir_all(project.join(subfolder)).unwrap();
+            std::fs::write(project.join(subfolder).join("photo.jpg"), contents).unwrap();
+        }
+
+        create_delivery_impl(
+            &state.delivery_queue,
+            "proj-structure".to_owned(),
+            "Structure Test".to_owned(),
+            vec![
+                project
+                    .join("Selects/photo.jpg")
+                    .to_string_lossy()
+                    .to_string(),
+                project
+                    .join("Edited/photo.jpg")
+                    .to_string_lossy()
+                    .to_string(),
+            ],
+            temp_dir
+                .path()
+                .join("delivery")
+                .to_string_lossy()
+                .to_string(),
+            None,
+            DeliveryOptions {
+                preserve_structure,
+                ..DeliveryOptions::default()
+            },
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_process_delivery_preserves_structure() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let state = crate::state::AppState::default();
+        let job = queue_same_named_files(&temp_dir, &state, true).await;
+
+        let context = DeliveryContext {
+            project_root: Some(temp_dir.path().join("project")),
+            ..DeliveryContext::default()
+        };
+        process_delivery(job, context, state.delivery_queue.clone(), |_| {})
+            .await
+            .unwrap();
+
+        let delivery = temp_dir.path().join("delivery");
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("Selects/photo.jpg")).unwrap(),
+            "selects"
+        );
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("Edited/photo.jpg")).unwrap(),
+            "edited"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_delivery_stops_when_cancelled() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let state = crate::state::AppState::default();
+        let context = DeliveryContext::default();
+        context.cancel.cancel();
+
+        let job = queue_same_named_files(&temp_dir, &state, false).await;
+        let result =
+            process_delivery(job, context.clone(), state.delivery_queue.clone(), |_| {}).await;
+        assert!(matches!(result, Err(DeliveryError::Cancelled)));
+
+        let mut job = queue_same_named_files(&temp_dir, &state, false).await;
+        job.options.package_as_zip = true;
+        let result = process_delivery(job, context, state.delivery_queue.clone(), |_| {}).await;
+        assert!(matches!(result, Err(DeliveryError::Cancelled)));
+
+        let delivery = temp_dir.path().join("delivery");
+        assert_eq!(std::fs::read_dir(delivery).unwrap().count(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_process_delivery_renames_flattened_collisions() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let state = crate::state::AppState::default();
+        let job = queue_same_named_files(&temp_dir, &state, false).await;
+        let job_id = job.id.clone();
+
+        process_delivery(
+            job,
+            DeliveryContext::default(),
+            state.delivery_queue.clone(),
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        let delivery = temp_dir.path().join("delivery");
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("photo.jpg")).unwrap(),
+            "selects"
+        );
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("photo (1).jpg")).unwrap(),
+            "edited"
+        );
+
+        let job = state.delivery_queue.lock().await[&job_id].clone();
+        assert_eq!(job.files_copied, 2);
+        assert_eq!(
+            job.output_path.as_deref(),
+            Some(delivery.to_string_lossy().as_ref())
+        );
+    }
+
+    /// Deliver `photo.jpg` ("new") into a folder already holding a `photo.jpg`
+    /// ("old") under `conflict_mode`, returning the folder and the finished job.
+    async fn deliver_over_existing_file(
+        temp_dir: &tempfile::TempDir,
+        conflict_mode: &str,
+    ) -> (PathBuf, DeliveryJob) {
+        let source = temp_dir.path().join("project/photo.jpg");
+        std::fs::create_dir_all(source.parent().unwrap()).unwrap();
+        std::fs::write(&source, "new").unwrap();
+        let delivery = temp_dir.path().join("delivery");
+        std::fs::create_dir_all(&delivery).unwrap();
+        std::fs::write(delivery.join("photo.jpg"), "old").unwrap();
+
+        let state = crate::state::AppState::default();
+        let job = create_delivery_impl(
+            &state.delivery_queue,
+            "proj".to_owned(),
+            "Conflict Test".to_owned(),
+            vec![source.to_string_lossy().to_string()],
+            delivery.to_string_lossy().to_string(),
+            None,
+            DeliveryOptions {
+                conflict_mode: conflict_mode.to_owned(),
+                ..DeliveryOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+        let job_id = job.id.clone();
+
+        process_delivery(
+            job,
+            DeliveryContext::default(),
+            state.delivery_queue.clone(),
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        let job = state.delivery_queue.lock().await[&job_id].clone();
+        (delivery, job)
+    }
+
+    #[tokio::test]
+    async fn test_conflict_mode_skip_keeps_existing_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (delivery, job) = deliver_over_existing_file(&temp_dir, "skip").await;
+
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("photo.jpg")).unwrap(),
+            "old"
+        );
+        assert!(!delivery.join("photo (1).jpg").exists());
+        assert_eq!(job.files_copied, 0);
+        assert_eq!(job.files_skipped, 1);
+    }
+
+    #[tokio::test]
+    async fn test_conflict_mode_rename_adds_counter() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (delivery, job) = deliver_over_existing_file(&temp_dir, "rename").await;
+
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("photo.jpg")).unwrap(),
+            "old"
+        );
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("photo (1).jpg")).unwrap(),
+            "new"
+        );
+        assert_eq!(job.files_copied, 1);
+        assert_eq!(job.files_skipped, 0);
+    }
+
+    /// Deliver `files` into `delivery` with `skip_existing_identical` set,
+    /// returning the finished job.
+    async fn deliver_incrementally(
+        files: &[PathBuf],
+        delivery: &Path,
+        conflict_mode: &str,
+    ) -> DeliveryJob {
+        let state = crate::state::AppState::default();
+        let job = create_delivery_impl(
+            &state.delivery_queue,
+            "proj".to_owned(),
+            "Resume Test".to_owned(),
+            files
+                .iter()
+                .map(|f| f.to_string_lossy().to_string())
+                .collect(),
+            delivery.to_string_lossy().to_string(),
+            None,
+            DeliveryOptions {
+                conflict_mode: conflict_mode.to_owned(),
+                skip_existing_identical: true,
+                ..DeliveryOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+        let job_id = job.id.clone();
+
+        process_delivery(
+            job,
+            DeliveryContext::default(),
+            state.delivery_queue.clone(),
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        let job = state.delivery_queue.lock().await[&job_id].clone();
+        job
+    }
+
+    #[tokio::test]
+    async fn test_failed_delivery_can_be_retried() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let photo = temp_dir.path().join("photo.jpg");
+        let delivery = temp_dir.path().join("delivery");
+        let state = crate::state::AppState::default();
+        let job = create_delivery_impl(
+            &state.delivery_queue,
+            "proj".to_owned(),
+            "Retry Test".to_owned(),
+            vec![photo.to_string_lossy().to_string()],
+            delivery.to_string_lossy().to_string(),
+            None,
+            DeliveryOptions::default(),
+        )
+        .await
+        .unwrap();
+        let job_id = job.id.clone();
+
+        // Only pending-then-failed jobs can be retried
+        assert!(retry_delivery_impl(&state.delivery_queue, &job_id)
+            .await
+            .is_err());
+
+        // The source is missing, so the first run fails
+        let result = process_delivery(
+            job,
+            DeliveryContext::default(),
+            state.delivery_queue.clone(),
+            |_| {},
+        )
+        .await;
+        assert!(result.is_err());
+        if let Some(job) = state.delivery_queue.lock().await.get_mut(&job_id) {
+            job.status = DeliveryStatus::Failed;
+            job.error_message = Some("File not found".to_owned());
+            job.started_at = Some(get_timestamp());
+            job.completed_at = Some(get_timestamp());
+            job.files_copied = 1;
+            job.bytes_transferred = 10;
+            job.output_path = Some(delivery.to_string_lossy().to_string());
+        }
+
+        std::fs::write(&photo, "photo").unwrap();
+        let retried = retry_delivery_impl(&state.delivery_queue, &job_id)
+            .await
+            .unwrap();
+        assert_eq!(retried.status, DeliveryStatus::Pending);
+        assert!(retried.error_message.is_none());
+        assert!(retried.completed_at.is_none());
+        assert_eq!(retried.files_copied, 0);
+        assert_eq!(retried.bytes_transferred, 0);
+        assert!(retried.output_path.is_none());
+
+        process_delivery(
+            retried,
+            DeliveryContext::default(),
+            state.delivery_queue.clone(),
+            |_| {},
+        )
+        .await
+        .unwrap();
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("photo.jpg")).unwrap(),
+            "photo"
+        );
+        assert_eq!(state.delivery_queue.lock().await[&job_id].files_copied, 1);
+        assert!(retry_delivery_impl(&state.delivery_queue, "missing")
+            .await
+            .is_err());
+    }
+
+    #[test]
+    fn test_already_delivered_compares_rendered_contents() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let source = temp_dir.path().join("IMG_0001.jpg");
+        let dest = temp_dir.path().join("delivered.jpg");
+        std::fs::write(&source, b"original").unwrap();
+        std::fs::write(&dest, b"rendered").unwrap();
+
+        assert!(is_already_delivered(&source, Some(b"rendered"), &dest).unwrap());
+        assert!(!is_already_delivered(&source, Some(b"reworked"), &dest).unwrap());
+        assert!(!is_already_delivered(&source, None, &dest).unwrap());
+        std::fs::write(&dest, b"original").unwrap();
+        assert!(is_already_delivered(&source, None, &dest).unwrap());
+        assert!(!is_already_delivered(&source, None, &temp_dir.path().join("gone.jpg")).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_redelivery_skips_identical_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let files = vec![project.join("a.jpg"), project.join("b.jpg")];
+        std::fs::write(&files[0], "first photo").unwrap();
+        std::fs::write(&files[1], "second photo").unwrap();
+        let delivery = temp_dir.path().join("delivery");
+
+        let first = deliver_incrementally(&files, &delivery, "rename").await;
+        assert_eq!(first.files_copied, 2);
+        assert_eq!(first.files_unchanged, 0);
+
+        let second = deliver_incrementally(&files, &delivery, "rename").await;
+        assert_eq!(second.files_copied, 0);
+        assert_eq!(second.files_unchanged, 2);
+        assert_eq!(second.bytes_transferred, 0);
+        assert!(!delivery.join("a (1).jpg").exists());
+        let manifest = std::fs::read_to_string(delivery.join(MANIFEST_FILE_NAME)).unwrap();
+        assert!(manifest.contains("a.jpg -> a.jpg"));
+        assert!(manifest.contains("b.jpg -> b.jpg"));
+    }
+
+    #[tokio::test]
+    async fn test_redelivery_copies_changed_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let project = temp_dir.path().join("project");
+        std::fs::create_dir_all(&project).unwrap();
+        let files = vec![project.join("a.jpg"), project.join("b.jpg")];
+        std::fs::write(&files[0], "first photo").unwrap();
+        std::fs::write(&files[1], "second photo").unwrap();
+        let delivery = temp_dir.path().join("delivery");
+        deliver_incrementally(&files, &delivery, "overwrite").await;
+
+        // Same size, different contents: only the checksum tells them apart
+        std::fs::write(&files[1], "SECOND PHOTO").unwrap();
+        let second = deliver_incrementally(&files, &delivery, "overwrite").await;
+
+        assert_eq!(second.files_copied, 1);
+        assert_eq!(second.files_unchanged, 1);
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("b.jpg")).unwrap(),
+            "SECOND PHOTO"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_conflict_mode_overwrite_replaces_existing_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (delivery, job) = deliver_over_existing_file(&temp_dir, "overwrite").await;
+
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("photo.jpg")).unwrap(),
+            "new"
+        );
+        assert!(!delivery.join("photo (1).jpg").exists());
+        assert_eq!(job.files_copied, 1);
+    }
+
+    #[tokio::test]
+    async fn test_conflict_mode_overwrite_keep_backup_preserves_old_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let (delivery, job) = deliver_over_existing_file(&temp_dir, "overwrite_keep_backup").await;
+
+        assert_eq!(
+            std::fs::read_to_string(delivery.join("photo.jpg")).unwrap(),
+            "new"
+        );
+        let backups: Vec<PathBuf> = std::fs::read_dir(&delivery)
+            .unwrap()
+            .map(|entry| entry.unwrap().path())
+            .filter(|path| {
+                path.file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .starts_with("photo.jpg.bak-")
+            })
+            .collect();
+        assert_eq!(backups.len(), 1);
+        assert_eq!(std::fs::read_to_string(&backups[0]).unwrap(), "old");
+        assert_eq!(job.files_copied, 1);
+
+        // Keeping the file again, even within the same second, never replaces the first backup
+        let kept = keep_existing_file(&delivery.join("photo.jpg")).unwrap();
+        assert_ne!(kept, backups[0]);
+        assert_eq!(std::fs::read_to_string(&backups[0]).unwrap(), "old");
+        assert_eq!(std::fs::read_to_string(kept).unwrap(), "new");
+    }
+
+    #[tokio::test]
+    async fn test_invalid_conflict_mode_is_rejected() {
+        let state = crate::state::AppState::default();
+        let result = create_delivery_impl(
+            &state.delivery_queue,
+            "proj".to_owned(),
+            "Conflict Test".to_owned(),
+            vec![],
+            "/tmp/delivery".to_owned(),
+            None,
+            DeliveryOptions {
+                conflict_mode: "merge".to_owned(),
+                ..DeliveryOptions::default()
+            },
+        )
+        .await;
+
+        assert_eq!(result.unwrap_err(), "Invalid conflict mode: merge");
+        assert!(state.delivery_queue.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_process_delivery_final_progress_reports_all_files() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let state = crate::state::AppState::default();
+        let job = queue_same_named_files(&temp_dir, &state, false).await;
+
+        let events = std::sync::Arc::new(std::sync::Mutex::new(Vec::new()));
+        let recorded = events.clone();
+        process_delivery(
+            job,
+            DeliveryContext::default(),
+            state.delivery_queue.clone(),
+            move |progress| recorded.lock().unwrap().push(progress.clone()),
+        )
+        .await
+        .unwrap();
+
+        let events = events.lock().unwrap().clone();
+        let (last, earlier) = events.split_last().unwrap();
+        assert_eq!(last.files_copied, 2);
+        assert_eq!(last.total_files, 2);
+        assert_eq!(last.bytes_transferred, last.total_bytes);
+        assert!((last.job_percent - 100.0).abs() < f64::EPSILON);
+        assert!(earlier.iter().all(|p| p.job_percent < 100.0));
+        assert_eq!(earlier.first().map(|p| p.files_copied), Some(0));
+    }
+
+    #[test]
+    fn test_delivery_entry_name_counter_keeps_subfolder() {
+        let mut job = DeliveryJob {
+            id: "del-names".to_owned(),
+            project_id: "proj".to_owned(),
+            project_name: "Names".to_owned(),
+            selected_files: vec![],
+            delivery_path: "/delivery".to_owned(),
+            naming_template: None,
+            status: DeliveryStatus::Pending,
+            total_files: 0,
+            files_copied: 0,
+            total_bytes: 0,
+            bytes_transferred: 0,
+            created_at: "2024-01-01".to_owned(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+            manifest_path: None,
+            output_path: None,
+            options: DeliveryOptions {
+                preserve_structure: true,
+                ..DeliveryOptions::default()
+            },
+            files_skipped: 0,
+            files_unchanged: 0,
+        };
+        let context = DeliveryContext {
+            project_root: Some(PathBuf::from("/project")),
+            ..DeliveryContext::default()
+        };
+        let source = Path::new("/project/RAW/Day 1/IMG_0001.CR3");
+
+        let name = delivery_entry_name(&job, 0, source, &context, |_| false).unwrap();
+        assert_eq!(name, "RAW/Day 1/IMG_0001.CR3");
+
+        let name = delivery_entry_name(&job, 0, source, &context, |n| {
+            n == "RAW/Day 1/IMG_0001.CR3" || n == "RAW/Day 1/IMG_0001 (1).CR3"
+        })
+        .unwrap();
+        assert_eq!(name, "RAW/Day 1/IMG_0001 (2).CR3");
+
+        // Files outside the project root are delivered at the top level
+        let name = delivery_entry_name(&job, 0, Path::new("/elsewhere/a.jpg"), &context, |_| false)
+            .unwrap();
+        assert_eq!(name, "a.jpg");
+
+        job.options.preserve_structure = false;
+        let name = delivery_entry_name(&job, 0, source, &context, |_| false).unwrap();
+        assert_eq!(name, "IMG_0001.CR3");
+    }
+
+    fn naming_context() -> NamingContext {
+        NamingContext {
+            project: "Smith Wedding".to_owned(),
+            client: "Jane Smith".to_owned(),
+            date: "2024-06-15".to_owned(),
+            index_padding: DEFAULT_INDEX_PADDING,
+        }
+    }
+
+    #[test]
+    fn test_apply_naming_template_date_token() {
+        let result = apply_naming_template("{date}.{ext}", "photo.jpg", 0, &naming_context());
+        assert_eq!(result, "2024-06-15.jpg");
+    }
+
+    #[test]
+    fn test_apply_naming_template_project_token() {
+        let result = apply_naming_template("{project}_{name}", "photo.jpg", 0, &naming_context());
+        assert_eq!(result, "Smith Wedding_photo");
+    }
+
+    #[test]
+    fn test_apply_naming_template_client_token() {
+        let result = apply_naming_template("{client}-{index}", "photo.jpg", 1, &naming_context());
+        assert_eq!(result, "Jane Smith-002");
+    }
+
+    #[test]
+    fn test_apply_naming_template_combined_tokens() {
+        let result = apply_naming_template(
+            "{date}_{project}_{index}.{ext}",
+            "IMG_0001.CR3",
+            41,
+            &naming_context(),
+        );
+        assert_eq!(result, "2024-06-15_Smith Wedding_042.CR3");
+    }
+
+    #[test]
+    fn test_validate_template_valid() {
+        let validation = validate_template(
+            concat!("{project}_{index", ":4}_{name}.{ext}"),
+            DEFAULT_INDEX_PADDING,
+        );
+
+        assert!(validation.valid);
+        assert!(validation.errors.is_empty());
+        assert_eq!(validation.tokens, vec!["project", "index", "name", "ext"]);
+        assert_eq!(
+            validation.sample.as_deref(),
+            Some("Sample Project_0001_IMG_0001.jpg")
+        );
+    }
+
+    #[test]
+    fn test_validate_template_unknown_token() {
+        let validation = validate_template("{name}_{foo}.{ext}", DEFAULT_INDEX_PADDING);
+
+        assert!(!validation.valid);
+        assert_eq!(validation.errors, vec!["Unknown token {foo}"]);
+        assert_eq!(validation.tokens, vec!["name", "ext"]);
+        assert!(validation.sample.is_none());
+        assert!(!validate_template("{index:99}", DEFAULT_INDEX_PADDING).valid);
+    }
+
+    #[test]
+    fn test_validate_template_unbalanced_braces() {
+        let validation = validate_template("{name.{ext}}", DEFAULT_INDEX_PADDING);
+
+        assert!(!validation.valid);
+        assert_eq!(
+            validation.errors,
+            vec!["Unclosed '{' at position 1", "Unmatched '}' at position 12"]
+        );
+        assert_eq!(validation.tokens, vec!["ext"]);
+        assert!(!validate_template("", DEFAULT_INDEX_PADDING).valid);
+    }
+
+    #[test]
+    fn test_apply_naming_template_context_tokens_sanitized_and_unknown_kept() {
+        let context = NamingContext {
+            project: "Smith/Jones: Gala".to_owned(),
+            ..naming_context()
+        };
+        let result = apply_naming_template("{project}_{camera}_{name}", "a.jpg", 0, &context);
+        assert_eq!(result, "Smith_Jones_ Gala_{camera}_a");
+    }
+
+    #[test]
+    fn test_apply_naming_template_index_padding() {
+        let context = NamingContext::default();
+        assert_eq!(
+            apply_naming_template("{index}", "a.jpg", 41, &context),
+            "042"
+        );
+        assert_eq!(
+            apply_naming_template("{index:2}", "a.jpg", 41, &context),
+            "42"
+        );
+        assert_eq!(
+            apply_naming_template("{index:5}_{name}", "a.jpg", 41, &context),
+            "00042_a"
+        );
+        assert_eq!(
+            apply_naming_template("{index:1}", "a.jpg", 1233, &context),
+            "1234"
+        );
+        assert_eq!(
+            apply_naming_template(concat!("{index:2}-{index", ":4}"), "a.jpg", 6, &context),
+            "07-0007"
+        );
+    }
+
+    #[test]
+    fn test_apply_naming_template_malformed_index_spec() {
+        let context = NamingContext::default();
+        assert_eq!(
+            apply_naming_template("{index:abc}", "a.jpg", 0, &context),
+            "{index:abc}"
+        );
+        assert_eq!(
+            apply_naming_template("{index:999}", "a.jpg", 0, &context),
+            "{index:999}"
+        );
+        assert_eq!(
+            apply_naming_template("{index:}", "a.jpg", 0, &context),
+            "{index:}"
+        );
+        assert_eq!(
+            apply_naming_template("{indexed}_{index", "a.jpg", 0, &context),
+            "{indexed}_{index"
+        );
+    }
+
+    #[test]
+    fn test_bare_index_padding_follows_the_context() {
+        let unpadded = NamingContext {
+            index_padding: 0,
+            ..naming_context()
+        };
+        assert_eq!(apply_naming_template("{index}", "a.jpg", 6, &unpadded), "7");
+        assert_eq!(
+            apply_naming_template(concat!("{index", ":2}"), "a.jpg", 6, &unpadded),
+            "07"
+        );
+        assert_eq!(
+            validate_template("{index}_{name}", 5).sample.as_deref(),
+            Some("00001_IMG_0001")
+        );
+    }
+
+    #[test]
+    fn test_validate_template_agrees_with_rendering_on_index_specs() {
+        let context = naming_context();
+        for (template, valid, rendered) in [
+            ("{index:0}", true, "7"),
+            ("{index:13}", false, "{index:13}"),
+            ("{index:x}", false, "{index:x}"),
+        ] {
+            assert_eq!(
+                validate_template(template, DEFAULT_INDEX_PADDING).valid,
+                valid,
+                "{template}"
+            );
+            assert_eq!(
+                apply_naming_template(template, "a.jpg", 6, &context),
+                rendered
+            );
+        }
+    }
+
+    /// Write a JPEG carrying a GPS latitude and a camera serial number.
+    fn write_gps_jpeg(path: &Path) {
+        use exif::experimental::Writer;
+        use exif::{Field, In, Rational, Tag, Value};
+
+        let fields = [
+            Field {
+                tag: Tag::GPSLatitude,
+                ifd_num: In::PRIMARY,
+                value: Value::Rational(vec![Rational { num: 52, denom: 1 }; 3]),
+            },
+            Field {
+                tag: Tag::BodySerialNumber,
+                ifd_num: In::PRIMARY,
+                value: Value::Ascii(vec![b"SN12345".to_vec()]),
+            },
+        ];
+        let mut writer = Writer::new();
+        for field in &fields {
+            writer.push_field(field);
+        }
+        let mut tiff = std::io::Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let tiff = tiff.into_inner();
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend_from_slice(&u16::try_from(tiff.len() + 8).unwrap().to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend_from_slice(&tiff);
+        jpeg.extend_from_slice(&[0xFF, 0xD9]);
+        std::fs::write(path, jpeg).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_process_delivery_strips_gps_and_embeds_copyright() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let state = crate::state::AppState::default();
+        let photo = temp_dir.path().join("photo.jpg");
+        let clip = temp_dir.path().join("clip.mp4");
+        write_gps_jpeg(&photo);
+        std::fs::write(&clip, b"video bytes").unwrap();
+        let delivery = temp_dir.path().join("delivery");
+
+        let job = create_delivery_impl(
+            &state.delivery_queue,
+            "test-project".to_owned(),
+            "Metadata Test".to_owned(),
+            vec![
+                photo.to_string_lossy().to_string(),
+                clip.to_string_lossy().to_string(),
+            ],
+            delivery.to_string_lossy().to_string(),
+            None,
+            DeliveryOptions {
+                images: ImageOptions {
+                    strip_exif: true,
+                    copyright: Some("© 2024 Jane Doe".to_owned()),
+                    ..ImageOptions::default()
+                },
+                ..DeliveryOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        process_delivery(
+            job,
+            DeliveryContext::default(),
+            state.delivery_queue.clone(),
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        let delivered = std::fs::read(delivery.join("photo.jpg")).unwrap();
+        let exif = exif::Reader::new()
+            .read_from_container(&mut std::io::Cursor::new(&delivered))
+            .unwrap();
+        assert!(exif
+            .get_field(exif::Tag::GPSLatitude, exif::In::PRIMARY)
+            .is_none());
+        assert!(exif
+            .get_field(exif::Tag::BodySerialNumber, exif::In::PRIMARY)
+            .is_none());
+        let copyright = exif
+            .get_field(exif::Tag::Copyright, exif::In::PRIMARY)
+            .unwrap();
+        assert!(copyright
+            .display_value()
+            .to_string()
+            .contains("2024 Jane Doe"));
+        assert!(delivered
+            .windows("© 2024 Jane Doe".len())
+            .any(|w| w == "© 2024 Jane Doe".as_bytes()));
+
+        assert_eq!(
+            std::fs::read(delivery.join("clip.mp4")).unwrap(),
+            b"video bytes"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_process_delivery_exports_images_to_preset() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let state = crate::state::AppState::default();
+        let photo = temp_dir.path().join("large.jpg");
+        let clip = temp_dir.path().join("clip.mp4");
+        image::RgbImage::from_fn(2560, 1600, |x, y| {
+            image::Rgb([(x % 256) as u8, (y % 256) as u8, ((x * y) % 256) as u8])
+        })
+        .save(&photo)
+        .unwrap();
+        std::fs::write(&clip, b"video bytes").unwrap();
+        let delivery = temp_dir.path().join("delivery");
+
+        let job = create_delivery_impl(
+            &state.delivery_queue,
+            "test-project".to_owned(),
+            "Export Test".to_owned(),
+            vec![
+                photo.to_string_lossy().to_string(),
+                clip.to_string_lossy().to_string(),
+            ],
+            delivery.to_string_lossy().to_string(),
+            None,
+            DeliveryOptions {
+                images: ImageOptions {
+                    export_preset: Some(ExportPreset {
+                        max_long_edge: 2048,
+                        quality: 85,
+                        format: ExportFormat::Jpeg,
+                    }),
+                    ..ImageOptions::default()
+                },
+                ..DeliveryOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        process_delivery(
+            job,
+            DeliveryContext::default(),
+            state.delivery_queue.clone(),
+            |_| {},
+        )
+        .await
+        .unwrap();
+
+        let exported = std::fs::read(delivery.join("large.jpg")).unwrap();
+        let image = image::load_from_memory(&exported).unwrap();
+        assert_eq!((image.width(), image.height()), (2048, 1280));
+        let original_size = std::fs::metadata(&photo).unwrap().len();
+        assert!((exported.len() as u64) < original_size);
+
+        assert_eq!(
+            std::fs::read(delivery.join("clip.mp4")).unwrap(),
+            b"video bytes"
+        );
+        let manifest = std::fs::read_to_string(delivery.join(MANIFEST_FILE_NAME)).unwrap();
+        assert!(manifest.contains(&format!(
+            "large.jpg -> large.jpg ({original_size} -> {})",
+            exported.len()
+        )));
+    }
+
+    #[test]
+    fn test_delivery_entry_name_uses_export_extension() {
+        let job = DeliveryJob {
+            id: "del-export".to_owned(),
+            project_id: "proj".to_owned(),
+            project_name: "Export".to_owned(),
+            selected_files: vec![],
+            delivery_path: "/delivery".to_owned(),
+            naming_template: None,
+            status: DeliveryStatus::Pending,
+            total_files: 0,
+            files_copied: 0,
+            total_bytes: 0,
+            bytes_transferred: 0,
+            created_at: "2024-01-01".to_owned(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+            manifest_path: None,
+            output_path: None,
+            options: DeliveryOptions {
+                images: ImageOptions {
+                    export_preset: Some(ExportPreset {
+                        max_long_edge: 1024,
+                        quality: 90,
+                        format: ExportFormat::Png,
+                    }),
+                    ..ImageOptions::default()
+                },
+                ..DeliveryOptions::default()
+            },
+            files_skipped: 0,
+            files_unchanged: 0,
+        };
+        let context = DeliveryContext::default();
+
+        let name = |path: &str| {
+            delivery_entry_name(&job, 0, Path::new(path), &context, |_| false).unwrap()
+        };
+        assert_eq!(name("/shoot/IMG_0001.CR2"), "IMG_0001.png");
+        assert_eq!(name("/shoot/clip.mp4"), "clip.mp4");
+    }
+
+    #[tokio::test]
+    async fn test_create_delivery_rejects_invalid_export_preset() {
+        let state = crate::state::AppState::default();
+        let result = create_delivery_impl(
+            &state.delivery_queue,
+            "test-project".to_owned(),
+            "Export Test".to_owned(),
+            vec![],
+            "/tmp/delivery".to_owned(),
+            None,
+            DeliveryOptions {
+                images: ImageOptions {
+                    export_preset: Some(ExportPreset {
+                        max_long_edge: 2048,
+                        quality: 0,
+                        format: ExportFormat::Jpeg,
+                    }),
+                    ..ImageOptions::default()
+                },
+                ..DeliveryOptions::default()
+            },
+        )
+        .await;
+
+        assert!(result.is_err());
+        assert!(state.delivery_queue.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_delivery_queue_round_trip_fails_interrupted_jobs() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let state = crate::state::AppState::default();
+        let mut ids = Vec::new();
+        for _ in 0..3 {
+            let job = create_delivery_impl(
+                &state.delivery_queue,
+                "proj".to_owned(),
+                "Persisted".to_owned(),
+                vec![],
+                "/tmp/delivery".to_owned(),
+                None,
+                DeliveryOptions::default(),
+            )
+            .await
+            .unwrap();
+            ids.push(job.id);
+        }
+        {
+            let mut queue = state.delivery_queue.lock().await;
+            queue.get_mut(&ids[1]).unwrap().status = DeliveryStatus::InProgress;
+            queue.get_mut(&ids[2]).unwrap().status = DeliveryStatus::Completed;
+        }
+
+        let path = temp_dir.path().join(QUEUE_FILE_NAME);
+        save_delivery_queue(&state.delivery_queue.lock().await.clone(), &path).unwrap();
+
+        let restored_state = crate::state::AppState::default();
+        let queue = restored_state.delivery_queue.clone();
+        let count = tokio::task::spawn_blocking(move || restore_delivery_queue_from(&queue, &path))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(count, 3);
+
+        let restored = restored_state.delivery_queue.lock().await;
+        assert_eq!(restored[&ids[0]].status, DeliveryStatus::Pending);
+        assert_eq!(restored[&ids[1]].status, DeliveryStatus::Failed);
+        assert_eq!(
+            restored[&ids[1]].error_message.as_deref(),
+            Some(INTERRUPTED_MESSAGE)
+        );
+        assert_eq!(restored[&ids[2]].status, DeliveryStatus::Completed);
+        assert!(restored[&ids[2]].error_message.is_none());
+        drop(restored);
+    }
+
+    #[tokio::test]
+    async fn test_delivery_history_is_saved_and_filtered_by_project() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("CreatorOps").join(HISTORY_FILE_NAME);
+        assert!(load_delivery_history(&path, None).unwrap().is_empty());
+
+        let state = crate::state::AppState::default();
+        for (project_id, completed_at) in [
+            ("proj-a", "2024-06-01T10:00:00Z"),
+            ("proj-b", "2024-06-02T10:00:00Z"),
+            ("proj-a", "2024-06-03T10:00:00Z"),
+        ] {
+            let mut job = create_delivery_impl(
+                &state.delivery_queue,
+                project_id.to_owned(),
+                "Wedding".to_owned(),
+                vec![],
+                "/tmp/delivery".to_owned(),
+                None,
+                DeliveryOptions {
+                    package_as_zip: true,
+                    ..DeliveryOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+            job.status = DeliveryStatus::Completed;
+            job.files_copied = 12;
+            job.bytes_transferred = 4096;
+            job.output_path = Some("/tmp/delivery/Wedding.zip".to_owned());
+            job.manifest_path = Some("/tmp/delivery/delivery_manifest.txt".to_owned());
+            job.started_at = Some("2024-06-01T09:00:00Z".to_owned());
+            job.completed_at = Some(completed_at.to_owned());
+            save_delivery_history(DeliveryHistory::from(&job), &path)
+                .await
+                .unwrap();
+        }
+
+        let history = load_delivery_history(&path, None).unwrap();
+        assert_eq!(history.len(), 3);
+        assert_eq!(history[0].completed_at, "2024-06-03T10:00:00Z");
+        assert_eq!(history[0].files_copied, 12);
+        assert_eq!(history[0].total_bytes, 4096);
+        assert_eq!(
+            history[0].output_path.as_deref(),
+            Some("/tmp/delivery/Wedding.zip")
+        );
+        assert_eq!(
+            history[0].manifest_path.as_deref(),
+            Some("/tmp/delivery/delivery_manifest.txt")
+        );
+
+        let project_a: Vec<_> = load_delivery_history(&path, Some("proj-a"))
+            .unwrap()
+            .into_iter()
+            .map(|h| h.completed_at)
+            .collect();
+        assert_eq!(project_a, ["2024-06-03T10:00:00Z", "2024-06-01T10:00:00Z"]);
+        assert!(load_delivery_history(&path, Some("proj-c"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_load_delivery_queue_missing_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let jobs = load_delivery_queue(&temp_dir.path().join(QUEUE_FILE_NAME)).unwrap();
+        assert!(jobs.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_corrupt_delivery_queue_is_recovered_from_backup() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join(QUEUE_FILE_NAME);
+        let state = crate::state::AppState::default();
+        let mut ids = Vec::new();
+        for _ in 0..2 {
+            let job = create_delivery_impl(
+                &state.delivery_queue,
+                "proj".to_owned(),
+                "Persisted".to_owned(),
+                vec![],
+                "/tmp/delivery".to_owned(),
+                None,
+                DeliveryOptions::default(),
+            )
+            .await
+            .unwrap();
+            ids.push(job.id);
+            save_delivery_queue(&state.delivery_queue.lock().await.clone(), &path).unwrap();
+        }
+
+        // A crash mid-write used to leave a truncated file behind
+        fs::write(&path, b"{\"truncated\": {\"id\": ").unwrap();
+
+        let jobs = load_delivery_queue(&path).unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert!(jobs.contains_key(&ids[0]));
+    }
 }
@@ -0,0 +1,612 @@
+//! Disk usage of the volumes backup and delivery destinations live on.
+//!
+//! Paths are resolved to the volume that holds them (or would hold them, for
+//! folders not created yet), and paths on the same volume are reported together
+//! so a drive is never counted twice. Mounted external drives can be listed so
+//! destinations are picked instead of typed, read from `/proc/self/mounts` on
+//! Linux and from `mount` on macOS. The size of a folder can be totalled ahead of
+//! a backup or archive, with progress reported while large trees are walked.
+
+use crate::modules::file_utils::count_files_and_size_with_progress;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use tauri::Emitter;
+
+/// Files counted between two `folder-size-progress` events.
+const FOLDER_SIZE_PROGRESS_INTERVAL: usize = 500;
+
+/// Capacity of one volume and the requested paths that live on it.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeUsage {
+    /// Root of the volume's file system.
+    pub mount_point: String,
+    /// Requested paths on this volume, in request order.
+    pub paths: Vec<String>,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    /// Space available to the current user, which can be less than
+    /// `total_bytes - used_bytes` on volumes that reserve blocks for root.
+    pub free_bytes: u64,
+}
+
+/// A mounted external drive a backup or delivery can be sent to.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeInfo {
+    pub name: String,
+    pub mount_path: String,
+    pub total_bytes: u64,
+    /// Space available to the current user.
+    pub free_bytes: u64,
+    /// Whether the drive can be ejected, such as an SD card or USB drive.
+    pub is_removable: bool,
+}
+
+/// Total size of a folder, or the running total while it is still being counted.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct FolderSize {
+    pub path: String,
+    pub total_bytes: u64,
+    pub file_count: usize,
+}
+
+/// A device and where it is mounted, from the system's mount table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct MountEntry {
+    device: String,
+    mount_path: PathBuf,
+}
+
+/// Folders external drives are mounted below.
+const EXTERNAL_MOUNT_ROOTS: &[&str] = &["/Volumes", "/media", "/run/media", "/mnt"];
+
+/// Usage figures of the file system holding a path.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct FsStats {
+    /// Identifies the volume; equal for paths on the same file system.
+    device: u64,
+    total_bytes: u64,
+    used_bytes: u64,
+    free_bytes: u64,
+}
+
+/// `path` itself, or its nearest ancestor that exists.
+fn existing_ancestor(path: &Path) -> Result<PathBuf, String> {
+    path.ancestors()
+        .find(|ancestor| !ancestor.as_os_str().is_empty() && ancestor.exists())
+        .map(Path::to_path_buf)
+        .ok_or_else(|| format!("No existing folder found for {}", path.display()))
+}
+
+#[cfg(unix)]
+fn device_of(path: &Path) -> Result<u64, String> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(path)
+        .map(|metadata| metadata.dev())
+        .map_err(|e| format!("{}: {e}", path.display()))
+}
+
+/// Topmost ancestor of `path` still on the same device, i.e. its mount point.
+#[cfg(unix)]
+fn mount_point_of(path: &Path, device: u64) -> PathBuf {
+    path.ancestors()
+        .take_while(|ancestor| device_of(ancestor).is_ok_and(|d| d == device))
+        .last()
+        .unwrap_or(path)
+        .to_path_buf()
+}
+
+/// Widens a statvfs field, whose width varies by platform, to `u64`.
+#[cfg(unix)]
+fn widen(value: impl Into<u64>) -> u64 {
+    value.into()
+}
+
+#[cfg(unix)]
+fn fs_stats(path: &Path) -> Result<FsStats, String> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes())
+        .map_err(|_| format!("Invalid path: {}", path.display()))?;
+
+    // Safe: statvfs(3) writes into a zeroed struct we own, and the pointer is only
+    // held for the duration of the call. The struct is read only when it returns 0.
+    #[allow(unsafe_code)]
+    let stats = unsafe {
+        let mut stats: libc::statvfs = std::mem::zeroed();
+        if libc::statvfs(c_path.as_ptr(), &mut stats) != 0 {
+            return Err(format!(
+                "{}: {}",
+                path.display(),
+                std::io::Error::last_os_error()
+            ));
+        }
+        stats
+    };
+
+    let block_size = widen(stats.f_frsize);
+    let total_bytes = widen(stats.f_blocks) * block_size;
+    let unused_bytes = widen(stats.f_bfree) * block_size;
+
+    Ok(FsStats {
+        device: device_of(path)?,
+        total_bytes,
+        used_bytes: total_bytes.saturating_sub(unused_bytes),
+        free_bytes: widen(stats.f_bavail) * block_size,
+    })
+}
+
+#[cfg(not(unix))]
+fn fs_stats(_path: &Path) -> Result<FsStats, String> {
+    Err("Storage usage is not supported on this platform".to_owned())
+}
+
+#[cfg(not(unix))]
+fn mount_point_of(path: &Path, _device: u64) -> PathBuf {
+    path.to_path_buf()
+}
+
+/// Identifier of the volume holding `path` (or that would hold it, for a folder
+/// not created yet). Two paths are on the same disk when their ids are equal.
+///
+/// # Errors
+///
+/// Returns error if the path has no existing ancestor or its volume can't be queried
+pub fn get_disk_for_path(path: &Path) -> Result<u64, String> {
+    fs_stats(&existing_ancestor(path)?).map(|stats| stats.device)
+}
+
+/// Bytes available to the current user on the volume holding `path`.
+///
+/// # Errors
+///
+/// Returns error if the path has no existing ancestor or its volume can't be queried
+pub fn get_free_space_for_path(path: &Path) -> Result<u64, String> {
+    fs_stats(&existing_ancestor(path)?).map(|stats| stats.free_bytes)
+}
+
+/// Usage of every volume holding one of `paths`, one entry per volume in the
+/// order the volumes are first mentioned.
+///
+/// # Errors
+///
+/// Returns error if a path has no existing ancestor or its volume can't be queried
+pub fn storage_report(paths: &[String]) -> Result<Vec<VolumeUsage>, String> {
+    let mut volumes: Vec<(u64, VolumeUsage)> = Vec::new();
+
+    for requested in paths {
+        let existing = existing_ancestor(Path::new(requested))?;
+        let stats = fs_stats(&existing)?;
+
+        if let Some((_, volume)) = volumes.iter_mut().find(|(dev, _)| *dev == stats.device) {
+            if !volume.paths.contains(requested) {
+                volume.paths.push(requested.clone());
+            }
+            continue;
+        }
+
+        let resolved = existing.canonicalize().unwrap_or(existing);
+        volumes.push((
+            stats.device,
+            VolumeUsage {
+                mount_point: mount_point_of(&resolved, stats.device)
+                    .to_string_lossy()
+                    .to_string(),
+                paths: vec![requested.clone()],
+                total_bytes: stats.total_bytes,
+                used_bytes: stats.used_bytes,
+                free_bytes: stats.free_bytes,
+            },
+        ));
+    }
+
+    Ok(volumes.into_iter().map(|(_, volume)| volume).collect())
+}
+
+/// Parse Linux's `/proc/self/mounts`: `device mount-point type options ...`,
+/// with spaces and other special characters in paths written as octal escapes.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn parse_proc_mounts(contents: &str) -> Vec<MountEntry> {
+    contents
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let mount_path = fields.next()?;
+            Some(MountEntry {
+                device: unescape_mount_field(device),
+                mount_path: PathBuf::from(unescape_mount_field(mount_path)),
+            })
+        })
+        .collect()
+}
+
+/// Undo the `\040`-style octal escapes of a mount table field.
+#[cfg_attr(not(target_os = "linux"), allow(dead_code))]
+fn unescape_mount_field(field: &str) -> String {
+    let bytes = field.as_bytes();
+    let mut unescaped = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let escaped = bytes
+            .get(i + 1..i + 4)
+            .filter(|_| bytes[i] == b'\\')
+            .and_then(|digits| std::str::from_utf8(digits).ok())
+            .and_then(|digits| u8::from_str_radix(digits, 8).ok());
+        if let Some(byte) = escaped {
+            unescaped.push(byte);
+            i += 4;
+        } else {
+            unescaped.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8_lossy(&unescaped).into_owned()
+}
+
+/// Parse the output of BSD `mount`: `device on mount-point (type, options)`.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_mount_output(output: &str) -> Vec<MountEntry> {
+    output
+        .lines()
+        .filter_map(|line| {
+            let (device, rest) = line.split_once(" on ")?;
+            let mount_path = rest.rsplit_once(" (").map_or(rest, |(path, _)| path);
+            Some(MountEntry {
+                device: device.to_owned(),
+                mount_path: PathBuf::from(mount_path),
+            })
+        })
+        .collect()
+}
+
+/// Whether a mount is a real device mounted below one of the
+/// [`EXTERNAL_MOUNT_ROOTS`], rather than a system or virtual file system.
+fn is_external_mount(entry: &MountEntry) -> bool {
+    entry.device.starts_with("/dev/")
+        && EXTERNAL_MOUNT_ROOTS.iter().any(|root| {
+            entry
+                .mount_path
+                .strip_prefix(root)
+                .is_ok_and(|rest| !rest.as_os_str().is_empty())
+        })
+}
+
+/// The external drives among `mounts`, sorted by name. Drives whose usage
+/// can't be read are left out.
+fn volumes_from_mounts(
+    mounts: &[MountEntry],
+    stats: impl Fn(&Path) -> Result<FsStats, String>,
+    is_removable: impl Fn(&MountEntry) -> bool,
+) -> Vec<VolumeInfo> {
+    let mut volumes: Vec<VolumeInfo> = Vec::new();
+    for entry in mounts.iter().filter(|entry| is_external_mount(entry)) {
+        let mount_path = entry.mount_path.to_string_lossy().to_string();
+        if volumes.iter().any(|volume| volume.mount_path == mount_path) {
+            continue;
+        }
+        let stats = match stats(&entry.mount_path) {
+            Ok(stats) => stats,
+            Err(e) => {
+                log::warn!("Skipping volume {mount_path}: {e}");
+                continue;
+            }
+        };
+        volumes.push(VolumeInfo {
+            name: entry.mount_path.file_name().map_or_else(
+                || mount_path.clone(),
+                |name| name.to_string_lossy().to_string(),
+            ),
+            mount_path,
+            total_bytes: stats.total_bytes,
+            free_bytes: stats.free_bytes,
+            is_removable: is_removable(entry),
+        });
+    }
+    volumes.sort_by(|a, b| a.name.cmp(&b.name));
+    volumes
+}
+
+#[cfg(target_os = "linux")]
+fn read_mounts() -> Result<Vec<MountEntry>, String> {
+    std::fs::read_to_string("/proc/self/mounts")
+        .map(|contents| parse_proc_mounts(&contents))
+        .map_err(|e| format!("Failed to read mounts: {e}"))
+}
+
+#[cfg(target_os = "macos")]
+fn read_mounts() -> Result<Vec<MountEntry>, String> {
+    let output = std::process::Command::new("/sbin/mount")
+        .output()
+        .map_err(|e| format!("Failed to list mounts: {e}"))?;
+    Ok(parse_mount_output(&String::from_utf8_lossy(&output.stdout)))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn read_mounts() -> Result<Vec<MountEntry>, String> {
+    Err("Listing volumes is not supported on this platform".to_owned())
+}
+
+/// Whether the kernel flags the disk of a partition like `/dev/sdb1` as removable.
+#[cfg(target_os = "linux")]
+fn is_removable_device(entry: &MountEntry) -> bool {
+    let Some(name) = entry.device.strip_prefix("/dev/") else {
+        return false;
+    };
+    // A partition's sysfs folder sits inside its disk's, which has the flag
+    let Ok(block) = Path::new("/sys/class/block").join(name).canonicalize() else {
+        return false;
+    };
+    [block.join("removable"), block.join("../removable")]
+        .iter()
+        .find_map(|flag| std::fs::read_to_string(flag).ok())
+        .is_some_and(|flag| flag.trim() == "1")
+}
+
+#[cfg(target_os = "macos")]
+fn is_removable_device(entry: &MountEntry) -> bool {
+    crate::modules::sd_card::get_device_info(&entry.mount_path.to_string_lossy()).1
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+const fn is_removable_device(_entry: &MountEntry) -> bool {
+    false
+}
+
+/// Mounted external drives with their capacity.
+///
+/// # Errors
+///
+/// Returns error if the mount table can't be read
+pub fn list_external_volumes() -> Result<Vec<VolumeInfo>, String> {
+    Ok(volumes_from_mounts(
+        &read_mounts()?,
+        fs_stats,
+        is_removable_device,
+    ))
+}
+
+/// List mounted external drives to pick a backup or delivery destination from.
+#[tauri::command]
+pub async fn list_volumes() -> Result<Vec<VolumeInfo>, String> {
+    tokio::task::spawn_blocking(list_external_volumes)
+        .await
+        .map_err(|e| format!("Volume listing task failed: {e}"))?
+}
+
+/// Total the files under `path`, passing the running total to `on_progress`
+/// every `interval` files.
+///
+/// # Errors
+///
+/// Returns error if a folder in the tree can't be listed
+pub fn folder_size<F>(path: &str, interval: usize, mut on_progress: F) -> Result<FolderSize, String>
+where
+    F: FnMut(&FolderSize),
+{
+    let interval = interval.max(1);
+    let (file_count, total_bytes) = count_files_and_size_with_progress(path, |files, bytes| {
+        if files % interval == 0 {
+            on_progress(&FolderSize {
+                path: path.to_owned(),
+                total_bytes: bytes,
+                file_count: files,
+            });
+        }
+    })?;
+    Ok(FolderSize {
+        path: path.to_owned(),
+        total_bytes,
+        file_count,
+    })
+}
+
+/// Total size and file count of a folder, emitting `folder-size-progress` while
+/// large trees are counted.
+#[tauri::command]
+pub async fn compute_folder_size(
+    window: tauri::Window,
+    path: String,
+) -> Result<FolderSize, String> {
+    tokio::task::spawn_blocking(move || {
+        folder_size(&path, FOLDER_SIZE_PROGRESS_INTERVAL, |progress| {
+            let _ = window.emit("folder-size-progress", progress);
+        })
+    })
+    .await
+    .map_err(|e| format!("Folder size task failed: {e}"))?
+}
+
+/// Report total, used and free space for the volumes holding `paths`, with paths
+/// on the same volume grouped into one entry.
+#[tauri::command]
+pub async fn get_storage_report(paths: Vec<String>) -> Result<Vec<VolumeUsage>, String> {
+    tokio::task::spawn_blocking(move || storage_report(&paths))
+        .await
+        .map_err(|e| format!("Storage report task failed: {e}"))?
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn path_string(path: &Path) -> String {
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_report_for_temp_paths() {
+        let temp_dir = TempDir::new().unwrap();
+        let backups = temp_dir.path().join("Backups");
+        std::fs::create_dir(&backups).unwrap();
+
+        let report = storage_report(&[path_string(&backups)]).unwrap();
+
+        assert_eq!(report.len(), 1);
+        let volume = &report[0];
+        assert!(volume.total_bytes > 0);
+        assert!(volume.free_bytes > 0);
+        assert!(volume.free_bytes <= volume.total_bytes);
+        assert!(volume.used_bytes <= volume.total_bytes);
+        assert!(backups
+            .canonicalize()
+            .unwrap()
+            .starts_with(&volume.mount_point));
+    }
+
+    #[test]
+    fn test_paths_on_one_volume_are_grouped() {
+        let temp_dir = TempDir::new().unwrap();
+        let backups = path_string(&temp_dir.path().join("Backups"));
+        let deliveries = path_string(&temp_dir.path().join("Deliveries"));
+        std::fs::create_dir(temp_dir.path().join("Backups")).unwrap();
+
+        // Deliveries doesn't exist yet; it resolves through its parent
+        let report =
+            storage_report(&[backups.clone(), deliveries.clone(), backups.clone()]).unwrap();
+
+        assert_eq!(report.len(), 1);
+        assert_eq!(report[0].paths, vec![backups, deliveries]);
+    }
+
+    #[test]
+    fn test_proc_mounts_are_parsed() {
+        let mounts = parse_proc_mounts(
+            "/dev/nvme0n1p2 / ext4 rw,relatime 0 0\n\
+             proc /proc proc rw,nosuid 0 0\n\
+             /dev/sdb1 /media/jane/EOS\\040DIGITAL vfat rw,nosuid 0 0\n",
+        );
+
+        assert_eq!(mounts.len(), 3);
+        assert_eq!(mounts[2].device, "/dev/sdb1");
+        assert_eq!(mounts[2].mount_path, Path::new("/media/jane/EOS DIGITAL"));
+        assert_eq!(unescape_mount_field("a\\134b\\0"), "a\\b\\0");
+    }
+
+    #[test]
+    fn test_mount_output_is_parsed() {
+        let mounts = parse_mount_output(
+            "/dev/disk3s1s1 on / (apfs, sealed, local, read-only, journaled)\n\
+             devfs on /dev (devfs, local, nobrowse)\n\
+             /dev/disk4s1 on /Volumes/Card (2) (msdos, local, nodev, nosuid, noowners)\n",
+        );
+
+        assert_eq!(mounts.len(), 3);
+        assert_eq!(mounts[2].device, "/dev/disk4s1");
+        assert_eq!(mounts[2].mount_path, Path::new("/Volumes/Card (2)"));
+    }
+
+    #[test]
+    fn test_volumes_are_built_from_external_mounts() {
+        let mounts = parse_proc_mounts(
+            "/dev/nvme0n1p2 / ext4 rw 0 0\n\
+             tmpfs /run/media tmpfs rw 0 0\n\
+             /dev/sdc1 /run/media/jane/Backup\\040A ext4 rw 0 0\n\
+             /dev/sdb1 /media/jane/EOS_DIGITAL vfat rw 0 0\n\
+             /dev/sdb1 /media/jane/EOS_DIGITAL vfat rw 0 0\n\
+             /dev/sdd1 /mnt/broken ext4 rw 0 0\n\
+             /dev/nvme0n1p1 /boot/efi vfat rw 0 0\n",
+        );
+        let stats = |path: &Path| {
+            if path.ends_with("broken") {
+                return Err("unreadable".to_owned());
+            }
+            Ok(FsStats {
+                device: 1,
+                total_bytes: 64_000,
+                used_bytes: 16_000,
+                free_bytes: 48_000,
+            })
+        };
+
+        let volumes = volumes_from_mounts(&mounts, stats, |entry| entry.device == "/dev/sdb1");
+
+        assert_eq!(
+            volumes,
+            vec![
+                VolumeInfo {
+                    name: "Backup A".to_owned(),
+                    mount_path: "/run/media/jane/Backup A".to_owned(),
+                    total_bytes: 64_000,
+                    free_bytes: 48_000,
+                    is_removable: false,
+                },
+                VolumeInfo {
+                    name: "EOS_DIGITAL".to_owned(),
+                    mount_path: "/media/jane/EOS_DIGITAL".to_owned(),
+                    total_bytes: 64_000,
+                    free_bytes: 48_000,
+                    is_removable: true,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_empty_path_is_rejected() {
+        assert!(storage_report(&[String::new()]).is_err());
+        assert!(storage_report(&[]).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_disk_for_paths_on_one_volume() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("Projects");
+        std::fs::create_dir(&source).unwrap();
+
+        let source_disk = get_disk_for_path(&source).unwrap();
+        assert_eq!(
+            get_disk_for_path(&temp_dir.path().join("Backups/not yet created")).unwrap(),
+            source_disk
+        );
+        assert!(get_disk_for_path(Path::new("")).is_err());
+    }
+
+    #[test]
+    fn test_folder_size_reports_totals_and_progress() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().join("Shoot");
+        std::fs::create_dir_all(root.join("RAW").join("Day1")).unwrap();
+        std::fs::write(root.join("notes.txt"), b"shot list").unwrap();
+        std::fs::write(root.join("RAW").join("IMG_0001.CR3"), vec![0; 1000]).unwrap();
+        std::fs::write(
+            root.join("RAW").join("Day1").join("IMG_0002.CR3"),
+            vec![0; 2000],
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("RAW").join("Day1").join("IMG_0003.CR3"),
+            vec![0; 3000],
+        )
+        .unwrap();
+        let path = path_string(&root);
+
+        let mut events = Vec::new();
+        let size = folder_size(&path, 2, |progress| events.push(progress.clone())).unwrap();
+
+        assert_eq!(
+            size,
+            FolderSize {
+                path: path.clone(),
+                total_bytes: 6009,
+                file_count: 4,
+            }
+        );
+        let counts: Vec<usize> = events.iter().map(|event| event.file_count).collect();
+        assert_eq!(counts, [2, 4]);
+        assert!(events.iter().all(|event| event.path == path));
+        assert_eq!(events.last().unwrap().total_bytes, 6009);
+
+        let mut empty_events = 0;
+        let empty = folder_size(&path_string(&temp_dir.path().join("missing")), 2, |_| {
+            empty_events += 1;
+        })
+        .unwrap();
+        assert_eq!((empty.total_bytes, empty.file_count), (0, 0));
+        assert_eq!(empty_events, 0);
+    }
+}
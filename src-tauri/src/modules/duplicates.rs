@@ -0,0 +1,168 @@
+//! Duplicate media detection within a project folder.
+//!
+//! Files are first grouped by size, which is cheap, and only files sharing a
+//! size are hashed to confirm they are identical, with digests of unchanged
+//! files taken from the checksum cache. `project.json` and empty files are never
+//! reported.
+
+use crate::modules::checksums::cached_hash_file;
+use crate::modules::db::Database;
+use crate::modules::project::get_project_by_id;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
+use walkdir::WalkDir;
+
+/// Files with identical contents.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct DuplicateGroup {
+    /// Size of each file in bytes.
+    pub size: u64,
+    /// SHA-256 digest shared by every file in the group.
+    pub checksum: String,
+    /// Paths of the identical files, sorted.
+    pub paths: Vec<String>,
+}
+
+/// Find groups of two or more identical files under `root`, largest files first,
+/// using `hash` to digest files that share a size.
+///
+/// # Errors
+///
+/// Returns error if a candidate file cannot be read
+pub fn find_duplicates<F>(root: &Path, mut hash: F) -> Result<Vec<DuplicateGroup>, String>
+where
+    F: FnMut(&Path) -> Result<String, String>,
+{
+    let mut by_size: HashMap<u64, Vec<PathBuf>> = HashMap::new();
+
+    for entry in WalkDir::new(root).into_iter().filter_map(Result::ok) {
+        if !entry.file_type().is_file() || entry.file_name() == "project.json" {
+            continue;
+        }
+        let size = entry.metadata().map_err(|e| e.to_string())?.len();
+        if size > 0 {
+            by_size.entry(size).or_default().push(entry.into_path());
+        }
+    }
+
+    let mut groups = Vec::new();
+    for (size, paths) in by_size.into_iter().filter(|(_, paths)| paths.len() > 1) {
+        let mut by_checksum: HashMap<String, Vec<String>> = HashMap::new();
+        for path in paths {
+            by_checksum
+                .entry(hash(&path)?)
+                .or_default()
+                .push(path.to_string_lossy().to_string());
+        }
+
+        for (checksum, mut paths) in by_checksum {
+            if paths.len() > 1 {
+                paths.sort();
+                groups.push(DuplicateGroup {
+                    size,
+                    checksum,
+                    paths,
+                });
+            }
+        }
+    }
+
+    groups.sort_by(|a, b| b.size.cmp(&a.size).then_with(|| a.paths.cmp(&b.paths)));
+    Ok(groups)
+}
+
+/// Find files with identical contents inside a project's folder.
+#[tauri::command]
+pub async fn find_duplicate_files(
+    app_handle: tauri::AppHandle,
+    db: tauri::State<'_, Database>,
+    project_id: String,
+) -> Result<Vec<DuplicateGroup>, String> {
+    let project = get_project_by_id(&db, &project_id)?;
+
+    tokio::task::spawn_blocking(move || {
+        let db = app_handle.state::<Database>();
+        find_duplicates(Path::new(&project.folder_path), |path| {
+            cached_hash_file(&db, path)
+        })
+    })
+    .await
+    .map_err(|e| format!("Duplicate scan task failed: {e}"))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::checksums::hash_file;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_finds_one_duplicate_group() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        fs::create_dir_all(project.join("RAW/Card 1")).unwrap();
+        fs::create_dir_all(project.join("RAW/Card 2")).unwrap();
+
+        fs::write(project.join("RAW/Card 1/IMG_0001.CR3"), b"same shot").unwrap();
+        fs::write(project.join("RAW/Card 2/IMG_0001.CR3"), b"same shot").unwrap();
+        // Same size as the duplicates but different contents
+        fs::write(project.join("RAW/Card 1/IMG_0002.CR3"), b"next shot").unwrap();
+        fs::write(project.join("project.json"), b"same shot").unwrap();
+
+        let groups = find_duplicates(project, hash_file).unwrap();
+
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].size, 9);
+        assert_eq!(
+            groups[0].paths,
+            vec![
+                project
+                    .join("RAW/Card 1/IMG_0001.CR3")
+                    .to_string_lossy()
+                    .to_string(),
+                project
+                    .join("RAW/Card 2/IMG_0001.CR3")
+                    .to_string_lossy()
+                    .to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_unique_and_empty_files_are_not_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let project = temp_dir.path();
+        fs::write(project.join("a.jpg"), b"one").unwrap();
+        fs::write(project.join("b.jpg"), b"two!").unwrap();
+        fs::write(project.join("empty1.txt"), b"").unwrap();
+        fs::write(project.join("empty2.txt"), b"").unwrap();
+
+        assert!(find_duplicates(project, hash_file).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_scan_reuses_cached_checksums() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+        let project = temp_dir.path().join("Smith");
+        fs::create_dir_all(&project).unwrap();
+        fs::write(project.join("a.jpg"), b"same shot").unwrap();
+        fs::write(project.join("b.jpg"), b"same shot").unwrap();
+
+        let scan = || find_duplicates(&project, |path| cached_hash_file(&db, path)).unwrap();
+        let first = scan();
+        assert_eq!(first.len(), 1);
+        assert_eq!(scan(), first);
+
+        let cached: i64 = db
+            .execute(|conn| {
+                Ok(conn.query_row("SELECT COUNT(*) FROM checksum_cache", [], |row| row.get(0))?)
+            })
+            .unwrap();
+        assert_eq!(cached, 2);
+    }
+}
@@ -4,10 +4,16 @@
 //! runs up to `MAX_CONCURRENT_COPIES` parallel tasks, and supports cancellation
 //! via a per-import `CancellationToken`. Failed copies are retried with
 //! exponential back-off; persistent failures are counted as skipped.
+//! [`ImportOptions`] can limit, rename and verify the files an import copies.
 
 use crate::error::ImportError;
+use crate::modules::checksums::hash_file;
+use crate::modules::file_utils::calculate_file_hash;
+use crate::modules::metadata::resolve_capture_date;
 use crate::utils::file_ops;
+use futures::future::BoxFuture;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::atomic::{AtomicUsize, Ordering};
@@ -17,6 +23,7 @@ use tokio::sync::Semaphore;
 use tokio_retry::strategy::{jitter, ExponentialBackoff};
 use tokio_retry::Retry;
 use tokio_util::sync::CancellationToken;
+use walkdir::WalkDir;
 
 const MAX_RETRY_ATTEMPTS: usize = 3;
 const MAX_CONCURRENT_COPIES: usize = 4; // Parallel file copies
@@ -32,6 +39,22 @@ const VIDEO_EXTENSIONS: &[&str] = &[
     "mp4", "mov", "avi", "mkv", "wmv", "flv", "webm", "m4v", "mpg", "mpeg", "3gp", "mts", "m2ts",
 ];
 
+/// File types an import can be limited to, as returned by `get_file_type`.
+const FILE_TYPES: &[&str] = &["photo", "video"];
+
+/// Subfolder of the destination photos are copied into.
+const PHOTOS_FOLDER: &str = "Photos";
+/// Subfolder of the destination videos are copied into.
+const VIDEOS_FOLDER: &str = "Videos";
+
+/// Tokens an import rename template can use.
+const RENAME_TOKENS: &[&str] = &["datetime", "original", "seq"];
+const PLACEHOLDER_DATETIME: &str = "{datetime}";
+const PLACEHOLDER_ORIGINAL: &str = "{original}";
+const PLACEHOLDER_SEQ: &str = "{seq}";
+/// How `{datetime}` renders a file's capture time.
+const RENAME_DATETIME_FORMAT: &str = "%Y-%m-%d_%H%M%S";
+
 /// Detect if file is a photo or video based on extension
 fn get_file_type(path: &Path) -> Option<&'static str> {
     let ext = path.extension()?.to_str()?.to_lowercase();
@@ -44,18 +67,66 @@ fn get_file_type(path: &Path) -> Option<&'static str> {
     }
 }
 
+/// Photo and video extensions an import recognises, lowercase and without dots.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct SupportedExtensions {
+    pub photos: Vec<String>,
+    pub videos: Vec<String>,
+}
+
+/// The extensions `get_file_type` sorts into photos and videos.
+pub fn supported_extensions() -> SupportedExtensions {
+    let owned = |extensions: &[&str]| extensions.iter().map(|&ext| ext.to_owned()).collect();
+    SupportedExtensions {
+        photos: owned(PHOTO_EXTENSIONS),
+        videos: owned(VIDEO_EXTENSIONS),
+    }
+}
+
 /// Summary returned to the frontend after an import operation completes or is cancelled.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct CopyResult {
     pub success: bool,
+    /// Whether the import was cancelled before every file was copied; `error`
+    /// then carries the message to show.
+    #[serde(default)]
+    pub cancelled: bool,
     pub error: Option<String>,
     pub files_copied: usize,
+    /// Files that could not be copied.
     pub files_skipped: usize,
+    /// Files left alone because they were already at the destination; they don't
+    /// make the import unsuccessful.
+    #[serde(default)]
+    pub duplicates_skipped: usize,
+    /// Files left alone because their type wasn't in the import's allowed types.
+    #[serde(default)]
+    pub excluded_skipped: usize,
     pub skipped_files: Vec<String>,
+    /// Why each file in `skipped_files` was skipped.
+    #[serde(default)]
+    pub skip_reasons: Vec<SkippedFile>,
     pub total_bytes: u64,
     pub photos_copied: usize,
     pub videos_copied: usize,
+    /// Folder the import wrote into; files of unknown type land here directly.
+    #[serde(default)]
+    pub destination_root: String,
+    /// Folder photos were routed into.
+    #[serde(default)]
+    pub photos_path: String,
+    /// Folder videos were routed into.
+    #[serde(default)]
+    pub videos_path: String,
+}
+
+/// A file an import gave up on, and the error that made it give up.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SkippedFile {
+    pub file_name: String,
+    pub reason: String,
 }
 
 /// Per-file progress payload emitted as the `import-progress` Tauri event.
@@ -63,11 +134,49 @@ pub struct CopyResult {
 #[serde(rename_all = "camelCase")]
 pub struct ImportProgress {
     pub files_copied: usize,
+    /// Files handled so far, whether copied, left alone as duplicates or
+    /// skipped after an error.
+    pub files_processed: usize,
     pub total_files: usize,
     pub current_file: String,
 }
 
-/// Copy files from source to destination with parallel processing.
+/// Which files an import copies and how it names and checks them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportOptions {
+    /// File types to copy (e.g. `["photo", "video"]`); every file when `None`.
+    /// Files of other types, including unrecognised ones, are left on the card
+    /// and counted as excluded.
+    #[serde(default)]
+    pub allowed_types: Option<Vec<String>>,
+    /// Template files are renamed from: `{datetime}` is the capture time,
+    /// `{original}` the original name without extension and `{seq}` the file's
+    /// number in the import; the extension is always kept. A name taken twice
+    /// gets a ` (1)`, ` (2)`, ... suffix so files from different cards don't
+    /// overwrite each other.
+    #[serde(default)]
+    pub rename_template: Option<String>,
+    /// Check every copy against its source by SHA-256 and copy it again on a
+    /// mismatch; files that still don't match are removed and counted as skipped.
+    #[serde(default)]
+    pub verify: bool,
+}
+
+/// Sink for `import-progress` payloads.
+type ImportProgressEmitter = Arc<dyn Fn(ImportProgress) + Send + Sync>;
+
+/// Copies one file of an import, returning the bytes written.
+type FileCopier = fn(PathBuf, PathBuf) -> BoxFuture<'static, Result<u64, String>>;
+
+/// Copy a file with the platform's fast native copy.
+fn native_copy(src: PathBuf, dest: PathBuf) -> BoxFuture<'static, Result<u64, String>> {
+    Box::pin(async move { file_ops::copy_file(&src, &dest).await })
+}
+
+/// Copy files from source to destination with parallel processing, limited,
+/// renamed and verified as `options` asks; without options every file is
+/// copied under its own name.
 #[tauri::command]
 pub async fn copy_files(
     state: tauri::State<'_, crate::state::AppState>,
@@ -75,8 +184,265 @@ pub async fn copy_files(
     import_id: String,
     source_paths: Vec<String>,
     destination: String,
+    options: Option<ImportOptions>,
+) -> Result<CopyResult, String> {
+    let emit: ImportProgressEmitter = Arc::new(move |progress| {
+        let _ = app.emit("import-progress", progress);
+    });
+    copy_files_impl(
+        &state.import_tokens,
+        import_id,
+        &source_paths,
+        &destination,
+        &options.unwrap_or_default(),
+        emit,
+    )
+    .await
+}
+
+/// Whether `dest` already holds a copy of `src` from an earlier import: the
+/// same size, then the same SHA-256, so a different file that happens to share
+/// the name and size is never taken for it.
+fn is_already_imported(src: &Path, dest: &Path) -> bool {
+    match (fs::metadata(src), fs::metadata(dest)) {
+        (Ok(src_meta), Ok(dest_meta))
+            if dest_meta.is_file() && src_meta.len() == dest_meta.len() =>
+        {
+            matches!((hash_file(src), hash_file(dest)), (Ok(a), Ok(b)) if a == b)
+        }
+        _ => false,
+    }
+}
+
+/// Check that an import rename template only uses known tokens, has balanced
+/// braces and stays within one folder.
+fn validate_rename_template(template: &str) -> Result<(), String> {
+    if template.contains(['/', '\\']) {
+        return Err("Rename template cannot contain path separators".to_owned());
+    }
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        if rest[..open].contains('}') {
+            return Err("Unmatched '}' in rename template".to_owned());
+        }
+        let close = rest[open..]
+            .find('}')
+            .ok_or("Unclosed '{' in rename template")?;
+        let token = &rest[open + 1..open + close];
+        if !RENAME_TOKENS.contains(&token) {
+            return Err(format!("Unknown token {{{token}}} in rename template"));
+        }
+        rest = &rest[open + close + 1..];
+    }
+    if rest.contains('}') {
+        return Err("Unmatched '}' in rename template".to_owned());
+    }
+    Ok(())
+}
+
+/// Name `src` gets under a rename template, as the `seq`th file of the import.
+/// Files without a capture time render `{datetime}` as `unknown`.
+fn render_import_name(template: &str, src: &Path, seq: usize) -> String {
+    let original = src.file_stem().unwrap_or_default().to_string_lossy();
+    let datetime = resolve_capture_date(src).map_or_else(
+        || "unknown".to_owned(),
+        |captured| captured.format(RENAME_DATETIME_FORMAT).to_string(),
+    );
+    let name = template
+        .replace(PLACEHOLDER_DATETIME, &datetime)
+        .replace(PLACEHOLDER_ORIGINAL, &original)
+        .replace(PLACEHOLDER_SEQ, &format!("{seq:04}"));
+    match src.extension() {
+        Some(ext) => format!("{name}.{}", ext.to_string_lossy()),
+        None => name,
+    }
+}
+
+/// First of `dest`, `dest (1)`, `dest (2)`, ... not taken by another file of
+/// this import and either free or already holding `src` from an earlier import,
+/// compared by checksum so a same-size stranger still gets a suffix.
+fn claim_import_path(dest: PathBuf, src: &Path, claimed: &mut HashSet<PathBuf>) -> PathBuf {
+    let is_free = |path: &Path, claimed: &HashSet<PathBuf>| {
+        !claimed.contains(path) && (!path.exists() || is_already_imported(src, path))
+    };
+    let path = if is_free(&dest, claimed) {
+        dest
+    } else {
+        let stem = dest.file_stem().unwrap_or_default().to_string_lossy();
+        let extension = dest
+            .extension()
+            .map(|ext| format!(".{}", ext.to_string_lossy()))
+            .unwrap_or_default();
+        (1..=usize::MAX)
+            .map(|counter| dest.with_file_name(format!("{stem} ({counter}){extension}")))
+            .find(|path| is_free(path, claimed))
+            .unwrap_or(dest)
+    };
+    claimed.insert(path.clone());
+    path
+}
+
+/// Where each `(source, folder)` lands under the rename template, decided in
+/// import order so `{seq}` and ` (n)` suffixes don't depend on which copy runs
+/// first. Capture times are read on a blocking thread, which stops once
+/// `cancel` fires.
+async fn plan_renamed_paths(
+    template: &str,
+    sources: Vec<(PathBuf, PathBuf)>,
+    cancel: CancellationToken,
+) -> Result<Vec<PathBuf>, ImportError> {
+    let template = template.to_owned();
+    tokio::task::spawn_blocking(move || {
+        let mut claimed = HashSet::new();
+        sources
+            .into_iter()
+            .enumerate()
+            .map(|(index, (src, folder))| {
+                if cancel.is_cancelled() {
+                    return Err(ImportError::Cancelled);
+                }
+                let dest = folder.join(render_import_name(&template, &src, index + 1));
+                Ok(claim_import_path(dest, &src, &mut claimed))
+            })
+            .collect()
+    })
+    .await
+    .map_err(|e| ImportError::TaskFailed(e.to_string()))?
+}
+
+/// A file to import, and the folder it goes in below `Photos/` or `Videos/`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImportSource {
+    path: PathBuf,
+    /// Empty for files passed directly; for files found inside a source folder,
+    /// that folder's name plus their path within it, so clip folders like AVCHD
+    /// `BDMV/STREAM` keep their structure.
+    subfolder: PathBuf,
+}
+
+/// Sources left to copy, and the skips recorded for missing ones.
+type NormalizedSources = (Vec<ImportSource>, Vec<SkippedFile>);
+
+/// Expand source folders into the media files below them and drop source paths
+/// that name a file already listed, comparing canonical paths so `./a.jpg` and
+/// `a.jpg` count as one. Paths that don't exist or can't be resolved (permission
+/// denied, a card pulled mid-walk) are returned as skips instead.
+fn normalize_sources(source_paths: &[String]) -> NormalizedSources {
+    let mut seen = HashSet::new();
+    let mut sources = Vec::new();
+    let mut missing = Vec::new();
+
+    let mut add = |path: PathBuf, subfolder: PathBuf| match path.canonicalize() {
+        Ok(canonical) => {
+            if seen.insert(canonical) {
+                sources.push(ImportSource { path, subfolder });
+            }
+            None
+        }
+        Err(e) => Some(skipped_source(&path, e.to_string())),
+    };
+
+    for src_path in source_paths {
+        let src = PathBuf::from(src_path);
+        if src.is_file() {
+            missing.extend(add(src, PathBuf::new()));
+        } else if src.is_dir() {
+            let base = src.parent().unwrap_or(&src);
+            for entry in WalkDir::new(&src)
+                .sort_by_file_name()
+                .into_iter()
+                .filter_map(Result::ok)
+                .filter(|e| e.file_type().is_file() && get_file_type(e.path()).is_some())
+            {
+                let subfolder = entry
+                    .path()
+                    .parent()
+                    .and_then(|dir| dir.strip_prefix(base).ok())
+                    .map(Path::to_path_buf)
+                    .unwrap_or_default();
+                missing.extend(add(entry.into_path(), subfolder));
+            }
+        } else {
+            missing.push(skipped_source(
+                &src,
+                ImportError::SourceNotFound.to_string(),
+            ));
+        }
+    }
+
+    (sources, missing)
+}
+
+/// Skip record for a source path that can't be imported.
+fn skipped_source(path: &Path, reason: String) -> SkippedFile {
+    SkippedFile {
+        file_name: path
+            .file_name()
+            .unwrap_or(path.as_os_str())
+            .to_string_lossy()
+            .to_string(),
+        reason,
+    }
+}
+
+/// Core logic for copying an import's files (testable)
+///
+/// # Errors
+///
+/// Returns error if an allowed type is unknown, the rename template is
+/// invalid, the destination cannot be created or a copy task panics
+pub async fn copy_files_impl(
+    import_tokens: &crate::state::ImportTokens,
+    import_id: String,
+    source_paths: &[String],
+    destination: &str,
+    options: &ImportOptions,
+    emit: ImportProgressEmitter,
+) -> Result<CopyResult, String> {
+    copy_files_with(
+        import_tokens,
+        import_id,
+        source_paths,
+        destination,
+        options,
+        native_copy,
+        emit,
+    )
+    .await
+}
+
+/// Copy an import's files, writing each one with `copy_fn`.
+///
+/// Sources are expanded and de-duplicated by [`normalize_sources`], so camera
+/// clip folders keep their structure. Files [`is_already_imported`] at their
+/// destination are counted as duplicates instead of being copied again.
+async fn copy_files_with(
+    import_tokens: &crate::state::ImportTokens,
+    import_id: String,
+    source_paths: &[String],
+    destination: &str,
+    options: &ImportOptions,
+    copy_fn: FileCopier,
+    emit: ImportProgressEmitter,
 ) -> Result<CopyResult, String> {
-    let dest_path = PathBuf::from(&destination);
+    let allowed_types = options.allowed_types.as_deref();
+    let verify = options.verify;
+    if let Some(unknown) = allowed_types
+        .into_iter()
+        .flatten()
+        .find(|t| !FILE_TYPES.contains(&t.as_str()))
+    {
+        return Err(format!("Unknown file type: {unknown}"));
+    }
+    let rename_template = options
+        .rename_template
+        .as_deref()
+        .filter(|t| !t.trim().is_empty());
+    if let Some(template) = rename_template {
+        validate_rename_template(template)?;
+    }
+
+    let dest_path = PathBuf::from(destination);
 
     // Create destination directory if it doesn't exist
     if !dest_path.exists() {
@@ -86,46 +452,92 @@ pub async fn copy_files(
     // Create cancellation token and register it
     let cancel_token = CancellationToken::new();
     {
-        let mut tokens = state.import_tokens.lock().await;
+        let mut tokens = import_tokens.lock().await;
         tokens.insert(import_id.clone(), cancel_token.clone());
     }
 
+    let (mut sources, missing) = normalize_sources(source_paths);
+    let excluded_skipped = allowed_types.map_or(0, |allowed| {
+        let count = sources.len();
+        sources.retain(|source| {
+            get_file_type(&source.path).is_some_and(|t| allowed.iter().any(|a| a == t))
+        });
+        count - sources.len()
+    });
+    let photos_path = dest_path.join(PHOTOS_FOLDER);
+    let videos_path = dest_path.join(VIDEOS_FOLDER);
+    // Route to Photos or Videos subdirectory based on file type
+    let folder_for = |source: &ImportSource| match get_file_type(&source.path) {
+        Some("photo") => photos_path.join(&source.subfolder),
+        Some("video") => videos_path.join(&source.subfolder),
+        _ => dest_path.clone(), // Fallback to root if unknown type
+    };
+
+    let mut cancelled = false;
+    let renamed = match rename_template {
+        Some(template) => {
+            let planned = sources
+                .iter()
+                .map(|source| (source.path.clone(), folder_for(source)))
+                .collect();
+            match plan_renamed_paths(template, planned, cancel_token.clone()).await {
+                Ok(paths) => paths,
+                Err(ImportError::Cancelled) => {
+                    cancelled = true;
+                    sources.clear();
+                    Vec::new()
+                }
+                Err(e) => {
+                    import_tokens.lock().await.remove(&import_id);
+                    return Err(e.to_string());
+                }
+            }
+        }
+        None => Vec::new(),
+    };
+
     let files_copied = Arc::new(AtomicUsize::new(0));
-    let files_skipped = Arc::new(AtomicUsize::new(0));
+    let files_skipped = Arc::new(AtomicUsize::new(missing.len()));
+    let duplicates_skipped = Arc::new(AtomicUsize::new(0));
+    let files_processed = Arc::new(AtomicUsize::new(0));
     let total_bytes = Arc::new(AtomicUsize::new(0));
     let photos_copied = Arc::new(AtomicUsize::new(0));
     let videos_copied = Arc::new(AtomicUsize::new(0));
-    let skipped_files = Arc::new(tokio::sync::Mutex::new(Vec::new()));
+    let skipped_files = Arc::new(tokio::sync::Mutex::new(missing));
     let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_COPIES));
-    let total_files = source_paths.len();
+    let total_files = sources.len();
 
     let mut tasks = Vec::new();
 
-    for src_path in &source_paths {
-        let src = PathBuf::from(src_path);
-        let file_name = src
+    for (index, source) in sources.into_iter().enumerate() {
+        let file_name = source
+            .path
             .file_name()
             .unwrap_or_default()
             .to_string_lossy()
             .to_string();
-        let file_type = get_file_type(&src);
-
-        // Route to Photos or Videos subdirectory based on file type
-        let dest_file = match file_type {
-            Some("photo") => dest_path.join("Photos").join(&file_name),
-            Some("video") => dest_path.join("Videos").join(&file_name),
-            _ => dest_path.join(&file_name), // Fallback to root if unknown type
-        };
+        let file_type = get_file_type(&source.path);
+        let dest_file = renamed
+            .get(index)
+            .cloned()
+            .unwrap_or_else(|| folder_for(&source).join(&file_name));
+        let ImportSource {
+            path: src,
+            subfolder,
+        } = source;
+        let nested = !subfolder.as_os_str().is_empty();
 
         let files_copied_clone = files_copied.clone();
         let files_skipped_clone = files_skipped.clone();
+        let duplicates_skipped_clone = duplicates_skipped.clone();
+        let files_processed_clone = files_processed.clone();
         let total_bytes_clone = total_bytes.clone();
         let photos_copied_clone = photos_copied.clone();
         let videos_copied_clone = videos_copied.clone();
         let skipped_files_clone = skipped_files.clone();
         let semaphore_clone = semaphore.clone();
         let cancel_token_clone = cancel_token.clone();
-        let app_clone = app.clone();
+        let emit_clone = emit.clone();
 
         let task = tokio::spawn(async move {
             let _permit = semaphore_clone
@@ -137,9 +549,41 @@ pub async fn copy_files(
                 return Err(ImportError::Cancelled);
             }
 
-            match copy_file_with_retry(&src, &dest_file, &cancel_token_clone).await {
+            let progress = |files_processed| ImportProgress {
+                files_copied: files_copied_clone.load(Ordering::SeqCst),
+                files_processed,
+                total_files,
+                current_file: file_name.clone(),
+            };
+
+            let (check_src, check_dest) = (src.clone(), dest_file.clone());
+            let duplicate =
+                tokio::task::spawn_blocking(move || is_already_imported(&check_src, &check_dest))
+                    .await
+                    .unwrap_or(false);
+            if duplicate {
+                duplicates_skipped_clone.fetch_add(1, Ordering::SeqCst);
+                let processed = files_processed_clone.fetch_add(1, Ordering::SeqCst) + 1;
+                emit_clone(progress(processed));
+                return Ok(());
+            }
+
+            let copied = if nested {
+                create_parent_dir(&dest_file).await
+            } else {
+                Ok(())
+            };
+            let copied = match copied {
+                Ok(()) => {
+                    copy_file_with_retry(&src, &dest_file, &cancel_token_clone, verify, copy_fn)
+                        .await
+                }
+                Err(e) => Err(e),
+            };
+
+            match copied {
                 Ok(size) => {
-                    let copied = files_copied_clone.fetch_add(1, Ordering::SeqCst) + 1;
+                    files_copied_clone.fetch_add(1, Ordering::SeqCst);
                     total_bytes_clone.fetch_add(size as usize, Ordering::SeqCst);
 
                     match file_type {
@@ -152,21 +596,20 @@ pub async fn copy_files(
                         _ => {}
                     }
 
-                    let _ = app_clone.emit(
-                        "import-progress",
-                        ImportProgress {
-                            files_copied: copied,
-                            total_files,
-                            current_file: file_name.clone(),
-                        },
-                    );
+                    let processed = files_processed_clone.fetch_add(1, Ordering::SeqCst) + 1;
+                    emit_clone(progress(processed));
 
                     Ok(())
                 }
                 Err(ImportError::Cancelled) => Err(ImportError::Cancelled),
                 Err(e) => {
                     files_skipped_clone.fetch_add(1, Ordering::SeqCst);
-                    skipped_files_clone.lock().await.push(file_name);
+                    let processed = files_processed_clone.fetch_add(1, Ordering::SeqCst) + 1;
+                    emit_clone(progress(processed));
+                    skipped_files_clone.lock().await.push(SkippedFile {
+                        file_name,
+                        reason: e.to_string(),
+                    });
                     Err(e)
                 }
             }
@@ -175,7 +618,6 @@ pub async fn copy_files(
         tasks.push(task);
     }
 
-    let mut cancelled = false;
     for result in futures::future::join_all(tasks).await {
         match result {
             Ok(Err(ImportError::Cancelled)) => {
@@ -188,19 +630,22 @@ pub async fn copy_files(
 
     // Clean up token
     {
-        let mut tokens = state.import_tokens.lock().await;
+        let mut tokens = import_tokens.lock().await;
         tokens.remove(&import_id);
     }
 
     let files_copied = files_copied.load(Ordering::SeqCst);
     let files_skipped = files_skipped.load(Ordering::SeqCst);
+    let duplicates_skipped = duplicates_skipped.load(Ordering::SeqCst);
     let total_bytes = total_bytes.load(Ordering::SeqCst) as u64;
     let photos_copied = photos_copied.load(Ordering::SeqCst);
     let videos_copied = videos_copied.load(Ordering::SeqCst);
-    let skipped_files = skipped_files.lock().await.clone();
+    let skip_reasons = skipped_files.lock().await.clone();
+    let skipped_files = skip_reasons.iter().map(|s| s.file_name.clone()).collect();
 
     Ok(CopyResult {
-        success: !cancelled && files_copied > 0,
+        success: !cancelled && files_skipped == 0,
+        cancelled,
         error: if cancelled {
             Some(format!("Import cancelled ({files_copied} files copied)"))
         } else if files_skipped > 0 {
@@ -210,32 +655,76 @@ pub async fn copy_files(
         },
         files_copied,
         files_skipped,
+        duplicates_skipped,
+        excluded_skipped,
         skipped_files,
+        skip_reasons,
         total_bytes,
         photos_copied,
         videos_copied,
+        destination_root: dest_path.to_string_lossy().to_string(),
+        photos_path: photos_path.to_string_lossy().to_string(),
+        videos_path: videos_path.to_string_lossy().to_string(),
     })
 }
 
-/// Copy file with retry logic using fast native copy
+/// Create the folder `dest` goes in, for files keeping their source folder's structure.
+async fn create_parent_dir(dest: &Path) -> Result<(), ImportError> {
+    match dest.parent() {
+        Some(parent) => tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| ImportError::CopyFailed(e.to_string())),
+        None => Ok(()),
+    }
+}
+
+/// Copy file with retry logic, checking the copy against its source when
+/// `verify` is set. A copy that still doesn't match after the last retry is
+/// removed, since a same-size file would pass for an earlier import next time.
 async fn copy_file_with_retry(
     src: &Path,
     dest: &Path,
     cancel_token: &CancellationToken,
+    verify: bool,
+    copy_fn: FileCopier,
 ) -> Result<u64, ImportError> {
     let retry_strategy = ExponentialBackoff::from_millis(10)
         .map(jitter)
         .take(MAX_RETRY_ATTEMPTS);
 
-    Retry::spawn(retry_strategy, || async {
+    let result = Retry::spawn(retry_strategy, || async {
         if cancel_token.is_cancelled() {
             return Err(ImportError::Cancelled);
         }
-        file_ops::copy_file(src, dest)
+        let size = copy_fn(src.to_path_buf(), dest.to_path_buf())
             .await
-            .map_err(ImportError::CopyFailed)
+            .map_err(ImportError::CopyFailed)?;
+        if verify {
+            verify_copy(src, dest).await?;
+        }
+        Ok(size)
     })
-    .await
+    .await;
+
+    if matches!(result, Err(ImportError::ChecksumMismatch)) {
+        let _ = file_ops::remove_file(dest).await;
+    }
+    result
+}
+
+/// Fail with [`ImportError::ChecksumMismatch`] unless `dest` has the same
+/// SHA-256 as `src`.
+async fn verify_copy(src: &Path, dest: &Path) -> Result<(), ImportError> {
+    let digest = |path| async move {
+        calculate_file_hash(path)
+            .await
+            .map_err(|e| ImportError::CopyFailed(e.to_string()))
+    };
+    if digest(src).await? == digest(dest).await? {
+        Ok(())
+    } else {
+        Err(ImportError::ChecksumMismatch)
+    }
 }
 
 /// Core logic for canceling an import (testable)
@@ -268,6 +757,12 @@ pub async fn cancel_import(
         .map_err(String::from)
 }
 
+/// List the photo and video extensions imports recognise.
+#[tauri::command]
+pub fn get_supported_extensions() -> SupportedExtensions {
+    supported_extensions()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -306,13 +801,20 @@ mod tests {
     fn test_copy_result_serialization() {
         let result = CopyResult {
             success: true,
+            cancelled: false,
             error: None,
             files_copied: 10,
             files_skipped: 2,
+            duplicates_skipped: 0,
+            excluded_skipped: 0,
             skipped_files: vec!["file1.jpg".to_owned()],
+            skip_reasons: Vec::new(),
             total_bytes: 1024,
             photos_copied: 8,
             videos_copied: 2,
+            destination_root: String::new(),
+            photos_path: String::new(),
+            videos_path: String::new(),
         };
 
         let json = serde_json::to_string(&result).unwrap();
@@ -321,10 +823,37 @@ mod tests {
         assert!(json.contains("file1.jpg"));
     }
 
+    #[test]
+    fn test_copy_result_serializes_destination_paths() {
+        let result = CopyResult {
+            success: true,
+            cancelled: false,
+            error: None,
+            files_copied: 1,
+            files_skipped: 0,
+            duplicates_skipped: 0,
+            excluded_skipped: 0,
+            skipped_files: Vec::new(),
+            skip_reasons: Vec::new(),
+            total_bytes: 5,
+            photos_copied: 1,
+            videos_copied: 0,
+            destination_root: "/Projects/Smith/RAW".to_owned(),
+            photos_path: "/Projects/Smith/RAW/Photos".to_owned(),
+            videos_path: "/Projects/Smith/RAW/Videos".to_owned(),
+        };
+
+        let json: serde_json::Value = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["destinationRoot"], "/Projects/Smith/RAW");
+        assert_eq!(json["photosPath"], "/Projects/Smith/RAW/Photos");
+        assert_eq!(json["videosPath"], "/Projects/Smith/RAW/Videos");
+    }
+
     #[test]
     fn test_import_progress_serialization() {
         let progress = ImportProgress {
             files_copied: 5,
+            files_processed: 5,
             total_files: 10,
             current_file: "test.jpg".to_owned(),
         };
@@ -343,6 +872,25 @@ mod tests {
         assert!(PHOTO_EXTENSIONS.contains(&"heic"));
     }
 
+    #[test]
+    fn test_supported_extensions_match_constants() {
+        let extensions = supported_extensions();
+        assert_eq!(extensions.photos, PHOTO_EXTENSIONS);
+        assert_eq!(extensions.videos, VIDEO_EXTENSIONS);
+        assert!(extensions
+            .photos
+            .iter()
+            .all(|ext| get_file_type(Path::new(&format!("a.{ext}"))) == Some("photo")));
+        assert!(extensions
+            .videos
+            .iter()
+            .all(|ext| get_file_type(Path::new(&format!("a.{ext}"))) == Some("video")));
+
+        let json = serde_json::to_value(&extensions).unwrap();
+        assert_eq!(json["photos"][0], "jpg");
+        assert_eq!(json["videos"][0], "mp4");
+    }
+
     #[test]
     fn test_video_extensions() {
         assert!(VIDEO_EXTENSIONS.contains(&"mp4"));
@@ -360,7 +908,7 @@ mod tests {
         file.write_all(b"test photo data").unwrap();
 
         let cancel_token = CancellationToken::new();
-        let result = copy_file_with_retry(&src, &dest, &cancel_token).await;
+        let result = copy_file_with_retry(&src, &dest, &cancel_token, false, native_copy).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 15);
@@ -379,7 +927,7 @@ mod tests {
         let cancel_token = CancellationToken::new();
         cancel_token.cancel();
 
-        let result = copy_file_with_retry(&src, &dest, &cancel_token).await;
+        let result = copy_file_with_retry(&src, &dest, &cancel_token, false, native_copy).await;
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ImportError::Cancelled));
@@ -392,7 +940,7 @@ mod tests {
         let dest = temp_dir.path().join("dest.jpg");
 
         let cancel_token = CancellationToken::new();
-        let result = copy_file_with_retry(&src, &dest, &cancel_token).await;
+        let result = copy_file_with_retry(&src, &dest, &cancel_token, false, native_copy).await;
 
         assert!(result.is_err());
     }
@@ -420,17 +968,24 @@ mod tests {
     fn test_copy_result_with_errors() {
         let result = CopyResult {
             success: false,
+            cancelled: false,
             error: Some("3 file(s) skipped due to errors".to_owned()),
             files_copied: 5,
             files_skipped: 3,
+            duplicates_skipped: 0,
+            excluded_skipped: 0,
             skipped_files: vec![
                 "file1.jpg".to_owned(),
                 "file2.mp4".to_owned(),
                 "file3.png".to_owned(),
             ],
+            skip_reasons: Vec::new(),
             total_bytes: 2048,
             photos_copied: 4,
             videos_copied: 1,
+            destination_root: String::new(),
+            photos_path: String::new(),
+            videos_path: String::new(),
         };
 
         assert!(!result.success);
@@ -443,13 +998,20 @@ mod tests {
     fn test_copy_result_cancelled() {
         let result = CopyResult {
             success: false,
+            cancelled: true,
             error: Some("Import cancelled (10 files copied)".to_owned()),
             files_copied: 10,
             files_skipped: 0,
+            duplicates_skipped: 0,
+            excluded_skipped: 0,
             skipped_files: vec![],
+            skip_reasons: Vec::new(),
             total_bytes: 5120,
             photos_copied: 8,
             videos_copied: 2,
+            destination_root: String::new(),
+            photos_path: String::new(),
+            videos_path: String::new(),
         };
 
         assert!(!result.success);
@@ -504,6 +1066,7 @@ mod tests {
     fn test_import_progress_complete() {
         let progress = ImportProgress {
             files_copied: 10,
+            files_processed: 10,
             total_files: 10,
             current_file: "last.jpg".to_owned(),
         };
@@ -524,7 +1087,7 @@ mod tests {
         std::fs::create_dir_all(dest.parent().unwrap()).unwrap();
 
         let cancel_token = CancellationToken::new();
-        let result = copy_file_with_retry(&src, &dest, &cancel_token).await;
+        let result = copy_file_with_retry(&src, &dest, &cancel_token, false, native_copy).await;
 
         assert!(result.is_ok());
         assert!(dest.exists());
@@ -534,13 +1097,20 @@ mod tests {
     fn test_copy_result_success_criteria() {
         let success = CopyResult {
             success: true,
+            cancelled: false,
             error: None,
             files_copied: 10,
             files_skipped: 0,
+            duplicates_skipped: 0,
+            excluded_skipped: 0,
             skipped_files: vec![],
+            skip_reasons: Vec::new(),
             total_bytes: 1024,
             photos_copied: 6,
             videos_copied: 4,
+            destination_root: String::new(),
+            photos_path: String::new(),
+            videos_path: String::new(),
         };
 
         assert!(success.success);
@@ -599,7 +1169,7 @@ mod tests {
 
         let cancel_token = CancellationToken::new();
         let start = std::time::Instant::now();
-        let result = copy_file_with_retry(&src, &dest, &cancel_token).await;
+        let result = copy_file_with_retry(&src, &dest, &cancel_token, false, native_copy).await;
         let elapsed = start.elapsed();
 
         assert!(result.is_ok());
@@ -618,7 +1188,7 @@ mod tests {
         let dest = temp_dir.path().join("dest.dat");
 
         let cancel_token = CancellationToken::new();
-        let result = copy_file_with_retry(&src, &dest, &cancel_token).await;
+        let result = copy_file_with_retry(&src, &dest, &cancel_token, false, native_copy).await;
 
         assert!(result.is_err());
         assert!(!dest.exists());
@@ -672,13 +1242,20 @@ mod tests {
     fn test_copy_result_with_partial_success() {
         let result = CopyResult {
             success: true,
+            cancelled: false,
             error: Some("2 file(s) skipped due to errors".to_owned()),
             files_copied: 8,
             files_skipped: 2,
+            duplicates_skipped: 0,
+            excluded_skipped: 0,
             skipped_files: vec!["bad1.jpg".to_owned(), "bad2.mp4".to_owned()],
+            skip_reasons: Vec::new(),
             total_bytes: 8192,
             photos_copied: 6,
             videos_copied: 2,
+            destination_root: String::new(),
+            photos_path: String::new(),
+            videos_path: String::new(),
         };
 
         assert!(result.success);
@@ -696,13 +1273,20 @@ mod tests {
     fn test_copy_result_complete_success() {
         let result = CopyResult {
             success: true,
+            cancelled: false,
             error: None,
             files_copied: 15,
             files_skipped: 0,
+            duplicates_skipped: 0,
+            excluded_skipped: 0,
             skipped_files: vec![],
+            skip_reasons: Vec::new(),
             total_bytes: 15360,
             photos_copied: 10,
             videos_copied: 5,
+            destination_root: String::new(),
+            photos_path: String::new(),
+            videos_path: String::new(),
         };
 
         assert!(result.success);
@@ -729,7 +1313,7 @@ mod tests {
         // Cancel immediately before copy
         cancel_token.cancel();
 
-        let result = copy_file_with_retry(&src, &dest, &cancel_token).await;
+        let result = copy_file_with_retry(&src, &dest, &cancel_token, false, native_copy).await;
 
         assert!(result.is_err());
         assert!(matches!(result.unwrap_err(), ImportError::Cancelled));
@@ -747,7 +1331,7 @@ mod tests {
         std::fs::write(&src, &data).unwrap();
 
         let cancel_token = CancellationToken::new();
-        let result = copy_file_with_retry(&src, &dest, &cancel_token).await;
+        let result = copy_file_with_retry(&src, &dest, &cancel_token, false, native_copy).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), data.len() as u64);
@@ -761,6 +1345,7 @@ mod tests {
     fn test_import_progress_zero_progress() {
         let progress = ImportProgress {
             files_copied: 0,
+            files_processed: 0,
             total_files: 100,
             current_file: String::new(),
         };
@@ -773,6 +1358,7 @@ mod tests {
     fn test_import_progress_mid_progress() {
         let progress = ImportProgress {
             files_copied: 50,
+            files_processed: 50,
             total_files: 100,
             current_file: "photo_50.jpg".to_owned(),
         };
@@ -818,7 +1404,7 @@ mod tests {
         std::fs::write(&src, b"").unwrap();
 
         let cancel_token = CancellationToken::new();
-        let result = copy_file_with_retry(&src, &dest, &cancel_token).await;
+        let result = copy_file_with_retry(&src, &dest, &cancel_token, false, native_copy).await;
 
         assert!(result.is_ok());
         assert_eq!(result.unwrap(), 0);
@@ -830,13 +1416,20 @@ mod tests {
     fn test_copy_result_all_skipped() {
         let result = CopyResult {
             success: false,
+            cancelled: false,
             error: Some("10 file(s) skipped due to errors".to_owned()),
             files_copied: 0,
             files_skipped: 10,
+            duplicates_skipped: 0,
+            excluded_skipped: 0,
             skipped_files: vec!["f1.jpg".to_owned(), "f2.jpg".to_owned()],
+            skip_reasons: Vec::new(),
             total_bytes: 0,
             photos_copied: 0,
             videos_copied: 0,
+            destination_root: String::new(),
+            photos_path: String::new(),
+            videos_path: String::new(),
         };
 
         assert!(!result.success);
@@ -844,4 +1437,650 @@ mod tests {
         assert_eq!(result.files_copied, 0);
         assert_eq!(result.files_skipped, 10);
     }
+
+    /// A file name on a test card and the bytes written to it.
+    type CardFile<'a> = (&'a str, &'a [u8]);
+
+    /// Write each `(name, contents)` pair into `card` and return the paths as import sources.
+    fn write_card_files(card: &Path, files: &[CardFile]) -> Vec<String> {
+        fs::create_dir_all(card).unwrap();
+        files
+            .iter()
+            .map(|(name, contents)| {
+                let path = card.join(name);
+                fs::write(&path, contents).unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect()
+    }
+
+    /// Import `sources` into `destination`, laid out like a project's `RAW` folder.
+    async fn run_import(sources: &[String], destination: &Path) -> CopyResult {
+        run_import_of_types(sources, destination, None)
+            .await
+            .unwrap()
+    }
+
+    async fn run_import_of_types(
+        sources: &[String],
+        destination: &Path,
+        allowed_types: Option<&[String]>,
+    ) -> Result<CopyResult, String> {
+        fs::create_dir_all(destination.join(PHOTOS_FOLDER)).unwrap();
+        fs::create_dir_all(destination.join(VIDEOS_FOLDER)).unwrap();
+
+        let state = crate::state::AppState::default();
+        copy_files_impl(
+            &state.import_tokens,
+            "import-1".to_owned(),
+            sources,
+            &destination.to_string_lossy(),
+            &ImportOptions {
+                allowed_types: allowed_types.map(<[String]>::to_vec),
+                ..ImportOptions::default()
+            },
+            Arc::new(|_| {}),
+        )
+        .await
+    }
+
+    /// Import `sources` into `destination`, renaming them from `template`.
+    async fn run_renaming_import(
+        sources: &[String],
+        destination: &Path,
+        template: &str,
+    ) -> Result<CopyResult, String> {
+        fs::create_dir_all(destination.join(PHOTOS_FOLDER)).unwrap();
+        fs::create_dir_all(destination.join(VIDEOS_FOLDER)).unwrap();
+
+        let state = crate::state::AppState::default();
+        copy_files_impl(
+            &state.import_tokens,
+            "import-1".to_owned(),
+            sources,
+            &destination.to_string_lossy(),
+            &ImportOptions {
+                rename_template: Some(template.to_owned()),
+                ..ImportOptions::default()
+            },
+            Arc::new(|_| {}),
+        )
+        .await
+    }
+
+    /// Import `sources` into `destination`, writing each file with `copy_fn`.
+    async fn run_import_with_copier(
+        sources: &[String],
+        destination: &Path,
+        verify: bool,
+        copy_fn: FileCopier,
+    ) -> CopyResult {
+        fs::create_dir_all(destination.join(PHOTOS_FOLDER)).unwrap();
+        fs::create_dir_all(destination.join(VIDEOS_FOLDER)).unwrap();
+
+        let state = crate::state::AppState::default();
+        copy_files_with(
+            &state.import_tokens,
+            "import-1".to_owned(),
+            sources,
+            &destination.to_string_lossy(),
+            &ImportOptions {
+                verify,
+                ..ImportOptions::default()
+            },
+            copy_fn,
+            Arc::new(|_| {}),
+        )
+        .await
+        .unwrap()
+    }
+
+    /// Copy that flips the first byte it writes, like a card read error.
+    fn corrupting_copy(src: PathBuf, dest: PathBuf) -> BoxFuture<'static, Result<u64, String>> {
+        Box::pin(async move {
+            let mut bytes = tokio::fs::read(&src).await.map_err(|e| e.to_string())?;
+            bytes[0] ^= 0xFF;
+            tokio::fs::write(&dest, &bytes)
+                .await
+                .map_err(|e| e.to_string())?;
+            Ok(bytes.len() as u64)
+        })
+    }
+
+    /// Copies made by [`corrupt_once_copy`] so far.
+    static CORRUPT_ONCE_CALLS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Copy that corrupts only its first file, so a retry gets a good copy.
+    fn corrupt_once_copy(src: PathBuf, dest: PathBuf) -> BoxFuture<'static, Result<u64, String>> {
+        if CORRUPT_ONCE_CALLS.fetch_add(1, Ordering::SeqCst) == 0 {
+            corrupting_copy(src, dest)
+        } else {
+            native_copy(src, dest)
+        }
+    }
+
+    #[tokio::test]
+    async fn test_verified_import_flags_corrupted_copy() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("IMG_0001.jpg");
+        fs::write(&source, b"photo data").unwrap();
+        let sources = [source.to_string_lossy().to_string()];
+
+        // Without verification the corrupted copy goes unnoticed
+        let unverified = temp_dir.path().join("unverified");
+        let result = run_import_with_copier(&sources, &unverified, false, corrupting_copy).await;
+        assert!(result.success);
+        assert_eq!(result.files_copied, 1);
+
+        let verified = temp_dir.path().join("verified");
+        let result = run_import_with_copier(&sources, &verified, true, corrupting_copy).await;
+        assert!(!result.success);
+        assert_eq!(result.files_copied, 0);
+        assert_eq!(result.files_skipped, 1);
+        assert_eq!(result.skipped_files, ["IMG_0001.jpg"]);
+        assert_eq!(
+            result.skip_reasons[0].reason,
+            ImportError::ChecksumMismatch.to_string()
+        );
+        // The corrupt copy isn't left behind to pass for a finished import
+        assert!(!verified.join(PHOTOS_FOLDER).join("IMG_0001.jpg").exists());
+    }
+
+    #[tokio::test]
+    async fn test_verified_import_retries_after_mismatch() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("IMG_0001.jpg");
+        fs::write(&source, b"photo data").unwrap();
+        let destination = temp_dir.path().join("raw");
+
+        let result = run_import_with_copier(
+            &[source.to_string_lossy().to_string()],
+            &destination,
+            true,
+            corrupt_once_copy,
+        )
+        .await;
+
+        assert!(result.success);
+        assert_eq!(result.files_copied, 1);
+        assert!(CORRUPT_ONCE_CALLS.load(Ordering::SeqCst) >= 2);
+        assert_eq!(
+            fs::read(destination.join(PHOTOS_FOLDER).join("IMG_0001.jpg")).unwrap(),
+            b"photo data"
+        );
+    }
+
+    /// Write a minimal JPEG whose EXIF `DateTimeOriginal` is `captured`
+    /// (`YYYY:MM:DD HH:MM:SS`), padded with `padding` bytes.
+    fn write_jpeg_taken_at(path: &Path, captured: &str, padding: usize) {
+        use exif::experimental::Writer;
+        use exif::{Field, In, Tag, Value};
+
+        let field = Field {
+            tag: Tag::DateTimeOriginal,
+            ifd_num: In::PRIMARY,
+            value: Value::Ascii(vec![captured.as_bytes().to_vec()]),
+        };
+        let mut writer = Writer::new();
+        writer.push_field(&field);
+        let mut tiff = std::io::Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let tiff = tiff.into_inner();
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        jpeg.extend_from_slice(&u16::try_from(tiff.len() + 8).unwrap().to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend_from_slice(&tiff);
+        jpeg.extend_from_slice(&[0xFF, 0xD9]);
+        jpeg.resize(jpeg.len() + padding, 0);
+        fs::write(path, jpeg).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_import_renames_files_by_capture_time() {
+        let temp_dir = TempDir::new().unwrap();
+        let card = temp_dir.path().join("card");
+        fs::create_dir_all(&card).unwrap();
+        let exif_photo = card.join("IMG_1234.JPG");
+        write_jpeg_taken_at(&exif_photo, "2024:06:15 14:32:05", 0);
+        // No EXIF, so the modification time is used
+        let clip = card.join("CLIP_0007.MOV");
+        fs::write(&clip, b"video").unwrap();
+        let modified =
+            std::time::SystemTime::UNIX_EPOCH + std::time::Duration::from_secs(1_700_000_000);
+        fs::File::options()
+            .write(true)
+            .open(&clip)
+            .unwrap()
+            .set_modified(modified)
+            .unwrap();
+        let clip_time = chrono::DateTime::<chrono::Local>::from(modified)
+            .naive_local()
+            .format(RENAME_DATETIME_FORMAT)
+            .to_string();
+        let raw = temp_dir.path().join("RAW");
+
+        let result = run_renaming_import(
+            &[
+                exif_photo.to_string_lossy().to_string(),
+                clip.to_string_lossy().to_string(),
+            ],
+            &raw,
+            "{datetime}_{original}_{seq}",
+        )
+        .await
+        .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.files_copied, 2);
+        assert!(raw
+            .join(PHOTOS_FOLDER)
+            .join("2024-06-15_143205_IMG_1234_0001.JPG")
+            .is_file());
+        assert!(raw
+            .join(VIDEOS_FOLDER)
+            .join(format!("{clip_time}_CLIP_0007_0002.MOV"))
+            .is_file());
+    }
+
+    #[tokio::test]
+    async fn test_renamed_collisions_get_a_suffix() {
+        let temp_dir = TempDir::new().unwrap();
+        // Two cards that both shot IMG_0001 in the same second
+        let mut sources = Vec::new();
+        for (card, padding) in [("card-a", 1), ("card-b", 2)] {
+            let dir = temp_dir.path().join(card);
+            fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("IMG_0001.JPG");
+            write_jpeg_taken_at(&path, "2024:06:15 14:32:05", padding);
+            sources.push(path.to_string_lossy().to_string());
+        }
+        let raw = temp_dir.path().join("RAW");
+        let photos = raw.join(PHOTOS_FOLDER);
+
+        let result = run_renaming_import(&sources, &raw, "{datetime}_{original}")
+            .await
+            .unwrap();
+
+        assert_eq!(result.files_copied, 2);
+        let first = photos.join("2024-06-15_143205_IMG_0001.JPG");
+        let second = photos.join("2024-06-15_143205_IMG_0001 (1).JPG");
+        assert_eq!(fs::read(&first).unwrap(), fs::read(&sources[0]).unwrap());
+        assert_eq!(fs::read(&second).unwrap(), fs::read(&sources[1]).unwrap());
+
+        // Importing the same cards again finds the copies instead of adding more
+        let again = run_renaming_import(&sources, &raw, "{datetime}_{original}")
+            .await
+            .unwrap();
+        assert_eq!(again.files_copied, 0);
+        assert_eq!(again.duplicates_skipped, 2);
+        assert_eq!(fs::read_dir(&photos).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_claim_import_path_skips_same_size_strangers() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("IMG_0001.JPG");
+        fs::write(&src, b"new photo").unwrap();
+        let dest = temp_dir.path().join("2024-06-15_143205.JPG");
+        fs::write(&dest, b"old photo").unwrap();
+
+        let claimed_path = claim_import_path(dest.clone(), &src, &mut HashSet::new());
+        assert_eq!(
+            claimed_path,
+            temp_dir.path().join("2024-06-15_143205 (1).JPG")
+        );
+
+        fs::write(&dest, b"new photo").unwrap();
+        assert_eq!(
+            claim_import_path(dest.clone(), &src, &mut HashSet::new()),
+            dest
+        );
+    }
+
+    #[tokio::test]
+    async fn test_planning_renamed_paths_stops_when_cancelled() {
+        let temp_dir = TempDir::new().unwrap();
+        let src = temp_dir.path().join("IMG_0001.JPG");
+        fs::write(&src, b"photo").unwrap();
+        let cancel = CancellationToken::new();
+        cancel.cancel();
+
+        let planned = plan_renamed_paths(
+            "{original}_{seq}",
+            vec![(src, temp_dir.path().join(PHOTOS_FOLDER))],
+            cancel,
+        )
+        .await;
+
+        assert!(matches!(planned, Err(ImportError::Cancelled)));
+    }
+
+    #[tokio::test]
+    async fn test_invalid_rename_template_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+
+        for template in [
+            "{date}_{original}",
+            "{original",
+            "original}",
+            "{seq}/{original}",
+        ] {
+            assert!(
+                run_renaming_import(&[], temp_dir.path(), template)
+                    .await
+                    .is_err(),
+                "{template}"
+            );
+        }
+        assert!(run_renaming_import(&[], temp_dir.path(), "  ")
+            .await
+            .is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_empty_import_succeeds() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = run_import(&[], &temp_dir.path().join("RAW")).await;
+
+        assert!(result.success);
+        assert!(result.error.is_none());
+        assert_eq!(result.files_copied, 0);
+    }
+
+    #[tokio::test]
+    async fn test_import_of_already_imported_files_succeeds() {
+        let temp_dir = TempDir::new().unwrap();
+        let sources = write_card_files(
+            &temp_dir.path().join("card"),
+            &[("IMG_0001.JPG", b"photo"), ("CLIP_0001.MOV", b"clip")],
+        );
+        let destination = temp_dir.path().join("RAW");
+
+        let first = run_import(&sources, &destination).await;
+        assert!(first.success);
+        assert_eq!(first.files_copied, 2);
+        assert_eq!(first.duplicates_skipped, 0);
+
+        let second = run_import(&sources, &destination).await;
+        assert!(second.success);
+        assert!(second.error.is_none());
+        assert_eq!(second.files_copied, 0);
+        assert_eq!(second.files_skipped, 0);
+        assert_eq!(second.duplicates_skipped, 2);
+    }
+
+    #[tokio::test]
+    async fn test_same_name_and_size_is_not_a_duplicate_without_same_contents() {
+        let temp_dir = TempDir::new().unwrap();
+        let card = temp_dir.path().join("card");
+        fs::create_dir_all(&card).unwrap();
+        let source = card.join("IMG_0001.JPG");
+        fs::write(&source, b"second card").unwrap();
+        let destination = temp_dir.path().join("RAW");
+        fs::create_dir_all(destination.join(PHOTOS_FOLDER)).unwrap();
+        // Another photo, same name and size, from an earlier card
+        fs::write(
+            destination.join(PHOTOS_FOLDER).join("IMG_0001.JPG"),
+            b"first card!",
+        )
+        .unwrap();
+
+        let result = run_import(&[source.to_string_lossy().to_string()], &destination).await;
+
+        assert_eq!(result.duplicates_skipped, 0);
+        assert_eq!(result.files_copied, 1);
+    }
+
+    #[tokio::test]
+    async fn test_progress_is_emitted_for_every_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let card = temp_dir.path().join("card");
+        fs::create_dir_all(&card).unwrap();
+        let imported = card.join("IMG_0001.JPG");
+        fs::write(&imported, b"photo").unwrap();
+        let destination = temp_dir.path().join("RAW");
+        run_import(&[imported.to_string_lossy().to_string()], &destination).await;
+        let fresh = card.join("IMG_0002.JPG");
+        fs::write(&fresh, b"photo 2").unwrap();
+        let sources = [
+            imported.to_string_lossy().to_string(),
+            fresh.to_string_lossy().to_string(),
+            card.join("IMG_0003.JPG").to_string_lossy().to_string(),
+        ];
+        let events = Arc::new(std::sync::Mutex::new(Vec::new()));
+        let sink = events.clone();
+
+        let state = crate::state::AppState::default();
+        let result = copy_files_impl(
+            &state.import_tokens,
+            "import-1".to_owned(),
+            &sources,
+            &destination.to_string_lossy(),
+            &ImportOptions::default(),
+            Arc::new(move |progress: ImportProgress| {
+                sink.lock().unwrap().push(progress.files_processed);
+            }),
+        )
+        .await
+        .unwrap();
+
+        // The missing source never reaches a copy task; the duplicate and the
+        // copied file both move the counter
+        assert_eq!(result.duplicates_skipped, 1);
+        assert_eq!(result.files_copied, 1);
+        let mut processed = events.lock().unwrap().clone();
+        processed.sort_unstable();
+        assert_eq!(processed, [1, 2]);
+    }
+
+    #[tokio::test]
+    async fn test_import_with_copy_error_fails() {
+        let temp_dir = TempDir::new().unwrap();
+        let card = temp_dir.path().join("card");
+        fs::create_dir_all(&card).unwrap();
+        let present = card.join("IMG_0001.JPG");
+        fs::write(&present, b"photo").unwrap();
+        let sources = vec![
+            present.to_string_lossy().to_string(),
+            card.join("IMG_0002.JPG").to_string_lossy().to_string(),
+        ];
+
+        let result = run_import(&sources, &temp_dir.path().join("RAW")).await;
+
+        assert!(!result.success);
+        assert_eq!(result.files_copied, 1);
+        assert_eq!(result.files_skipped, 1);
+        assert_eq!(result.skip_reasons[0].file_name, "IMG_0002.JPG");
+    }
+
+    #[tokio::test]
+    async fn test_duplicate_and_missing_sources_are_skipped() {
+        let temp_dir = TempDir::new().unwrap();
+        let card = temp_dir.path().join("card");
+        fs::create_dir_all(card.join("DCIM")).unwrap();
+        let photo = card.join("DCIM/IMG_0001.JPG");
+        fs::write(&photo, b"photo").unwrap();
+        let sources = vec![
+            photo.to_string_lossy().to_string(),
+            card.join("DCIM/../DCIM/IMG_0001.JPG")
+                .to_string_lossy()
+                .to_string(),
+            photo.to_string_lossy().to_string(),
+            card.join("DCIM/IMG_0002.JPG").to_string_lossy().to_string(),
+        ];
+
+        let result = run_import(&sources, &temp_dir.path().join("RAW")).await;
+
+        assert_eq!(result.files_copied, 1);
+        assert_eq!(result.photos_copied, 1);
+        assert_eq!(result.total_bytes, 5);
+        assert_eq!(result.duplicates_skipped, 0);
+        assert_eq!(result.files_skipped, 1);
+        assert_eq!(
+            result.skip_reasons,
+            vec![SkippedFile {
+                file_name: "IMG_0002.JPG".to_owned(),
+                reason: "Source file not found".to_owned(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_clip_folder_import_keeps_structure() {
+        let temp_dir = TempDir::new().unwrap();
+        let card = temp_dir.path().join("card");
+        let stream = card.join("PRIVATE/AVCHD/BDMV/STREAM");
+        fs::create_dir_all(&stream).unwrap();
+        fs::write(stream.join("00000.MTS"), b"clip 0").unwrap();
+        fs::write(stream.join("00001.MTS"), b"clip 1").unwrap();
+        fs::write(card.join("PRIVATE/AVCHD/BDMV/index.bdmv"), b"index").unwrap();
+        fs::create_dir_all(card.join("DCIM")).unwrap();
+        fs::write(card.join("DCIM/IMG_0001.JPG"), b"photo").unwrap();
+        let sources = vec![
+            card.join("PRIVATE/AVCHD/BDMV")
+                .to_string_lossy()
+                .to_string(),
+            card.join("DCIM/IMG_0001.JPG").to_string_lossy().to_string(),
+            // Also covered by the folder above
+            stream.join("00000.MTS").to_string_lossy().to_string(),
+        ];
+        let raw = temp_dir.path().join("RAW");
+
+        let result = run_import(&sources, &raw).await;
+
+        assert!(result.success);
+        assert_eq!(result.files_copied, 3);
+        assert_eq!(result.videos_copied, 2);
+        assert_eq!(result.photos_copied, 1);
+        assert!(raw.join("Videos/BDMV/STREAM/00000.MTS").is_file());
+        assert!(raw.join("Videos/BDMV/STREAM/00001.MTS").is_file());
+        assert!(raw.join("Photos/IMG_0001.JPG").is_file());
+        // Non-media files in clip folders aren't imported
+        assert!(!raw.join("Videos/BDMV/index.bdmv").exists());
+        assert!(!raw.join("index.bdmv").exists());
+    }
+
+    #[tokio::test]
+    async fn test_import_reports_where_files_landed() {
+        let temp_dir = TempDir::new().unwrap();
+        let sources = write_card_files(
+            &temp_dir.path().join("card"),
+            &[
+                ("IMG_0001.JPG", b"photo"),
+                ("CLIP_0001.MOV", b"clip"),
+                ("CAMERA.LOG", b"log"),
+            ],
+        );
+        let raw = temp_dir.path().join("RAW");
+
+        let result = run_import(&sources, &raw).await;
+
+        assert_eq!(result.destination_root, raw.to_string_lossy());
+        assert_eq!(Path::new(&result.photos_path), raw.join("Photos"));
+        assert_eq!(Path::new(&result.videos_path), raw.join("Videos"));
+        assert!(Path::new(&result.photos_path)
+            .join("IMG_0001.JPG")
+            .is_file());
+        assert!(Path::new(&result.videos_path)
+            .join("CLIP_0001.MOV")
+            .is_file());
+        assert!(Path::new(&result.destination_root)
+            .join("CAMERA.LOG")
+            .is_file());
+    }
+
+    #[tokio::test]
+    async fn test_import_of_allowed_types_excludes_the_rest() {
+        let temp_dir = TempDir::new().unwrap();
+        let sources = write_card_files(
+            &temp_dir.path().join("card"),
+            &[
+                ("IMG_0001.JPG", b"photo"),
+                ("IMG_0002.CR2", b"raw photo"),
+                ("CLIP_0001.MOV", b"clip"),
+                ("FW.BIN", b"firmware"),
+            ],
+        );
+        let raw = temp_dir.path().join("RAW");
+
+        let result = run_import_of_types(&sources, &raw, Some(&["photo".to_owned()]))
+            .await
+            .unwrap();
+
+        assert!(result.success);
+        assert_eq!(result.files_copied, 2);
+        assert_eq!(result.photos_copied, 2);
+        assert_eq!(result.videos_copied, 0);
+        assert_eq!(result.excluded_skipped, 2);
+        assert_eq!(result.files_skipped, 0);
+        assert!(raw.join("Photos/IMG_0001.JPG").is_file());
+        assert!(!raw.join("Videos/CLIP_0001.MOV").exists());
+        assert!(!raw.join("FW.BIN").exists());
+    }
+
+    #[tokio::test]
+    async fn test_import_rejects_unknown_allowed_type() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let err = run_import_of_types(
+            &[],
+            &temp_dir.path().join("RAW"),
+            Some(&["audio".to_owned()]),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(err, "Unknown file type: audio");
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_import_is_flagged() {
+        let temp_dir = TempDir::new().unwrap();
+        let card = temp_dir.path().join("card");
+        fs::create_dir_all(&card).unwrap();
+        let sources: Vec<String> = (0..20)
+            .map(|i| {
+                let path = card.join(format!("IMG_{i:04}.JPG"));
+                fs::write(&path, b"photo").unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+
+        let completed = run_import(&sources[..2], &temp_dir.path().join("Done")).await;
+        assert!(completed.success);
+        assert!(!completed.cancelled);
+
+        // Cancel as soon as the first file lands; files not yet started are abandoned
+        let raw = temp_dir.path().join("RAW");
+        fs::create_dir_all(raw.join(PHOTOS_FOLDER)).unwrap();
+        let state = crate::state::AppState::default();
+        let tokens = state.import_tokens.clone();
+        let result = copy_files_impl(
+            &state.import_tokens,
+            "import-1".to_owned(),
+            &sources,
+            &raw.to_string_lossy(),
+            &ImportOptions::default(),
+            Arc::new(move |_| {
+                if let Ok(tokens) = tokens.try_lock() {
+                    tokens.values().for_each(CancellationToken::cancel);
+                }
+            }),
+        )
+        .await
+        .unwrap();
+
+        assert!(result.cancelled);
+        assert!(!result.success);
+        assert!(result.files_copied < sources.len());
+        assert_eq!(
+            result.error,
+            Some(format!(
+                "Import cancelled ({} files copied)",
+                result.files_copied
+            ))
+        );
+    }
 }
@@ -2,9 +2,17 @@
 //!
 //! Provides Tauri commands for revealing files in the OS file manager and
 //! launching third-party editors (Lightroom, `AfterShoot`, `DaVinci` Resolve,
-//! Final Cut Pro). All launch calls are fire-and-forget background processes.
+//! Final Cut Pro), or any other application via `open_in_application`. All
+//! launch calls are fire-and-forget background processes.
+//! `reveal_project_folder` resolves a project's folder from the database first.
+//! Also moves folders to the platform Trash / Recycle Bin so removals stay
+//! recoverable.
 
 use crate::error::AppError;
+use crate::modules::db::Database;
+use crate::modules::project::get_project_by_id;
+use std::ffi::OsString;
+use std::path::PathBuf;
 use std::process::Command;
 
 // Windows application paths for external editors
@@ -26,9 +34,11 @@ const DAVINCI_RESOLVE_PATHS: &[&str] = &[
     r"C:\Program Files (x86)\Blackmagic Design\DaVinci Resolve\Resolve.exe",
 ];
 
-/// Opens a project's media folder in an external editing application.
+/// Opens selected files, or else a project's media folder, in an external editing
+/// application.
 ///
-/// This function assumes the standard `CreatorOps` project structure:
+/// Without a selection, this function assumes the standard `CreatorOps` project
+/// structure:
 /// ```text
 /// ProjectFolder/
 ///   RAW/
@@ -37,14 +47,32 @@ const DAVINCI_RESOLVE_PATHS: &[&str] = &[
 /// ```
 ///
 /// The function launches the external app in the background (fire-and-forget)
-/// which is appropriate for GUI applications that should run independently.
+/// which is appropriate for GUI applications that should run independently. All
+/// selected files go to a single launch of the app.
 fn open_in_external_app(
     project_path: &str,
+    selected: &[String],
     subfolder: &str,
     app_name: &str,
     #[cfg_attr(not(target_os = "windows"), allow(unused_variables))] windows_paths: &[&str],
     #[cfg_attr(not(target_os = "linux"), allow(unused_variables))] linux_path: Option<&str>,
 ) -> Result<(), AppError> {
+    let targets = editor_targets(project_path, selected, subfolder)?;
+    let app_identifier = resolve_editor(app_name, windows_paths, linux_path)?;
+    open_with(&app_identifier, app_name, &targets, spawn_launch)
+}
+
+/// Files an editor should open: the selection if there is one, otherwise the
+/// project's `RAW/<subfolder>` folder.
+fn editor_targets(
+    project_path: &str,
+    selected: &[String],
+    subfolder: &str,
+) -> Result<Vec<PathBuf>, AppError> {
+    if !selected.is_empty() {
+        return Ok(selected.iter().map(PathBuf::from).collect());
+    }
+
     let media_path = std::path::Path::new(project_path)
         .join("RAW")
         .join(subfolder);
@@ -55,66 +83,205 @@ fn open_in_external_app(
         )));
     }
 
-    let media_path_str = media_path
-        .to_str()
-        .ok_or_else(|| AppError::ExternalApp("Invalid path encoding".to_owned()))?;
+    Ok(vec![media_path])
+}
 
-    #[cfg(target_os = "macos")]
+/// Error returned when an editor can't be found on this machine.
+fn not_installed(app_name: &str) -> AppError {
+    AppError::ExternalApp(format!("{app_name} is not installed"))
+}
+
+/// Identifier `open_in_application` uses for one of the built-in editors: its
+/// name on macOS, the first installed executable on Windows and Linux.
+fn resolve_editor(
+    app_name: &str,
+    #[cfg_attr(not(target_os = "windows"), allow(unused_variables))] windows_paths: &[&str],
+    #[cfg_attr(not(target_os = "linux"), allow(unused_variables))] linux_path: Option<&str>,
+) -> Result<String, AppError> {
+    #[cfg(target_os = "windows")]
     {
-        Command::new("open")
-            .arg("-a")
-            .arg(app_name)
-            .arg(media_path_str)
-            .spawn()
-            .map_err(|e| AppError::ExternalApp(format!("Failed to open in {app_name}: {e}")))?;
+        windows_paths
+            .iter()
+            .find(|exe_path| std::path::Path::new(exe_path).exists())
+            .map(|&exe_path| exe_path.to_owned())
+            .ok_or_else(|| not_installed(app_name))
     }
 
-    #[cfg(target_os = "windows")]
+    #[cfg(target_os = "linux")]
     {
-        let mut launched = false;
-        for exe_path in windows_paths {
-            if std::path::Path::new(exe_path).exists() {
-                Command::new(exe_path)
-                    .arg(media_path_str)
-                    .spawn()
-                    .map_err(|e| {
-                        AppError::ExternalApp(format!("Failed to open in {app_name}: {e}"))
-                    })?;
-                launched = true;
-                break;
-            }
+        match linux_path {
+            Some(path) if std::path::Path::new(path).exists() => Ok(path.to_owned()),
+            Some(_) => Err(not_installed(app_name)),
+            None => Err(AppError::ExternalApp(format!(
+                "{app_name} not supported on Linux"
+            ))),
         }
+    }
 
-        if !launched {
-            return Err(AppError::ExternalApp(format!(
-                "{app_name} not found. Please ensure it's installed."
-            )));
+    #[cfg(not(any(target_os = "windows", target_os = "linux")))]
+    {
+        if is_application_installed(app_name) {
+            Ok(app_name.to_owned())
+        } else {
+            Err(not_installed(app_name))
         }
     }
+}
 
-    #[cfg(target_os = "linux")]
-    {
-        if let Some(path) = linux_path {
-            if std::path::Path::new(path).exists() {
-                Command::new(path)
-                    .arg(media_path_str)
-                    .spawn()
-                    .map_err(|e| {
-                        AppError::ExternalApp(format!("Failed to open in {app_name}: {e}"))
-                    })?;
-            } else {
-                return Err(AppError::ExternalApp(format!(
-                    "{app_name} not found. Please ensure it's installed."
-                )));
-            }
+/// Whether the application named by `app_identifier` is installed: an existing
+/// path, a macOS bundle id or application name, or an executable on `PATH`.
+#[tauri::command]
+pub fn is_application_installed(app_identifier: &str) -> bool {
+    let app_identifier = app_identifier.trim();
+    !app_identifier.is_empty()
+        && (std::path::Path::new(app_identifier).exists() || is_registered_app(app_identifier))
+}
+
+/// Look an application up by bundle id (through Spotlight) or by `.app` name in
+/// the usual Applications folders, including vendor subfolders such as
+/// `/Applications/Adobe Lightroom Classic/`.
+#[cfg(target_os = "macos")]
+fn is_registered_app(app_identifier: &str) -> bool {
+    if is_bundle_id(app_identifier) {
+        return Command::new("mdfind")
+            .arg(format!("kMDItemCFBundleIdentifier == '{app_identifier}'"))
+            .output()
+            .is_ok_and(|output| {
+                output.status.success() && output.stdout.iter().any(|b| !b.is_ascii_whitespace())
+            });
+    }
+
+    let name = app_identifier
+        .strip_suffix(".app")
+        .unwrap_or(app_identifier);
+    let bundle = format!("{name}.app");
+    let home_apps = crate::modules::file_utils::get_home_dir()
+        .ok()
+        .map(|home| home.join("Applications"));
+
+    [
+        "/Applications",
+        "/Applications/Utilities",
+        "/System/Applications",
+    ]
+    .into_iter()
+    .map(PathBuf::from)
+    .chain(home_apps)
+    .any(|dir| dir.join(&bundle).exists() || dir.join(name).join(&bundle).exists())
+}
+
+/// Look an executable up on `PATH`.
+#[cfg(not(target_os = "macos"))]
+fn is_registered_app(app_identifier: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path_var| find_in_path(app_identifier, &path_var).is_some())
+}
+
+/// First file named `program` (or `program.exe` on Windows) in the directories
+/// of a `PATH`-style list.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn find_in_path(program: &str, path_var: &std::ffi::OsStr) -> Option<PathBuf> {
+    std::env::split_paths(path_var)
+        .flat_map(|dir| {
+            let exe = cfg!(windows).then(|| dir.join(format!("{program}.exe")));
+            std::iter::once(dir.join(program)).chain(exe)
+        })
+        .find(|candidate| candidate.is_file())
+}
+
+/// Program and arguments that open a set of files in an application.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LaunchCommand {
+    program: OsString,
+    args: Vec<OsString>,
+}
+
+/// Whether `app_identifier` looks like a macOS bundle id (`com.adobe.Photoshop`)
+/// rather than an application name or path.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn is_bundle_id(app_identifier: &str) -> bool {
+    !app_identifier.contains(['/', '\\', ' '])
+        && !app_identifier.to_lowercase().ends_with(".app")
+        && app_identifier
+            .split('.')
+            .filter(|part| !part.is_empty())
+            .count()
+            >= 3
+}
+
+/// Build the single launch that opens all of `paths` in `app_identifier`.
+///
+/// On macOS this goes through `open`, by bundle id (`-b`) or by application name
+/// or path (`-a`); elsewhere the identifier is the executable itself.
+fn launch_command(app_identifier: &str, paths: &[PathBuf]) -> LaunchCommand {
+    let files = paths.iter().map(|path| path.as_os_str().to_owned());
+
+    if cfg!(target_os = "macos") {
+        let flag = if is_bundle_id(app_identifier) {
+            "-b"
         } else {
-            return Err(AppError::ExternalApp(format!(
-                "{app_name} not supported on Linux"
-            )));
+            "-a"
+        };
+        LaunchCommand {
+            program: "open".into(),
+            args: [flag.into(), app_identifier.into()]
+                .into_iter()
+                .chain(files)
+                .collect(),
+        }
+    } else {
+        LaunchCommand {
+            program: app_identifier.into(),
+            args: files.collect(),
         }
     }
+}
 
-    Ok(())
+/// Start `command` as a detached background process.
+fn spawn_launch(command: &LaunchCommand) -> std::io::Result<()> {
+    Command::new(&command.program)
+        .args(&command.args)
+        .spawn()
+        .map(drop)
+}
+
+/// Open `paths` in the application named by `app_identifier`, handing the built
+/// command to `launch`. `app_name` is only used in error messages.
+fn open_with<L>(
+    app_identifier: &str,
+    app_name: &str,
+    paths: &[PathBuf],
+    launch: L,
+) -> Result<(), AppError>
+where
+    L: FnOnce(&LaunchCommand) -> std::io::Result<()>,
+{
+    if app_identifier.trim().is_empty() {
+        return Err(AppError::ExternalApp("No application given".to_owned()));
+    }
+    if paths.is_empty() {
+        return Err(AppError::ExternalApp("No files to open".to_owned()));
+    }
+    if let Some(missing) = paths.iter().find(|path| !path.exists()) {
+        return Err(AppError::ExternalApp(format!(
+            "File not found: {}",
+            missing.display()
+        )));
+    }
+
+    launch(&launch_command(app_identifier, paths))
+        .map_err(|e| AppError::ExternalApp(format!("Failed to open in {app_name}: {e}")))
+}
+
+/// Open files in any application, identified by macOS bundle id
+/// (`com.adobe.Photoshop`), application name (`Capture One`) or executable path.
+#[tauri::command]
+pub fn open_in_application(app_identifier: &str, paths: Vec<String>) -> Result<(), String> {
+    if !is_application_installed(app_identifier) {
+        return Err(not_installed(app_identifier).into());
+    }
+    let paths: Vec<PathBuf> = paths.into_iter().map(PathBuf::from).collect();
+    open_with(app_identifier, app_identifier, &paths, spawn_launch).map_err(String::from)
 }
 
 /// Reveal a file or folder in the OS file manager (Finder / Explorer / xdg-open).
@@ -154,52 +321,90 @@ pub fn reveal_in_finder(path: &str) -> Result<(), String> {
     Ok(())
 }
 
-/// Open the project's `RAW/Photos` folder in Adobe Lightroom Classic.
+/// Reveal a project's folder in the OS file manager.
+#[tauri::command]
+pub async fn reveal_project_folder(
+    db: tauri::State<'_, Database>,
+    project_id: String,
+) -> Result<(), String> {
+    reveal_project_folder_with(&db, &project_id, reveal_in_finder)
+}
+
+/// Look up the project's folder and hand it to `reveal`, failing with a clear
+/// message when the folder no longer exists on disk.
+fn reveal_project_folder_with<R>(db: &Database, project_id: &str, reveal: R) -> Result<(), String>
+where
+    R: FnOnce(&str) -> Result<(), String>,
+{
+    let project = get_project_by_id(db, project_id)?;
+    if !std::path::Path::new(&project.folder_path).is_dir() {
+        return Err(format!("Project folder not found: {}", project.folder_path));
+    }
+    reveal(&project.folder_path)
+}
+
+/// Open the selected files, or the project's `RAW/Photos` folder, in Adobe
+/// Lightroom Classic.
 #[tauri::command]
-pub fn open_in_lightroom(path: &str) -> Result<(), String> {
+pub fn open_in_lightroom(path: &str, paths: Option<Vec<String>>) -> Result<(), String> {
+    let selected = paths.unwrap_or_default();
     #[cfg(target_os = "windows")]
-    let paths = LIGHTROOM_PATHS;
+    let windows_paths = LIGHTROOM_PATHS;
     #[cfg(not(target_os = "windows"))]
-    let paths = &[];
+    let windows_paths = &[];
 
-    open_in_external_app(path, "Photos", "Adobe Lightroom Classic", paths, None)
-        .map_err(String::from)
+    open_in_external_app(
+        path,
+        &selected,
+        "Photos",
+        "Adobe Lightroom Classic",
+        windows_paths,
+        None,
+    )
+    .map_err(String::from)
 }
 
-/// Open the project's `RAW/Photos` folder in `AfterShoot`.
+/// Open the selected files, or the project's `RAW/Photos` folder, in `AfterShoot`.
 #[tauri::command]
-pub fn open_in_aftershoot(path: &str) -> Result<(), String> {
+pub fn open_in_aftershoot(path: &str, paths: Option<Vec<String>>) -> Result<(), String> {
+    let selected = paths.unwrap_or_default();
     #[cfg(target_os = "windows")]
-    let paths = AFTERSHOOT_PATHS;
+    let windows_paths = AFTERSHOOT_PATHS;
     #[cfg(not(target_os = "windows"))]
-    let paths = &[];
+    let windows_paths = &[];
 
-    open_in_external_app(path, "Photos", "AfterShoot", paths, None).map_err(String::from)
+    open_in_external_app(path, &selected, "Photos", "AfterShoot", windows_paths, None)
+        .map_err(String::from)
 }
 
-/// Open the project's `RAW/Videos` folder in `DaVinci` Resolve.
+/// Open the selected files, or the project's `RAW/Videos` folder, in `DaVinci`
+/// Resolve.
 #[tauri::command]
-pub fn open_in_davinci_resolve(path: &str) -> Result<(), String> {
+pub fn open_in_davinci_resolve(path: &str, paths: Option<Vec<String>>) -> Result<(), String> {
+    let selected = paths.unwrap_or_default();
     #[cfg(target_os = "windows")]
-    let paths = DAVINCI_RESOLVE_PATHS;
+    let windows_paths = DAVINCI_RESOLVE_PATHS;
     #[cfg(not(target_os = "windows"))]
-    let paths = &[];
+    let windows_paths = &[];
 
     open_in_external_app(
         path,
+        &selected,
         "Videos",
         "DaVinci Resolve",
-        paths,
+        windows_paths,
         Some("/opt/resolve/bin/resolve"),
     )
     .map_err(String::from)
 }
 
-/// Open the project's `RAW/Videos` folder in Final Cut Pro (macOS only).
+/// Open the selected files, or the project's `RAW/Videos` folder, in Final Cut Pro
+/// (macOS only).
 #[tauri::command]
-pub fn open_in_final_cut_pro(path: &str) -> Result<(), String> {
+pub fn open_in_final_cut_pro(path: &str, paths: Option<Vec<String>>) -> Result<(), String> {
     open_in_external_app(
         path,
+        &paths.unwrap_or_default(),
         "Videos",
         "Final Cut Pro",
         &[],
@@ -208,6 +413,162 @@ pub fn open_in_final_cut_pro(path: &str) -> Result<(), String> {
     .map_err(String::from)
 }
 
+/// Move a file or folder to the Trash (macOS), Recycle Bin (Windows) or the
+/// freedesktop.org trash (Linux), so it can still be recovered by the user.
+///
+/// # Errors
+///
+/// Returns error if the platform refuses the move, e.g. for a folder on a drive
+/// without a trash
+pub fn move_to_trash(path: &std::path::Path) -> Result<(), String> {
+    if !path.exists() {
+        return Err(format!("{} does not exist", path.display()));
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        // Finder's delete is what puts items in the Trash with "Put Back" support
+        let output = Command::new("osascript")
+            .args([
+                "-e",
+                "on run argv",
+                "-e",
+                "tell application \"Finder\" to delete POSIX file (item 1 of argv)",
+                "-e",
+                "end run",
+            ])
+            .arg(path)
+            .output()
+            .map_err(|e| format!("Failed to move to Trash: {e}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to move to Trash: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+    }
+
+    #[cfg(target_os = "windows")]
+    {
+        // The path goes through the environment so no quoting is needed
+        let script = "Add-Type -AssemblyName Microsoft.VisualBasic; \
+            $p = $env:CREATOROPS_TRASH_PATH; \
+            if (Test-Path -LiteralPath $p -PathType Container) { \
+            [Microsoft.VisualBasic.FileIO.FileSystem]::DeleteDirectory($p, 'OnlyErrorDialogs', 'SendToRecycleBin') \
+            } else { \
+            [Microsoft.VisualBasic.FileIO.FileSystem]::DeleteFile($p, 'OnlyErrorDialogs', 'SendToRecycleBin') }";
+        let output = Command::new("powershell")
+            .args(["-NoProfile", "-NonInteractive", "-Command", script])
+            .env("CREATOROPS_TRASH_PATH", path)
+            .output()
+            .map_err(|e| format!("Failed to move to Recycle Bin: {e}"))?;
+        if !output.status.success() {
+            return Err(format!(
+                "Failed to move to Recycle Bin: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+    }
+
+    #[cfg(target_os = "linux")]
+    {
+        let data_home = std::env::var_os("XDG_DATA_HOME")
+            .filter(|dir| !dir.is_empty())
+            .map(std::path::PathBuf::from)
+            .map_or_else(
+                || {
+                    crate::modules::file_utils::get_home_dir()
+                        .map(|home| home.join(".local").join("share"))
+                        .map_err(|e| e.to_string())
+                },
+                Ok,
+            )?;
+        trash_into(path, &data_home.join("Trash"))?;
+    }
+
+    Ok(())
+}
+
+/// Move `path` into the freedesktop.org trash at `trash_dir`, writing the
+/// `.trashinfo` file file managers use to restore it.
+#[cfg(target_os = "linux")]
+fn trash_into(path: &std::path::Path, trash_dir: &std::path::Path) -> Result<(), String> {
+    use std::io::Write;
+
+    let absolute = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir()
+            .map_err(|e| e.to_string())?
+            .join(path)
+    };
+    let name = absolute
+        .file_name()
+        .ok_or_else(|| format!("Cannot trash {}", absolute.display()))?
+        .to_string_lossy()
+        .to_string();
+    let files_dir = trash_dir.join("files");
+    let info_dir = trash_dir.join("info");
+    std::fs::create_dir_all(&files_dir).map_err(|e| e.to_string())?;
+    std::fs::create_dir_all(&info_dir).map_err(|e| e.to_string())?;
+
+    // Reserve a free name by creating its info file exclusively
+    let mut counter = 1;
+    let (trashed_name, mut info_file) = loop {
+        let candidate = if counter == 1 {
+            name.clone()
+        } else {
+            format!("{name}.{counter}")
+        };
+        let info_path = info_dir.join(format!("{candidate}.trashinfo"));
+        if !files_dir.join(&candidate).exists() {
+            match std::fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&info_path)
+            {
+                Ok(file) => break (candidate, file),
+                Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {}
+                Err(e) => return Err(e.to_string()),
+            }
+        }
+        counter += 1;
+    };
+    let info_path = info_dir.join(format!("{trashed_name}.trashinfo"));
+
+    let info = format!(
+        "[Trash Info]\nPath={}\nDeletionDate={}\n",
+        percent_encode_path(&absolute.to_string_lossy()),
+        chrono::Local::now().format("%Y-%m-%dT%H:%M:%S")
+    );
+    let result = info_file
+        .write_all(info.as_bytes())
+        .map_err(|e| e.to_string())
+        .and_then(|()| {
+            std::fs::rename(&absolute, files_dir.join(&trashed_name))
+                .map_err(|e| format!("Failed to move {} to the trash: {e}", absolute.display()))
+        });
+    if result.is_err() {
+        let _ = std::fs::remove_file(&info_path);
+    }
+    result
+}
+
+/// Percent-encode a path for a `.trashinfo` file, keeping `/` separators.
+#[cfg(target_os = "linux")]
+fn percent_encode_path(path: &str) -> String {
+    use std::fmt::Write;
+
+    path.bytes().fold(String::new(), |mut encoded, byte| {
+        if byte.is_ascii_alphanumeric() || b"/-_.~".contains(&byte) {
+            encoded.push(char::from(byte));
+        } else {
+            let _ = write!(encoded, "%{byte:02X}");
+        }
+        encoded
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -231,7 +592,7 @@ mod tests {
         let project_path = temp_dir.to_str().unwrap();
 
         // Test with non-existent subdirectory
-        let result = open_in_external_app(project_path, "NonExistent", "TestApp", &[], None);
+        let result = open_in_external_app(project_path, &[], "NonExistent", "TestApp", &[], None);
 
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
@@ -259,7 +620,7 @@ mod tests {
         std::fs::create_dir_all(temp_dir.path().join("RAW").join("Photos")).unwrap();
 
         let project_path = temp_dir.path().to_str().unwrap();
-        let result = open_in_external_app(project_path, "Photos", "TestApp", &[], None);
+        let result = open_in_external_app(project_path, &[], "Photos", "TestApp", &[], None);
 
         // Should validate path exists (error occurs when trying to spawn)
         assert!(result.is_err() || result.is_ok());
@@ -292,7 +653,7 @@ mod tests {
     #[test]
     fn test_open_in_lightroom_validates_path() {
         let temp_dir = TempDir::new().unwrap();
-        let result = open_in_lightroom(&temp_dir.path().to_string_lossy());
+        let result = open_in_lightroom(&temp_dir.path().to_string_lossy(), None);
 
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("not found"));
@@ -301,7 +662,7 @@ mod tests {
     #[test]
     fn test_open_in_aftershoot_validates_path() {
         let temp_dir = TempDir::new().unwrap();
-        let result = open_in_aftershoot(&temp_dir.path().to_string_lossy());
+        let result = open_in_aftershoot(&temp_dir.path().to_string_lossy(), None);
 
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("not found"));
@@ -310,7 +671,7 @@ mod tests {
     #[test]
     fn test_open_in_davinci_resolve_validates_path() {
         let temp_dir = TempDir::new().unwrap();
-        let result = open_in_davinci_resolve(&temp_dir.path().to_string_lossy());
+        let result = open_in_davinci_resolve(&temp_dir.path().to_string_lossy(), None);
 
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("not found"));
@@ -319,7 +680,7 @@ mod tests {
     #[test]
     fn test_open_in_final_cut_pro_validates_path() {
         let temp_dir = TempDir::new().unwrap();
-        let result = open_in_final_cut_pro(&temp_dir.path().to_string_lossy());
+        let result = open_in_final_cut_pro(&temp_dir.path().to_string_lossy(), None);
 
         assert!(result.is_err());
         assert!(result.unwrap_err().contains("not found"));
@@ -336,6 +697,56 @@ mod tests {
         assert!(result.is_ok() || result.is_err());
     }
 
+    fn create_test_project(temp_dir: &TempDir, db: &Database) -> crate::modules::project::Project {
+        crate::modules::project::create_project_impl(
+            db,
+            &temp_dir.path().join("Projects"),
+            crate::modules::project::NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                shoot_type: "Wedding".to_owned(),
+                ..crate::modules::project::NewProject::default()
+            },
+            false,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_reveal_project_folder_passes_stored_folder() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+        let project = create_test_project(&temp_dir, &db);
+
+        let mut revealed = None;
+        reveal_project_folder_with(&db, &project.id, |path| {
+            revealed = Some(path.to_owned());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(revealed.as_deref(), Some(project.folder_path.as_str()));
+    }
+
+    #[test]
+    fn test_reveal_project_folder_missing_folder_or_project() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = Database::new_with_path(&temp_dir.path().join("test.db")).unwrap();
+        let project = create_test_project(&temp_dir, &db);
+        std::fs::remove_dir_all(&project.folder_path).unwrap();
+
+        let reveal_unused = |_: &str| -> Result<(), String> { Ok(()) };
+        let err = reveal_project_folder_with(&db, &project.id, reveal_unused).unwrap_err();
+        assert_eq!(
+            err,
+            format!("Project folder not found: {}", project.folder_path)
+        );
+
+        let err = reveal_project_folder_with(&db, "no-such-id", reveal_unused).unwrap_err();
+        assert!(err.contains("Project not found"));
+    }
+
     #[cfg(windows)]
     #[test]
     fn test_windows_lightroom_paths() {
@@ -365,6 +776,7 @@ mod tests {
 
         let result = open_in_external_app(
             temp_dir.path().to_str().unwrap(),
+            &[],
             "Photos",
             "TestApp",
             &[],
@@ -374,4 +786,218 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("not found"));
     }
+
+    #[test]
+    fn test_move_to_trash_missing_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let result = move_to_trash(&temp_dir.path().join("missing"));
+
+        assert!(result.unwrap_err().contains("does not exist"));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn test_trash_into_writes_info_and_avoids_collisions() {
+        let temp_dir = TempDir::new().unwrap();
+        let trash_dir = temp_dir.path().join("Trash");
+
+        for _ in 0..2 {
+            let project = temp_dir.path().join("Smith Wedding");
+            std::fs::create_dir_all(&project).unwrap();
+            std::fs::write(project.join("IMG_0001.CR3"), b"raw").unwrap();
+            trash_into(&project, &trash_dir).unwrap();
+            assert!(!project.exists());
+        }
+
+        let files = trash_dir.join("files");
+        assert!(files.join("Smith Wedding").join("IMG_0001.CR3").exists());
+        assert!(files.join("Smith Wedding.2").join("IMG_0001.CR3").exists());
+
+        let info = std::fs::read_to_string(trash_dir.join("info").join("Smith Wedding.trashinfo"))
+            .unwrap();
+        assert!(info.starts_with("[Trash Info]\n"));
+        assert!(info.contains("/Smith%20Wedding\n"));
+        assert!(info.contains("DeletionDate="));
+    }
+
+    #[test]
+    fn test_launch_command_passes_every_path_once() {
+        let paths = vec![
+            PathBuf::from("/photos/IMG_0001.CR3"),
+            PathBuf::from("/photos/IMG_0002.CR3"),
+        ];
+        let command = launch_command("/opt/editor/bin/editor", &paths);
+
+        if cfg!(target_os = "macos") {
+            assert_eq!(command.program, "open");
+            assert_eq!(
+                command.args,
+                [
+                    "-a",
+                    "/opt/editor/bin/editor",
+                    "/photos/IMG_0001.CR3",
+                    "/photos/IMG_0002.CR3"
+                ]
+            );
+        } else {
+            assert_eq!(command.program, "/opt/editor/bin/editor");
+            assert_eq!(
+                command.args,
+                ["/photos/IMG_0001.CR3", "/photos/IMG_0002.CR3"]
+            );
+        }
+    }
+
+    #[test]
+    fn test_is_bundle_id() {
+        assert!(is_bundle_id("com.adobe.Photoshop"));
+        assert!(is_bundle_id("com.captureone.captureone16"));
+        assert!(!is_bundle_id("Capture One"));
+        assert!(!is_bundle_id("Adobe Bridge.app"));
+        assert!(!is_bundle_id("/Applications/Photoshop.app"));
+        assert!(!is_bundle_id("photoshop"));
+    }
+
+    #[test]
+    fn test_open_with_launches_once_for_all_files() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths: Vec<PathBuf> = ["a.jpg", "b.jpg", "c.jpg"]
+            .iter()
+            .map(|name| {
+                let path = temp_dir.path().join(name);
+                std::fs::write(&path, b"jpg").unwrap();
+                path
+            })
+            .collect();
+
+        let mut launches = Vec::new();
+        open_with("com.adobe.Photoshop", "Photoshop", &paths, |command| {
+            launches.push(command.clone());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(launches.len(), 1);
+        let args = &launches[0].args;
+        assert_eq!(
+            &args[args.len() - 3..],
+            paths.iter().map(|p| p.as_os_str()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_open_with_rejects_missing_files_and_reports_launch_errors() {
+        let temp_dir = TempDir::new().unwrap();
+        let present = temp_dir.path().join("a.jpg");
+        std::fs::write(&present, b"jpg").unwrap();
+        let missing = temp_dir.path().join("b.jpg");
+
+        // Validation fails before anything is launched
+        let launch_unused = |_: &LaunchCommand| -> std::io::Result<()> { Ok(()) };
+        let err = open_with(
+            "Photoshop",
+            "Photoshop",
+            &[present.clone(), missing],
+            launch_unused,
+        );
+        assert!(err.unwrap_err().to_string().contains("File not found"));
+        let err = open_with("Photoshop", "Photoshop", &[], launch_unused);
+        assert!(err.unwrap_err().to_string().contains("No files"));
+
+        let err = open_with("Photoshop", "Photoshop", &[present], |_| {
+            Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+        });
+        assert!(err
+            .unwrap_err()
+            .to_string()
+            .contains("Failed to open in Photoshop"));
+    }
+
+    #[test]
+    fn test_is_application_installed() {
+        let current_exe = std::env::current_exe().unwrap();
+        assert!(is_application_installed(&current_exe.to_string_lossy()));
+
+        assert!(!is_application_installed(
+            "com.example.DefinitelyMissing7f3c"
+        ));
+        assert!(!is_application_installed("Definitely Missing App 7f3c"));
+        assert!(!is_application_installed("   "));
+    }
+
+    #[test]
+    fn test_find_in_path() {
+        let temp_dir = TempDir::new().unwrap();
+        let bin = temp_dir.path().join("bin");
+        std::fs::create_dir_all(&bin).unwrap();
+        std::fs::write(bin.join("darktable"), b"").unwrap();
+        let path_var = std::env::join_paths([temp_dir.path().join("empty"), bin.clone()]).unwrap();
+
+        assert_eq!(
+            find_in_path("darktable", &path_var),
+            Some(bin.join("darktable"))
+        );
+        assert_eq!(find_in_path("rawtherapee", &path_var), None);
+    }
+
+    #[test]
+    fn test_open_in_application_rejects_missing_app() {
+        let temp_dir = TempDir::new().unwrap();
+        let photo = temp_dir.path().join("a.jpg");
+        std::fs::write(&photo, b"jpg").unwrap();
+
+        let err = open_in_application(
+            "Definitely Missing App 7f3c",
+            vec![photo.to_string_lossy().to_string()],
+        )
+        .unwrap_err();
+        assert_eq!(err, "Definitely Missing App 7f3c is not installed");
+    }
+
+    #[test]
+    fn test_editor_targets_prefers_selection() {
+        let temp_dir = TempDir::new().unwrap();
+        let project_path = temp_dir.path().to_str().unwrap();
+        std::fs::create_dir_all(temp_dir.path().join("RAW").join("Photos")).unwrap();
+
+        let folder = editor_targets(project_path, &[], "Photos").unwrap();
+        assert_eq!(folder, vec![temp_dir.path().join("RAW").join("Photos")]);
+
+        let selected = vec![
+            "/shoot/IMG_0001.CR3".to_owned(),
+            "/shoot/IMG_0002.CR3".to_owned(),
+        ];
+        let targets = editor_targets(project_path, &selected, "Photos").unwrap();
+        assert_eq!(
+            targets,
+            vec![PathBuf::from(&selected[0]), PathBuf::from(&selected[1])]
+        );
+    }
+
+    #[test]
+    fn test_multi_file_selection_is_one_launch() {
+        let temp_dir = TempDir::new().unwrap();
+        let selected: Vec<String> = (1..=200)
+            .map(|n| {
+                let path = temp_dir.path().join(format!("IMG_{n:04}.CR3"));
+                std::fs::write(&path, b"raw").unwrap();
+                path.to_string_lossy().to_string()
+            })
+            .collect();
+        let targets = editor_targets("/unused", &selected, "Photos").unwrap();
+
+        let mut launches = Vec::new();
+        open_with("/opt/lightroom", "Lightroom", &targets, |command| {
+            launches.push(command.clone());
+            Ok(())
+        })
+        .unwrap();
+
+        assert_eq!(launches.len(), 1);
+        let files = &launches[0].args[launches[0].args.len() - 200..];
+        assert!(files
+            .iter()
+            .zip(&selected)
+            .all(|(arg, path)| arg == path.as_str()));
+    }
 }
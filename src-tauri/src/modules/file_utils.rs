@@ -1,12 +1,18 @@
 //! Shared file-system utilities used across multiple modules.
 //!
-//! Provides SHA-256 hashing, recursive directory traversal, home-directory
-//! resolution (cross-platform), and timestamp helpers.
+//! Provides SHA-256 hashing, recursive directory traversal, glob and
+//! `.creatoropsignore` matching, home-directory resolution (cross-platform),
+//! destination writability checks, crash-safe JSON persistence, timestamp
+//! helpers and a moving-average transfer rate.
 
 use crate::error::AppError;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use sha2::{Digest, Sha256};
+use std::collections::VecDeque;
 use std::fs;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use tokio::io::AsyncReadExt;
 
 const CHUNK_SIZE: usize = 4 * 1024 * 1024; // 4MB chunks
@@ -28,20 +34,18 @@ pub async fn calculate_file_hash(path: &Path) -> Result<String, AppError> {
         hasher.update(&buffer[..bytes_read]);
     }
 
-    let hash = hasher.finalize();
-    let hex = hash.iter().fold(String::with_capacity(64), |mut s, b| {
-        use std::fmt::Write as _;
-        write!(s, "{b:02x}").ok();
-        s
-    });
-    Ok(hex)
+    Ok(hex_encode(&hasher.finalize()))
 }
 
-/// Verify file integrity using SHA-256 checksum
-pub async fn verify_checksum(src: &Path, dest: &Path) -> Result<bool, AppError> {
-    let src_hash = calculate_file_hash(src).await?;
-    let dest_hash = calculate_file_hash(dest).await?;
-    Ok(src_hash == dest_hash)
+/// Lowercase hex representation of a digest.
+pub fn hex_encode(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .fold(String::with_capacity(bytes.len() * 2), |mut s, b| {
+            use std::fmt::Write as _;
+            write!(s, "{b:02x}").ok();
+            s
+        })
 }
 
 /// Recursively collect all files in a directory
@@ -67,20 +71,341 @@ pub fn collect_files_recursive(path: &Path) -> Result<Vec<PathBuf>, AppError> {
     Ok(files)
 }
 
+/// Whether the path `segments` match the glob `pattern` segments, where a `**`
+/// segment stands for any number of folders.
+pub fn glob_matches(pattern: &[&str], segments: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => segments.is_empty(),
+        Some((&"**", rest)) => {
+            (0..=segments.len()).any(|skip| glob_matches(rest, &segments[skip..]))
+        }
+        Some((glob, rest)) => segments.split_first().is_some_and(|(name, remaining)| {
+            let is_file_name = remaining.is_empty();
+            segment_matches(glob, name, is_file_name) && glob_matches(rest, remaining)
+        }),
+    }
+}
+
+/// Match one path segment, comparing a file name's extension without case.
+fn segment_matches(glob: &str, name: &str, is_file_name: bool) -> bool {
+    if is_file_name {
+        if let (Some((glob_stem, glob_ext)), Some((stem, ext))) =
+            (glob.rsplit_once('.'), name.rsplit_once('.'))
+        {
+            return wildcard_matches(glob_stem, stem)
+                && wildcard_matches(&glob_ext.to_lowercase(), &ext.to_lowercase());
+        }
+    }
+    wildcard_matches(glob, name)
+}
+
+/// Match `text` against a pattern where `*` is any run of characters and `?` is
+/// any single character.
+fn wildcard_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    // Last `*` seen and the text position it currently extends to
+    let mut star: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                star = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match star {
+                Some((star_p, star_t)) => {
+                    p = star_p + 1;
+                    t = star_t + 1;
+                    star = Some((star_p, t));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+/// Name of the file in a source folder listing patterns its backups skip.
+pub const IGNORE_FILE_NAME: &str = ".creatoropsignore";
+
+/// Patterns from a [`IGNORE_FILE_NAME`] file, in the style of `.gitignore`.
+///
+/// Each line is a glob; blank lines and `#` comments are skipped. A pattern
+/// without a `/` matches a file or folder of that name at any depth, while one
+/// containing a `/` is matched from the source root. A trailing `/` limits a
+/// pattern to folders, and a leading `!` re-includes what an earlier line
+/// excluded. The last matching line wins, and nothing inside an excluded
+/// folder can be re-included.
+#[derive(Debug, Default)]
+pub struct IgnoreRules {
+    patterns: Vec<IgnorePattern>,
+}
+
+#[derive(Debug)]
+struct IgnorePattern {
+    segments: Vec<String>,
+    negated: bool,
+    dir_only: bool,
+}
+
+impl IgnoreRules {
+    /// Parse the contents of an ignore file.
+    pub fn parse(contents: &str) -> Self {
+        let patterns = contents
+            .lines()
+            .filter_map(|line| {
+                let line = line.trim();
+                if line.is_empty() || line.starts_with('#') {
+                    return None;
+                }
+                let (negated, line) = line
+                    .strip_prefix('!')
+                    .map_or((false, line), |rest| (true, rest));
+                let (dir_only, line) = line
+                    .strip_suffix('/')
+                    .map_or((false, line), |rest| (true, rest));
+                let anchored = line.contains('/');
+                let mut segments: Vec<String> = line
+                    .split('/')
+                    .filter(|segment| !segment.is_empty() && *segment != ".")
+                    .map(str::to_owned)
+                    .collect();
+                if segments.is_empty() {
+                    return None;
+                }
+                if !anchored {
+                    segments.insert(0, "**".to_owned());
+                }
+                Some(IgnorePattern {
+                    segments,
+                    negated,
+                    dir_only,
+                })
+            })
+            .collect();
+        Self { patterns }
+    }
+
+    /// Read the ignore file at the root of `source`. A source without one, or
+    /// one that is a single file, ignores nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the ignore file exists but can't be read
+    pub fn load(source: &Path) -> Result<Self, AppError> {
+        let ignore_file = source.join(IGNORE_FILE_NAME);
+        if !ignore_file.is_file() {
+            return Ok(Self::default());
+        }
+        Ok(Self::parse(&fs::read_to_string(ignore_file)?))
+    }
+
+    /// Whether the file at `relative` (a path below the source root) is excluded,
+    /// either by name or because one of its folders is.
+    pub fn is_ignored(&self, relative: &Path) -> bool {
+        let segments: Vec<String> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+        (1..=segments.len()).any(|depth| self.excludes(&segments[..depth], depth < segments.len()))
+    }
+
+    /// Whether the last pattern matching `segments` excludes it.
+    fn excludes(&self, segments: &[&str], is_dir: bool) -> bool {
+        self.patterns
+            .iter()
+            .rev()
+            .find(|pattern| {
+                let glob: Vec<&str> = pattern.segments.iter().map(String::as_str).collect();
+                (is_dir || !pattern.dir_only) && glob_matches(&glob, segments)
+            })
+            .is_some_and(|pattern| !pattern.negated)
+    }
+}
+
+/// Recursively collect the files under `source`, leaving out those excluded by
+/// its [`IGNORE_FILE_NAME`] file.
+///
+/// # Errors
+///
+/// Returns error if the folder can't be listed or its ignore file can't be read
+pub fn collect_unignored_files(source: &Path) -> Result<Vec<PathBuf>, AppError> {
+    let rules = IgnoreRules::load(source)?;
+    let mut files = collect_files_recursive(source)?;
+    files.retain(|file| {
+        file.strip_prefix(source)
+            .map_or(true, |relative| !rules.is_ignored(relative))
+    });
+    Ok(files)
+}
+
 /// Count files and calculate total size in bytes under a directory path.
 type FileSizeResult = Result<(usize, u64), AppError>;
 
 pub fn count_files_and_size(path: &str) -> FileSizeResult {
-    let files = collect_files_recursive(Path::new(path))?;
-    let mut total_size = 0_u64;
+    count_files_and_size_with_progress(path, |_, _| {})
+}
 
-    for file in &files {
-        if let Ok(metadata) = fs::metadata(file) {
-            total_size += metadata.len();
+/// Count files and total size under a directory path, calling `on_progress`
+/// with the running file count and byte total after each file.
+pub fn count_files_and_size_with_progress<F>(path: &str, mut on_progress: F) -> FileSizeResult
+where
+    F: FnMut(usize, u64),
+{
+    let mut totals = (0, 0);
+    tally_files(Path::new(path), &mut totals, &mut on_progress)?;
+    Ok(totals)
+}
+
+/// Add every file under `path` to `totals` while walking the tree, so progress
+/// is reported before the whole tree has been listed.
+fn tally_files<F>(
+    path: &Path,
+    totals: &mut (usize, u64),
+    on_progress: &mut F,
+) -> Result<(), AppError>
+where
+    F: FnMut(usize, u64),
+{
+    if path.is_file() {
+        totals.0 += 1;
+        totals.1 += fs::metadata(path).map_or(0, |metadata| metadata.len());
+        on_progress(totals.0, totals.1);
+    } else if path.is_dir() {
+        for entry in fs::read_dir(path)? {
+            tally_files(&entry?.path(), totals, on_progress)?;
         }
     }
+    Ok(())
+}
+
+/// Count files and total size under a directory path, leaving out those
+/// excluded by its [`IGNORE_FILE_NAME`] file.
+pub fn count_unignored_files_and_size(path: &str) -> FileSizeResult {
+    let files = collect_unignored_files(Path::new(path))?;
+    Ok((files.len(), total_size(&files)))
+}
+
+/// Combined size in bytes of `files`, skipping any that can't be read.
+fn total_size(files: &[PathBuf]) -> u64 {
+    files
+        .iter()
+        .filter_map(|file| fs::metadata(file).ok())
+        .map(|metadata| metadata.len())
+        .sum()
+}
+
+/// Make sure `path` is a folder the app can write to, creating it if needed.
+///
+/// Jobs check their destination with this before being queued, so a read-only
+/// folder or an unplugged drive is reported right away instead of failing
+/// once the job runs.
+///
+/// # Errors
+///
+/// Returns error if the folder can't be created or a file can't be written in it
+pub fn ensure_writable_dir(path: &Path) -> Result<(), String> {
+    if path.as_os_str().is_empty() {
+        return Err("Destination path is empty".to_owned());
+    }
+
+    fs::create_dir_all(path)
+        .map_err(|e| format!("Cannot create destination {}: {e}", path.display()))?;
+
+    let probe = path.join(format!(".creatorops-write-test-{}", uuid::Uuid::new_v4()));
+    fs::OpenOptions::new()
+        .write(true)
+        .create_new(true)
+        .open(&probe)
+        .map_err(|e| format!("Destination {} is not writable: {e}", path.display()))?;
+    let _ = fs::remove_file(&probe);
+
+    Ok(())
+}
+
+/// `path` with `suffix` appended to its file name, e.g. `queue.json.bak`.
+fn with_suffix(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(suffix);
+    PathBuf::from(name)
+}
+
+/// Write `value` as pretty JSON to `path` without ever leaving it half-written.
+///
+/// The JSON goes to a `.tmp` sibling that is synced and renamed over `path`; the
+/// version it replaces is kept as `.bak` for [`read_json_with_backup`].
+///
+/// # Errors
+///
+/// Returns error if serialization or any file operation fails
+pub fn write_json_atomic<T: Serialize + ?Sized>(path: &Path, value: &T) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    let temp_path = with_suffix(path, ".tmp");
+    let mut file = fs::File::create(&temp_path)?;
+    serde_json::to_writer_pretty(&mut file, value)?;
+    file.sync_all()?;
+    drop(file);
+
+    if path.exists() {
+        fs::rename(path, with_suffix(path, ".bak"))?;
+    }
+    fs::rename(&temp_path, path)
+}
 
-    Ok((files.len(), total_size))
+/// Parse the JSON file at `path`, or `None` if it doesn't exist.
+fn read_json<T: DeserializeOwned>(path: &Path) -> std::io::Result<Option<T>> {
+    match fs::read_to_string(path) {
+        Ok(data) => Ok(Some(serde_json::from_str(&data)?)),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+        Err(e) => Err(e),
+    }
+}
+
+/// Read a file written by [`write_json_atomic`], or `None` if it was never written.
+///
+/// If `path` is missing or corrupt but its `.bak` is readable, the backup is
+/// returned and copied back over `path`.
+///
+/// # Errors
+///
+/// Returns error if neither the file nor its backup can be read and parsed
+pub fn read_json_with_backup<T: DeserializeOwned>(path: &Path) -> std::io::Result<Option<T>> {
+    let primary_error = match read_json(path) {
+        Ok(Some(value)) => return Ok(Some(value)),
+        Ok(None) => None,
+        Err(e) => Some(e),
+    };
+
+    let backup_path = with_suffix(path, ".bak");
+    match read_json(&backup_path) {
+        Ok(Some(value)) => {
+            log::warn!(
+                "Recovered {} from its backup: {}",
+                path.display(),
+                primary_error.map_or_else(|| "file missing".to_owned(), |e| e.to_string())
+            );
+            let temp_path = with_suffix(path, ".tmp");
+            if let Err(e) =
+                fs::copy(&backup_path, &temp_path).and_then(|_| fs::rename(&temp_path, path))
+            {
+                log::warn!("Failed to restore {}: {e}", path.display());
+            }
+            Ok(Some(value))
+        }
+        Ok(None) => primary_error.map_or(Ok(None), Err),
+        Err(e) => Err(primary_error.unwrap_or(e)),
+    }
 }
 
 /// Get home directory (cross-platform)
@@ -128,6 +453,86 @@ pub fn get_timestamp() -> String {
         .to_string()
 }
 
+/// Name an existing file is kept under when the `overwrite_keep_backup`
+/// conflict mode replaces it, e.g. `IMG_0001.jpg.bak-1718000000`.
+pub fn keep_backup_name(file_name: &str, timestamp: &str) -> String {
+    format!("{file_name}.bak-{timestamp}")
+}
+
+/// Span of recent progress that transfer speed is averaged over.
+const SPEED_WINDOW: Duration = Duration::from_secs(3);
+
+/// Transfer speed (bytes/s) averaged over the last few seconds, so the readout
+/// doesn't jump around at the start of a job or lag behind a slowdown at its end.
+#[derive(Debug, Clone)]
+pub struct TransferRate {
+    window: Duration,
+    /// `(time, bytes transferred so far)`, oldest first. The oldest sample is the
+    /// last one taken at or before the window start, so the window stays covered.
+    samples: VecDeque<(Instant, u64)>,
+}
+
+impl Default for TransferRate {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TransferRate {
+    /// Start measuring a transfer that begins now.
+    pub fn new() -> Self {
+        Self::starting_at(Instant::now())
+    }
+
+    fn starting_at(start: Instant) -> Self {
+        Self {
+            window: SPEED_WINDOW,
+            samples: VecDeque::from([(start, 0)]),
+        }
+    }
+
+    /// Record the job's byte count and return the average speed (bytes/s) and
+    /// estimated seconds remaining.
+    pub fn update(&mut self, bytes_transferred: u64, total_bytes: u64) -> (f64, u64) {
+        self.record(Instant::now(), bytes_transferred);
+        (self.speed(), self.eta(total_bytes))
+    }
+
+    fn record(&mut self, now: Instant, bytes_transferred: u64) {
+        self.samples.push_back((now, bytes_transferred));
+        if let Some(window_start) = now.checked_sub(self.window) {
+            while self.samples.len() > 2 && self.samples[1].0 <= window_start {
+                self.samples.pop_front();
+            }
+        }
+    }
+
+    fn speed(&self) -> f64 {
+        let (Some(&(first_time, first_bytes)), Some(&(last_time, last_bytes))) =
+            (self.samples.front(), self.samples.back())
+        else {
+            return 0.0;
+        };
+
+        let elapsed = last_time.duration_since(first_time).as_secs_f64();
+        if elapsed > 0.0 {
+            last_bytes.saturating_sub(first_bytes) as f64 / elapsed
+        } else {
+            0.0
+        }
+    }
+
+    fn eta(&self, total_bytes: u64) -> u64 {
+        let speed = self.speed();
+        let transferred = self.samples.back().map_or(0, |&(_, bytes)| bytes);
+        if speed > 0.0 {
+            (total_bytes.saturating_sub(transferred) as f64 / speed) as u64
+        } else {
+            0
+        }
+    }
+}
+
 /// Expose the home directory path to the frontend as a string.
 #[tauri::command]
 pub fn get_home_directory() -> Result<String, String> {
@@ -142,6 +547,54 @@ mod tests {
     use super::*;
     use std::io::Write;
 
+    #[test]
+    fn test_wildcard_matches() {
+        assert!(wildcard_matches("*", ""));
+        assert!(wildcard_matches("IMG_*_final", "IMG_0001_final"));
+        assert!(wildcard_matches("IMG_????", "IMG_0001"));
+        assert!(!wildcard_matches("IMG_????", "IMG_001"));
+        assert!(wildcard_matches("*a*b", "xxaxxab"));
+        assert!(!wildcard_matches("*a*b", "xxaxxa"));
+    }
+
+    #[test]
+    fn test_ignore_rules() {
+        let rules = IgnoreRules::parse(
+            "# editor leftovers\n\n*.tmp\nCache/\n/exports/drafts\nRAW/**/*.xmp\n!keep.tmp\n",
+        );
+        let ignored = |path: &str| rules.is_ignored(Path::new(path));
+
+        assert!(ignored("render.tmp"));
+        assert!(
+            ignored("RAW/day1/IMG_0001.TMP"),
+            "extensions match without case"
+        );
+        assert!(!ignored("keep.tmp"), "! re-includes a file");
+        assert!(ignored("Cache/previews/p1.jpg"));
+        assert!(
+            ignored("Selects/Cache/p1.jpg"),
+            "unanchored folders match at any depth"
+        );
+        assert!(!ignored("Cache"), "folder patterns don't match files");
+        assert!(ignored("exports/drafts/cut1.mov"));
+        assert!(
+            !ignored("old/exports/drafts/cut1.mov"),
+            "anchored patterns start at the root"
+        );
+        assert!(ignored("RAW/day1/IMG_0001.xmp"));
+        assert!(!ignored("RAW/IMG_0001.CR3"));
+        assert!(!IgnoreRules::default().is_ignored(Path::new("render.tmp")));
+    }
+
+    #[test]
+    fn test_collect_unignored_files_without_ignore_file() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("render.tmp"), b"x").unwrap();
+
+        let files = collect_unignored_files(temp_dir.path()).unwrap();
+        assert_eq!(files, [temp_dir.path().join("render.tmp")]);
+    }
+
     #[tokio::test]
     async fn test_calculate_file_hash() {
         let temp_dir = std::env::temp_dir();
@@ -163,40 +616,6 @@ mod tests {
         std::fs::remove_file(test_file).ok();
     }
 
-    #[tokio::test]
-    async fn test_verify_checksum_matching() {
-        let temp_dir = std::env::temp_dir();
-        let src_file = temp_dir.join("test_src.txt");
-        let dest_file = temp_dir.join("test_dest.txt");
-
-        // Create identical files
-        std::fs::write(&src_file, b"test content").unwrap();
-        std::fs::write(&dest_file, b"test content").unwrap();
-
-        let result = verify_checksum(&src_file, &dest_file).await.unwrap();
-        assert!(result);
-
-        std::fs::remove_file(src_file).ok();
-        std::fs::remove_file(dest_file).ok();
-    }
-
-    #[tokio::test]
-    async fn test_verify_checksum_different() {
-        let temp_dir = std::env::temp_dir();
-        let src_file = temp_dir.join("test_src2.txt");
-        let dest_file = temp_dir.join("test_dest2.txt");
-
-        // Create different files
-        std::fs::write(&src_file, b"test content 1").unwrap();
-        std::fs::write(&dest_file, b"test content 2").unwrap();
-
-        let result = verify_checksum(&src_file, &dest_file).await.unwrap();
-        assert!(!result);
-
-        std::fs::remove_file(src_file).ok();
-        std::fs::remove_file(dest_file).ok();
-    }
-
     #[test]
     fn test_collect_files_recursive() {
         let temp_dir = std::env::temp_dir().join("test_collect");
@@ -328,4 +747,131 @@ mod tests {
 
         std::fs::remove_dir_all(temp_dir).ok();
     }
+
+    #[test]
+    fn test_ensure_writable_dir_creates_missing_folders() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let destination = temp_dir.path().join("Deliveries/Smith Wedding");
+
+        ensure_writable_dir(&destination).unwrap();
+
+        assert!(destination.is_dir());
+        // The probe file is cleaned up
+        assert_eq!(std::fs::read_dir(&destination).unwrap().count(), 0);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_ensure_writable_dir_rejects_read_only_destination() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let read_only = temp_dir.path().join("ReadOnly");
+        std::fs::create_dir(&read_only).unwrap();
+        std::fs::set_permissions(&read_only, std::fs::Permissions::from_mode(0o555)).unwrap();
+
+        // Root ignores directory permissions, so there is nothing to check
+        if std::fs::write(read_only.join("root-check"), b"").is_ok() {
+            return;
+        }
+
+        let err = ensure_writable_dir(&read_only).unwrap_err();
+        assert!(err.contains("is not writable"), "{err}");
+
+        // A folder that would have to be created inside it, like a destination on a
+        // volume that isn't mounted, fails too
+        let err = ensure_writable_dir(&read_only.join("Backups")).unwrap_err();
+        assert!(err.contains("Cannot create destination"), "{err}");
+
+        std::fs::set_permissions(&read_only, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_ensure_writable_dir_rejects_empty_path() {
+        assert!(ensure_writable_dir(Path::new("")).is_err());
+    }
+
+    #[test]
+    fn test_write_json_atomic_keeps_previous_version() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("queue.json");
+
+        write_json_atomic(&path, &vec![1]).unwrap();
+        write_json_atomic(&path, &vec![1, 2]).unwrap();
+
+        let current: Option<Vec<i32>> = read_json_with_backup(&path).unwrap();
+        assert_eq!(current, Some(vec![1, 2]));
+        let previous: Vec<i32> = serde_json::from_str(
+            &std::fs::read_to_string(temp_dir.path().join("queue.json.bak")).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(previous, vec![1]);
+        assert!(!temp_dir.path().join("queue.json.tmp").exists());
+    }
+
+    #[test]
+    fn test_corrupt_json_is_recovered_from_backup() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("history.json");
+        write_json_atomic(&path, &vec!["first"]).unwrap();
+        write_json_atomic(&path, &vec!["first", "second"]).unwrap();
+
+        // Simulate a crash that left the main file truncated
+        std::fs::write(&path, b"[\"first\", \"sec").unwrap();
+
+        let recovered: Option<Vec<String>> = read_json_with_backup(&path).unwrap();
+        assert_eq!(recovered, Some(vec!["first".to_owned()]));
+        // The main file is repaired from the backup
+        let repaired: Vec<String> =
+            serde_json::from_str(&std::fs::read_to_string(&path).unwrap()).unwrap();
+        assert_eq!(repaired, vec!["first".to_owned()]);
+    }
+
+    #[test]
+    fn test_read_json_with_backup_missing_and_unrecoverable() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("missing.json");
+        assert!(read_json_with_backup::<Vec<i32>>(&path).unwrap().is_none());
+
+        std::fs::write(&path, b"not json").unwrap();
+        assert!(read_json_with_backup::<Vec<i32>>(&path).is_err());
+    }
+
+    #[test]
+    fn test_transfer_rate_follows_recent_speed() {
+        let start = Instant::now();
+        let mut rate = TransferRate::starting_at(start);
+        let mb = 1_000_000;
+
+        // 10 MB/s for 5 seconds, sampled every half second
+        for step in 1..=10 {
+            rate.record(start + Duration::from_millis(step * 500), step * 5 * mb);
+        }
+        assert_eq!(rate.speed().round() as u64, 10 * mb);
+
+        // Then 1 MB/s for 4 seconds
+        for step in 1..=8 {
+            rate.record(
+                start + Duration::from_millis(5000 + step * 500),
+                50 * mb + step * mb / 2,
+            );
+        }
+        // Only the last 3 seconds count, not the whole-job average of 6.75 MB/s
+        assert_eq!(rate.speed().round() as u64, mb);
+        // 46 MB left at 1 MB/s
+        assert_eq!(rate.eta(100 * mb), 46);
+        assert!(rate.samples.len() <= 8);
+    }
+
+    #[test]
+    fn test_transfer_rate_without_elapsed_time() {
+        let start = Instant::now();
+        let mut rate = TransferRate::starting_at(start);
+        assert!(rate.speed().abs() < f64::EPSILON);
+        assert_eq!(rate.eta(1000), 0);
+
+        rate.record(start, 500);
+        assert!(rate.speed().abs() < f64::EPSILON);
+        assert_eq!(rate.eta(1000), 0);
+    }
 }
@@ -2,13 +2,25 @@
 //!
 //! Saves completed import metadata to `~/CreatorOps/import_history.json`
 //! and provides query commands for the full history or a single project's
-//! history. At most 100 records are kept; older entries are pruned on write.
+//! history. At most `import_history_limit` records (a setting, 100 by default)
+//! are kept; older entries are pruned on write.
+//! Single records can be deleted; the file is always rewritten through a
+//! temporary file and a rename so a crash never leaves it half-written, and the
+//! previous version is kept as a backup to recover from if it is corrupted. The
+//! history can also be exported as a CSV spreadsheet for bookkeeping, or
+//! summarised into lifetime totals.
 
 use crate::error::AppError;
-use crate::modules::file_utils::{get_home_dir, get_timestamp};
+use crate::modules::db::Database;
+use crate::modules::file_copy::SkippedFile;
+use crate::modules::file_utils::{
+    get_home_dir, get_timestamp, read_json_with_backup, write_json_atomic,
+};
+use crate::modules::settings::import_history_limit;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use std::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use uuid::Uuid;
 
 /// Record of a completed SD card import operation.
@@ -22,6 +34,9 @@ pub struct ImportHistory {
     pub destination_path: String,
     pub files_copied: usize,
     pub files_skipped: usize,
+    /// Files the import gave up on, with the reason for each.
+    #[serde(default)]
+    pub skipped_files: Vec<SkippedFile>,
     pub total_bytes: u64,
     pub photos_copied: usize,
     pub videos_copied: usize,
@@ -40,10 +55,12 @@ pub enum ImportStatus {
     Failed,
 }
 
-/// Record a completed import and persist it to the history file.
+/// Record a completed import and persist it to the history file, keeping at
+/// most the configured number of records.
 #[tauri::command(rename_all = "camelCase")]
 #[allow(clippy::too_many_arguments)]
 pub async fn save_import_history(
+    db: tauri::State<'_, Database>,
     project_id: String,
     project_name: String,
     source_path: String,
@@ -55,6 +72,48 @@ pub async fn save_import_history(
     videos_copied: usize,
     started_at: String,
     error_message: Option<String>,
+    skipped_files: Option<Vec<SkippedFile>>,
+) -> Result<ImportHistory, String> {
+    let limit = import_history_limit(&db)?;
+    save_import_history_impl(
+        limit,
+        project_id,
+        project_name,
+        source_path,
+        destination_path,
+        files_copied,
+        files_skipped,
+        total_bytes,
+        photos_copied,
+        videos_copied,
+        started_at,
+        error_message,
+        skipped_files,
+    )
+    .await
+}
+
+/// Core logic for recording an import (testable), keeping the newest `limit`
+/// records.
+///
+/// # Errors
+///
+/// Returns error if the history file cannot be written
+#[allow(clippy::too_many_arguments)]
+pub async fn save_import_history_impl(
+    limit: usize,
+    project_id: String,
+    project_name: String,
+    source_path: String,
+    destination_path: String,
+    files_copied: usize,
+    files_skipped: usize,
+    total_bytes: u64,
+    photos_copied: usize,
+    videos_copied: usize,
+    started_at: String,
+    error_message: Option<String>,
+    skipped_files: Option<Vec<SkippedFile>>,
 ) -> Result<ImportHistory, String> {
     let id = Uuid::new_v4().to_string();
     let completed_at = get_timestamp();
@@ -75,6 +134,7 @@ pub async fn save_import_history(
         destination_path,
         files_copied,
         files_skipped,
+        skipped_files: skipped_files.unwrap_or_default(),
         total_bytes,
         photos_copied,
         videos_copied,
@@ -85,21 +145,30 @@ pub async fn save_import_history(
     };
 
     // Save to history file
-    let history_path = get_history_file_path()?;
     let mut histories = load_all_histories().unwrap_or_default();
     histories.insert(0, history.clone());
 
-    // Keep only last 100 imports
-    if histories.len() > 100 {
-        histories.truncate(100);
-    }
+    histories.truncate(limit);
 
-    let json_data = serde_json::to_string_pretty(&histories).map_err(|e| e.to_string())?;
-    fs::write(&history_path, json_data).map_err(|e| e.to_string())?;
+    save_all_histories(&histories)?;
 
     Ok(history)
 }
 
+/// Remove one record from the import history.
+#[tauri::command]
+pub async fn delete_import_history_entry(id: String) -> Result<(), String> {
+    let mut histories = load_all_histories()?;
+    let count = histories.len();
+    histories.retain(|h| h.id != id);
+
+    if histories.len() == count {
+        return Err(format!("Import history entry not found: {id}"));
+    }
+
+    save_all_histories(&histories).map_err(String::from)
+}
+
 /// Return recent import history, newest first. Defaults to 50 records.
 #[tauri::command]
 pub async fn get_import_history(limit: Option<usize>) -> Result<Vec<ImportHistory>, String> {
@@ -118,15 +187,178 @@ pub async fn get_project_import_history(project_id: String) -> Result<Vec<Import
         .collect())
 }
 
-fn load_all_histories() -> Result<Vec<ImportHistory>, AppError> {
-    let history_path = get_history_file_path()?;
+/// Lifetime totals across the import history.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ImportStats {
+    pub total_imports: usize,
+    pub total_files: usize,
+    pub total_bytes: u64,
+    pub photos_copied: usize,
+    pub videos_copied: usize,
+    /// Per-project totals, most files first.
+    pub projects: Vec<ProjectImportStats>,
+    /// Month with the most files imported, `None` for an empty history.
+    pub busiest_month: Option<MonthlyImportStats>,
+}
+
+/// Import totals for one project.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProjectImportStats {
+    pub project_id: String,
+    pub project_name: String,
+    pub imports: usize,
+    pub files: usize,
+    pub bytes: u64,
+}
+
+/// Import totals for one calendar month.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MonthlyImportStats {
+    /// `YYYY-MM`, in UTC.
+    pub month: String,
+    pub imports: usize,
+    pub files: usize,
+}
 
-    if !history_path.exists() {
-        return Ok(Vec::new());
+/// Aggregate `histories` into totals. Records whose completion time isn't a Unix
+/// timestamp still count towards the totals but not towards any month.
+fn import_statistics(histories: &[ImportHistory]) -> ImportStats {
+    let mut stats = ImportStats::default();
+    let mut projects: Vec<ProjectImportStats> = Vec::new();
+    let mut months: BTreeMap<String, MonthlyImportStats> = BTreeMap::new();
+
+    for history in histories {
+        stats.total_imports += 1;
+        stats.total_files += history.files_copied;
+        stats.total_bytes += history.total_bytes;
+        stats.photos_copied += history.photos_copied;
+        stats.videos_copied += history.videos_copied;
+
+        if let Some(project) = projects
+            .iter_mut()
+            .find(|p| p.project_id == history.project_id)
+        {
+            project.imports += 1;
+            project.files += history.files_copied;
+            project.bytes += history.total_bytes;
+        } else {
+            projects.push(ProjectImportStats {
+                project_id: history.project_id.clone(),
+                project_name: history.project_name.clone(),
+                imports: 1,
+                files: history.files_copied,
+                bytes: history.total_bytes,
+            });
+        }
+
+        let month = history
+            .completed_at
+            .parse::<i64>()
+            .ok()
+            .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+            .map(|time| time.format("%Y-%m").to_string());
+        if let Some(month) = month {
+            let entry = months
+                .entry(month.clone())
+                .or_insert_with(|| MonthlyImportStats {
+                    month,
+                    imports: 0,
+                    files: 0,
+                });
+            entry.imports += 1;
+            entry.files += history.files_copied;
+        }
     }
 
-    let json_data = fs::read_to_string(&history_path)?;
-    Ok(serde_json::from_str(&json_data)?)
+    // Stable sort keeps history order (newest first) between projects with equal counts
+    projects.sort_by_key(|project| std::cmp::Reverse(project.files));
+    stats.projects = projects;
+    // Earliest month wins a tie
+    stats.busiest_month = months.into_values().rev().max_by_key(|month| month.files);
+
+    stats
+}
+
+/// Summarise the whole import history into lifetime and per-project totals.
+#[tauri::command]
+pub async fn get_import_statistics() -> Result<ImportStats, String> {
+    let histories = load_all_histories()?;
+    Ok(import_statistics(&histories))
+}
+
+/// Column headers of the CSV export.
+const CSV_HEADERS: [&str; 9] = [
+    "Project",
+    "Source",
+    "Destination",
+    "Files Copied",
+    "Files Skipped",
+    "Bytes",
+    "Photos",
+    "Videos",
+    "Completed At",
+];
+
+/// Write the whole import history to a CSV file, newest import first.
+#[tauri::command]
+pub async fn export_import_history_csv(output_path: String) -> Result<usize, String> {
+    let histories = load_all_histories()?;
+    write_history_csv(&histories, Path::new(&output_path))?;
+    Ok(histories.len())
+}
+
+/// Write `histories` as CSV rows under a header row; no records still yields the
+/// header.
+fn write_history_csv(histories: &[ImportHistory], output_path: &Path) -> Result<(), String> {
+    let mut writer = csv::Writer::from_path(output_path)
+        .map_err(|e| format!("Failed to create {}: {e}", output_path.display()))?;
+    writer
+        .write_record(CSV_HEADERS)
+        .map_err(|e| e.to_string())?;
+
+    for history in histories {
+        writer
+            .write_record([
+                history.project_name.clone(),
+                history.source_path.clone(),
+                history.destination_path.clone(),
+                history.files_copied.to_string(),
+                history.files_skipped.to_string(),
+                history.total_bytes.to_string(),
+                history.photos_copied.to_string(),
+                history.videos_copied.to_string(),
+                spreadsheet_timestamp(&history.completed_at),
+            ])
+            .map_err(|e| e.to_string())?;
+    }
+
+    writer.flush().map_err(|e| e.to_string())
+}
+
+/// RFC 3339 form of a Unix-seconds timestamp, which spreadsheets can parse;
+/// anything else is passed through unchanged.
+fn spreadsheet_timestamp(timestamp: &str) -> String {
+    timestamp
+        .parse::<i64>()
+        .ok()
+        .and_then(|secs| chrono::DateTime::from_timestamp(secs, 0))
+        .map_or_else(|| timestamp.to_owned(), |time| time.to_rfc3339())
+}
+
+/// Read the history file, falling back to its backup if it is corrupt.
+fn load_all_histories() -> Result<Vec<ImportHistory>, AppError> {
+    let history_path = get_history_file_path()?;
+    Ok(read_json_with_backup(&history_path)?.unwrap_or_default())
+}
+
+/// Replace the history file, keeping the previous version as a backup.
+fn save_all_histories(histories: &[ImportHistory]) -> Result<(), AppError> {
+    let history_path = get_history_file_path()?;
+    write_json_atomic(&history_path, histories)?;
+    Ok(())
 }
 
 fn get_history_file_path() -> Result<PathBuf, AppError> {
@@ -139,6 +371,7 @@ fn get_history_file_path() -> Result<PathBuf, AppError> {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::modules::settings::DEFAULT_IMPORT_HISTORY_LIMIT;
     use std::sync::Arc;
     use tempfile::TempDir;
     use tokio::sync::Mutex;
@@ -190,6 +423,7 @@ mod tests {
             destination_path: "/dest".to_owned(),
             files_copied: 10,
             files_skipped: 2,
+            skipped_files: Vec::new(),
             total_bytes: 1024,
             photos_copied: 8,
             videos_copied: 2,
@@ -215,6 +449,7 @@ mod tests {
             destination_path: "/dest".to_owned(),
             files_copied: 5,
             files_skipped: 5,
+            skipped_files: Vec::new(),
             total_bytes: 512,
             photos_copied: 5,
             videos_copied: 0,
@@ -235,7 +470,8 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         std::env::set_var("HOME", temp_dir.path());
 
-        let result = save_import_history(
+        let result = save_import_history_impl(
+            DEFAULT_IMPORT_HISTORY_LIMIT,
             "proj-123".to_owned(),
             "Test Project".to_owned(),
             "/source".to_owned(),
@@ -247,6 +483,7 @@ mod tests {
             2,
             "2024-01-01T00:00:00Z".to_owned(),
             None,
+            None,
         )
         .await;
 
@@ -265,7 +502,8 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         std::env::set_var("HOME", temp_dir.path());
 
-        let result = save_import_history(
+        let result = save_import_history_impl(
+            DEFAULT_IMPORT_HISTORY_LIMIT,
             "proj-456".to_owned(),
             "Partial Project".to_owned(),
             "/source".to_owned(),
@@ -277,6 +515,7 @@ mod tests {
             1,
             "2024-01-01T00:00:00Z".to_owned(),
             Some("3 files failed".to_owned()),
+            None,
         )
         .await;
 
@@ -294,7 +533,8 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         std::env::set_var("HOME", temp_dir.path());
 
-        let result = save_import_history(
+        let result = save_import_history_impl(
+            DEFAULT_IMPORT_HISTORY_LIMIT,
             "proj-789".to_owned(),
             "Failed Project".to_owned(),
             "/source".to_owned(),
@@ -306,6 +546,7 @@ mod tests {
             0,
             "2024-01-01T00:00:00Z".to_owned(),
             Some("All files failed".to_owned()),
+            None,
         )
         .await;
 
@@ -323,7 +564,8 @@ mod tests {
         let home_path = temp_dir.path().to_string_lossy().to_string();
         std::env::set_var("HOME", &home_path);
 
-        let history1 = save_import_history(
+        let history1 = save_import_history_impl(
+            DEFAULT_IMPORT_HISTORY_LIMIT,
             "proj-1".to_owned(),
             "Project 1".to_owned(),
             "/src".to_owned(),
@@ -335,6 +577,7 @@ mod tests {
             2,
             "2024-01-01T00:00:00Z".to_owned(),
             None,
+            None,
         )
         .await;
 
@@ -356,7 +599,8 @@ mod tests {
         std::env::set_var("HOME", temp_dir.path());
 
         // Test Failed status (0 files copied)
-        let failed = save_import_history(
+        let failed = save_import_history_impl(
+            DEFAULT_IMPORT_HISTORY_LIMIT,
             "proj-fail".to_owned(),
             "Failed".to_owned(),
             "/src".to_owned(),
@@ -368,6 +612,7 @@ mod tests {
             0,
             "2024-01-01T00:00:00Z".to_owned(),
             Some("All failed".to_owned()),
+            None,
         )
         .await
         .unwrap();
@@ -375,7 +620,8 @@ mod tests {
         assert!(matches!(failed.status, ImportStatus::Failed));
 
         // Test Partial status (some files copied, some skipped)
-        let partial = save_import_history(
+        let partial = save_import_history_impl(
+            DEFAULT_IMPORT_HISTORY_LIMIT,
             "proj-partial".to_owned(),
             "Partial".to_owned(),
             "/src".to_owned(),
@@ -387,6 +633,7 @@ mod tests {
             1,
             "2024-01-01T00:00:00Z".to_owned(),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -394,7 +641,8 @@ mod tests {
         assert!(matches!(partial.status, ImportStatus::Partial));
 
         // Test Success status (all files copied, none skipped)
-        let success = save_import_history(
+        let success = save_import_history_impl(
+            DEFAULT_IMPORT_HISTORY_LIMIT,
             "proj-success".to_owned(),
             "Success".to_owned(),
             "/src".to_owned(),
@@ -406,6 +654,7 @@ mod tests {
             2,
             "2024-01-01T00:00:00Z".to_owned(),
             None,
+            None,
         )
         .await
         .unwrap();
@@ -439,4 +688,269 @@ mod tests {
         assert!(path.to_string_lossy().contains("CreatorOps"));
         assert!(path.to_string_lossy().contains("import_history.json"));
     }
+
+    #[tokio::test]
+    async fn test_save_import_history_keeps_newest_up_to_limit() {
+        let _lock = HOME_TEST_MUTEX.lock().await;
+        let temp_dir = TempDir::new().unwrap();
+        std::env::set_var("HOME", temp_dir.path());
+
+        for project_id in ["proj-1", "proj-2", "proj-3"] {
+            save_import_history_impl(
+                2,
+                project_id.to_owned(),
+                "Project".to_owned(),
+                "/src".to_owned(),
+                "/dst".to_owned(),
+                10,
+                0,
+                1024,
+                8,
+                2,
+                "2024-01-01T00:00:00Z".to_owned(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+        }
+
+        let kept: Vec<String> = load_all_histories()
+            .unwrap()
+            .into_iter()
+            .map(|history| history.project_id)
+            .collect();
+        assert_eq!(kept, ["proj-3", "proj-2"]);
+    }
+
+    #[tokio::test]
+    async fn test_delete_import_history_entry() {
+        let _lock = HOME_TEST_MUTEX.lock().await;
+        let temp_dir = TempDir::new().unwrap();
+        std::env::set_var("HOME", temp_dir.path());
+
+        let mut saved = Vec::new();
+        for project_id in ["proj-1", "proj-2", "proj-3"] {
+            let history = save_import_history_impl(
+                DEFAULT_IMPORT_HISTORY_LIMIT,
+                project_id.to_owned(),
+                "Project".to_owned(),
+                "/src".to_owned(),
+                "/dst".to_owned(),
+                10,
+                0,
+                1024,
+                8,
+                2,
+                "2024-01-01T00:00:00Z".to_owned(),
+                None,
+                None,
+            )
+            .await
+            .unwrap();
+            saved.push(history.id);
+        }
+
+        delete_import_history_entry(saved[1].clone()).await.unwrap();
+
+        let remaining: Vec<String> = get_import_history(None)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|h| h.id)
+            .collect();
+        assert_eq!(remaining, vec![saved[2].clone(), saved[0].clone()]);
+        assert!(!get_history_file_path()
+            .unwrap()
+            .with_extension("json.tmp")
+            .exists());
+
+        let missing = delete_import_history_entry(saved[1].clone()).await;
+        assert!(missing.unwrap_err().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_skipped_file_details_round_trip() {
+        let _lock = HOME_TEST_MUTEX.lock().await;
+        let temp_dir = TempDir::new().unwrap();
+        std::env::set_var("HOME", temp_dir.path());
+
+        let skipped = vec![
+            SkippedFile {
+                file_name: "IMG_0042.CR3".to_owned(),
+                reason: "IO error: Input/output error (os error 5)".to_owned(),
+            },
+            SkippedFile {
+                file_name: "C0007.MP4".to_owned(),
+                reason: "File copy failed: checksum mismatch".to_owned(),
+            },
+        ];
+        let saved = save_import_history_impl(
+            DEFAULT_IMPORT_HISTORY_LIMIT,
+            "proj-flaky".to_owned(),
+            "Flaky Card".to_owned(),
+            "/Volumes/CARD".to_owned(),
+            "/dst".to_owned(),
+            8,
+            2,
+            4096,
+            7,
+            1,
+            "2024-01-01T00:00:00Z".to_owned(),
+            Some("2 file(s) skipped due to errors".to_owned()),
+            Some(skipped.clone()),
+        )
+        .await
+        .unwrap();
+        assert_eq!(saved.skipped_files, skipped);
+
+        let all = get_import_history(None).await.unwrap();
+        assert_eq!(all[0].skipped_files, skipped);
+        let for_project = get_project_import_history("proj-flaky".to_owned())
+            .await
+            .unwrap();
+        assert_eq!(for_project[0].skipped_files, skipped);
+
+        let json = serde_json::to_string(&all[0]).unwrap();
+        assert!(json.contains(r#""skippedFiles":[{"fileName":"IMG_0042.CR3","reason":"#));
+    }
+
+    #[test]
+    fn test_import_history_without_skipped_files_loads() {
+        let json = r#"{
+            "id": "old", "projectId": "p", "projectName": "P", "sourcePath": "/s",
+            "destinationPath": "/d", "filesCopied": 1, "filesSkipped": 0, "totalBytes": 1,
+            "photosCopied": 1, "videosCopied": 0, "startedAt": "a", "completedAt": "b",
+            "status": "success", "errorMessage": null
+        }"#;
+        let history: ImportHistory = serde_json::from_str(json).unwrap();
+        assert!(history.skipped_files.is_empty());
+    }
+
+    #[test]
+    fn test_export_history_csv_round_trip() {
+        let temp_dir = TempDir::new().unwrap();
+        let output = temp_dir.path().join("imports.csv");
+        let record = |name: &str, copied: usize, completed_at: &str| ImportHistory {
+            id: format!("id-{name}"),
+            project_id: "proj".to_owned(),
+            project_name: name.to_owned(),
+            source_path: "/Volumes/EOS_DIGITAL".to_owned(),
+            destination_path: "/Projects/RAW".to_owned(),
+            files_copied: copied,
+            files_skipped: 1,
+            skipped_files: Vec::new(),
+            total_bytes: 2048,
+            photos_copied: copied,
+            videos_copied: 0,
+            started_at: "2024-06-01T10:00:00Z".to_owned(),
+            completed_at: completed_at.to_owned(),
+            status: ImportStatus::Partial,
+            error_message: None,
+        };
+
+        write_history_csv(
+            &[
+                record("Smith, Jones Wedding", 120, "1717236000"),
+                record("Quote \"Test\"", 3, "not-a-number"),
+            ],
+            &output,
+        )
+        .unwrap();
+
+        let mut reader = csv::Reader::from_path(&output).unwrap();
+        assert_eq!(reader.headers().unwrap(), CSV_HEADERS.as_slice());
+        let rows: Vec<csv::StringRecord> = reader.records().map(Result::unwrap).collect();
+        assert_eq!(rows.len(), 2);
+        assert_eq!(&rows[0][0], "Smith, Jones Wedding");
+        assert_eq!(&rows[0][1], "/Volumes/EOS_DIGITAL");
+        assert_eq!(&rows[0][3], "120");
+        assert_eq!(&rows[0][4], "1");
+        assert_eq!(&rows[0][5], "2048");
+        assert_eq!(&rows[0][8], "2024-06-01T10:00:00+00:00");
+        assert_eq!(&rows[1][0], "Quote \"Test\"");
+        assert_eq!(&rows[1][8], "not-a-number");
+    }
+
+    #[test]
+    fn test_export_empty_history_csv_has_header_only() {
+        let temp_dir = TempDir::new().unwrap();
+        let output = temp_dir.path().join("empty.csv");
+
+        write_history_csv(&[], &output).unwrap();
+
+        let contents = std::fs::read_to_string(&output).unwrap();
+        assert_eq!(contents.lines().count(), 1);
+        assert!(contents.starts_with("Project,Source,"));
+    }
+
+    #[test]
+    fn test_import_statistics() {
+        let record =
+            |project: &str, files: usize, videos: usize, completed_at: &str| ImportHistory {
+                id: Uuid::new_v4().to_string(),
+                project_id: format!("proj-{project}"),
+                project_name: project.to_owned(),
+                source_path: "/Volumes/EOS_DIGITAL".to_owned(),
+                destination_path: format!("/Projects/{project}/RAW"),
+                files_copied: files,
+                files_skipped: 0,
+                skipped_files: Vec::new(),
+                total_bytes: files as u64 * 1000,
+                photos_copied: files - videos,
+                videos_copied: videos,
+                started_at: completed_at.to_owned(),
+                completed_at: completed_at.to_owned(),
+                status: ImportStatus::Success,
+                error_message: None,
+            };
+        let histories = vec![
+            // 2024-07-01
+            record("Smith Wedding", 40, 10, "1719792000"),
+            // 2024-06-15 and 2024-06-01
+            record("Smith Wedding", 100, 0, "1718409600"),
+            record("Acme Headshots", 30, 0, "1717200000"),
+            record("Acme Headshots", 5, 5, "not-a-timestamp"),
+        ];
+
+        let stats = import_statistics(&histories);
+
+        assert_eq!(stats.total_imports, 4);
+        assert_eq!(stats.total_files, 175);
+        assert_eq!(stats.total_bytes, 175_000);
+        assert_eq!(stats.photos_copied, 160);
+        assert_eq!(stats.videos_copied, 15);
+        assert_eq!(
+            stats.projects,
+            vec![
+                ProjectImportStats {
+                    project_id: "proj-Smith Wedding".to_owned(),
+                    project_name: "Smith Wedding".to_owned(),
+                    imports: 2,
+                    files: 140,
+                    bytes: 140_000,
+                },
+                ProjectImportStats {
+                    project_id: "proj-Acme Headshots".to_owned(),
+                    project_name: "Acme Headshots".to_owned(),
+                    imports: 2,
+                    files: 35,
+                    bytes: 35_000,
+                },
+            ]
+        );
+        assert_eq!(
+            stats.busiest_month,
+            Some(MonthlyImportStats {
+                month: "2024-06".to_owned(),
+                imports: 2,
+                files: 130,
+            })
+        );
+    }
+
+    #[test]
+    fn test_import_statistics_for_empty_history() {
+        assert_eq!(import_statistics(&[]), ImportStats::default());
+    }
 }
@@ -0,0 +1,216 @@
+//! Per-job diagnostic logs in `~/CreatorOps/logs/<job_id>.log`.
+//!
+//! Backups and Drive uploads write one line per file copied, skipped or retried
+//! so a failed job can be investigated after the fact. Opening a log prunes the
+//! oldest ones so the directory stays within [`MAX_LOG_FILES`] and
+//! [`MAX_LOG_DIR_BYTES`].
+
+use crate::modules::file_utils::get_home_dir;
+use std::fs::{self, File, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+const LOG_EXTENSION: &str = "log";
+/// Most job logs kept; the oldest are removed first.
+pub const MAX_LOG_FILES: usize = 200;
+/// Most bytes of job logs kept; the oldest are removed first.
+pub const MAX_LOG_DIR_BYTES: u64 = 50 * 1024 * 1024;
+
+/// Append-only log of one job. A log that couldn't be opened is disabled and
+/// ignores writes, so logging never fails a job.
+#[derive(Debug)]
+pub struct JobLog {
+    file: Option<Mutex<File>>,
+}
+
+impl JobLog {
+    /// Open the log of `job_id` under `~/CreatorOps/logs`, or a disabled log if
+    /// that fails.
+    pub fn open(job_id: &str) -> Self {
+        match logs_dir().and_then(|dir| Self::open_in(&dir, job_id)) {
+            Ok(log) => log,
+            Err(e) => {
+                log::warn!("Failed to open log for job {job_id}: {e}");
+                Self::disabled()
+            }
+        }
+    }
+
+    /// Open the log of `job_id` in `dir`, appending to an existing one, after
+    /// pruning old logs.
+    ///
+    /// # Errors
+    ///
+    /// Returns error if the job id is invalid or the file can't be created
+    pub fn open_in(dir: &Path, job_id: &str) -> Result<Self, String> {
+        let path = log_path(dir, job_id)?;
+        fs::create_dir_all(dir).map_err(|e| format!("Failed to create log directory: {e}"))?;
+        prune_logs(dir, MAX_LOG_FILES, MAX_LOG_DIR_BYTES);
+
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| format!("Failed to open {}: {e}", path.display()))?;
+        Ok(Self {
+            file: Some(Mutex::new(file)),
+        })
+    }
+
+    /// A log that discards everything written to it.
+    pub const fn disabled() -> Self {
+        Self { file: None }
+    }
+
+    /// Append `message` as a timestamped line.
+    pub fn write(&self, message: &str) {
+        let Some(file) = &self.file else {
+            return;
+        };
+        let timestamp = chrono::Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Millis, true);
+        if let Ok(mut file) = file.lock() {
+            let _ = writeln!(file, "{timestamp} {message}");
+        }
+    }
+}
+
+/// Folder job logs are written to.
+///
+/// # Errors
+///
+/// Returns error if the home directory is unknown
+pub fn logs_dir() -> Result<PathBuf, String> {
+    Ok(get_home_dir()
+        .map_err(|e| e.to_string())?
+        .join("CreatorOps")
+        .join("logs"))
+}
+
+/// Path of the log of `job_id` in `dir`. Ids are restricted to letters, digits,
+/// `-` and `_` so they can't point outside `dir`.
+fn log_path(dir: &Path, job_id: &str) -> Result<PathBuf, String> {
+    let valid = !job_id.is_empty()
+        && job_id
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if !valid {
+        return Err(format!("Invalid job id: {job_id}"));
+    }
+    Ok(dir.join(format!("{job_id}.{LOG_EXTENSION}")))
+}
+
+/// Remove the oldest logs in `dir` until at most `max_files` remain and they
+/// total at most `max_bytes`. Failures are logged and otherwise ignored.
+fn prune_logs(dir: &Path, max_files: usize, max_bytes: u64) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    let mut logs: Vec<_> = entries
+        .flatten()
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext == LOG_EXTENSION)
+        })
+        .filter_map(|entry| {
+            let metadata = entry.metadata().ok()?;
+            Some((metadata.modified().ok()?, metadata.len(), entry.path()))
+        })
+        .collect();
+    // Newest first, so everything past the limits is the oldest
+    logs.sort_by_key(|(modified, _, _)| std::cmp::Reverse(*modified));
+
+    let mut kept_bytes = 0_u64;
+    for (index, (_, len, path)) in logs.into_iter().enumerate() {
+        kept_bytes += len;
+        if index < max_files && kept_bytes <= max_bytes {
+            continue;
+        }
+        if let Err(e) = fs::remove_file(&path) {
+            log::warn!("Failed to remove old job log {}: {e}", path.display());
+        }
+    }
+}
+
+/// Read the log of `job_id` in `dir`.
+///
+/// # Errors
+///
+/// Returns error if the job id is invalid or the job has no log
+pub fn read_job_log(dir: &Path, job_id: &str) -> Result<String, String> {
+    let path = log_path(dir, job_id)?;
+    fs::read_to_string(&path).map_err(|e| match e.kind() {
+        std::io::ErrorKind::NotFound => format!("No log for job {job_id}"),
+        _ => format!("Failed to read {}: {e}", path.display()),
+    })
+}
+
+/// Get the diagnostic log of a backup or Drive upload job.
+#[tauri::command]
+pub async fn get_job_log(job_id: String) -> Result<String, String> {
+    read_job_log(&logs_dir()?, &job_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_log_lines_are_appended_and_read_back() {
+        let temp_dir = TempDir::new().unwrap();
+
+        JobLog::open_in(temp_dir.path(), "job-1")
+            .unwrap()
+            .write("Copied a.jpg");
+        JobLog::open_in(temp_dir.path(), "job-1")
+            .unwrap()
+            .write("Copied b.jpg");
+
+        let log = read_job_log(temp_dir.path(), "job-1").unwrap();
+        let lines: Vec<_> = log.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with(" Copied a.jpg"));
+        assert!(lines[1].ends_with(" Copied b.jpg"));
+        assert!(read_job_log(temp_dir.path(), "job-2")
+            .unwrap_err()
+            .contains("No log"));
+    }
+
+    #[test]
+    fn test_job_ids_cannot_leave_the_log_dir() {
+        let temp_dir = TempDir::new().unwrap();
+
+        assert!(JobLog::open_in(temp_dir.path(), "../escape").is_err());
+        assert!(read_job_log(temp_dir.path(), "").is_err());
+        assert!(read_job_log(temp_dir.path(), "a/b").is_err());
+    }
+
+    #[test]
+    fn test_prune_removes_oldest_logs() {
+        let temp_dir = TempDir::new().unwrap();
+        let base = std::time::SystemTime::now() - std::time::Duration::from_secs(60);
+        for (age, name) in ["old", "middle", "new"].iter().enumerate() {
+            let path = temp_dir.path().join(format!("{name}.log"));
+            fs::write(&path, "0123456789").unwrap();
+            File::options()
+                .write(true)
+                .open(&path)
+                .unwrap()
+                .set_modified(base + std::time::Duration::from_secs(age as u64))
+                .unwrap();
+        }
+        fs::write(temp_dir.path().join("notes.txt"), "kept").unwrap();
+
+        prune_logs(temp_dir.path(), 2, u64::MAX);
+        assert!(!temp_dir.path().join("old.log").exists());
+        assert!(temp_dir.path().join("middle.log").exists());
+
+        prune_logs(temp_dir.path(), 10, 15);
+        assert!(!temp_dir.path().join("middle.log").exists());
+        assert!(temp_dir.path().join("new.log").exists());
+        assert!(temp_dir.path().join("notes.txt").exists());
+    }
+}
@@ -0,0 +1,627 @@
+//! Media metadata helpers.
+//!
+//! Reads EXIF tags from photo files (JPEG, HEIF, TIFF-based RAW formats) and
+//! falls back to filesystem timestamps when a file carries no embedded
+//! capture date (videos, PNGs, stripped exports). JPEG metadata can also be
+//! rewritten for delivery: stripping location/serial data and embedding a
+//! copyright notice in both EXIF and IPTC.
+
+use chrono::{DateTime, Local, NaiveDate, NaiveDateTime};
+use exif::experimental::Writer;
+use exif::{Context, Exif, Field, In, Reader, Tag, Value};
+use serde::{Deserialize, Serialize};
+use std::fs::File;
+use std::io::{BufReader, Cursor};
+use std::path::Path;
+
+/// Format used when sending capture dates to the frontend.
+pub const CAPTURE_DATE_FORMAT: &str = "%Y-%m-%dT%H:%M:%S";
+
+const JPEG_SOI: [u8; 2] = [0xFF, 0xD8];
+const MARKER_APP0: u8 = 0xE0;
+const MARKER_APP1: u8 = 0xE1;
+const MARKER_APP13: u8 = 0xED;
+const MARKER_SOS: u8 = 0xDA;
+const MARKER_EOI: u8 = 0xD9;
+const EXIF_HEADER: &[u8] = b"Exif\0\0";
+const PHOTOSHOP_HEADER: &[u8] = b"Photoshop 3.0\0";
+/// Largest payload a JPEG segment can carry after its 2-byte length.
+const MAX_SEGMENT_PAYLOAD: usize = 0xFFFF - 2;
+
+/// EXIF tags identifying where a photo was taken or which body/lens took it.
+const PRIVATE_TAGS: [Tag; 4] = [
+    Tag::MakerNote,
+    Tag::BodySerialNumber,
+    Tag::LensSerialNumber,
+    Tag::CameraOwnerName,
+];
+
+/// Camera and exposure details read from a file's EXIF block.
+///
+/// Every field is optional: videos, PNGs and stripped exports simply return `None`s.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ExifData {
+    pub make: Option<String>,
+    pub model: Option<String>,
+    pub lens: Option<String>,
+    pub date_time_original: Option<String>,
+    pub iso: Option<u32>,
+    pub aperture: Option<f64>,
+    /// Exposure time formatted for display, e.g. `1/250` or `2.5s`.
+    pub shutter_speed: Option<String>,
+    /// Focal length in millimetres.
+    pub focal_length: Option<f64>,
+    pub orientation: Option<u32>,
+}
+
+/// Read camera/exposure EXIF metadata for a file.
+#[tauri::command]
+pub async fn read_exif(path: String) -> Result<ExifData, String> {
+    let file_path = Path::new(&path);
+    if !file_path.is_file() {
+        return Err(format!("File not found: {path}"));
+    }
+    Ok(read_exif_data(file_path))
+}
+
+/// Parse the EXIF block of a file, if it has one.
+fn read_exif_container(path: &Path) -> Option<Exif> {
+    let file = File::open(path).ok()?;
+    Reader::new()
+        .read_from_container(&mut BufReader::new(file))
+        .ok()
+}
+
+/// Collect the `ExifData` fields present in a file; missing tags stay `None`.
+pub fn read_exif_data(path: &Path) -> ExifData {
+    let Some(exif) = read_exif_container(path) else {
+        return ExifData::default();
+    };
+
+    let field = |tag: Tag| exif.get_field(tag, In::PRIMARY).map(|f| &f.value);
+    let ascii = |tag: Tag| match field(tag) {
+        Some(Value::Ascii(values)) => values
+            .first()
+            .map(|v| {
+                String::from_utf8_lossy(v)
+                    .trim_end_matches('\0')
+                    .trim()
+                    .to_owned()
+            })
+            .filter(|v| !v.is_empty()),
+        _ => None,
+    };
+    let rational = |tag: Tag| match field(tag) {
+        Some(Value::Rational(values)) => values
+            .first()
+            .filter(|r| r.denom != 0)
+            .map(exif::Rational::to_f64),
+        _ => None,
+    };
+    let uint = |tag: Tag| field(tag).and_then(|v| v.get_uint(0));
+
+    ExifData {
+        make: ascii(Tag::Make),
+        model: ascii(Tag::Model),
+        lens: ascii(Tag::LensModel),
+        date_time_original: date_time_original(&exif)
+            .map(|d| d.format(CAPTURE_DATE_FORMAT).to_string()),
+        iso: uint(Tag::PhotographicSensitivity),
+        aperture: rational(Tag::FNumber),
+        shutter_speed: rational(Tag::ExposureTime).map(format_shutter_speed),
+        focal_length: rational(Tag::FocalLength),
+        orientation: uint(Tag::Orientation),
+    }
+}
+
+/// Format an exposure time in seconds the way cameras display it.
+fn format_shutter_speed(seconds: f64) -> String {
+    if seconds >= 1.0 || seconds <= 0.0 {
+        format!("{seconds}s")
+    } else {
+        format!("1/{}", (1.0 / seconds).round())
+    }
+}
+
+/// Extract `DateTimeOriginal` from parsed EXIF.
+fn date_time_original(exif: &Exif) -> Option<NaiveDateTime> {
+    let field = exif.get_field(Tag::DateTimeOriginal, In::PRIMARY)?;
+
+    let Value::Ascii(ref values) = field.value else {
+        return None;
+    };
+    let dt = exif::DateTime::from_ascii(values.first()?).ok()?;
+
+    NaiveDate::from_ymd_opt(i32::from(dt.year), u32::from(dt.month), u32::from(dt.day))?
+        .and_hms_opt(
+            u32::from(dt.hour),
+            u32::from(dt.minute),
+            u32::from(dt.second),
+        )
+}
+
+/// Read the EXIF `DateTimeOriginal` tag, if the file has one.
+pub fn read_date_time_original(path: &Path) -> Option<NaiveDateTime> {
+    date_time_original(&read_exif_container(path)?)
+}
+
+/// Extract the JPEG preview embedded in a file's EXIF block (e.g. a RAW file's thumbnail).
+pub fn read_embedded_preview(path: &Path) -> Option<Vec<u8>> {
+    let exif = read_exif_container(path)?;
+
+    [In::THUMBNAIL, In::PRIMARY]
+        .into_iter()
+        .find_map(|ifd| read_thumbnail_slice(&exif, ifd).map(<[u8]>::to_vec))
+}
+
+/// Rewrite a JPEG's metadata segments for delivery.
+///
+/// With `strip_private`, GPS data, maker notes and serial numbers are removed from
+/// the EXIF block. A `copyright` notice replaces the EXIF `Copyright` tag and any
+/// existing IPTC (APP13) block. Image data is copied byte for byte.
+pub fn rewrite_jpeg_metadata(
+    jpeg: &[u8],
+    strip_private: bool,
+    copyright: Option<&str>,
+) -> Result<Vec<u8>, String> {
+    if !jpeg.starts_with(&JPEG_SOI) {
+        return Err("Not a JPEG file".to_owned());
+    }
+
+    let mut leading = Vec::new();
+    let mut segments = Vec::new();
+    let mut exif_payload = None;
+    let mut pos = JPEG_SOI.len();
+
+    while pos + 4 <= jpeg.len() && jpeg[pos] == 0xFF {
+        let marker = jpeg[pos + 1];
+        if marker == MARKER_SOS || marker == MARKER_EOI {
+            break;
+        }
+
+        let length = usize::from(u16::from_be_bytes([jpeg[pos + 2], jpeg[pos + 3]]));
+        // The length counts its own two bytes, so anything shorter is corrupt
+        if length < 2 {
+            return Err("Malformed JPEG segment".to_owned());
+        }
+        let end = pos + 2 + length;
+        let segment = jpeg
+            .get(pos..end)
+            .ok_or_else(|| "Truncated JPEG segment".to_owned())?;
+        let payload = &segment[4..];
+
+        if marker == MARKER_APP1 && payload.starts_with(EXIF_HEADER) {
+            exif_payload = Some(&payload[EXIF_HEADER.len()..]);
+        } else if marker == MARKER_APP13 && copyright.is_some() {
+            // Replaced by the new IPTC block below
+        } else if marker == MARKER_APP0 && segments.is_empty() {
+            // JFIF must stay directly after SOI
+            leading.push(segment);
+        } else {
+            segments.push(segment);
+        }
+        pos = end;
+    }
+
+    let exif = match exif_payload {
+        Some(payload) => Some(
+            Reader::new()
+                .read_raw(payload.to_vec())
+                .map_err(|e| format!("Failed to parse EXIF: {e}"))?,
+        ),
+        None => None,
+    };
+
+    let mut output = Vec::with_capacity(jpeg.len());
+    output.extend_from_slice(&JPEG_SOI);
+    for segment in leading {
+        output.extend_from_slice(segment);
+    }
+    if exif.is_some() || copyright.is_some() {
+        let tiff = build_exif(exif.as_ref(), strip_private, copyright)?;
+        write_segment(&mut output, MARKER_APP1, &[EXIF_HEADER, &tiff].concat())?;
+    }
+    if let Some(notice) = copyright {
+        write_segment(&mut output, MARKER_APP13, &build_iptc_copyright(notice))?;
+    }
+    for segment in segments {
+        output.extend_from_slice(segment);
+    }
+    output.extend_from_slice(&jpeg[pos..]);
+
+    Ok(output)
+}
+
+/// Re-encode EXIF fields as a TIFF structure, applying the delivery edits.
+fn build_exif(
+    exif: Option<&Exif>,
+    strip_private: bool,
+    copyright: Option<&str>,
+) -> Result<Vec<u8>, String> {
+    let copyright_field = copyright.map(|notice| Field {
+        tag: Tag::Copyright,
+        ifd_num: In::PRIMARY,
+        value: Value::Ascii(vec![notice.as_bytes().to_vec()]),
+    });
+
+    let mut writer = Writer::new();
+    if let Some(exif) = exif {
+        for field in exif.fields() {
+            let is_private =
+                field.tag.context() == Context::Gps || PRIVATE_TAGS.contains(&field.tag);
+            let is_stripped = strip_private && is_private;
+            let is_replaced = copyright_field.is_some() && field.tag == Tag::Copyright;
+            if !is_stripped && !is_replaced {
+                writer.push_field(field);
+            }
+        }
+        if let Some(thumbnail) = read_thumbnail_slice(exif, In::THUMBNAIL) {
+            writer.set_jpeg(thumbnail, In::THUMBNAIL);
+        }
+    }
+    if let Some(field) = &copyright_field {
+        writer.push_field(field);
+    }
+
+    let mut tiff = Cursor::new(Vec::new());
+    writer
+        .write(&mut tiff, exif.is_some_and(Exif::little_endian))
+        .map_err(|e| format!("Failed to write EXIF: {e}"))?;
+
+    Ok(tiff.into_inner())
+}
+
+/// Build an APP13 payload holding an IPTC-IIM copyright notice (dataset 2:116).
+fn build_iptc_copyright(notice: &str) -> Vec<u8> {
+    // Coded character set 1:90 = ESC % G (UTF-8), record version 2:00 = 4
+    let mut iim = vec![0x1C, 0x01, 0x5A, 0x00, 0x03, 0x1B, 0x25, 0x47];
+    iim.extend_from_slice(&[0x1C, 0x02, 0x00, 0x00, 0x02, 0x00, 0x04]);
+
+    // IIM datasets are capped at 0x7FFF bytes; copyright notices never get close
+    let notice = &notice.as_bytes()[..notice.len().min(0x7FFF)];
+    iim.extend_from_slice(&[0x1C, 0x02, 116]);
+    iim.extend_from_slice(&(notice.len() as u16).to_be_bytes());
+    iim.extend_from_slice(notice);
+
+    // Photoshop image resource 0x0404 (IPTC-NAA) with an empty, padded name
+    let mut payload = PHOTOSHOP_HEADER.to_vec();
+    payload.extend_from_slice(b"8BIM");
+    payload.extend_from_slice(&[0x04, 0x04, 0x00, 0x00]);
+    payload.extend_from_slice(&(iim.len() as u32).to_be_bytes());
+    payload.extend_from_slice(&iim);
+    if iim.len() % 2 == 1 {
+        payload.push(0);
+    }
+    payload
+}
+
+/// Append a JPEG marker segment, failing if the payload exceeds the 64 KB limit.
+fn write_segment(output: &mut Vec<u8>, marker: u8, payload: &[u8]) -> Result<(), String> {
+    if payload.len() > MAX_SEGMENT_PAYLOAD {
+        return Err(format!(
+            "Metadata segment too large ({} bytes)",
+            payload.len()
+        ));
+    }
+    output.extend_from_slice(&[0xFF, marker]);
+    output.extend_from_slice(&((payload.len() + 2) as u16).to_be_bytes());
+    output.extend_from_slice(payload);
+    Ok(())
+}
+
+/// Slice the JPEG thumbnail referenced by an IFD out of the EXIF buffer.
+fn read_thumbnail_slice(exif: &Exif, ifd: In) -> Option<&[u8]> {
+    let offset = exif
+        .get_field(Tag::JPEGInterchangeFormat, ifd)?
+        .value
+        .get_uint(0)? as usize;
+    let length = exif
+        .get_field(Tag::JPEGInterchangeFormatLength, ifd)?
+        .value
+        .get_uint(0)? as usize;
+    exif.buf().get(offset..offset.checked_add(length)?)
+}
+
+/// Resolve when a file was captured: EXIF `DateTimeOriginal`, else local mtime.
+pub fn resolve_capture_date(path: &Path) -> Option<NaiveDateTime> {
+    read_date_time_original(path).or_else(|| {
+        let modified = std::fs::metadata(path).ok()?.modified().ok()?;
+        Some(DateTime::<Local>::from(modified).naive_local())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tempfile::TempDir;
+
+    /// Write a minimal JPEG (SOI, APP1 Exif segment, EOI) carrying `fields`.
+    fn write_jpeg_with_exif(path: &Path, fields: &[Field]) {
+        let mut writer = Writer::new();
+        for field in fields {
+            writer.push_field(field);
+        }
+        let mut tiff = Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let tiff = tiff.into_inner();
+
+        let mut jpeg = vec![0xFF, 0xD8, 0xFF, 0xE1];
+        let segment_len = u16::try_from(tiff.len() + 8).unwrap();
+        jpeg.extend_from_slice(&segment_len.to_be_bytes());
+        jpeg.extend_from_slice(b"Exif\0\0");
+        jpeg.extend_from_slice(&tiff);
+        jpeg.extend_from_slice(&[0xFF, 0xD9]);
+        std::fs::write(path, jpeg).unwrap();
+    }
+
+    #[test]
+    fn test_read_date_time_original() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("IMG_0001.jpg");
+        let field = Field {
+            tag: Tag::DateTimeOriginal,
+            ifd_num: In::PRIMARY,
+            value: Value::Ascii(vec![b"2024:06:15 10:30:45".to_vec()]),
+        };
+        write_jpeg_with_exif(&path, &[field]);
+
+        let captured = read_date_time_original(&path).unwrap();
+        assert_eq!(
+            captured.format(CAPTURE_DATE_FORMAT).to_string(),
+            "2024-06-15T10:30:45"
+        );
+        assert_eq!(resolve_capture_date(&path), Some(captured));
+    }
+
+    #[test]
+    fn test_resolve_capture_date_falls_back_to_mtime() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("clip.mp4");
+        std::fs::write(&path, b"video").unwrap();
+
+        let expected = NaiveDate::from_ymd_opt(2023, 3, 4)
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let mtime = Local.from_local_datetime(&expected).single().unwrap();
+        File::options()
+            .write(true)
+            .open(&path)
+            .unwrap()
+            .set_modified(mtime.into())
+            .unwrap();
+
+        assert!(read_date_time_original(&path).is_none());
+        assert_eq!(resolve_capture_date(&path), Some(expected));
+    }
+
+    #[test]
+    fn test_read_exif_data_known_values() {
+        use exif::Rational;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("IMG_0002.jpg");
+        let fields = [
+            Field {
+                tag: Tag::Make,
+                ifd_num: In::PRIMARY,
+                value: Value::Ascii(vec![b"Canon".to_vec()]),
+            },
+            Field {
+                tag: Tag::Model,
+                ifd_num: In::PRIMARY,
+                value: Value::Ascii(vec![b"Canon EOS R5".to_vec()]),
+            },
+            Field {
+                tag: Tag::Orientation,
+                ifd_num: In::PRIMARY,
+                value: Value::Short(vec![6]),
+            },
+            Field {
+                tag: Tag::LensModel,
+                ifd_num: In::PRIMARY,
+                value: Value::Ascii(vec![b"RF24-70mm F2.8 L IS USM".to_vec()]),
+            },
+            Field {
+                tag: Tag::DateTimeOriginal,
+                ifd_num: In::PRIMARY,
+                value: Value::Ascii(vec![b"2024:06:15 10:30:45".to_vec()]),
+            },
+            Field {
+                tag: Tag::PhotographicSensitivity,
+                ifd_num: In::PRIMARY,
+                value: Value::Short(vec![800]),
+            },
+            Field {
+                tag: Tag::FNumber,
+                ifd_num: In::PRIMARY,
+                value: Value::Rational(vec![Rational { num: 28, denom: 10 }]),
+            },
+            Field {
+                tag: Tag::ExposureTime,
+                ifd_num: In::PRIMARY,
+                value: Value::Rational(vec![Rational { num: 1, denom: 250 }]),
+            },
+            Field {
+                tag: Tag::FocalLength,
+                ifd_num: In::PRIMARY,
+                value: Value::Rational(vec![Rational { num: 50, denom: 1 }]),
+            },
+        ];
+        write_jpeg_with_exif(&path, &fields);
+
+        let data = read_exif_data(&path);
+        assert_eq!(data.make.as_deref(), Some("Canon"));
+        assert_eq!(data.model.as_deref(), Some("Canon EOS R5"));
+        assert_eq!(data.lens.as_deref(), Some("RF24-70mm F2.8 L IS USM"));
+        assert_eq!(
+            data.date_time_original.as_deref(),
+            Some("2024-06-15T10:30:45")
+        );
+        assert_eq!(data.iso, Some(800));
+        assert_eq!(data.aperture, Some(2.8));
+        assert_eq!(data.shutter_speed.as_deref(), Some("1/250"));
+        assert_eq!(data.focal_length, Some(50.0));
+        assert_eq!(data.orientation, Some(6));
+    }
+
+    #[tokio::test]
+    async fn test_read_exif_without_metadata() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("clip.mp4");
+        std::fs::write(&path, b"not an image").unwrap();
+
+        let data = read_exif(path.to_string_lossy().to_string()).await.unwrap();
+        assert_eq!(data, ExifData::default());
+    }
+
+    #[tokio::test]
+    async fn test_read_exif_missing_file() {
+        let result = read_exif("/nonexistent/file.jpg".to_owned()).await;
+        assert_eq!(result.unwrap_err(), "File not found: /nonexistent/file.jpg");
+    }
+
+    #[test]
+    fn test_format_shutter_speed() {
+        assert_eq!(format_shutter_speed(1.0 / 250.0), "1/250");
+        assert_eq!(format_shutter_speed(0.5), "1/2");
+        assert_eq!(format_shutter_speed(2.5), "2.5s");
+        assert_eq!(format_shutter_speed(30.0), "30s");
+    }
+
+    #[test]
+    fn test_read_embedded_preview_from_tiff_raw() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("IMG_0003.dng");
+        let preview = [0xFF, 0xD8, 0x01, 0x02, 0x03, 0xFF, 0xD9];
+        let make = Field {
+            tag: Tag::Make,
+            ifd_num: In::PRIMARY,
+            value: Value::Ascii(vec![b"Canon".to_vec()]),
+        };
+
+        let mut writer = Writer::new();
+        writer.push_field(&make);
+        writer.set_jpeg(&preview, In::THUMBNAIL);
+        let mut tiff = Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        std::fs::write(&path, tiff.into_inner()).unwrap();
+
+        assert_eq!(read_embedded_preview(&path), Some(preview.to_vec()));
+        assert!(read_embedded_preview(Path::new("/nonexistent/file.cr2")).is_none());
+    }
+
+    #[test]
+    fn test_resolve_capture_date_missing_file() {
+        assert!(resolve_capture_date(Path::new("/nonexistent/file.jpg")).is_none());
+    }
+
+    fn gps_fields() -> Vec<Field> {
+        use exif::Rational;
+
+        vec![
+            Field {
+                tag: Tag::Make,
+                ifd_num: In::PRIMARY,
+                value: Value::Ascii(vec![b"Canon".to_vec()]),
+            },
+            Field {
+                tag: Tag::BodySerialNumber,
+                ifd_num: In::PRIMARY,
+                value: Value::Ascii(vec![b"012345678".to_vec()]),
+            },
+            Field {
+                tag: Tag::GPSLatitudeRef,
+                ifd_num: In::PRIMARY,
+                value: Value::Ascii(vec![b"N".to_vec()]),
+            },
+            Field {
+                tag: Tag::GPSLatitude,
+                ifd_num: In::PRIMARY,
+                value: Value::Rational(vec![
+                    Rational { num: 52, denom: 1 },
+                    Rational { num: 13, denom: 1 },
+                    Rational { num: 0, denom: 1 },
+                ]),
+            },
+        ]
+    }
+
+    #[test]
+    fn test_rewrite_jpeg_metadata_strips_gps_and_serials() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("IMG_0004.jpg");
+        write_jpeg_with_exif(&path, &gps_fields());
+
+        let rewritten = rewrite_jpeg_metadata(&std::fs::read(&path).unwrap(), true, None).unwrap();
+        let exif = Reader::new()
+            .read_from_container(&mut Cursor::new(&rewritten))
+            .unwrap();
+
+        assert!(exif.get_field(Tag::GPSLatitude, In::PRIMARY).is_none());
+        assert!(exif.get_field(Tag::GPSLatitudeRef, In::PRIMARY).is_none());
+        assert!(exif.get_field(Tag::BodySerialNumber, In::PRIMARY).is_none());
+        assert!(exif.get_field(Tag::Make, In::PRIMARY).is_some());
+        assert!(rewritten.ends_with(&[0xFF, 0xD9]));
+    }
+
+    #[test]
+    fn test_rewrite_jpeg_metadata_keeps_gps_without_strip() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("IMG_0005.jpg");
+        write_jpeg_with_exif(&path, &gps_fields());
+
+        let rewritten = rewrite_jpeg_metadata(&std::fs::read(&path).unwrap(), false, None).unwrap();
+        let exif = Reader::new()
+            .read_from_container(&mut Cursor::new(&rewritten))
+            .unwrap();
+
+        assert!(exif.get_field(Tag::GPSLatitude, In::PRIMARY).is_some());
+    }
+
+    #[test]
+    fn test_rewrite_jpeg_metadata_injects_copyright() {
+        let notice = "© 2024 Studio North";
+        let jpeg = [0xFF, 0xD8, 0xFF, 0xD9];
+
+        let rewritten = rewrite_jpeg_metadata(&jpeg, true, Some(notice)).unwrap();
+        let exif = Reader::new()
+            .read_from_container(&mut Cursor::new(&rewritten))
+            .unwrap();
+        let copyright = exif.get_field(Tag::Copyright, In::PRIMARY).unwrap();
+        assert!(matches!(&copyright.value, Value::Ascii(values) if values[0] == notice.as_bytes()));
+
+        let iptc_start = rewritten
+            .windows(PHOTOSHOP_HEADER.len())
+            .position(|w| w == PHOTOSHOP_HEADER)
+            .unwrap();
+        let iptc = &rewritten[iptc_start..];
+        let dataset = [&[0x1C, 0x02, 116][..], &(notice.len() as u16).to_be_bytes()].concat();
+        let dataset_start = iptc.windows(5).position(|w| w == dataset).unwrap();
+        assert_eq!(
+            &iptc[dataset_start + 5..dataset_start + 5 + notice.len()],
+            notice.as_bytes()
+        );
+    }
+
+    #[test]
+    fn test_rewrite_jpeg_metadata_rejects_non_jpeg() {
+        assert_eq!(
+            rewrite_jpeg_metadata(b"\x89PNG", true, None).unwrap_err(),
+            "Not a JPEG file"
+        );
+    }
+
+    #[test]
+    fn test_rewrite_jpeg_metadata_rejects_short_segment_length() {
+        for length in [0_u8, 1] {
+            let jpeg = [0xFF, 0xD8, 0xFF, 0xE1, 0x00, length, 0xFF, 0xD9];
+            assert_eq!(
+                rewrite_jpeg_metadata(&jpeg, true, None).unwrap_err(),
+                "Malformed JPEG segment"
+            );
+        }
+    }
+}
@@ -5,13 +5,24 @@
 
 pub mod archive;
 pub mod backup;
+pub mod checksums;
 pub mod client;
+pub mod contact_sheet;
 pub mod db;
 pub mod delivery;
+pub mod disk;
+pub mod duplicates;
 pub mod file_copy;
 pub mod file_system;
 pub mod file_utils;
 pub mod google_drive;
 pub mod import_history;
+pub mod job_log;
+pub mod metadata;
+pub mod notifications;
 pub mod project;
+pub mod queue;
 pub mod sd_card;
+pub mod settings;
+pub mod storage;
+pub mod thumbnail;
@@ -0,0 +1,321 @@
+//! Desktop notifications for finished long-running jobs.
+//!
+//! Backups, deliveries, archives and Google Drive uploads post a system
+//! notification when they complete or fail, unless the `notifications_enabled`
+//! setting is `false`. Cancelled jobs stay silent since the user stopped them.
+
+use crate::modules::archive::{ArchiveJob, ArchiveStatus};
+use crate::modules::backup::{BackupJob, BackupStatus};
+use crate::modules::db::Database;
+use crate::modules::delivery::{DeliveryJob, DeliveryStatus};
+use crate::modules::google_drive::{DriveUploadJob, DriveUploadStatus};
+use crate::modules::settings::notifications_enabled;
+use std::fmt::Write;
+use tauri::Manager;
+use tauri_plugin_notification::NotificationExt;
+
+/// Title and body of a job notification.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JobNotification {
+    pub title: String,
+    pub body: String,
+}
+
+impl JobNotification {
+    fn new(title: &str, body: String) -> Self {
+        Self {
+            title: title.to_owned(),
+            body,
+        }
+    }
+}
+
+/// "1 file" or "N files".
+fn file_count(count: usize) -> String {
+    if count == 1 {
+        "1 file".to_owned()
+    } else {
+        format!("{count} files")
+    }
+}
+
+/// Reason shown for a failed job.
+fn failure_reason(error_message: Option<&str>) -> &str {
+    error_message.unwrap_or("unknown error")
+}
+
+/// Notification for a finished backup, or `None` if it hasn't finished or was cancelled.
+pub fn backup_notification(job: &BackupJob) -> Option<JobNotification> {
+    match job.status {
+        BackupStatus::Completed => {
+            let mut body = format!(
+                "{}: {} backed up to {}",
+                job.project_name,
+                file_count(job.files_copied),
+                job.destination_name
+            );
+            if job.files_skipped > 0 {
+                let _ = write!(body, ", {} skipped", file_count(job.files_skipped));
+            }
+            Some(JobNotification::new("Backup complete", body))
+        }
+        BackupStatus::Failed => Some(JobNotification::new(
+            "Backup failed",
+            format!(
+                "{} to {}: {}",
+                job.project_name,
+                job.destination_name,
+                failure_reason(job.error_message.as_deref())
+            ),
+        )),
+        _ => None,
+    }
+}
+
+/// Notification for a finished delivery, or `None` if it hasn't finished or was cancelled.
+pub fn delivery_notification(job: &DeliveryJob) -> Option<JobNotification> {
+    match job.status {
+        DeliveryStatus::Completed => Some(JobNotification::new(
+            "Delivery complete",
+            format!(
+                "{}: {} delivered to {}",
+                job.project_name,
+                file_count(job.files_copied),
+                job.output_path.as_deref().unwrap_or(&job.delivery_path)
+            ),
+        )),
+        DeliveryStatus::Failed => Some(JobNotification::new(
+            "Delivery failed",
+            format!(
+                "{}: {}",
+                job.project_name,
+                failure_reason(job.error_message.as_deref())
+            ),
+        )),
+        _ => None,
+    }
+}
+
+/// Notification for a finished archive, or `None` if it hasn't finished or was cancelled.
+pub fn archive_notification(job: &ArchiveJob) -> Option<JobNotification> {
+    match job.status {
+        ArchiveStatus::Completed => Some(JobNotification::new(
+            "Archive complete",
+            format!(
+                "{}: {} archived to {}",
+                job.project_name,
+                file_count(job.files_archived),
+                job.archive_path
+            ),
+        )),
+        ArchiveStatus::Failed => Some(JobNotification::new(
+            "Archive failed",
+            format!(
+                "{}: {}",
+                job.project_name,
+                failure_reason(job.error_message.as_deref())
+            ),
+        )),
+        _ => None,
+    }
+}
+
+/// Notification for a finished Drive upload, or `None` if it hasn't finished or
+/// was cancelled.
+pub fn drive_upload_notification(job: &DriveUploadJob) -> Option<JobNotification> {
+    match job.status {
+        DriveUploadStatus::Completed => Some(JobNotification::new(
+            "Upload complete",
+            format!(
+                "{}: {} uploaded to Google Drive folder {}",
+                job.project_name,
+                file_count(job.uploaded_files),
+                job.folder_name
+            ),
+        )),
+        DriveUploadStatus::Failed => Some(JobNotification::new(
+            "Upload failed",
+            format!(
+                "{}: {} of {} could not be uploaded to Google Drive folder {}",
+                job.project_name,
+                job.failed_files,
+                file_count(job.total_files),
+                job.folder_name
+            ),
+        )),
+        _ => None,
+    }
+}
+
+/// Show `notification` unless notifications are turned off. Failures are logged
+/// rather than returned so a job never fails because it couldn't notify.
+pub fn notify_job_finished(app_handle: &tauri::AppHandle, notification: Option<JobNotification>) {
+    let Some(notification) = notification else {
+        return;
+    };
+
+    let enabled = app_handle
+        .try_state::<Database>()
+        .map_or(Ok(true), |db| notifications_enabled(&db));
+    match enabled {
+        Ok(true) => {}
+        Ok(false) => return,
+        Err(e) => log::warn!("Failed to read notification setting: {e}"),
+    }
+
+    if let Err(e) = app_handle
+        .notification()
+        .builder()
+        .title(notification.title)
+        .body(notification.body)
+        .show()
+    {
+        log::warn!("Failed to show notification: {e}");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::delivery::DeliveryOptions;
+
+    fn backup_job(status: BackupStatus) -> BackupJob {
+        BackupJob {
+            id: "backup-1".to_owned(),
+            project_id: "proj-1".to_owned(),
+            project_name: "Smith Wedding".to_owned(),
+            source_path: "/projects/Smith Wedding".to_owned(),
+            destination_id: "dest-1".to_owned(),
+            destination_name: "Backup Drive".to_owned(),
+            destination_path: "/Volumes/Backup".to_owned(),
+            status,
+            total_files: 120,
+            files_copied: 118,
+            files_skipped: 2,
+            total_bytes: 0,
+            bytes_transferred: 0,
+            created_at: "2024-01-01".to_owned(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+            same_volume_warning: false,
+            group_id: None,
+        }
+    }
+
+    #[test]
+    fn test_backup_notifications() {
+        let completed = backup_notification(&backup_job(BackupStatus::Completed)).unwrap();
+        assert_eq!(completed.title, "Backup complete");
+        assert_eq!(
+            completed.body,
+            "Smith Wedding: 118 files backed up to Backup Drive, 2 files skipped"
+        );
+
+        let mut failed = backup_job(BackupStatus::Failed);
+        failed.error_message = Some("Disk full".to_owned());
+        let failed = backup_notification(&failed).unwrap();
+        assert_eq!(failed.title, "Backup failed");
+        assert_eq!(failed.body, "Smith Wedding to Backup Drive: Disk full");
+
+        assert!(backup_notification(&backup_job(BackupStatus::Cancelled)).is_none());
+        assert!(backup_notification(&backup_job(BackupStatus::InProgress)).is_none());
+    }
+
+    #[test]
+    fn test_delivery_notifications() {
+        let mut job = DeliveryJob {
+            id: "delivery-1".to_owned(),
+            project_id: "proj-1".to_owned(),
+            project_name: "Smith Wedding".to_owned(),
+            selected_files: vec![],
+            delivery_path: "/deliveries".to_owned(),
+            naming_template: None,
+            status: DeliveryStatus::Completed,
+            total_files: 1,
+            files_copied: 1,
+            total_bytes: 0,
+            bytes_transferred: 0,
+            created_at: "2024-01-01".to_owned(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+            manifest_path: None,
+            output_path: Some("/deliveries/Smith Wedding.zip".to_owned()),
+            options: DeliveryOptions {
+                package_as_zip: true,
+                ..DeliveryOptions::default()
+            },
+            files_skipped: 0,
+            files_unchanged: 0,
+        };
+
+        let completed = delivery_notification(&job).unwrap();
+        assert_eq!(completed.title, "Delivery complete");
+        assert_eq!(
+            completed.body,
+            "Smith Wedding: 1 file delivered to /deliveries/Smith Wedding.zip"
+        );
+
+        job.status = DeliveryStatus::Failed;
+        let failed = delivery_notification(&job).unwrap();
+        assert_eq!(failed.title, "Delivery failed");
+        assert_eq!(failed.body, "Smith Wedding: unknown error");
+
+        job.status = DeliveryStatus::Cancelled;
+        assert!(delivery_notification(&job).is_none());
+    }
+
+    #[test]
+    fn test_archive_and_drive_upload_notifications() {
+        let archive = ArchiveJob {
+            id: "archive-1".to_owned(),
+            project_id: "proj-1".to_owned(),
+            project_name: "Smith Wedding".to_owned(),
+            source_path: "/projects/Smith Wedding".to_owned(),
+            archive_path: "/archives".to_owned(),
+            compress: true,
+            compression_format: None,
+            password: None,
+            and_remove_source: false,
+            status: ArchiveStatus::Completed,
+            total_files: 40,
+            files_archived: 40,
+            total_bytes: 0,
+            bytes_transferred: 0,
+            created_at: "2024-01-01".to_owned(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+        };
+        assert_eq!(
+            archive_notification(&archive).unwrap().body,
+            "Smith Wedding: 40 files archived to /archives"
+        );
+
+        let mut upload = DriveUploadJob {
+            id: "upload-1".to_owned(),
+            project_name: "Smith Wedding".to_owned(),
+            folder_name: "Smith Wedding - Selects".to_owned(),
+            folder_id: "folder-1".to_owned(),
+            shareable_link: String::new(),
+            total_files: 10,
+            uploaded_files: 7,
+            failed_files: 3,
+            total_bytes: 0,
+            bytes_uploaded: 0,
+            status: DriveUploadStatus::Failed,
+            created_at: "2024-01-01".to_owned(),
+            completed_at: None,
+        };
+        let failed = drive_upload_notification(&upload).unwrap();
+        assert_eq!(failed.title, "Upload failed");
+        assert_eq!(
+            failed.body,
+            "Smith Wedding: 3 of 10 files could not be uploaded to Google Drive folder Smith Wedding - Selects"
+        );
+
+        upload.status = DriveUploadStatus::Cancelled;
+        assert!(drive_upload_notification(&upload).is_none());
+    }
+}
@@ -2,16 +2,25 @@
 //!
 //! Each project maps to a folder under `~/CreatorOps/Projects/` with the
 //! structure `YYYY-MM-DD_ClientName[_ShootType]/{RAW,Selects,Delivery}`.
-//! Project metadata is persisted in `SQLite` via the `Database` wrapper.
+//! Project metadata is persisted in `SQLite` via the `Database` wrapper. Shoot
+//! types are normalised against the list of known types kept in settings.
+//! Projects can be moved to another base directory, including on another volume,
+//! where the folder is copied, verified and then removed from the old one.
 
-use rusqlite::params;
+use chrono::NaiveDate;
+use rusqlite::{params, Connection, OptionalExtension};
 use serde::{Deserialize, Serialize};
 use std::fs;
+use std::path::{Path, PathBuf};
+use tauri::Manager;
 use uuid::Uuid;
+use walkdir::WalkDir;
 
 use crate::error::AppError;
+use crate::modules::checksums::hash_file;
+use crate::modules::client::validate_email;
 use crate::modules::db::Database;
-use crate::modules::file_utils::get_home_dir;
+use crate::modules::settings::{normalize_shoot_type, projects_directory};
 
 /// Core project entity stored in `SQLite` and serialised to the frontend.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -30,6 +39,30 @@ pub struct Project {
     pub deadline: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub client_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_email: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub client_phone: Option<String>,
+    /// Free-form labels such as "destination" or "paid", sorted ignoring case.
+    #[serde(default)]
+    pub tags: Vec<String>,
+}
+
+/// Details of a project to create, as the new-project form sends them.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct NewProject {
+    pub name: String,
+    /// Client name, replaced by the client's own name when `client_id` is set.
+    pub client_name: String,
+    /// Shoot date; `2024/6/1` and similar spellings are stored as `2024-06-01`.
+    pub date: String,
+    #[serde(default)]
+    pub shoot_type: String,
+    #[serde(default)]
+    pub deadline: Option<String>,
+    #[serde(default)]
+    pub client_id: Option<String>,
 }
 
 /// Workflow stage of a project from creation through archiving.
@@ -81,7 +114,90 @@ fn sanitize_path_component(s: &str) -> String {
         .collect()
 }
 
-/// Map a database row to a `Project`.
+/// Date formats accepted for a project date, tried in order.
+const PROJECT_DATE_FORMATS: &[&str] = &["%Y-%m-%d", "%Y/%m/%d", "%Y.%m.%d", "%Y%m%d"];
+
+/// Parse a project date in one of [`PROJECT_DATE_FORMATS`] and return it as
+/// `YYYY-MM-DD`, the form used in folder names.
+fn normalize_project_date(date: &str) -> Result<String, String> {
+    let trimmed = date.trim();
+    PROJECT_DATE_FORMATS
+        .iter()
+        .find_map(|format| NaiveDate::parse_from_str(trimmed, format).ok())
+        .map(|parsed| parsed.format("%Y-%m-%d").to_string())
+        .ok_or_else(|| format!("Invalid project date '{date}': expected YYYY-MM-DD"))
+}
+
+/// Create a new, empty folder for a project in `base_path`. An existing folder
+/// of the same name is never reused: the name gets a ` (2)`, ` (3)`, ... suffix
+/// instead, or an error is returned if `reject_existing` is set.
+fn create_project_folder(
+    base_path: &Path,
+    folder_name: &str,
+    reject_existing: bool,
+) -> Result<PathBuf, String> {
+    fs::create_dir_all(base_path).map_err(|e| e.to_string())?;
+
+    let mut attempt = 1_u32;
+    loop {
+        let candidate = if attempt == 1 {
+            base_path.join(folder_name)
+        } else {
+            base_path.join(format!("{folder_name} ({attempt})"))
+        };
+        // `create_dir` fails on an existing folder, so two projects can't both claim it
+        match fs::create_dir(&candidate) {
+            Ok(()) => return Ok(candidate),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                if reject_existing {
+                    return Err(format!(
+                        "Project folder already exists: {}",
+                        candidate.display()
+                    ));
+                }
+                attempt += 1;
+            }
+            Err(e) => return Err(e.to_string()),
+        }
+    }
+}
+
+/// Create the `RAW`, `Selects` and `Delivery` folders inside a new project folder.
+fn create_project_subfolders(project_path: &Path) -> Result<(), String> {
+    for subfolder in ["RAW/Photos", "RAW/Videos", "Selects", "Delivery"] {
+        fs::create_dir_all(project_path.join(subfolder)).map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Insert a newly created project's row.
+fn insert_project(db: &Database, project: &Project) -> Result<(), String> {
+    db.execute(|conn| {
+        conn.execute(
+            "INSERT INTO projects (id, name, client_name, date, shoot_type, status, folder_path,
+              created_at, updated_at, deadline, client_id)
+             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                &project.id,
+                &project.name,
+                &project.client_name,
+                &project.date,
+                &project.shoot_type,
+                project.status.to_string(),
+                &project.folder_path,
+                &project.created_at,
+                &project.updated_at,
+                &project.deadline,
+                &project.client_id,
+            ],
+        )?;
+        Ok(())
+    })
+    .map_err(|e| format!("Failed to insert project: {e}"))
+}
+
+/// Map a database row to a `Project`. Tags live in their own table and are
+/// left empty; see [`attach_project_tags`].
 pub fn map_project_row(row: &rusqlite::Row) -> rusqlite::Result<Project> {
     let status_str: String = row.get(5)?;
     let status = status_str.parse::<ProjectStatus>().map_err(|e| {
@@ -104,21 +220,94 @@ pub fn map_project_row(row: &rusqlite::Row) -> rusqlite::Result<Project> {
         updated_at: row.get(8)?,
         deadline: row.get(9)?,
         client_id: row.get(10)?,
+        client_email: row.get(11)?,
+        client_phone: row.get(12)?,
+        tags: Vec::new(),
     })
 }
 
+/// Tags of one project, sorted ignoring case.
+fn project_tags(conn: &Connection, project_id: &str) -> rusqlite::Result<Vec<String>> {
+    let mut stmt = conn
+        .prepare_cached("SELECT tag FROM tags WHERE project_id = ?1 ORDER BY tag COLLATE NOCASE")?;
+    let tags = stmt
+        .query_map(params![project_id], |row| row.get(0))?
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(tags)
+}
+
+/// Fill in the tags of each project.
+///
+/// # Errors
+///
+/// Returns error if the tags can't be queried
+pub fn attach_project_tags(conn: &Connection, projects: &mut [Project]) -> rusqlite::Result<()> {
+    for project in projects {
+        project.tags = project_tags(conn, &project.id)?;
+    }
+    Ok(())
+}
+
+/// Trim a tag, rejecting empty ones.
+fn normalize_tag(tag: &str) -> Result<&str, String> {
+    let tag = tag.trim();
+    if tag.is_empty() {
+        return Err("Tag cannot be empty".to_owned());
+    }
+    Ok(tag)
+}
+
 /// Create a new project, building its folder structure and inserting the DB record.
+///
+/// A folder name already in use gets a numbered suffix unless
+/// `reject_existing_folder` is set, in which case creation fails.
 #[tauri::command]
 pub async fn create_project(
     db: tauri::State<'_, Database>,
-    name: String,
-    client_name: String,
-    date: String,
-    shoot_type: String,
-    deadline: Option<String>,
-    client_id: Option<String>,
+    project: NewProject,
+    reject_existing_folder: Option<bool>,
+) -> Result<Project, String> {
+    let base_path = projects_directory(&db).map_err(String::from)?;
+
+    create_project_impl(
+        &db,
+        &base_path,
+        project,
+        reject_existing_folder.unwrap_or(false),
+    )
+}
+
+/// Core logic for creating a project under `base_path` (testable)
+///
+/// The shoot type is matched against the known shoot types, so "wedding" is
+/// stored as an existing "Wedding"; unknown types are recorded as new ones.
+/// When another project already has the folder name, the new folder gets a
+/// ` (2)`, ` (3)`, ... suffix, or the project is refused if
+/// `reject_existing_folder` is set.
+///
+/// # Errors
+///
+/// Returns error if the date isn't a valid date, the client doesn't exist, the
+/// folder exists and `reject_existing_folder` is set, or the folders or DB
+/// record can't be created
+pub fn create_project_impl(
+    db: &Database,
+    base_path: &Path,
+    project: NewProject,
+    reject_existing_folder: bool,
 ) -> Result<Project, String> {
+    let NewProject {
+        name,
+        client_name,
+        date,
+        shoot_type,
+        deadline,
+        client_id,
+    } = project;
+    let date = normalize_project_date(&date)?;
     let id = Uuid::new_v4().to_string();
+    let shoot_type = normalize_shoot_type(db, &shoot_type)
+        .map_err(|e| format!("Failed to record shoot type: {e}"))?;
 
     // When client_id is provided, look up the canonical client name
     let resolved_client_name = if let Some(ref cid) = client_id {
@@ -152,18 +341,7 @@ pub async fn create_project(
         format!("{date}_{sanitized_client}_{sanitized_type}")
     };
 
-    // Default location (should be configurable in settings)
-    let home_dir = get_home_dir()?;
-    let base_path = home_dir.join("CreatorOps").join("Projects");
-    let project_path = base_path.join(&folder_name);
-
-    // Create directory structure
-    fs::create_dir_all(&project_path).map_err(|e| e.to_string())?;
-    fs::create_dir_all(project_path.join("RAW/Photos")).map_err(|e| e.to_string())?;
-    fs::create_dir_all(project_path.join("RAW/Videos")).map_err(|e| e.to_string())?;
-    fs::create_dir_all(project_path.join("Selects")).map_err(|e| e.to_string())?;
-    fs::create_dir_all(project_path.join("Delivery")).map_err(|e| e.to_string())?;
-
+    let project_path = create_project_folder(base_path, &folder_name, reject_existing_folder)?;
     let now = chrono::Utc::now().to_rfc3339();
 
     let project = Project {
@@ -178,31 +356,19 @@ pub async fn create_project(
         updated_at: now,
         deadline: deadline.filter(|d| !d.is_empty()),
         client_id,
+        client_email: None,
+        client_phone: None,
+        tags: Vec::new(),
     };
 
-    // Insert into database
-    db.execute(|conn| {
-        conn.execute(
-            "INSERT INTO projects (id, name, client_name, date, shoot_type, status, folder_path,
-              created_at, updated_at, deadline, client_id)
-             VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
-            params![
-                &project.id,
-                &project.name,
-                &project.client_name,
-                &project.date,
-                &project.shoot_type,
-                project.status.to_string(),
-                &project.folder_path,
-                &project.created_at,
-                &project.updated_at,
-                &project.deadline,
-                &project.client_id,
-            ],
-        )?;
-        Ok(())
-    })
-    .map_err(|e| format!("Failed to insert project: {e}"))?;
+    // The folder is new and belongs to this project alone, so a failed setup
+    // removes it rather than leaving an orphan that pushes the next try to ` (2)`
+    if let Err(e) =
+        create_project_subfolders(&project_path).and_then(|()| insert_project(db, &project))
+    {
+        let _ = fs::remove_dir_all(&project_path);
+        return Err(e);
+    }
 
     Ok(project)
 }
@@ -210,23 +376,156 @@ pub async fn create_project(
 /// List all projects ordered by most recently updated.
 #[tauri::command]
 pub async fn list_projects(db: tauri::State<'_, Database>) -> Result<Vec<Project>, String> {
+    search_projects_impl(&db, &[]).map_err(|e| format!("Database error: {e}"))
+}
+
+/// Force refresh project cache (now just returns list)
+#[tauri::command]
+pub async fn refresh_projects(db: tauri::State<'_, Database>) -> Result<Vec<Project>, String> {
+    list_projects(db).await
+}
+
+/// Core logic for listing the projects carrying every one of `tags`, ignoring
+/// case, most recently updated first. No tags lists all projects (testable)
+///
+/// # Errors
+///
+/// Returns error if the projects or their tags can't be queried
+pub fn search_projects_impl(db: &Database, tags: &[String]) -> Result<Vec<Project>, AppError> {
+    let wanted: Vec<&str> = tags
+        .iter()
+        .map(|tag| tag.trim())
+        .filter(|tag| !tag.is_empty())
+        .collect();
+
     db.execute(|conn| {
         let mut stmt = conn
-            .prepare("SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id FROM projects ORDER BY updated_at DESC")?;
+            .prepare("SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id, client_email, client_phone FROM projects ORDER BY updated_at DESC")?;
 
-        let projects = stmt
+        let mut projects = stmt
             .query_map([], map_project_row)?
             .collect::<Result<Vec<_>, _>>()?;
-
+        attach_project_tags(conn, &mut projects)?;
+
+        projects.retain(|project| {
+            wanted.iter().all(|wanted| {
+                project
+                    .tags
+                    .iter()
+                    .any(|tag| tag.eq_ignore_ascii_case(wanted))
+            })
+        });
         Ok(projects)
     })
-    .map_err(|e| format!("Database error: {e}"))
 }
 
-/// Force refresh project cache (now just returns list)
+/// List the projects tagged with all of `tags`, or every project if none are given.
 #[tauri::command]
-pub async fn refresh_projects(db: tauri::State<'_, Database>) -> Result<Vec<Project>, String> {
-    list_projects(db).await
+pub async fn search_projects(
+    db: tauri::State<'_, Database>,
+    tags: Vec<String>,
+) -> Result<Vec<Project>, String> {
+    search_projects_impl(&db, &tags).map_err(|e| format!("Failed to search projects: {e}"))
+}
+
+/// Core logic for tagging a project; returns its tags. A tag it already has,
+/// in any case, is left as is (testable)
+///
+/// # Errors
+///
+/// Returns error if the tag is empty, the project doesn't exist, or the tag
+/// can't be saved
+pub fn add_project_tag_impl(
+    db: &Database,
+    project_id: &str,
+    tag: &str,
+) -> Result<Vec<String>, String> {
+    let tag = normalize_tag(tag)?;
+    get_project_by_id(db, project_id).map_err(String::from)?;
+
+    db.execute(|conn| {
+        conn.execute(
+            "INSERT OR IGNORE INTO tags (project_id, tag) VALUES (?1, ?2)",
+            params![project_id, tag],
+        )?;
+        Ok(project_tags(conn, project_id)?)
+    })
+    .map_err(|e| format!("Failed to add tag: {e}"))
+}
+
+/// Tag a project and return its tags.
+#[tauri::command]
+pub async fn add_project_tag(
+    db: tauri::State<'_, Database>,
+    project_id: String,
+    tag: String,
+) -> Result<Vec<String>, String> {
+    add_project_tag_impl(&db, &project_id, &tag)
+}
+
+/// Core logic for removing a tag from a project, ignoring case; returns its
+/// remaining tags (testable)
+///
+/// # Errors
+///
+/// Returns error if the project doesn't exist or the tag can't be removed
+pub fn remove_project_tag_impl(
+    db: &Database,
+    project_id: &str,
+    tag: &str,
+) -> Result<Vec<String>, String> {
+    get_project_by_id(db, project_id).map_err(String::from)?;
+
+    db.execute(|conn| {
+        conn.execute(
+            "DELETE FROM tags WHERE project_id = ?1 AND tag = ?2",
+            params![project_id, tag.trim()],
+        )?;
+        Ok(project_tags(conn, project_id)?)
+    })
+    .map_err(|e| format!("Failed to remove tag: {e}"))
+}
+
+/// Remove a tag from a project and return its remaining tags.
+#[tauri::command]
+pub async fn remove_project_tag(
+    db: tauri::State<'_, Database>,
+    project_id: String,
+    tag: String,
+) -> Result<Vec<String>, String> {
+    remove_project_tag_impl(&db, &project_id, &tag)
+}
+
+/// Core logic for listing the tags of one project, or every tag in use when no
+/// project is given, sorted ignoring case (testable)
+///
+/// # Errors
+///
+/// Returns error if the tags can't be queried
+pub fn list_project_tags_impl(
+    db: &Database,
+    project_id: Option<&str>,
+) -> Result<Vec<String>, AppError> {
+    db.execute(|conn| {
+        if let Some(project_id) = project_id {
+            return Ok(project_tags(conn, project_id)?);
+        }
+        let mut stmt = conn.prepare("SELECT DISTINCT tag FROM tags ORDER BY tag COLLATE NOCASE")?;
+        let tags = stmt
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(tags)
+    })
+}
+
+/// List a project's tags, or every tag in use to offer as filters.
+#[tauri::command]
+pub async fn list_project_tags(
+    db: tauri::State<'_, Database>,
+    project_id: Option<String>,
+) -> Result<Vec<String>, String> {
+    list_project_tags_impl(&db, project_id.as_deref())
+        .map_err(|e| format!("Failed to list tags: {e}"))
 }
 
 /// Delete a project: remove its folder from disk then delete the DB record.
@@ -252,6 +551,10 @@ pub async fn delete_project(
 
     // Delete from database (only after filesystem deletion succeeds)
     db.execute(|conn| {
+        conn.execute(
+            "DELETE FROM tags WHERE project_id = ?1",
+            params![project_id],
+        )?;
         conn.execute("DELETE FROM projects WHERE id = ?1", params![project_id])?;
         Ok(())
     })
@@ -283,6 +586,62 @@ pub async fn update_project_status(
     get_project_by_id(&db, &project_id).map_err(String::from)
 }
 
+/// Update the workflow status of several projects at once and return the updated records.
+///
+/// All rows are updated in a single transaction; if any ID is missing the whole batch is
+/// rolled back.
+#[tauri::command]
+pub async fn update_projects_status(
+    db: tauri::State<'_, Database>,
+    project_ids: Vec<String>,
+    new_status: ProjectStatus,
+) -> Result<Vec<Project>, String> {
+    update_projects_status_in_db(&db, &project_ids, &new_status).map_err(String::from)
+}
+
+/// Apply `new_status` to every project in `project_ids` atomically.
+///
+/// # Errors
+///
+/// Returns error if a project doesn't exist or the database write fails
+pub fn update_projects_status_in_db(
+    db: &Database,
+    project_ids: &[String],
+    new_status: &ProjectStatus,
+) -> Result<Vec<Project>, AppError> {
+    let now = chrono::Utc::now().to_rfc3339();
+
+    db.execute(|conn| {
+        let tx = conn.unchecked_transaction()?;
+        let mut projects = Vec::with_capacity(project_ids.len());
+
+        {
+            let mut update = tx.prepare(
+                "UPDATE projects SET status = ?1, updated_at = ?2 WHERE id = ?3",
+            )?;
+            let mut select = tx.prepare(
+                "SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id, client_email, client_phone FROM projects WHERE id = ?1",
+            )?;
+
+            for project_id in project_ids {
+                let updated = update.execute(params![new_status.to_string(), now, project_id])?;
+                if updated == 0 {
+                    // Dropping `tx` without committing rolls back earlier updates
+                    return Err(AppError::ProjectNotFound {
+                        id: project_id.clone(),
+                    });
+                }
+                let mut project = select.query_row(params![project_id], map_project_row)?;
+                project.tags = project_tags(&tx, project_id)?;
+                projects.push(project);
+            }
+        }
+
+        tx.commit()?;
+        Ok(projects)
+    })
+}
+
 /// Update a project's delivery deadline (pass `None` or empty string to clear).
 #[tauri::command]
 pub async fn update_project_deadline(
@@ -307,19 +666,199 @@ pub async fn update_project_deadline(
     get_project_by_id(&db, &project_id).map_err(String::from)
 }
 
+/// Set or clear the client email and phone stored on a project.
+#[tauri::command]
+pub async fn update_project_client(
+    db: tauri::State<'_, Database>,
+    project_id: String,
+    email: Option<String>,
+    phone: Option<String>,
+) -> Result<Project, String> {
+    update_project_client_impl(&db, &project_id, email, phone)
+}
+
+/// Core logic for updating a project's client contact (testable). `None` or an
+/// empty string clears a field.
+pub fn update_project_client_impl(
+    db: &Database,
+    project_id: &str,
+    email: Option<String>,
+    phone: Option<String>,
+) -> Result<Project, String> {
+    let email = email.map(|e| e.trim().to_owned()).filter(|e| !e.is_empty());
+    let phone = phone.map(|p| p.trim().to_owned()).filter(|p| !p.is_empty());
+
+    if let Some(ref e) = email {
+        if !validate_email(e) {
+            return Err("Invalid email format".to_owned());
+        }
+    }
+
+    let now = chrono::Utc::now().to_rfc3339();
+    let updated = db
+        .execute(|conn| {
+            Ok(conn.execute(
+                "UPDATE projects SET client_email = ?1, client_phone = ?2, updated_at = ?3 WHERE id = ?4",
+                params![email, phone, now, project_id],
+            )?)
+        })
+        .map_err(|e| format!("Failed to update project client: {e}"))?;
+    if updated == 0 {
+        return Err(AppError::ProjectNotFound {
+            id: project_id.to_owned(),
+        }
+        .into());
+    }
+
+    get_project_by_id(db, project_id).map_err(String::from)
+}
+
+/// Move a project's folder into `new_base_dir` and return the project with its
+/// new folder path.
+#[tauri::command]
+pub async fn move_project(
+    app_handle: tauri::AppHandle,
+    project_id: String,
+    new_base_dir: String,
+) -> Result<Project, String> {
+    tokio::task::spawn_blocking(move || {
+        let db = app_handle.state::<Database>();
+        move_project_impl(&db, &project_id, Path::new(&new_base_dir), |from, to| {
+            fs::rename(from, to)
+        })
+    })
+    .await
+    .map_err(|e| format!("Move task failed: {e}"))?
+}
+
+/// Core logic for moving a project (testable). The folder is moved with
+/// `rename`; the database points at the new folder only if that move succeeds.
+pub fn move_project_impl<R>(
+    db: &Database,
+    project_id: &str,
+    new_base_dir: &Path,
+    rename: R,
+) -> Result<Project, String>
+where
+    R: FnOnce(&Path, &Path) -> std::io::Result<()>,
+{
+    if !new_base_dir.is_absolute() {
+        return Err("New base directory must be an absolute path".to_owned());
+    }
+
+    let project = get_project_by_id(db, project_id)?;
+    let source = Path::new(&project.folder_path);
+    if !source.is_dir() {
+        return Err(format!("Project folder not found: {}", project.folder_path));
+    }
+    let folder_name = source.file_name().ok_or("Invalid project folder path")?;
+    let destination = new_base_dir.join(folder_name);
+    if destination.exists() {
+        return Err(format!("Folder already exists: {}", destination.display()));
+    }
+    fs::create_dir_all(new_base_dir)
+        .map_err(|e| format!("Failed to create {}: {e}", new_base_dir.display()))?;
+
+    let now = chrono::Utc::now().to_rfc3339();
+    set_project_folder(db, project_id, &destination.to_string_lossy(), &now)?;
+
+    if let Err(e) = move_folder(source, &destination, rename) {
+        if let Err(rollback) =
+            set_project_folder(db, project_id, &project.folder_path, &project.updated_at)
+        {
+            log::error!("Failed to restore folder path of project {project_id}: {rollback}");
+        }
+        return Err(e);
+    }
+
+    get_project_by_id(db, project_id).map_err(String::from)
+}
+
+fn set_project_folder(
+    db: &Database,
+    project_id: &str,
+    folder_path: &str,
+    updated_at: &str,
+) -> Result<(), AppError> {
+    db.execute(|conn| {
+        conn.execute(
+            "UPDATE projects SET folder_path = ?1, updated_at = ?2 WHERE id = ?3",
+            params![folder_path, updated_at, project_id],
+        )?;
+        Ok(())
+    })
+}
+
+/// OS error `rename` fails with when source and destination are on different volumes.
+#[cfg(unix)]
+const CROSS_DEVICE_ERROR: i32 = libc::EXDEV;
+#[cfg(windows)]
+const CROSS_DEVICE_ERROR: i32 = 17; // ERROR_NOT_SAME_DEVICE
+
+/// Move the folder `from` to `to` with `rename`. When they are on different
+/// volumes, copy the folder instead, check every copied file against its
+/// original, and only then remove the original.
+fn move_folder<R>(from: &Path, to: &Path, rename: R) -> Result<(), String>
+where
+    R: FnOnce(&Path, &Path) -> std::io::Result<()>,
+{
+    match rename(from, to) {
+        Ok(()) => Ok(()),
+        Err(e) if e.raw_os_error() == Some(CROSS_DEVICE_ERROR) => {
+            if let Err(e) = copy_folder_verified(from, to) {
+                let _ = fs::remove_dir_all(to);
+                return Err(e);
+            }
+            // The copy is complete, so a leftover original doesn't undo the move
+            if let Err(e) = fs::remove_dir_all(from) {
+                log::warn!(
+                    "Failed to remove moved project folder {}: {e}",
+                    from.display()
+                );
+            }
+            Ok(())
+        }
+        Err(e) => Err(format!("Failed to move project folder: {e}")),
+    }
+}
+
+fn copy_folder_verified(from: &Path, to: &Path) -> Result<(), String> {
+    for entry in WalkDir::new(from) {
+        let entry = entry.map_err(|e| e.to_string())?;
+        let relative = entry.path().strip_prefix(from).map_err(|e| e.to_string())?;
+        let dest = to.join(relative);
+
+        if entry.file_type().is_dir() {
+            fs::create_dir_all(&dest).map_err(|e| e.to_string())?;
+        } else if entry.file_type().is_file() {
+            fs::copy(entry.path(), &dest)
+                .map_err(|e| format!("Failed to copy {}: {e}", relative.display()))?;
+            if hash_file(entry.path())? != hash_file(&dest)? {
+                return Err(format!(
+                    "Copy of {} doesn't match the original",
+                    relative.display()
+                ));
+            }
+        }
+    }
+    Ok(())
+}
+
 /// Helper function to get project by ID
-fn get_project_by_id(db: &Database, project_id: &str) -> Result<Project, AppError> {
+pub fn get_project_by_id(db: &Database, project_id: &str) -> Result<Project, AppError> {
     db.execute(|conn| {
         let mut stmt = conn
-            .prepare("SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id FROM projects WHERE id = ?1")?;
+            .prepare("SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id, client_email, client_phone FROM projects WHERE id = ?1")?;
 
-        stmt.query_row(params![project_id], map_project_row).map_err(|e| {
+        let mut project = stmt.query_row(params![project_id], map_project_row).map_err(|e| {
             if e == rusqlite::Error::QueryReturnedNoRows {
                 AppError::ProjectNotFound { id: project_id.to_owned() }
             } else {
                 AppError::from(e)
             }
-        })
+        })?;
+        project.tags = project_tags(conn, project_id)?;
+        Ok(project)
     })
 }
 
@@ -332,6 +871,37 @@ pub async fn get_project(
     get_project_by_id(&db, &project_id).map_err(String::from)
 }
 
+/// Helper function to get the project stored at `folder_path`, if any.
+///
+/// A trailing path separator on `folder_path` is ignored.
+pub fn find_project_by_folder(
+    db: &Database,
+    folder_path: &str,
+) -> Result<Option<Project>, AppError> {
+    let folder_path = folder_path.trim_end_matches(['/', '\\']);
+    db.execute(|conn| {
+        let mut stmt = conn
+            .prepare("SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id, client_email, client_phone FROM projects WHERE folder_path = ?1")?;
+
+        let mut project = stmt
+            .query_row(params![folder_path], map_project_row)
+            .optional()?;
+        if let Some(project) = &mut project {
+            project.tags = project_tags(conn, &project.id)?;
+        }
+        Ok(project)
+    })
+}
+
+/// Fetch the project whose folder is `folder_path`, or `null` if none is.
+#[tauri::command]
+pub async fn get_project_by_folder(
+    db: tauri::State<'_, Database>,
+    folder_path: String,
+) -> Result<Option<Project>, String> {
+    find_project_by_folder(&db, &folder_path).map_err(String::from)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -446,6 +1016,9 @@ mod tests {
             updated_at: "2024-01-15T10:00:00Z".to_owned(),
             deadline: Some("2024-02-01".to_owned()),
             client_id: None,
+            client_email: None,
+            client_phone: None,
+            tags: Vec::new(),
         };
 
         let json = serde_json::to_string(&project).unwrap();
@@ -518,7 +1091,7 @@ mod tests {
         let projects = db
             .execute(|conn| {
                 let mut stmt = conn.prepare(
-                    "SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id FROM projects ORDER BY updated_at DESC",
+                    "SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id, client_email, client_phone FROM projects ORDER BY updated_at DESC",
                 )?;
                 let projects = stmt
                     .query_map([], map_project_row)?
@@ -576,7 +1149,7 @@ mod tests {
         let projects = db
             .execute(|conn| {
                 let mut stmt = conn.prepare(
-                    "SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id FROM projects ORDER BY updated_at DESC",
+                    "SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id, client_email, client_phone FROM projects ORDER BY updated_at DESC",
                 )?;
                 let projects = stmt
                     .query_map([], map_project_row)?
@@ -736,6 +1309,9 @@ mod tests {
             updated_at: "2024-01-15T10:00:00Z".to_owned(),
             deadline: Some("2024-07-01".to_owned()),
             client_id: None,
+            client_email: None,
+            client_phone: None,
+            tags: Vec::new(),
         };
 
         assert_eq!(project.id, "test-123");
@@ -759,6 +1335,9 @@ mod tests {
             updated_at: "2024-01-15T10:00:00Z".to_owned(),
             deadline: None,
             client_id: None,
+            client_email: None,
+            client_phone: None,
+            tags: Vec::new(),
         };
 
         assert_eq!(project.deadline, None);
@@ -788,6 +1367,9 @@ mod tests {
                 updated_at: "2024-01-01T00:00:00Z".to_owned(),
                 deadline: None,
                 client_id: None,
+                client_email: None,
+                client_phone: None,
+                tags: Vec::new(),
             };
 
             assert_eq!(project.status, status);
@@ -872,7 +1454,7 @@ mod tests {
 
         let projects = db.execute(|conn| {
             let mut stmt = conn.prepare(
-                "SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id FROM projects ORDER BY updated_at DESC"
+                "SELECT id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline, client_id, client_email, client_phone FROM projects ORDER BY updated_at DESC"
             )?;
             let projects = stmt
                 .query_map([], map_project_row)?
@@ -971,6 +1553,61 @@ mod tests {
         assert_eq!(project.status, ProjectStatus::Editing);
     }
 
+    fn insert_bulk_test_projects(db: &Database, ids: &[&str]) {
+        db.execute(|conn| {
+            for id in ids {
+                conn.execute(
+                    "INSERT INTO projects (id, name, client_name, date, shoot_type, status, folder_path, created_at, updated_at, deadline)
+                     VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)",
+                    rusqlite::params![id, "Bulk Test", "Client", "2024-01-01", "Wedding", "Delivered", "/path", "2024-01-01T10:00:00Z", "2024-01-01T10:00:00Z", None::<String>],
+                )?;
+            }
+            Ok(())
+        })
+        .unwrap();
+    }
+
+    #[test]
+    fn test_update_projects_status_archives_all() {
+        let (_temp_dir, db) = setup_test_db();
+        insert_bulk_test_projects(&db, &["bulk-1", "bulk-2", "bulk-3"]);
+
+        let ids = vec![
+            "bulk-1".to_owned(),
+            "bulk-2".to_owned(),
+            "bulk-3".to_owned(),
+        ];
+        let updated = update_projects_status_in_db(&db, &ids, &ProjectStatus::Archived).unwrap();
+
+        assert_eq!(updated.len(), 3);
+        assert!(updated.iter().all(|p| p.status == ProjectStatus::Archived));
+        for id in &ids {
+            let project = get_project_by_id(&db, id).unwrap();
+            assert_eq!(project.status, ProjectStatus::Archived);
+            assert_ne!(project.updated_at, "2024-01-01T10:00:00Z");
+        }
+    }
+
+    #[test]
+    fn test_update_projects_status_rolls_back_on_missing_id() {
+        let (_temp_dir, db) = setup_test_db();
+        insert_bulk_test_projects(&db, &["bulk-1", "bulk-2"]);
+
+        let ids = vec![
+            "bulk-1".to_owned(),
+            "missing".to_owned(),
+            "bulk-2".to_owned(),
+        ];
+        let result = update_projects_status_in_db(&db, &ids, &ProjectStatus::Archived);
+
+        assert!(matches!(result, Err(AppError::ProjectNotFound { ref id }) if id == "missing"));
+        for id in ["bulk-1", "bulk-2"] {
+            let project = get_project_by_id(&db, id).unwrap();
+            assert_eq!(project.status, ProjectStatus::Delivered);
+            assert_eq!(project.updated_at, "2024-01-01T10:00:00Z");
+        }
+    }
+
     #[tokio::test]
     async fn test_update_project_deadline_command() {
         let (_temp_dir, db) = setup_test_db();
@@ -1057,6 +1694,403 @@ mod tests {
         assert_eq!(folder_name, "2024-02-20_JaneSmith_Wedding");
     }
 
+    #[test]
+    fn test_normalize_project_date() {
+        assert_eq!(normalize_project_date("2024-06-01").unwrap(), "2024-06-01");
+        assert_eq!(normalize_project_date(" 2024/6/1 ").unwrap(), "2024-06-01");
+        assert_eq!(normalize_project_date("2024.06.01").unwrap(), "2024-06-01");
+        assert_eq!(normalize_project_date("20240601").unwrap(), "2024-06-01");
+
+        for invalid in ["2024/13/40", "2023-02-29", "01-06-2024", "June 1", ""] {
+            let err = normalize_project_date(invalid).unwrap_err();
+            assert!(err.contains("expected YYYY-MM-DD"), "{invalid}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_create_project_removes_its_folder_when_the_insert_fails() {
+        let (temp_dir, db) = setup_test_db();
+        let base_path = temp_dir.path().join("Projects");
+        db.execute(|conn| {
+            conn.execute_batch(
+                "CREATE TRIGGER reject_projects BEFORE INSERT ON projects
+                 BEGIN SELECT RAISE(ABORT, 'insert rejected'); END;",
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        let err = create_project_impl(
+            &db,
+            &base_path,
+            NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                shoot_type: "Wedding".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap_err();
+
+        assert!(err.contains("insert rejected"), "{err}");
+        assert_eq!(fs::read_dir(&base_path).unwrap().count(), 0);
+    }
+
+    #[test]
+    fn test_create_project_normalizes_or_rejects_date() {
+        let (temp_dir, db) = setup_test_db();
+        let base_path = temp_dir.path().join("Projects");
+
+        let project = create_project_impl(
+            &db,
+            &base_path,
+            NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024/6/1".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+        assert_eq!(project.date, "2024-06-01");
+        assert!(base_path.join("2024-06-01_JaneSmith").is_dir());
+
+        let err = create_project_impl(
+            &db,
+            &base_path,
+            NewProject {
+                name: "Broken".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024/13/40".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap_err();
+        assert!(err.contains("Invalid project date"));
+        assert_eq!(fs::read_dir(&base_path).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_colliding_projects_get_distinct_folders() {
+        let (temp_dir, db) = setup_test_db();
+        let base_path = temp_dir.path().join("Projects");
+        let create = |name: &str| {
+            create_project_impl(
+                &db,
+                &base_path,
+                NewProject {
+                    name: name.to_owned(),
+                    client_name: "Jane Smith".to_owned(),
+                    date: "2024-06-01".to_owned(),
+                    shoot_type: "Wedding".to_owned(),
+                    ..NewProject::default()
+                },
+                false,
+            )
+            .unwrap()
+        };
+
+        let first = create("Ceremony");
+        let second = create("Reception");
+        let third = create("After Party");
+
+        assert_eq!(
+            first.folder_path,
+            base_path
+                .join("2024-06-01_JaneSmith_Wedding")
+                .to_string_lossy()
+        );
+        assert_eq!(
+            second.folder_path,
+            base_path
+                .join("2024-06-01_JaneSmith_Wedding (2)")
+                .to_string_lossy()
+        );
+        assert_eq!(
+            third.folder_path,
+            base_path
+                .join("2024-06-01_JaneSmith_Wedding (3)")
+                .to_string_lossy()
+        );
+        assert!(Path::new(&second.folder_path).join("RAW/Photos").is_dir());
+    }
+
+    #[test]
+    fn test_colliding_project_can_be_rejected() {
+        let (temp_dir, db) = setup_test_db();
+        let base_path = temp_dir.path().join("Projects");
+        fs::create_dir_all(base_path.join("2024-06-01_JaneSmith")).unwrap();
+
+        let err = create_project_impl(
+            &db,
+            &base_path,
+            NewProject {
+                name: "Smith Portraits".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                ..NewProject::default()
+            },
+            true,
+        )
+        .unwrap_err();
+
+        assert!(err.contains("Project folder already exists"));
+        assert_eq!(fs::read_dir(&base_path).unwrap().count(), 1);
+        let count: i64 = db
+            .execute(|conn| {
+                Ok(conn.query_row("SELECT COUNT(*) FROM projects", [], |row| row.get(0))?)
+            })
+            .unwrap();
+        assert_eq!(count, 0);
+    }
+
+    fn create_tagged_project(
+        db: &Database,
+        base_path: &Path,
+        client: &str,
+        tags: &[&str],
+    ) -> String {
+        let project = create_project_impl(
+            db,
+            base_path,
+            NewProject {
+                name: format!("{client} Shoot"),
+                client_name: client.to_owned(),
+                date: "2024-06-01".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+        for tag in tags {
+            add_project_tag_impl(db, &project.id, tag).unwrap();
+        }
+        project.id
+    }
+
+    #[test]
+    fn test_project_tags_are_added_listed_and_removed() {
+        let (temp_dir, db) = setup_test_db();
+        let base_path = temp_dir.path().join("Projects");
+        let id = create_tagged_project(&db, &base_path, "Jane Smith", &["paid", "Destination"]);
+
+        // Adding a tag again in another case keeps the original
+        let tags = add_project_tag_impl(&db, &id, " PAID ").unwrap();
+        assert_eq!(tags, vec!["Destination", "paid"]);
+        assert_eq!(get_project_by_id(&db, &id).unwrap().tags, tags);
+        assert!(add_project_tag_impl(&db, &id, "  ").is_err());
+        assert!(add_project_tag_impl(&db, "missing", "paid").is_err());
+
+        let tags = remove_project_tag_impl(&db, &id, "destination").unwrap();
+        assert_eq!(tags, vec!["paid"]);
+        assert_eq!(list_project_tags_impl(&db, Some(&id)).unwrap(), tags);
+    }
+
+    #[test]
+    fn test_search_projects_filters_by_tags() {
+        let (temp_dir, db) = setup_test_db();
+        let base_path = temp_dir.path().join("Projects");
+        let paid_destination =
+            create_tagged_project(&db, &base_path, "Jane Smith", &["destination", "paid"]);
+        let paid_commercial =
+            create_tagged_project(&db, &base_path, "Acme", &["commercial", "Paid"]);
+        let untagged = create_tagged_project(&db, &base_path, "Sam Lee", &[]);
+
+        let ids = |tags: &[&str]| -> Vec<String> {
+            let tags: Vec<String> = tags.iter().map(|tag| (*tag).to_owned()).collect();
+            let mut ids: Vec<String> = search_projects_impl(&db, &tags)
+                .unwrap()
+                .into_iter()
+                .map(|project| project.id)
+                .collect();
+            ids.sort();
+            ids
+        };
+        let sorted = |mut ids: Vec<String>| {
+            ids.sort();
+            ids
+        };
+
+        assert_eq!(
+            ids(&["PAID"]),
+            sorted(vec![paid_destination.clone(), paid_commercial.clone()])
+        );
+        assert_eq!(
+            ids(&["paid", "destination"]),
+            vec![paid_destination.clone()]
+        );
+        assert!(ids(&["editorial"]).is_empty());
+        assert_eq!(
+            ids(&[]),
+            sorted(vec![paid_destination, paid_commercial, untagged])
+        );
+        assert_eq!(
+            list_project_tags_impl(&db, None).unwrap(),
+            vec!["commercial", "destination", "paid"]
+        );
+    }
+
+    #[test]
+    fn test_create_project_with_known_shoot_type() {
+        let (temp_dir, db) = setup_test_db();
+        let base_path = temp_dir.path().join("Projects");
+        normalize_shoot_type(&db, "Wedding").unwrap();
+
+        let project = create_project_impl(
+            &db,
+            &base_path,
+            NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                shoot_type: "wedding".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        assert_eq!(project.shoot_type, "Wedding");
+        assert_eq!(
+            project.folder_path,
+            base_path
+                .join("2024-06-01_JaneSmith_Wedding")
+                .to_string_lossy()
+        );
+        assert!(base_path
+            .join("2024-06-01_JaneSmith_Wedding/RAW/Photos")
+            .is_dir());
+        assert_eq!(
+            get_project_by_id(&db, &project.id).unwrap().shoot_type,
+            "Wedding"
+        );
+
+        // A new type is kept as entered and offered from then on
+        let project = create_project_impl(
+            &db,
+            &base_path,
+            NewProject {
+                name: "Baby Lee".to_owned(),
+                client_name: "Sam Lee".to_owned(),
+                date: "2024-06-02".to_owned(),
+                shoot_type: "Newborn".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+        assert_eq!(project.shoot_type, "Newborn");
+        assert_eq!(
+            crate::modules::settings::shoot_types(&db).unwrap(),
+            vec!["Wedding".to_owned(), "Newborn".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_project_resolves_by_id_and_folder() {
+        let (temp_dir, db) = setup_test_db();
+        let project = create_project_impl(
+            &db,
+            &temp_dir.path().join("Projects"),
+            NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                shoot_type: "Wedding".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        let by_id = get_project_by_id(&db, &project.id).unwrap();
+        assert_eq!(by_id.folder_path, project.folder_path);
+
+        let by_folder = find_project_by_folder(&db, &project.folder_path)
+            .unwrap()
+            .unwrap();
+        assert_eq!(by_folder.id, project.id);
+        let with_slash = format!("{}/", project.folder_path);
+        assert_eq!(
+            find_project_by_folder(&db, &with_slash)
+                .unwrap()
+                .unwrap()
+                .id,
+            project.id
+        );
+
+        let elsewhere = temp_dir.path().join("Elsewhere");
+        assert!(find_project_by_folder(&db, &elsewhere.to_string_lossy())
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_update_project_client_sets_and_clears_contact() {
+        let (temp_dir, db) = setup_test_db();
+        let project = create_project_impl(
+            &db,
+            &temp_dir.path().join("Projects"),
+            NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+        assert!(project.client_email.is_none());
+
+        let updated = update_project_client_impl(
+            &db,
+            &project.id,
+            Some(" jane@example.com ".to_owned()),
+            Some("+31 6 1234 5678".to_owned()),
+        )
+        .unwrap();
+        assert_eq!(updated.client_email.as_deref(), Some("jane@example.com"));
+        assert_eq!(updated.client_phone.as_deref(), Some("+31 6 1234 5678"));
+
+        let stored = get_project_by_id(&db, &project.id).unwrap();
+        assert_eq!(stored.client_email, updated.client_email);
+        assert_eq!(stored.client_phone, updated.client_phone);
+
+        let cleared =
+            update_project_client_impl(&db, &project.id, Some(String::new()), None).unwrap();
+        assert!(cleared.client_email.is_none());
+        assert!(cleared.client_phone.is_none());
+    }
+
+    #[test]
+    fn test_update_project_client_rejects_bad_input() {
+        let (temp_dir, db) = setup_test_db();
+        let project = create_project_impl(
+            &db,
+            &temp_dir.path().join("Projects"),
+            NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+
+        let err =
+            update_project_client_impl(&db, &project.id, Some("not-an-email".to_owned()), None)
+                .unwrap_err();
+        assert_eq!(err, "Invalid email format");
+        assert!(update_project_client_impl(&db, "missing", None, None)
+            .unwrap_err()
+            .contains("Project not found"));
+    }
+
     #[test]
     fn test_deadline_filtering() {
         // Test that empty string deadline is converted to None
@@ -1068,4 +2102,97 @@ mod tests {
         let filtered = deadline.filter(|d| !d.is_empty());
         assert_eq!(filtered, Some("2024-03-01".to_owned()));
     }
+
+    fn create_movable_project(temp_dir: &TempDir, db: &Database) -> Project {
+        let project = create_project_impl(
+            db,
+            &temp_dir.path().join("Projects"),
+            NewProject {
+                name: "Smith Wedding".to_owned(),
+                client_name: "Jane Smith".to_owned(),
+                date: "2024-06-01".to_owned(),
+                shoot_type: "Wedding".to_owned(),
+                ..NewProject::default()
+            },
+            false,
+        )
+        .unwrap();
+        fs::write(
+            Path::new(&project.folder_path).join("RAW/Photos/IMG_0001.JPG"),
+            b"photo",
+        )
+        .unwrap();
+        project
+    }
+
+    #[test]
+    fn test_move_project_within_volume() {
+        let (temp_dir, db) = setup_test_db();
+        let project = create_movable_project(&temp_dir, &db);
+        let new_base = temp_dir.path().join("Archive Drive");
+
+        let moved = move_project_impl(&db, &project.id, &new_base, |from, to| fs::rename(from, to))
+            .unwrap();
+
+        let folder = new_base.join(Path::new(&project.folder_path).file_name().unwrap());
+        assert_eq!(Path::new(&moved.folder_path), folder);
+        assert_eq!(
+            get_project_by_id(&db, &project.id).unwrap().folder_path,
+            moved.folder_path
+        );
+        assert!(folder.join("RAW/Photos/IMG_0001.JPG").is_file());
+        assert!(!Path::new(&project.folder_path).exists());
+    }
+
+    #[test]
+    fn test_move_project_across_volumes_copies_and_removes_original() {
+        let (temp_dir, db) = setup_test_db();
+        let project = create_movable_project(&temp_dir, &db);
+        let new_base = temp_dir.path().join("Other Disk");
+
+        let moved = move_project_impl(&db, &project.id, &new_base, |_, _| {
+            Err(std::io::Error::from_raw_os_error(CROSS_DEVICE_ERROR))
+        })
+        .unwrap();
+
+        let folder = Path::new(&moved.folder_path);
+        assert_eq!(
+            folder,
+            new_base.join(Path::new(&project.folder_path).file_name().unwrap())
+        );
+        assert_eq!(
+            fs::read(folder.join("RAW/Photos/IMG_0001.JPG")).unwrap(),
+            b"photo"
+        );
+        assert!(folder.join("Selects").is_dir());
+        assert!(!Path::new(&project.folder_path).exists());
+    }
+
+    #[test]
+    fn test_failed_move_keeps_project_where_it_was() {
+        let (temp_dir, db) = setup_test_db();
+        let project = create_movable_project(&temp_dir, &db);
+        let new_base = temp_dir.path().join("Full Disk");
+
+        let err = move_project_impl(&db, &project.id, &new_base, |_, _| {
+            Err(std::io::ErrorKind::PermissionDenied.into())
+        })
+        .unwrap_err();
+
+        assert!(err.starts_with("Failed to move project folder"));
+        let unchanged = get_project_by_id(&db, &project.id).unwrap();
+        assert_eq!(unchanged.folder_path, project.folder_path);
+        assert_eq!(unchanged.updated_at, project.updated_at);
+        assert!(Path::new(&project.folder_path)
+            .join("RAW/Photos/IMG_0001.JPG")
+            .is_file());
+
+        let taken = move_project_impl(
+            &db,
+            &project.id,
+            &temp_dir.path().join("Projects"),
+            |from, to| fs::rename(from, to),
+        );
+        assert!(taken.unwrap_err().contains("already exists"));
+    }
 }
@@ -0,0 +1,483 @@
+//! Summary of the job queues for the dashboard.
+//!
+//! Counts backups, deliveries, Drive uploads and archives by whether they are
+//! running, waiting or failed, so one call tells whether the app is busy, and
+//! estimates how long the queued transfers will take to finish.
+
+use crate::modules::archive::ArchiveStatus;
+use crate::modules::backup::BackupStatus;
+use crate::modules::delivery::DeliveryStatus;
+use crate::modules::google_drive::DriveUploadStatus;
+use crate::state::AppState;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Job counts of one queue, or of all of them.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueCounts {
+    /// Jobs running now; paused uploads count as running.
+    pub active: usize,
+    /// Jobs waiting to start.
+    pub pending: usize,
+    pub failed: usize,
+}
+
+impl QueueCounts {
+    fn add(&mut self, state: JobState) {
+        match state {
+            JobState::Active => self.active += 1,
+            JobState::Pending => self.pending += 1,
+            JobState::Failed => self.failed += 1,
+            JobState::Finished => {}
+        }
+    }
+}
+
+impl std::ops::Add for QueueCounts {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Self {
+            active: self.active + other.active,
+            pending: self.pending + other.pending,
+            failed: self.failed + other.failed,
+        }
+    }
+}
+
+/// Job counts per queue and across all queues.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct QueueSummary {
+    pub backups: QueueCounts,
+    pub deliveries: QueueCounts,
+    pub uploads: QueueCounts,
+    pub archives: QueueCounts,
+    pub total: QueueCounts,
+    /// Whether any job is running or waiting to start.
+    pub is_busy: bool,
+}
+
+/// Bytes still to transfer by the unfinished backups, deliveries and Drive
+/// uploads, with a rough time to finish.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WorkloadEstimate {
+    pub backup_bytes: u64,
+    pub delivery_bytes: u64,
+    pub upload_bytes: u64,
+    pub remaining_bytes: u64,
+    /// Combined rate of the running jobs so far, once any has transferred data.
+    pub bytes_per_second: Option<f64>,
+    /// Seconds until the remaining bytes are through at `bytes_per_second`.
+    pub eta_seconds: Option<u64>,
+}
+
+/// Remaining bytes and throughput of one queue.
+#[derive(Debug, Default)]
+struct Workload {
+    remaining: u64,
+    bytes_per_second: f64,
+}
+
+impl Workload {
+    /// Count a job's remaining bytes if it is running or waiting, and its
+    /// average rate since `started_at` if it is running.
+    fn add(
+        &mut self,
+        state: JobState,
+        total_bytes: u64,
+        transferred: u64,
+        started_at: Option<&str>,
+        now: DateTime<Utc>,
+    ) {
+        if !matches!(state, JobState::Active | JobState::Pending) {
+            return;
+        }
+        self.remaining += total_bytes.saturating_sub(transferred);
+
+        let elapsed = started_at.and_then(|started| seconds_since(started, now));
+        if let (JobState::Active, Some(elapsed)) = (state, elapsed) {
+            self.bytes_per_second += transferred as f64 / elapsed as f64;
+        }
+    }
+}
+
+/// Whole seconds from `timestamp` to `now`, or `None` if it doesn't parse or
+/// isn't in the past. Jobs stamp either Unix seconds or RFC 3339.
+fn seconds_since(timestamp: &str, now: DateTime<Utc>) -> Option<i64> {
+    let started = timestamp.parse::<i64>().map_or_else(
+        |_| {
+            DateTime::parse_from_rfc3339(timestamp)
+                .ok()
+                .map(|t| t.timestamp())
+        },
+        Some,
+    )?;
+    Some(now.timestamp() - started).filter(|elapsed| *elapsed > 0)
+}
+
+/// Which count a job's status falls under.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum JobState {
+    Active,
+    Pending,
+    Failed,
+    /// Completed or cancelled; not counted.
+    Finished,
+}
+
+impl From<&BackupStatus> for JobState {
+    fn from(status: &BackupStatus) -> Self {
+        match status {
+            BackupStatus::InProgress => Self::Active,
+            BackupStatus::Pending => Self::Pending,
+            BackupStatus::Failed => Self::Failed,
+            BackupStatus::Completed | BackupStatus::Cancelled => Self::Finished,
+        }
+    }
+}
+
+impl From<&DeliveryStatus> for JobState {
+    fn from(status: &DeliveryStatus) -> Self {
+        match status {
+            DeliveryStatus::InProgress => Self::Active,
+            DeliveryStatus::Pending => Self::Pending,
+            DeliveryStatus::Failed => Self::Failed,
+            DeliveryStatus::Completed | DeliveryStatus::Cancelled => Self::Finished,
+        }
+    }
+}
+
+impl From<&DriveUploadStatus> for JobState {
+    fn from(status: &DriveUploadStatus) -> Self {
+        match status {
+            DriveUploadStatus::InProgress | DriveUploadStatus::Paused => Self::Active,
+            DriveUploadStatus::Failed => Self::Failed,
+            DriveUploadStatus::Completed | DriveUploadStatus::Cancelled => Self::Finished,
+        }
+    }
+}
+
+impl From<&ArchiveStatus> for JobState {
+    fn from(status: &ArchiveStatus) -> Self {
+        match status {
+            ArchiveStatus::InProgress => Self::Active,
+            ArchiveStatus::Pending => Self::Pending,
+            ArchiveStatus::Failed => Self::Failed,
+            ArchiveStatus::Completed | ArchiveStatus::Cancelled => Self::Finished,
+        }
+    }
+}
+
+fn count<S: Into<JobState>>(statuses: impl IntoIterator<Item = S>) -> QueueCounts {
+    let mut counts = QueueCounts::default();
+    for status in statuses {
+        counts.add(status.into());
+    }
+    counts
+}
+
+/// Core logic for summarising the job queues (testable)
+pub async fn get_queue_summary_impl(state: &AppState) -> QueueSummary {
+    let backups = count(state.backup_queue.lock().await.values().map(|j| &j.status));
+    let deliveries = count(
+        state
+            .delivery_queue
+            .lock()
+            .await
+            .values()
+            .map(|j| &j.status),
+    );
+    let uploads = count(
+        state
+            .drive_upload_queue
+            .lock()
+            .await
+            .values()
+            .map(|j| &j.status),
+    );
+    let archives = count(state.archive_queue.lock().await.values().map(|j| &j.status));
+    let total = backups + deliveries + uploads + archives;
+
+    QueueSummary {
+        backups,
+        deliveries,
+        uploads,
+        archives,
+        total,
+        is_busy: total.active + total.pending > 0,
+    }
+}
+
+/// Core logic for estimating the queued workload as of `now` (testable)
+pub async fn get_pending_workload_impl(state: &AppState, now: DateTime<Utc>) -> WorkloadEstimate {
+    let mut backups = Workload::default();
+    for job in state.backup_queue.lock().await.values() {
+        backups.add(
+            (&job.status).into(),
+            job.total_bytes,
+            job.bytes_transferred,
+            job.started_at.as_deref(),
+            now,
+        );
+    }
+    let mut deliveries = Workload::default();
+    for job in state.delivery_queue.lock().await.values() {
+        deliveries.add(
+            (&job.status).into(),
+            job.total_bytes,
+            job.bytes_transferred,
+            job.started_at.as_deref(),
+            now,
+        );
+    }
+    let mut uploads = Workload::default();
+    for job in state.drive_upload_queue.lock().await.values() {
+        uploads.add(
+            (&job.status).into(),
+            job.total_bytes,
+            job.bytes_uploaded,
+            Some(&job.created_at),
+            now,
+        );
+    }
+
+    let remaining_bytes = backups.remaining + deliveries.remaining + uploads.remaining;
+    let rate = backups.bytes_per_second + deliveries.bytes_per_second + uploads.bytes_per_second;
+    let bytes_per_second = (rate > 0.0).then_some(rate);
+
+    WorkloadEstimate {
+        backup_bytes: backups.remaining,
+        delivery_bytes: deliveries.remaining,
+        upload_bytes: uploads.remaining,
+        remaining_bytes,
+        bytes_per_second,
+        eta_seconds: bytes_per_second.map(|rate| (remaining_bytes as f64 / rate).ceil() as u64),
+    }
+}
+
+/// Estimate the bytes left across the backup, delivery and upload queues and how
+/// long they will take at the current rate.
+#[tauri::command]
+pub async fn get_pending_workload(
+    state: tauri::State<'_, AppState>,
+) -> Result<WorkloadEstimate, String> {
+    Ok(get_pending_workload_impl(&state, Utc::now()).await)
+}
+
+/// Count running, waiting and failed jobs in every queue.
+#[tauri::command]
+pub async fn get_queue_summary(state: tauri::State<'_, AppState>) -> Result<QueueSummary, String> {
+    Ok(get_queue_summary_impl(&state).await)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::modules::backup::queue_backup_impl;
+    use crate::modules::delivery::{create_delivery_impl, DeliveryOptions};
+    use crate::modules::google_drive::DriveUploadJob;
+    use tempfile::TempDir;
+
+    #[tokio::test]
+    async fn test_empty_queues_are_idle() {
+        let state = AppState::default();
+
+        let summary = get_queue_summary_impl(&state).await;
+
+        assert_eq!(summary, QueueSummary::default());
+        assert!(!summary.is_busy);
+    }
+
+    #[tokio::test]
+    async fn test_summary_counts_jobs_across_queues() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("project");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join("IMG_0001.JPG"), b"photo").unwrap();
+        let state = AppState::default();
+
+        for name in ["Drive A", "Drive B"] {
+            queue_backup_impl(
+                &state.backup_queue,
+                "proj-1".to_owned(),
+                "Wedding".to_owned(),
+                source.to_string_lossy().to_string(),
+                name.to_owned(),
+                name.to_owned(),
+                temp_dir.path().join(name).to_string_lossy().to_string(),
+            )
+            .await
+            .unwrap();
+        }
+        if let Some(job) = state.backup_queue.lock().await.values_mut().next() {
+            job.status = BackupStatus::Failed;
+        }
+
+        let upload = |id: &str, status| DriveUploadJob {
+            id: id.to_owned(),
+            project_name: "Wedding".to_owned(),
+            folder_name: "Wedding".to_owned(),
+            folder_id: "folder-1".to_owned(),
+            shareable_link: String::new(),
+            total_files: 10,
+            uploaded_files: 0,
+            failed_files: 0,
+            total_bytes: 0,
+            bytes_uploaded: 0,
+            status,
+            created_at: "2025-01-15T10:00:00Z".to_owned(),
+            completed_at: None,
+        };
+        {
+            let mut uploads = state.drive_upload_queue.lock().await;
+            uploads.insert("up-1".to_owned(), upload("up-1", DriveUploadStatus::Paused));
+            uploads.insert(
+                "up-2".to_owned(),
+                upload("up-2", DriveUploadStatus::Completed),
+            );
+        }
+
+        let summary = get_queue_summary_impl(&state).await;
+
+        assert_eq!(
+            summary.backups,
+            QueueCounts {
+                active: 0,
+                pending: 1,
+                failed: 1,
+            }
+        );
+        assert_eq!(
+            summary.uploads,
+            QueueCounts {
+                active: 1,
+                pending: 0,
+                failed: 0,
+            }
+        );
+        assert_eq!(summary.deliveries, QueueCounts::default());
+        assert_eq!(summary.archives, QueueCounts::default());
+        assert_eq!(
+            summary.total,
+            QueueCounts {
+                active: 1,
+                pending: 1,
+                failed: 1,
+            }
+        );
+        assert!(summary.is_busy);
+    }
+
+    #[tokio::test]
+    async fn test_workload_sums_remaining_bytes() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("project");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join("IMG_0001.JPG"), vec![0_u8; 1000]).unwrap();
+        let state = AppState::default();
+        let now = Utc::now();
+        let started = (now.timestamp() - 10).to_string();
+
+        for name in ["Drive A", "Drive B", "Drive C"] {
+            queue_backup_impl(
+                &state.backup_queue,
+                "proj-1".to_owned(),
+                "Wedding".to_owned(),
+                source.to_string_lossy().to_string(),
+                name.to_owned(),
+                name.to_owned(),
+                temp_dir.path().join(name).to_string_lossy().to_string(),
+            )
+            .await
+            .unwrap();
+        }
+        let ids: Vec<String> = state.backup_queue.lock().await.keys().cloned().collect();
+        if let Some(job) = state.backup_queue.lock().await.get_mut(&ids[0]) {
+            job.status = BackupStatus::InProgress;
+            job.bytes_transferred = 400;
+            job.started_at = Some(started.clone());
+        }
+        if let Some(job) = state.backup_queue.lock().await.get_mut(&ids[1]) {
+            job.status = BackupStatus::Completed;
+        }
+
+        let delivery = create_delivery_impl(
+            &state.delivery_queue,
+            "proj-1".to_owned(),
+            "Wedding".to_owned(),
+            vec![source.join("IMG_0001.JPG").to_string_lossy().to_string()],
+            temp_dir
+                .path()
+                .join("delivery")
+                .to_string_lossy()
+                .to_string(),
+            None,
+            DeliveryOptions::default(),
+        )
+        .await
+        .unwrap();
+        if let Some(job) = state.delivery_queue.lock().await.get_mut(&delivery.id) {
+            job.status = DeliveryStatus::InProgress;
+            job.bytes_transferred = 200;
+            job.started_at = Some(started);
+        }
+
+        state.drive_upload_queue.lock().await.insert(
+            "up-1".to_owned(),
+            DriveUploadJob {
+                id: "up-1".to_owned(),
+                project_name: "Wedding".to_owned(),
+                folder_name: "Wedding".to_owned(),
+                folder_id: "folder-1".to_owned(),
+                shareable_link: String::new(),
+                total_files: 2,
+                uploaded_files: 1,
+                failed_files: 0,
+                total_bytes: 3000,
+                bytes_uploaded: 1000,
+                status: DriveUploadStatus::InProgress,
+                created_at: (now - chrono::Duration::seconds(20)).to_rfc3339(),
+                completed_at: None,
+            },
+        );
+
+        let workload = get_pending_workload_impl(&state, now).await;
+
+        assert_eq!(workload.backup_bytes, 600 + 1000);
+        assert_eq!(workload.delivery_bytes, 800);
+        assert_eq!(workload.upload_bytes, 2000);
+        assert_eq!(workload.remaining_bytes, 4400);
+        // 40 + 20 + 50 bytes per second
+        assert_eq!(workload.bytes_per_second, Some(110.0));
+        assert_eq!(workload.eta_seconds, Some(40));
+    }
+
+    #[tokio::test]
+    async fn test_workload_without_progress_has_no_eta() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("project");
+        std::fs::create_dir_all(&source).unwrap();
+        std::fs::write(source.join("IMG_0001.JPG"), b"photo").unwrap();
+        let state = AppState::default();
+        queue_backup_impl(
+            &state.backup_queue,
+            "proj-1".to_owned(),
+            "Wedding".to_owned(),
+            source.to_string_lossy().to_string(),
+            "drive".to_owned(),
+            "Drive".to_owned(),
+            temp_dir.path().join("Drive").to_string_lossy().to_string(),
+        )
+        .await
+        .unwrap();
+
+        let workload = get_pending_workload_impl(&state, Utc::now()).await;
+
+        assert_eq!(workload.remaining_bytes, 5);
+        assert_eq!(workload.bytes_per_second, None);
+        assert_eq!(workload.eta_seconds, None);
+    }
+}
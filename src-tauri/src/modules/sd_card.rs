@@ -1,8 +1,20 @@
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
+use std::collections::HashSet;
 use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+use tauri::Emitter;
+use tokio::sync::Semaphore;
+use tokio::task::JoinSet;
 use walkdir::WalkDir;
 
+use crate::modules::db::Database;
+use crate::modules::file_utils::calculate_file_hash;
+use crate::modules::metadata::{resolve_capture_date, CAPTURE_DATE_FORMAT};
+use crate::modules::project::get_project_by_id;
+
 #[cfg(target_os = "macos")]
 use std::process::Command;
 
@@ -16,6 +28,8 @@ pub struct SDCard {
     pub file_count: usize,
     pub device_type: String,
     pub is_removable: bool,
+    /// Volume UUID reported by `diskutil`, used to tell cards apart.
+    pub serial: Option<String>,
 }
 
 /// Determines if a device type should be excluded from the scan results.
@@ -25,66 +39,124 @@ fn should_exclude_device_type(device_type: &str) -> bool {
     device_type == "Disk Image" || device_type == "Internal Drive" || device_type == "Unknown"
 }
 
+/// Maximum number of volumes inspected in parallel during a scan.
+const MAX_CONCURRENT_SCANS: usize = 4;
+
+/// How long a single volume may take to inspect before it is skipped.
+const VOLUME_SCAN_TIMEOUT: Duration = Duration::from_secs(30);
+
 /// Scan /Volumes/ directory for mounted SD cards
+///
+/// Volumes are inspected concurrently so a slow or faulty card does not delay the others.
 #[tauri::command]
 pub async fn scan_sd_cards() -> Result<Vec<SDCard>, String> {
-    let volumes_path = Path::new("/Volumes");
+    let volumes = list_mounted_volumes(Path::new("/Volumes"));
+    Ok(scan_volumes_concurrently(volumes, inspect_volume, VOLUME_SCAN_TIMEOUT).await)
+}
+
+/// Enumerate mounted volume directories, skipping macOS system volumes.
+fn list_mounted_volumes(volumes_path: &Path) -> Vec<(String, PathBuf)> {
+    let Ok(entries) = fs::read_dir(volumes_path) else {
+        return Vec::new();
+    };
 
-    if !volumes_path.exists() {
-        return Ok(Vec::new());
+    entries
+        .flatten()
+        .filter(|entry| entry.metadata().is_ok_and(|m| m.is_dir()))
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            // Skip system volumes
+            if name == "Macintosh HD"
+                || name == "Data"
+                || name == "Preboot"
+                || name == "Recovery"
+                || name == "VM"
+                || name == "Update"
+                || name.starts_with('.')
+            {
+                return None;
+            }
+
+            Some((name, entry.path()))
+        })
+        .collect()
+}
+
+/// Inspect a single volume, returning `None` for device types excluded from the scan.
+fn inspect_volume(name: String, path: &Path) -> Option<SDCard> {
+    // Get device information early to filter before expensive operations
+    let (device_type, is_removable, serial) = get_device_info(&path.to_string_lossy());
+
+    // Only show removable storage: SD cards, USB drives, external drives
+    // Filter out: disk images (app installers), internal HDDs, unknown
+    if should_exclude_device_type(&device_type) {
+        return None;
     }
 
-    let mut cards = Vec::new();
+    // Count files (only for volumes that pass the filter)
+    let file_count = count_files(path);
+
+    // Get disk usage info (only for volumes that pass the filter)
+    let (size, free_space) = get_disk_usage(path);
+
+    Some(SDCard {
+        name,
+        path: path.to_string_lossy().to_string(),
+        size,
+        free_space,
+        file_count,
+        device_type,
+        is_removable,
+        serial,
+    })
+}
 
-    if let Ok(entries) = fs::read_dir(volumes_path) {
-        for entry in entries.flatten() {
-            if let Ok(metadata) = entry.metadata() {
-                if metadata.is_dir() {
-                    let path = entry.path();
-                    let name = entry.file_name().to_string_lossy().to_string();
-
-                    // Skip system volumes
-                    if name == "Macintosh HD"
-                        || name == "Data"
-                        || name == "Preboot"
-                        || name == "Recovery"
-                        || name == "VM"
-                        || name == "Update"
-                        || name.starts_with('.')
-                    {
-                        continue;
-                    }
-
-                    // Get device information early to filter before expensive operations
-                    let (device_type, is_removable) = get_device_info(&path.to_string_lossy());
-
-                    // Only show removable storage: SD cards, USB drives, external drives
-                    // Filter out: disk images (app installers), internal HDDs, unknown
-                    if should_exclude_device_type(&device_type) {
-                        continue;
-                    }
-
-                    // Count files (only for volumes that pass the filter)
-                    let file_count = count_files(&path);
-
-                    // Get disk usage info (only for volumes that pass the filter)
-                    let (size, free_space) = get_disk_usage(&path);
-
-                    cards.push(SDCard {
-                        name,
-                        path: path.to_string_lossy().to_string(),
-                        size,
-                        free_space,
-                        file_count,
-                        device_type,
-                        is_removable,
-                    });
+/// Inspect volumes on a bounded set of blocking tasks, collecting cards as they finish.
+///
+/// A volume whose inspection fails or exceeds `timeout` is logged and skipped.
+async fn scan_volumes_concurrently<F>(
+    volumes: Vec<(String, PathBuf)>,
+    inspect: F,
+    timeout: Duration,
+) -> Vec<SDCard>
+where
+    F: Fn(String, &Path) -> Option<SDCard> + Clone + Send + 'static,
+{
+    let semaphore = Arc::new(Semaphore::new(MAX_CONCURRENT_SCANS));
+    let mut tasks = JoinSet::new();
+
+    for (name, path) in volumes {
+        let semaphore = Arc::clone(&semaphore);
+        let inspect = inspect.clone();
+
+        tasks.spawn(async move {
+            let _permit = semaphore.acquire_owned().await.ok()?;
+            let label = name.clone();
+            let scan = tokio::task::spawn_blocking(move || inspect(name, &path));
+
+            match tokio::time::timeout(timeout, scan).await {
+                Ok(Ok(card)) => card,
+                Ok(Err(e)) => {
+                    log::warn!("Failed to scan volume {label}: {e}");
+                    None
+                }
+                Err(_) => {
+                    log::warn!("Timed out scanning volume {label}");
+                    None
                 }
             }
+        });
+    }
+
+    let mut cards = Vec::new();
+    while let Some(result) = tasks.join_next().await {
+        if let Ok(Some(card)) = result {
+            cards.push(card);
         }
     }
 
-    Ok(cards)
+    cards
 }
 
 fn count_files(path: &Path) -> usize {
@@ -130,16 +202,73 @@ const fn get_disk_usage(_path: &Path) -> (u64, u64) {
     (0, 0)
 }
 
+/// A photo/video file found on an SD card.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct SDCardFile {
+    pub path: String,
+    pub size: u64,
+    /// EXIF `DateTimeOriginal` if present, otherwise the file's modification time.
+    /// Only read when the listing is filtered by date.
+    pub capture_date: Option<String>,
+    /// Set when a same-name, same-size file already exists in the project's `RAW` tree.
+    pub already_imported: bool,
+    /// Path without extension; RAW+JPEG shots from the same exposure share this key.
+    pub pair_key: String,
+    pub is_raw: bool,
+    pub is_jpeg: bool,
+}
+
+/// Camera RAW extensions considered when pairing RAW+JPEG shots.
+const RAW_EXTENSIONS: &[&str] = &["raw", "cr2", "cr3", "nef", "arw", "dng"];
+
+/// JPEG extensions considered when pairing RAW+JPEG shots.
+const JPEG_EXTENSIONS: &[&str] = &["jpg", "jpeg"];
+
+/// Parse an optional `YYYY-MM-DD` filter bound; empty strings are treated as unset.
+fn parse_date_bound(value: Option<&str>) -> Result<Option<NaiveDate>, String> {
+    value
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| {
+            NaiveDate::parse_from_str(v, "%Y-%m-%d")
+                .map_err(|_| format!("Invalid date '{v}': expected YYYY-MM-DD"))
+        })
+        .transpose()
+}
+
 /// List all files from an SD card path (photo/video files)
+///
+/// When `from` and/or `to` (inclusive, `YYYY-MM-DD`) are given, only files whose
+/// capture date falls within the range are returned.
 #[tauri::command]
-pub async fn list_sd_card_files(card_path: String) -> Result<Vec<String>, String> {
-    let path = Path::new(&card_path);
-
-    if !path.exists() {
+pub async fn list_sd_card_files(
+    card_path: String,
+    from: Option<String>,
+    to: Option<String>,
+) -> Result<Vec<SDCardFile>, String> {
+    if !Path::new(&card_path).exists() {
         return Err("SD card path does not exist".to_owned());
     }
 
-    let mut file_paths = Vec::new();
+    let from_date = parse_date_bound(from.as_deref())?;
+    let to_date = parse_date_bound(to.as_deref())?;
+
+    // Walking a full card and reading EXIF would stall the async runtime
+    tokio::task::spawn_blocking(move || scan_card_media(Path::new(&card_path), from_date, to_date))
+        .await
+        .map_err(|e| format!("Card scan failed: {e}"))
+}
+
+/// Walk `path` for photo/video files. Capture dates are only read when a date
+/// bound is set, since that means opening every file.
+fn scan_card_media(
+    path: &Path,
+    from_date: Option<NaiveDate>,
+    to_date: Option<NaiveDate>,
+) -> Vec<SDCardFile> {
+    let dated = from_date.is_some() || to_date.is_some();
+    let mut files = Vec::new();
     let photo_video_extensions = [
         "jpg", "jpeg", "png", "heic", "heif", "raw", "cr2", "cr3", "nef", "arw", "dng", "mp4",
         "mov", "avi", "mkv", "m4v",
@@ -151,33 +280,203 @@ pub async fn list_sd_card_files(card_path: String) -> Result<Vec<String>, String
         .filter(|e| e.file_type().is_file())
     {
         let file_path = entry.path();
-        if let Some(ext) = file_path.extension() {
-            let ext_lower = ext.to_string_lossy().to_lowercase();
-            if photo_video_extensions.contains(&ext_lower.as_str()) {
-                file_paths.push(file_path.to_string_lossy().to_string());
+        let Some(ext) = file_path.extension() else {
+            continue;
+        };
+        let ext_lower = ext.to_string_lossy().to_lowercase();
+        if !photo_video_extensions.contains(&ext_lower.as_str()) {
+            continue;
+        }
+
+        let capture_date = if dated {
+            resolve_capture_date(file_path)
+        } else {
+            None
+        };
+
+        if dated {
+            let Some(day) = capture_date.map(|d| d.date()) else {
+                continue;
+            };
+            if from_date.is_some_and(|f| day < f) || to_date.is_some_and(|t| day > t) {
+                continue;
             }
         }
+
+        files.push(SDCardFile {
+            path: file_path.to_string_lossy().to_string(),
+            size: entry.metadata().map_or(0, |m| m.len()),
+            capture_date: capture_date.map(|d| d.format(CAPTURE_DATE_FORMAT).to_string()),
+            already_imported: false,
+            pair_key: file_path.with_extension("").to_string_lossy().to_string(),
+            is_raw: RAW_EXTENSIONS.contains(&ext_lower.as_str()),
+            is_jpeg: JPEG_EXTENSIONS.contains(&ext_lower.as_str()),
+        });
+    }
+
+    files
+}
+
+/// List SD card files, flagging those already present in a project's `RAW` folder.
+#[tauri::command]
+pub async fn diff_sd_card_against(
+    db: tauri::State<'_, Database>,
+    card_path: String,
+    project_id: String,
+) -> Result<Vec<SDCardFile>, String> {
+    let project = get_project_by_id(&db, &project_id).map_err(String::from)?;
+    let mut files = list_sd_card_files(card_path, None, None).await?;
+    mark_already_imported(&mut files, &Path::new(&project.folder_path).join("RAW"));
+    Ok(files)
+}
+
+/// Set `already_imported` on each file that has a same-name, same-size match under `raw_dir`.
+fn mark_already_imported(files: &mut [SDCardFile], raw_dir: &Path) {
+    let existing: HashSet<(String, u64)> = WalkDir::new(raw_dir)
+        .into_iter()
+        .filter_map(std::result::Result::ok)
+        .filter(|e| e.file_type().is_file())
+        .filter_map(|e| {
+            let size = e.metadata().ok()?.len();
+            Some((e.file_name().to_string_lossy().to_string(), size))
+        })
+        .collect();
+
+    for file in files {
+        let name = Path::new(&file.path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())
+            .unwrap_or_default();
+        file.already_imported = existing.contains(&(name, file.size));
+    }
+}
+
+/// Read-back result for a single file on an SD card.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileIntegrity {
+    pub path: String,
+    pub size: u64,
+    /// SHA-256 of the file contents; `None` when the file could not be read.
+    pub checksum: Option<String>,
+    pub readable: bool,
+    /// OS error reported while reading, if any.
+    pub error: Option<String>,
+}
+
+/// Progress event emitted while verifying an SD card.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VerifyProgress {
+    pub files_verified: usize,
+    pub total_files: usize,
+    pub failed_files: usize,
+    pub current_file: String,
+}
+
+/// Read every media file on a card end to end, checksumming it and flagging read errors.
+#[tauri::command]
+pub async fn verify_sd_card(
+    app: tauri::AppHandle,
+    card_path: String,
+) -> Result<Vec<FileIntegrity>, String> {
+    let files = list_sd_card_files(card_path, None, None).await?;
+    let paths = files.into_iter().map(|f| PathBuf::from(f.path)).collect();
+
+    Ok(verify_files(paths, |progress| {
+        let _ = app.emit("sd-verify-progress", progress);
+    })
+    .await)
+}
+
+/// Checksum each file in turn, reporting progress after every file.
+async fn verify_files<F>(paths: Vec<PathBuf>, on_progress: F) -> Vec<FileIntegrity>
+where
+    F: Fn(VerifyProgress),
+{
+    let total_files = paths.len();
+    let mut results = Vec::with_capacity(total_files);
+    let mut failed_files = 0;
+
+    for path in paths {
+        let size = fs::metadata(&path).map_or(0, |m| m.len());
+        let integrity = match calculate_file_hash(&path).await {
+            Ok(checksum) => FileIntegrity {
+                path: path.to_string_lossy().to_string(),
+                size,
+                checksum: Some(checksum),
+                readable: true,
+                error: None,
+            },
+            Err(e) => {
+                log::warn!("Failed to read {}: {e}", path.display());
+                failed_files += 1;
+                FileIntegrity {
+                    path: path.to_string_lossy().to_string(),
+                    size,
+                    checksum: None,
+                    readable: false,
+                    error: Some(e.to_string()),
+                }
+            }
+        };
+
+        on_progress(VerifyProgress {
+            files_verified: results.len() + 1,
+            total_files,
+            failed_files,
+            current_file: path
+                .file_name()
+                .map(|n| n.to_string_lossy().to_string())
+                .unwrap_or_default(),
+        });
+        results.push(integrity);
     }
 
-    Ok(file_paths)
+    results
 }
 
+/// How long to wait for the volume to disappear after `diskutil eject` returns.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const EJECT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Interval between mount checks while waiting for an eject to complete.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+const EJECT_POLL_INTERVAL: Duration = Duration::from_millis(250);
+
 /// Eject an SD card by volume path
+///
+/// Flushes pending writes, asks the OS to eject, then polls until the volume is
+/// actually unmounted so the UI never reports success while the card is still busy.
 #[tauri::command]
 #[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
 pub async fn eject_sd_card(volume_path: String) -> Result<(), String> {
     #[cfg(target_os = "macos")]
     {
+        // Flush pending writes before asking the OS to unmount
+        let _ = Command::new("sync").status();
+
         let output = Command::new("diskutil")
             .args(["eject", &volume_path])
             .output()
             .map_err(|e| format!("Failed to execute diskutil: {e}"))?;
 
-        if output.status.success() {
+        if !output.status.success() {
+            let error = String::from_utf8_lossy(&output.stderr);
+            return Err(format_busy_error(
+                error.trim(),
+                &find_blocking_processes(&volume_path),
+            ));
+        }
+
+        let mount_point = Path::new(&volume_path).to_path_buf();
+        if wait_for_unmount(|| mount_point.exists(), EJECT_TIMEOUT, EJECT_POLL_INTERVAL).await {
             Ok(())
         } else {
-            let error = String::from_utf8_lossy(&output.stderr);
-            Err(format!("Failed to eject SD card: {error}"))
+            Err(format_busy_error(
+                "volume is still mounted",
+                &find_blocking_processes(&volume_path),
+            ))
         }
     }
 
@@ -187,9 +486,60 @@ pub async fn eject_sd_card(volume_path: String) -> Result<(), String> {
     }
 }
 
-/// Get device type and removability using diskutil (macOS)
+/// Poll `is_mounted` until it reports the volume gone; returns `false` on timeout.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+async fn wait_for_unmount<F>(is_mounted: F, timeout: Duration, poll_interval: Duration) -> bool
+where
+    F: Fn() -> bool,
+{
+    let deadline = tokio::time::Instant::now() + timeout;
+    loop {
+        if !is_mounted() {
+            return true;
+        }
+        if tokio::time::Instant::now() >= deadline {
+            return false;
+        }
+        tokio::time::sleep(poll_interval).await;
+    }
+}
+
+/// Build the user-facing error for an eject that could not complete.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn format_busy_error(reason: &str, blocking_processes: &[String]) -> String {
+    if blocking_processes.is_empty() {
+        format!("Drive busy, retry: {reason}")
+    } else {
+        format!(
+            "Drive busy, retry: {reason} (in use by {})",
+            blocking_processes.join(", ")
+        )
+    }
+}
+
+/// List the names of processes holding files open on the volume (via `lsof`).
+#[cfg(target_os = "macos")]
+fn find_blocking_processes(volume_path: &str) -> Vec<String> {
+    let Ok(output) = Command::new("lsof")
+        .args(["-Fc", "+D", volume_path])
+        .output()
+    else {
+        return Vec::new();
+    };
+
+    let mut names: Vec<String> = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| line.strip_prefix('c'))
+        .map(str::to_owned)
+        .collect();
+    names.sort();
+    names.dedup();
+    names
+}
+
+/// Get device type, removability and volume UUID using diskutil (macOS)
 #[cfg_attr(not(target_os = "macos"), allow(unused_variables))]
-fn get_device_info(volume_name: &str) -> (String, bool) {
+pub fn get_device_info(volume_name: &str) -> (String, bool, Option<String>) {
     #[cfg(target_os = "macos")]
     {
         // Use diskutil to get device information
@@ -234,12 +584,22 @@ fn get_device_info(volume_name: &str) -> (String, bool) {
                 || device_type == "SD Card"
                 || device_type == "USB Drive";
 
-            return (device_type, is_removable);
+            return (device_type, is_removable, parse_volume_uuid(&info));
         }
     }
 
     // Fallback for non-macOS or if diskutil fails
-    ("Unknown".to_owned(), true)
+    ("Unknown".to_owned(), true, None)
+}
+
+/// Extract the `Volume UUID` value from `diskutil info` output.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn parse_volume_uuid(info: &str) -> Option<String> {
+    info.lines()
+        .filter_map(|line| line.trim_start().strip_prefix("Volume UUID:"))
+        .map(str::trim)
+        .find(|uuid| !uuid.is_empty())
+        .map(str::to_owned)
 }
 
 #[cfg(test)]
@@ -257,6 +617,7 @@ mod tests {
             file_count: 150,
             device_type: "SD Card".to_owned(),
             is_removable: true,
+            serial: None,
         };
 
         let json = serde_json::to_string(&card).unwrap();
@@ -299,7 +660,7 @@ mod tests {
     #[cfg(target_os = "macos")]
     #[test]
     fn test_get_device_info_returns_tuple() {
-        let (device_type, _) = get_device_info("TestVolume");
+        let (device_type, _, _) = get_device_info("TestVolume");
         assert!(!device_type.is_empty());
     }
 
@@ -333,15 +694,16 @@ mod tests {
         std::fs::write(temp_dir.path().join("video1.mp4"), b"video").unwrap();
         std::fs::write(temp_dir.path().join("ignored.txt"), b"text").unwrap();
 
-        let result = list_sd_card_files(temp_dir.path().to_string_lossy().to_string()).await;
+        let result =
+            list_sd_card_files(temp_dir.path().to_string_lossy().to_string(), None, None).await;
         assert!(result.is_ok());
 
         let files = result.unwrap();
         assert_eq!(files.len(), 3);
-        assert!(files.iter().any(|f| f.contains("photo1.jpg")));
-        assert!(files.iter().any(|f| f.contains("photo2.png")));
-        assert!(files.iter().any(|f| f.contains("video1.mp4")));
-        assert!(!files.iter().any(|f| f.contains("ignored.txt")));
+        assert!(files.iter().any(|f| f.path.contains("photo1.jpg")));
+        assert!(files.iter().any(|f| f.path.contains("photo2.png")));
+        assert!(files.iter().any(|f| f.path.contains("video1.mp4")));
+        assert!(!files.iter().any(|f| f.path.contains("ignored.txt")));
     }
 
     #[tokio::test]
@@ -353,7 +715,8 @@ mod tests {
         std::fs::write(temp_dir.path().join("raw3.arw"), b"raw").unwrap();
         std::fs::write(temp_dir.path().join("raw4.dng"), b"raw").unwrap();
 
-        let result = list_sd_card_files(temp_dir.path().to_string_lossy().to_string()).await;
+        let result =
+            list_sd_card_files(temp_dir.path().to_string_lossy().to_string(), None, None).await;
         assert!(result.is_ok());
 
         let files = result.unwrap();
@@ -362,7 +725,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_list_sd_card_files_nonexistent_path() {
-        let result = list_sd_card_files("/nonexistent/path".to_owned()).await;
+        let result = list_sd_card_files("/nonexistent/path".to_owned(), None, None).await;
         assert!(result.is_err());
         assert_eq!(result.unwrap_err(), "SD card path does not exist");
     }
@@ -378,7 +741,8 @@ mod tests {
         std::fs::write(folder1.join("IMG_0002.cr3"), b"raw").unwrap();
         std::fs::write(folder1.join("VID_0001.mov"), b"video").unwrap();
 
-        let result = list_sd_card_files(temp_dir.path().to_string_lossy().to_string()).await;
+        let result =
+            list_sd_card_files(temp_dir.path().to_string_lossy().to_string(), None, None).await;
         assert!(result.is_ok());
 
         let files = result.unwrap();
@@ -393,13 +757,220 @@ mod tests {
         std::fs::write(temp_dir.path().join("photo.jpeg"), b"photo").unwrap();
         std::fs::write(temp_dir.path().join("video.MOV"), b"video").unwrap();
 
-        let result = list_sd_card_files(temp_dir.path().to_string_lossy().to_string()).await;
+        let result =
+            list_sd_card_files(temp_dir.path().to_string_lossy().to_string(), None, None).await;
         assert!(result.is_ok());
 
         let files = result.unwrap();
         assert_eq!(files.len(), 3);
     }
 
+    fn write_with_mtime(path: &Path, date: &str) {
+        use chrono::TimeZone;
+
+        std::fs::write(path, b"media").unwrap();
+        let naive = NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_hms_opt(12, 0, 0)
+            .unwrap();
+        let mtime = chrono::Local.from_local_datetime(&naive).single().unwrap();
+        fs::File::options()
+            .write(true)
+            .open(path)
+            .unwrap()
+            .set_modified(mtime.into())
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_list_sd_card_files_date_range_filter() {
+        let temp_dir = TempDir::new().unwrap();
+        let dcim = temp_dir.path().join("DCIM/100CANON");
+        std::fs::create_dir_all(&dcim).unwrap();
+
+        write_with_mtime(&dcim.join("IMG_0001.jpg"), "2024-06-14");
+        write_with_mtime(&dcim.join("IMG_0002.jpg"), "2024-06-15");
+        write_with_mtime(&dcim.join("IMG_0003.cr3"), "2024-06-16");
+        write_with_mtime(&dcim.join("VID_0004.mov"), "2024-06-17");
+
+        let files = list_sd_card_files(
+            temp_dir.path().to_string_lossy().to_string(),
+            Some("2024-06-15".to_owned()),
+            Some("2024-06-16".to_owned()),
+        )
+        .await
+        .unwrap();
+
+        let mut names: Vec<_> = files
+            .iter()
+            .map(|f| {
+                Path::new(&f.path)
+                    .file_name()
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        names.sort();
+        assert_eq!(names, vec!["IMG_0002.jpg", "IMG_0003.cr3"]);
+        assert!(files
+            .iter()
+            .any(|f| f.capture_date.as_deref() == Some("2024-06-15T12:00:00")));
+    }
+
+    #[tokio::test]
+    async fn test_list_sd_card_files_open_ended_range() {
+        let temp_dir = TempDir::new().unwrap();
+
+        write_with_mtime(&temp_dir.path().join("early.jpg"), "2024-01-01");
+        write_with_mtime(&temp_dir.path().join("late.jpg"), "2024-12-31");
+
+        let card = temp_dir.path().to_string_lossy().to_string();
+
+        let from_only = list_sd_card_files(card.clone(), Some("2024-06-01".to_owned()), None)
+            .await
+            .unwrap();
+        assert_eq!(from_only.len(), 1);
+        assert!(from_only[0].path.ends_with("late.jpg"));
+
+        let to_only = list_sd_card_files(card, None, Some("2024-06-01".to_owned()))
+            .await
+            .unwrap();
+        assert_eq!(to_only.len(), 1);
+        assert!(to_only[0].path.ends_with("early.jpg"));
+    }
+
+    #[tokio::test]
+    async fn test_list_sd_card_files_skips_capture_dates_without_a_range() {
+        let temp_dir = TempDir::new().unwrap();
+        write_with_mtime(&temp_dir.path().join("IMG_0001.jpg"), "2024-06-15");
+
+        let files = list_sd_card_files(temp_dir.path().to_string_lossy().to_string(), None, None)
+            .await
+            .unwrap();
+
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].capture_date, None);
+    }
+
+    #[tokio::test]
+    async fn test_list_sd_card_files_invalid_date() {
+        let temp_dir = TempDir::new().unwrap();
+
+        let result = list_sd_card_files(
+            temp_dir.path().to_string_lossy().to_string(),
+            Some("15/06/2024".to_owned()),
+            None,
+        )
+        .await;
+
+        assert_eq!(
+            result.unwrap_err(),
+            "Invalid date '15/06/2024': expected YYYY-MM-DD"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_mark_already_imported() {
+        let card_dir = TempDir::new().unwrap();
+        let project_dir = TempDir::new().unwrap();
+        let raw_photos = project_dir.path().join("RAW/Photos");
+        let raw_videos = project_dir.path().join("RAW/Videos");
+        std::fs::create_dir_all(&raw_photos).unwrap();
+        std::fs::create_dir_all(&raw_videos).unwrap();
+
+        std::fs::write(card_dir.path().join("IMG_0001.jpg"), b"photo-one").unwrap();
+        std::fs::write(card_dir.path().join("IMG_0002.jpg"), b"photo-two").unwrap();
+        std::fs::write(card_dir.path().join("IMG_0003.cr3"), b"raw-three").unwrap();
+        std::fs::write(card_dir.path().join("VID_0004.mov"), b"video-four").unwrap();
+
+        // Same name and size: already imported
+        std::fs::write(raw_photos.join("IMG_0001.jpg"), b"photo-one").unwrap();
+        std::fs::write(raw_videos.join("VID_0004.mov"), b"video-four").unwrap();
+        // Same name, different size: not a match
+        std::fs::write(raw_photos.join("IMG_0002.jpg"), b"partial").unwrap();
+
+        let mut files =
+            list_sd_card_files(card_dir.path().to_string_lossy().to_string(), None, None)
+                .await
+                .unwrap();
+        mark_already_imported(&mut files, &project_dir.path().join("RAW"));
+
+        let imported = |name: &str| {
+            files
+                .iter()
+                .find(|f| f.path.ends_with(name))
+                .unwrap()
+                .already_imported
+        };
+        assert!(imported("IMG_0001.jpg"));
+        assert!(!imported("IMG_0002.jpg"));
+        assert!(!imported("IMG_0003.cr3"));
+        assert!(imported("VID_0004.mov"));
+    }
+
+    #[tokio::test]
+    async fn test_list_sd_card_files_raw_jpeg_pairs() {
+        use std::collections::HashMap;
+
+        let temp_dir = TempDir::new().unwrap();
+        let dcim = temp_dir.path().join("DCIM/100CANON");
+        std::fs::create_dir_all(&dcim).unwrap();
+
+        // Both RAW and JPEG
+        std::fs::write(dcim.join("IMG_0001.CR2"), b"raw").unwrap();
+        std::fs::write(dcim.join("IMG_0001.JPG"), b"jpeg").unwrap();
+        std::fs::write(dcim.join("IMG_0002.nef"), b"raw").unwrap();
+        std::fs::write(dcim.join("IMG_0002.jpeg"), b"jpeg").unwrap();
+        // RAW only
+        std::fs::write(dcim.join("IMG_0003.cr3"), b"raw").unwrap();
+        // JPEG only
+        std::fs::write(dcim.join("IMG_0004.jpg"), b"jpeg").unwrap();
+        // Neither
+        std::fs::write(dcim.join("VID_0005.mov"), b"video").unwrap();
+
+        let files = list_sd_card_files(temp_dir.path().to_string_lossy().to_string(), None, None)
+            .await
+            .unwrap();
+
+        let mut groups: HashMap<&str, Vec<&SDCardFile>> = HashMap::new();
+        for file in &files {
+            groups.entry(file.pair_key.as_str()).or_default().push(file);
+        }
+        let group = |stem: &str| {
+            let key = dcim.join(stem).to_string_lossy().to_string();
+            let members = &groups[key.as_str()];
+            (
+                members.iter().filter(|f| f.is_raw).count(),
+                members.iter().filter(|f| f.is_jpeg).count(),
+            )
+        };
+
+        assert_eq!(groups.len(), 5);
+        assert_eq!(group("IMG_0001"), (1, 1));
+        assert_eq!(group("IMG_0002"), (1, 1));
+        assert_eq!(group("IMG_0003"), (1, 0));
+        assert_eq!(group("IMG_0004"), (0, 1));
+        assert_eq!(group("VID_0005"), (0, 0));
+    }
+
+    #[test]
+    fn test_mark_already_imported_missing_raw_dir() {
+        let mut files = vec![SDCardFile {
+            path: "/card/IMG_0001.jpg".to_owned(),
+            size: 10,
+            capture_date: None,
+            already_imported: true,
+            pair_key: "/card/IMG_0001".to_owned(),
+            is_raw: false,
+            is_jpeg: true,
+        }];
+
+        mark_already_imported(&mut files, Path::new("/nonexistent/RAW"));
+
+        assert!(!files[0].already_imported);
+    }
+
     #[cfg(target_os = "macos")]
     #[test]
     fn test_get_disk_usage() {
@@ -430,6 +1001,189 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_verify_files_flags_unreadable_file() {
+        use std::sync::Mutex;
+
+        let temp_dir = TempDir::new().unwrap();
+        let good_one = temp_dir.path().join("IMG_0001.jpg");
+        let good_two = temp_dir.path().join("IMG_0002.cr3");
+        std::fs::write(&good_one, b"photo").unwrap();
+        std::fs::write(&good_two, b"raw data").unwrap();
+        // A directory opens fine but fails on read, like a damaged card sector
+        let unreadable = temp_dir.path().join("IMG_0003.jpg");
+        std::fs::create_dir(&unreadable).unwrap();
+
+        let progress = Mutex::new(Vec::new());
+        let results = verify_files(vec![good_one.clone(), unreadable, good_two], |p| {
+            progress.lock().unwrap().push(p);
+        })
+        .await;
+
+        assert_eq!(results.len(), 3);
+        assert!(results[0].readable);
+        assert_eq!(
+            results[0].checksum,
+            Some(calculate_file_hash(&good_one).await.unwrap())
+        );
+        assert!(!results[1].readable);
+        assert!(results[1].checksum.is_none());
+        assert!(results[1].error.as_deref().unwrap().contains("os error"));
+        assert!(results[2].readable);
+        assert_eq!(results[2].size, 8);
+
+        let progress = progress.into_inner().unwrap();
+        assert_eq!(progress.len(), 3);
+        assert_eq!(progress[2].files_verified, 3);
+        assert_eq!(progress[2].total_files, 3);
+        assert_eq!(progress[2].failed_files, 1);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_unmount_succeeds_once_unmounted() {
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        let checks = AtomicUsize::new(0);
+        // Reports mounted for the first three polls, then unmounted
+        let unmounted = wait_for_unmount(
+            || checks.fetch_add(1, Ordering::SeqCst) < 3,
+            Duration::from_secs(5),
+            Duration::from_millis(1),
+        )
+        .await;
+
+        assert!(unmounted);
+        assert_eq!(checks.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn test_wait_for_unmount_times_out() {
+        let start = std::time::Instant::now();
+        let unmounted =
+            wait_for_unmount(|| true, Duration::from_millis(50), Duration::from_millis(5)).await;
+
+        assert!(!unmounted);
+        assert!(start.elapsed() >= Duration::from_millis(50));
+    }
+
+    #[test]
+    fn test_format_busy_error() {
+        assert_eq!(
+            format_busy_error("volume is still mounted", &[]),
+            "Drive busy, retry: volume is still mounted"
+        );
+        assert_eq!(
+            format_busy_error(
+                "Unmount failed",
+                &["Adobe Lightroom".to_owned(), "Finder".to_owned()]
+            ),
+            "Drive busy, retry: Unmount failed (in use by Adobe Lightroom, Finder)"
+        );
+    }
+
+    fn mock_card(name: &str) -> SDCard {
+        SDCard {
+            name: name.to_owned(),
+            path: format!("/Volumes/{name}"),
+            size: 64_000_000_000,
+            free_space: 32_000_000_000,
+            file_count: 10,
+            device_type: "SD Card".to_owned(),
+            is_removable: true,
+            serial: Some(format!("{name}-UUID")),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_scan_volumes_concurrently_reports_all_cards() {
+        let volumes: Vec<(String, PathBuf)> = ["CARD_A", "CARD_B", "CARD_C", "CARD_D", "CARD_E"]
+            .iter()
+            .map(|name| {
+                (
+                    (*name).to_owned(),
+                    PathBuf::from(format!("/Volumes/{name}")),
+                )
+            })
+            .collect();
+
+        let cards = scan_volumes_concurrently(
+            volumes,
+            |name, _path| Some(mock_card(&name)),
+            Duration::from_secs(5),
+        )
+        .await;
+
+        let mut names: Vec<_> = cards.iter().map(|c| c.name.as_str()).collect();
+        names.sort_unstable();
+        assert_eq!(
+            names,
+            vec!["CARD_A", "CARD_B", "CARD_C", "CARD_D", "CARD_E"]
+        );
+        assert!(cards.iter().all(|c| c.serial.is_some() && c.size > 0));
+    }
+
+    #[tokio::test]
+    async fn test_scan_volumes_concurrently_skips_slow_and_excluded_volumes() {
+        let volumes = vec![
+            ("FAST".to_owned(), PathBuf::from("/Volumes/FAST")),
+            ("STUCK".to_owned(), PathBuf::from("/Volumes/STUCK")),
+            ("DMG".to_owned(), PathBuf::from("/Volumes/DMG")),
+        ];
+
+        let start = std::time::Instant::now();
+        let cards = scan_volumes_concurrently(
+            volumes,
+            |name, _path| match name.as_str() {
+                "STUCK" => {
+                    std::thread::sleep(Duration::from_secs(1));
+                    Some(mock_card(&name))
+                }
+                "DMG" => None,
+                _ => Some(mock_card(&name)),
+            },
+            Duration::from_millis(100),
+        )
+        .await;
+
+        assert!(start.elapsed() < Duration::from_secs(1));
+        assert_eq!(cards.len(), 1);
+        assert_eq!(cards[0].name, "FAST");
+    }
+
+    #[test]
+    fn test_list_mounted_volumes_skips_system_volumes() {
+        let temp_dir = TempDir::new().unwrap();
+        for name in [
+            "Macintosh HD",
+            "Preboot",
+            ".Spotlight",
+            "EOS_DIGITAL",
+            "Untitled",
+        ] {
+            std::fs::create_dir(temp_dir.path().join(name)).unwrap();
+        }
+        std::fs::write(temp_dir.path().join("not_a_volume"), b"file").unwrap();
+
+        let mut names: Vec<_> = list_mounted_volumes(temp_dir.path())
+            .into_iter()
+            .map(|(name, _)| name)
+            .collect();
+        names.sort();
+
+        assert_eq!(names, vec!["EOS_DIGITAL", "Untitled"]);
+        assert!(list_mounted_volumes(Path::new("/nonexistent/Volumes")).is_empty());
+    }
+
+    #[test]
+    fn test_parse_volume_uuid() {
+        let info = "   Volume Name:               EOS_DIGITAL\n   Volume UUID:               0A81F3B1-51D9-3335-B3E3-169C3640360D\n";
+        assert_eq!(
+            parse_volume_uuid(info).as_deref(),
+            Some("0A81F3B1-51D9-3335-B3E3-169C3640360D")
+        );
+        assert_eq!(parse_volume_uuid("   Volume Name: Untitled\n"), None);
+    }
+
     #[test]
     fn test_sd_card_complete_struct() {
         let card = SDCard {
@@ -440,6 +1194,7 @@ mod tests {
             file_count: 250,
             device_type: "SD Card".to_owned(),
             is_removable: true,
+            serial: None,
         };
 
         assert_eq!(card.name, "TestCard");
@@ -468,7 +1223,7 @@ mod tests {
     fn test_device_type_detection_with_actual_volume() {
         // This test validates that get_device_info returns valid device types
         // Testing with root path which should exist on macOS
-        let (device_type, _is_removable) = get_device_info("/");
+        let (device_type, _is_removable, _serial) = get_device_info("/");
 
         // Device type should be one of the known types
         assert!(
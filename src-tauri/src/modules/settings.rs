@@ -0,0 +1,590 @@
+//! Persistent application settings stored in the database's `settings` table.
+//!
+//! Values are kept as strings under free-form keys; the keys the backend itself
+//! reads have constants and typed helpers here, and values written to them are
+//! validated so a bad value can't break the features that use it.
+
+use crate::error::AppError;
+use crate::modules::db::Database;
+use crate::modules::file_utils::get_home_dir;
+use crate::modules::job_log::logs_dir;
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+/// Folder new projects and app data are created under.
+pub const BASE_DIRECTORY: &str = "base_directory";
+/// Maximum number of import history records kept.
+pub const IMPORT_HISTORY_LIMIT: &str = "import_history_limit";
+/// Conflict mode used when a job doesn't specify one.
+pub const DEFAULT_CONFLICT_MODE: &str = "default_conflict_mode";
+/// Whether finished jobs post a desktop notification (`true` or `false`).
+pub const NOTIFICATIONS_ENABLED: &str = "notifications_enabled";
+/// Shoot types offered for new projects, as a JSON array of names.
+pub const SHOOT_TYPES: &str = "shoot_types";
+/// Zero-padding width of a bare `{index}` in delivery naming templates.
+pub const INDEX_PADDING: &str = "index_padding";
+
+/// Import history records kept when no limit has been set.
+pub const DEFAULT_IMPORT_HISTORY_LIMIT: usize = 100;
+/// Ways a delivery or Drive upload can handle a name that's already taken.
+pub const CONFLICT_MODES: [&str; 4] = ["skip", "rename", "overwrite", "overwrite_keep_backup"];
+/// Conflict mode used when neither the job nor the settings choose one.
+pub const FALLBACK_CONFLICT_MODE: &str = "rename";
+/// Padding of a bare `{index}` when none has been set.
+pub const DEFAULT_INDEX_PADDING: usize = 3;
+/// Widest index padding a setting or `{index:N}` may ask for.
+pub const MAX_INDEX_PADDING: usize = 12;
+
+/// Settings and paths the frontend needs at startup, with defaults applied.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AppConfig {
+    pub base_directory: String,
+    pub projects_directory: String,
+    pub logs_directory: String,
+    pub import_history_limit: usize,
+    pub notifications_enabled: bool,
+    pub default_conflict_mode: String,
+    /// Whether a Google Drive account has been connected.
+    pub has_drive_account: bool,
+}
+
+/// Read a setting, or `None` if it was never set.
+///
+/// # Errors
+///
+/// Returns error if the database query fails
+pub fn read_setting(db: &Database, key: &str) -> Result<Option<String>, AppError> {
+    db.execute(|conn| {
+        Ok(conn
+            .query_row(
+                "SELECT value FROM settings WHERE key = ?1",
+                params![key],
+                |row| row.get(0),
+            )
+            .optional()?)
+    })
+}
+
+/// Insert or overwrite a setting.
+///
+/// # Errors
+///
+/// Returns error if the key is empty, the value is invalid for a known key, or
+/// the database write fails
+pub fn write_setting(db: &Database, key: &str, value: &str) -> Result<(), AppError> {
+    validate_setting(key, value)?;
+    let now = chrono::Utc::now().to_rfc3339();
+
+    db.execute(|conn| {
+        conn.execute(
+            "INSERT INTO settings (key, value, updated_at) VALUES (?1, ?2, ?3)
+             ON CONFLICT(key) DO UPDATE SET value = excluded.value, updated_at = excluded.updated_at",
+            params![key, value, now],
+        )?;
+        Ok(())
+    })
+}
+
+/// Read a setting and parse it, or `None` if it was never set.
+///
+/// # Errors
+///
+/// Returns error if the database query fails or the stored value doesn't parse
+pub fn read_parsed<T: FromStr>(db: &Database, key: &str) -> Result<Option<T>, AppError> {
+    read_setting(db, key)?
+        .map(|value| {
+            value
+                .parse()
+                .map_err(|_| AppError::InvalidData(format!("Invalid value for {key}: {value}")))
+        })
+        .transpose()
+}
+
+/// Folder projects are created under: the `base_directory` setting, or
+/// `~/CreatorOps` by default.
+///
+/// # Errors
+///
+/// Returns error if the database query fails or the home directory is unknown
+pub fn base_directory(db: &Database) -> Result<PathBuf, AppError> {
+    match read_setting(db, BASE_DIRECTORY)? {
+        Some(dir) => Ok(PathBuf::from(dir)),
+        None => Ok(get_home_dir()?.join("CreatorOps")),
+    }
+}
+
+/// Folder new projects are created in: `Projects` under [`base_directory`].
+///
+/// # Errors
+///
+/// Returns error if the database query fails or the home directory is unknown
+pub fn projects_directory(db: &Database) -> Result<PathBuf, AppError> {
+    Ok(base_directory(db)?.join("Projects"))
+}
+
+/// Number of import history records to keep.
+///
+/// # Errors
+///
+/// Returns error if the database query fails or the stored value is invalid
+pub fn import_history_limit(db: &Database) -> Result<usize, AppError> {
+    Ok(read_parsed(db, IMPORT_HISTORY_LIMIT)?.unwrap_or(DEFAULT_IMPORT_HISTORY_LIMIT))
+}
+
+/// Digits a bare `{index}` is zero-padded to; 0 leaves it unpadded.
+///
+/// # Errors
+///
+/// Returns error if the database query fails or the stored value is invalid
+pub fn index_padding(db: &Database) -> Result<usize, AppError> {
+    Ok(read_parsed(db, INDEX_PADDING)?.unwrap_or(DEFAULT_INDEX_PADDING))
+}
+
+/// Whether finished jobs post desktop notifications; on unless turned off.
+///
+/// # Errors
+///
+/// Returns error if the database query fails or the stored value is invalid
+pub fn notifications_enabled(db: &Database) -> Result<bool, AppError> {
+    Ok(read_parsed(db, NOTIFICATIONS_ENABLED)?.unwrap_or(true))
+}
+
+/// Conflict mode for jobs that don't choose one: the `default_conflict_mode`
+/// setting, or `rename` by default.
+///
+/// # Errors
+///
+/// Returns error if the database query fails or the stored value is invalid
+pub fn default_conflict_mode(db: &Database) -> Result<String, AppError> {
+    match read_setting(db, DEFAULT_CONFLICT_MODE)? {
+        Some(mode) if CONFLICT_MODES.contains(&mode.as_str()) => Ok(mode),
+        Some(mode) => Err(AppError::InvalidData(format!(
+            "Invalid value for {DEFAULT_CONFLICT_MODE}: {mode}"
+        ))),
+        None => Ok(FALLBACK_CONFLICT_MODE.to_owned()),
+    }
+}
+
+/// `conflict_mode` if the caller chose one, otherwise the default.
+///
+/// # Errors
+///
+/// Returns error if the default is needed and can't be read
+pub fn resolve_conflict_mode(
+    db: &Database,
+    conflict_mode: Option<String>,
+) -> Result<String, AppError> {
+    conflict_mode.map_or_else(|| default_conflict_mode(db), Ok)
+}
+
+/// Known shoot types, in the order they were added.
+///
+/// # Errors
+///
+/// Returns error if the database query fails or the stored list is invalid
+pub fn shoot_types(db: &Database) -> Result<Vec<String>, AppError> {
+    read_setting(db, SHOOT_TYPES)?
+        .map_or_else(|| Ok(Vec::new()), |json| Ok(serde_json::from_str(&json)?))
+}
+
+fn write_shoot_types(db: &Database, types: &[String]) -> Result<(), AppError> {
+    write_setting(db, SHOOT_TYPES, &serde_json::to_string(types)?)
+}
+
+/// Spelling of `shoot_type` to store on a project: the known type it matches
+/// ignoring case, or the trimmed value itself, which is then recorded as a new
+/// known type. Empty stays empty.
+///
+/// # Errors
+///
+/// Returns error if the shoot type list can't be read or updated
+pub fn normalize_shoot_type(db: &Database, shoot_type: &str) -> Result<String, AppError> {
+    let shoot_type = shoot_type.trim();
+    if shoot_type.is_empty() {
+        return Ok(String::new());
+    }
+
+    let mut types = shoot_types(db)?;
+    if let Some(known) = types.iter().find(|t| t.eq_ignore_ascii_case(shoot_type)) {
+        return Ok(known.clone());
+    }
+
+    types.push(shoot_type.to_owned());
+    write_shoot_types(db, &types)?;
+    Ok(shoot_type.to_owned())
+}
+
+/// Reject empty keys and values the backend couldn't use for its own keys.
+fn validate_setting(key: &str, value: &str) -> Result<(), AppError> {
+    let invalid =
+        |reason: &str| AppError::InvalidData(format!("Invalid value for {key}: {reason}"));
+
+    match key {
+        "" => return Err(AppError::InvalidData("Setting key is empty".to_owned())),
+        BASE_DIRECTORY if !std::path::Path::new(value).is_absolute() => {
+            return Err(invalid("must be an absolute path"));
+        }
+        IMPORT_HISTORY_LIMIT if value.parse::<usize>().map_or(true, |limit| limit == 0) => {
+            return Err(invalid("must be a positive whole number"));
+        }
+        DEFAULT_CONFLICT_MODE if !CONFLICT_MODES.contains(&value) => {
+            return Err(invalid(&format!(
+                "must be one of {}",
+                CONFLICT_MODES.join(", ")
+            )));
+        }
+        NOTIFICATIONS_ENABLED if value.parse::<bool>().is_err() => {
+            return Err(invalid("must be true or false"));
+        }
+        INDEX_PADDING
+            if value
+                .parse::<usize>()
+                .map_or(true, |width| width > MAX_INDEX_PADDING) =>
+        {
+            return Err(invalid(&format!(
+                "must be a whole number from 0 to {MAX_INDEX_PADDING}"
+            )));
+        }
+        SHOOT_TYPES if serde_json::from_str::<Vec<String>>(value).is_err() => {
+            return Err(invalid("must be a JSON array of names"));
+        }
+        _ => {}
+    }
+    Ok(())
+}
+
+/// Core logic for resolving the app configuration (testable)
+///
+/// # Errors
+///
+/// Returns error if a setting can't be read or is invalid, or the home
+/// directory is unknown
+pub fn get_app_config_impl(db: &Database) -> Result<AppConfig, AppError> {
+    let has_drive_account = db.execute(|conn| {
+        Ok(conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM google_drive_accounts)",
+            [],
+            |row| row.get(0),
+        )?)
+    })?;
+
+    Ok(AppConfig {
+        base_directory: base_directory(db)?.to_string_lossy().to_string(),
+        projects_directory: projects_directory(db)?.to_string_lossy().to_string(),
+        logs_directory: logs_dir()
+            .map_err(AppError::InvalidData)?
+            .to_string_lossy()
+            .to_string(),
+        import_history_limit: import_history_limit(db)?,
+        notifications_enabled: notifications_enabled(db)?,
+        default_conflict_mode: default_conflict_mode(db)?,
+        has_drive_account,
+    })
+}
+
+/// Get the resolved settings and folders in one call.
+#[tauri::command]
+pub async fn get_app_config(db: tauri::State<'_, Database>) -> Result<AppConfig, String> {
+    get_app_config_impl(&db).map_err(String::from)
+}
+
+/// Get a setting's value, or `null` if it was never set.
+#[tauri::command]
+pub async fn get_setting(
+    db: tauri::State<'_, Database>,
+    key: String,
+) -> Result<Option<String>, String> {
+    read_setting(&db, &key).map_err(String::from)
+}
+
+/// Set a setting, replacing any previous value.
+#[tauri::command]
+pub async fn set_setting(
+    db: tauri::State<'_, Database>,
+    key: String,
+    value: String,
+) -> Result<(), String> {
+    write_setting(&db, &key, &value).map_err(String::from)
+}
+
+/// Get the conflict mode used when a delivery or upload doesn't choose one.
+#[tauri::command]
+pub async fn get_default_conflict_mode(db: tauri::State<'_, Database>) -> Result<String, String> {
+    default_conflict_mode(&db).map_err(String::from)
+}
+
+/// Set the conflict mode used when a delivery or upload doesn't choose one.
+#[tauri::command]
+pub async fn set_default_conflict_mode(
+    db: tauri::State<'_, Database>,
+    conflict_mode: String,
+) -> Result<(), String> {
+    write_setting(&db, DEFAULT_CONFLICT_MODE, &conflict_mode).map_err(String::from)
+}
+
+/// List the known shoot types.
+#[tauri::command]
+pub async fn list_shoot_types(db: tauri::State<'_, Database>) -> Result<Vec<String>, String> {
+    shoot_types(&db).map_err(String::from)
+}
+
+/// Add a shoot type unless one differing only in case exists, returning the list.
+#[tauri::command]
+pub async fn add_shoot_type(
+    db: tauri::State<'_, Database>,
+    name: String,
+) -> Result<Vec<String>, String> {
+    if name.trim().is_empty() {
+        return Err("Shoot type is empty".to_owned());
+    }
+    normalize_shoot_type(&db, &name)?;
+    shoot_types(&db).map_err(String::from)
+}
+
+/// Remove a shoot type (ignoring case), returning the remaining list. Projects
+/// already using it keep their shoot type.
+#[tauri::command]
+pub async fn remove_shoot_type(
+    db: tauri::State<'_, Database>,
+    name: String,
+) -> Result<Vec<String>, String> {
+    let mut types = shoot_types(&db)?;
+    let count = types.len();
+    types.retain(|t| !t.eq_ignore_ascii_case(name.trim()));
+    if types.len() == count {
+        return Err(format!("Shoot type not found: {name}"));
+    }
+
+    write_shoot_types(&db, &types)?;
+    Ok(types)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    // Global mutex to serialize tests that manipulate HOME environment variable
+    lazy_static::lazy_static! {
+        static ref HOME_TEST_MUTEX: std::sync::Mutex<()> = std::sync::Mutex::new(());
+    }
+
+    fn test_db(temp_dir: &TempDir) -> Database {
+        Database::new_with_path(&temp_dir.path().join("test.db")).unwrap()
+    }
+
+    /// Resolve the app config with HOME pointing at `home`.
+    fn app_config_with_home(db: &Database, home: &std::path::Path) -> AppConfig {
+        let _lock = HOME_TEST_MUTEX.lock().unwrap();
+        let original_home = std::env::var_os("HOME");
+        std::env::set_var("HOME", home);
+
+        let config = get_app_config_impl(db);
+
+        if let Some(home) = original_home {
+            std::env::set_var("HOME", home);
+        } else {
+            std::env::remove_var("HOME");
+        }
+        config.unwrap()
+    }
+
+    #[test]
+    fn test_set_get_and_overwrite() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = test_db(&temp_dir);
+
+        write_setting(&db, "theme", "dark").unwrap();
+        assert_eq!(read_setting(&db, "theme").unwrap().as_deref(), Some("dark"));
+
+        write_setting(&db, "theme", "light").unwrap();
+        assert_eq!(
+            read_setting(&db, "theme").unwrap().as_deref(),
+            Some("light")
+        );
+    }
+
+    #[test]
+    fn test_missing_key_is_none_and_typed_defaults_apply() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = test_db(&temp_dir);
+
+        assert!(read_setting(&db, "never-set").unwrap().is_none());
+        assert!(read_parsed::<u32>(&db, "never-set").unwrap().is_none());
+        assert_eq!(
+            import_history_limit(&db).unwrap(),
+            DEFAULT_IMPORT_HISTORY_LIMIT
+        );
+        assert!(notifications_enabled(&db).unwrap());
+        assert_eq!(index_padding(&db).unwrap(), DEFAULT_INDEX_PADDING);
+    }
+
+    #[test]
+    fn test_typed_helpers() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = test_db(&temp_dir);
+        let base = temp_dir.path().join("Studio");
+
+        write_setting(&db, IMPORT_HISTORY_LIMIT, "250").unwrap();
+        write_setting(&db, BASE_DIRECTORY, &base.to_string_lossy()).unwrap();
+        write_setting(&db, NOTIFICATIONS_ENABLED, "false").unwrap();
+        write_setting(&db, INDEX_PADDING, "0").unwrap();
+
+        assert_eq!(import_history_limit(&db).unwrap(), 250);
+        assert!(!notifications_enabled(&db).unwrap());
+        assert_eq!(index_padding(&db).unwrap(), 0);
+        assert_eq!(base_directory(&db).unwrap(), base);
+    }
+
+    #[test]
+    fn test_app_config_defaults() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = test_db(&temp_dir);
+
+        let config = app_config_with_home(&db, temp_dir.path());
+
+        let base = temp_dir.path().join("CreatorOps");
+        assert_eq!(config.base_directory, base.to_string_lossy());
+        assert_eq!(
+            config.projects_directory,
+            base.join("Projects").to_string_lossy()
+        );
+        assert_eq!(config.logs_directory, base.join("logs").to_string_lossy());
+        assert_eq!(config.import_history_limit, DEFAULT_IMPORT_HISTORY_LIMIT);
+        assert!(config.notifications_enabled);
+        assert_eq!(config.default_conflict_mode, FALLBACK_CONFLICT_MODE);
+        assert!(!config.has_drive_account);
+    }
+
+    #[test]
+    fn test_app_config_reflects_settings() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = test_db(&temp_dir);
+        let base = temp_dir.path().join("Studio");
+        write_setting(&db, BASE_DIRECTORY, &base.to_string_lossy()).unwrap();
+        write_setting(&db, IMPORT_HISTORY_LIMIT, "25").unwrap();
+        write_setting(&db, NOTIFICATIONS_ENABLED, "false").unwrap();
+        write_setting(&db, DEFAULT_CONFLICT_MODE, "skip").unwrap();
+        db.execute(|conn| {
+            conn.execute(
+                "INSERT INTO google_drive_accounts (id, email, display_name, created_at, last_authenticated)
+                 VALUES ('acct-1', 'studio@example.com', 'Studio', '', '')",
+                [],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        let config = app_config_with_home(&db, temp_dir.path());
+
+        assert_eq!(config.base_directory, base.to_string_lossy());
+        assert_eq!(
+            config.projects_directory,
+            base.join("Projects").to_string_lossy()
+        );
+        assert_eq!(config.import_history_limit, 25);
+        assert!(!config.notifications_enabled);
+        assert_eq!(config.default_conflict_mode, "skip");
+        assert!(config.has_drive_account);
+    }
+
+    #[test]
+    fn test_invalid_values_are_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = test_db(&temp_dir);
+
+        assert!(write_setting(&db, "", "x").is_err());
+        assert!(write_setting(&db, IMPORT_HISTORY_LIMIT, "lots").is_err());
+        assert!(write_setting(&db, IMPORT_HISTORY_LIMIT, "0").is_err());
+        assert!(write_setting(&db, BASE_DIRECTORY, "relative/dir").is_err());
+        assert!(write_setting(&db, NOTIFICATIONS_ENABLED, "yes").is_err());
+        assert!(write_setting(&db, INDEX_PADDING, "13").is_err());
+        assert!(write_setting(&db, INDEX_PADDING, "-1").is_err());
+        assert!(write_setting(&db, SHOOT_TYPES, "Wedding").is_err());
+        let error = write_setting(&db, DEFAULT_CONFLICT_MODE, "replace").unwrap_err();
+        assert!(error.to_string().contains("overwrite_keep_backup"));
+        assert!(read_setting(&db, IMPORT_HISTORY_LIMIT).unwrap().is_none());
+    }
+
+    #[test]
+    fn test_default_conflict_mode_applies_when_none_is_given() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = test_db(&temp_dir);
+
+        assert_eq!(resolve_conflict_mode(&db, None).unwrap(), "rename");
+
+        write_setting(&db, DEFAULT_CONFLICT_MODE, "skip").unwrap();
+        assert_eq!(resolve_conflict_mode(&db, None).unwrap(), "skip");
+        assert_eq!(
+            resolve_conflict_mode(&db, Some("overwrite".to_owned())).unwrap(),
+            "overwrite"
+        );
+    }
+
+    #[test]
+    fn test_invalid_stored_conflict_mode_is_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = test_db(&temp_dir);
+        db.execute(|conn| {
+            conn.execute(
+                "INSERT INTO settings (key, value, updated_at) VALUES (?1, 'replace', '')",
+                params![DEFAULT_CONFLICT_MODE],
+            )?;
+            Ok(())
+        })
+        .unwrap();
+
+        assert!(resolve_conflict_mode(&db, None).is_err());
+        assert_eq!(
+            resolve_conflict_mode(&db, Some("skip".to_owned())).unwrap(),
+            "skip"
+        );
+    }
+
+    #[test]
+    fn test_shoot_types_are_normalized_and_recorded() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = test_db(&temp_dir);
+        assert!(shoot_types(&db).unwrap().is_empty());
+
+        assert_eq!(normalize_shoot_type(&db, " Wedding ").unwrap(), "Wedding");
+        assert_eq!(normalize_shoot_type(&db, "Portrait").unwrap(), "Portrait");
+        assert_eq!(normalize_shoot_type(&db, "wedding").unwrap(), "Wedding");
+        assert_eq!(normalize_shoot_type(&db, "  ").unwrap(), "");
+
+        assert_eq!(
+            shoot_types(&db).unwrap(),
+            vec!["Wedding".to_owned(), "Portrait".to_owned()]
+        );
+    }
+
+    #[test]
+    fn test_concurrent_writes_through_execute() {
+        let temp_dir = TempDir::new().unwrap();
+        let db = std::sync::Arc::new(test_db(&temp_dir));
+
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                let db = db.clone();
+                std::thread::spawn(move || {
+                    for j in 0..20 {
+                        write_setting(&db, &format!("key-{i}"), &j.to_string()).unwrap();
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for i in 0..8 {
+            assert_eq!(
+                read_setting(&db, &format!("key-{i}")).unwrap().as_deref(),
+                Some("19")
+            );
+        }
+    }
+}
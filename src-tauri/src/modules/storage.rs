@@ -0,0 +1,283 @@
+//! Pluggable storage backends for sending delivered files off the machine.
+//!
+//! A [`StorageProvider`] knows how to create a folder, upload a file into it with
+//! progress, hand out a link to the folder and check whether a file is already
+//! there. Folder ids are opaque to callers: the local backend uses absolute paths,
+//! Google Drive uses Drive file ids. Local deliveries copy their files through
+//! [`LocalStorage`]. New backends (Dropbox, B2, S3, ...) only need to implement
+//! the trait.
+
+use std::future::Future;
+use std::path::{Path, PathBuf};
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+const CHUNK_SIZE: usize = 4 * 1024 * 1024; // 4MB chunks
+
+/// Upload progress callback: stored file name, bytes transferred so far, total bytes.
+pub type ProgressCallback<'a> = &'a (dyn Fn(&str, u64, u64) + Send + Sync);
+
+/// A destination files can be uploaded to.
+pub trait StorageProvider: Send + Sync {
+    /// Create a folder named `name` under `parent` (the provider's root when `None`)
+    /// and return its id.
+    fn create_folder(
+        &self,
+        name: &str,
+        parent: Option<&str>,
+    ) -> impl Future<Output = Result<String, String>> + Send;
+
+    /// Upload `local_path` into `folder_id` as `file_name`, calling `on_progress` as
+    /// bytes are transferred.
+    fn upload_file_with_progress(
+        &self,
+        local_path: &Path,
+        folder_id: &str,
+        file_name: &str,
+        on_progress: ProgressCallback<'_>,
+    ) -> impl Future<Output = Result<(), String>> + Send;
+
+    /// Link the client can open to reach `folder_id`.
+    fn share_link(&self, folder_id: &str) -> impl Future<Output = Result<String, String>> + Send;
+
+    /// Whether `folder_id` already contains a file named `file_name`.
+    fn exists(
+        &self,
+        folder_id: &str,
+        file_name: &str,
+    ) -> impl Future<Output = Result<bool, String>> + Send;
+}
+
+/// Storage on a local or mounted disk, rooted at a directory.
+#[derive(Debug, Clone)]
+pub struct LocalStorage {
+    root: PathBuf,
+}
+
+impl LocalStorage {
+    /// Store folders under `root`.
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        Self { root: root.into() }
+    }
+}
+
+impl StorageProvider for LocalStorage {
+    async fn create_folder(&self, name: &str, parent: Option<&str>) -> Result<String, String> {
+        let parent = parent.map_or_else(|| self.root.clone(), PathBuf::from);
+        let folder = parent.join(name);
+
+        tokio::fs::create_dir_all(&folder)
+            .await
+            .map_err(|e| format!("Failed to create folder {}: {e}", folder.display()))?;
+
+        Ok(folder.to_string_lossy().to_string())
+    }
+
+    async fn upload_file_with_progress(
+        &self,
+        local_path: &Path,
+        folder_id: &str,
+        file_name: &str,
+        on_progress: ProgressCallback<'_>,
+    ) -> Result<(), String> {
+        let dest = Path::new(folder_id).join(file_name);
+
+        let mut source_file = tokio::fs::File::open(local_path)
+            .await
+            .map_err(|e| format!("Failed to open file {}: {e}", local_path.display()))?;
+        let total_bytes = source_file
+            .metadata()
+            .await
+            .map_err(|e| format!("Failed to get file metadata: {e}"))?
+            .len();
+        let mut dest_file = tokio::fs::File::create(&dest)
+            .await
+            .map_err(|e| format!("Failed to create {}: {e}", dest.display()))?;
+
+        let mut buffer = vec![0_u8; CHUNK_SIZE];
+        let mut bytes_copied = 0;
+        on_progress(file_name, 0, total_bytes);
+
+        loop {
+            let bytes_read = source_file
+                .read(&mut buffer)
+                .await
+                .map_err(|e| format!("Failed to read file chunk: {e}"))?;
+
+            if bytes_read == 0 {
+                break;
+            }
+
+            dest_file
+                .write_all(&buffer[..bytes_read])
+                .await
+                .map_err(|e| format!("Failed to write {}: {e}", dest.display()))?;
+
+            bytes_copied += bytes_read as u64;
+            on_progress(file_name, bytes_copied, total_bytes);
+        }
+
+        dest_file
+            .flush()
+            .await
+            .map_err(|e| format!("Failed to write {}: {e}", dest.display()))
+    }
+
+    async fn share_link(&self, folder_id: &str) -> Result<String, String> {
+        Ok(folder_id.to_owned())
+    }
+
+    async fn exists(&self, folder_id: &str, file_name: &str) -> Result<bool, String> {
+        Ok(Path::new(folder_id).join(file_name).is_file())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+    use tempfile::TempDir;
+
+    /// File names and contents of one in-memory folder.
+    type MemoryFolder = HashMap<String, Vec<u8>>;
+
+    /// In-memory provider: folders are ids mapping file names to contents.
+    #[derive(Default)]
+    struct MemoryStorage {
+        folders: Mutex<HashMap<String, MemoryFolder>>,
+    }
+
+    impl StorageProvider for MemoryStorage {
+        async fn create_folder(&self, name: &str, parent: Option<&str>) -> Result<String, String> {
+            let id = parent.map_or_else(|| name.to_owned(), |parent| format!("{parent}/{name}"));
+            self.folders.lock().unwrap().entry(id.clone()).or_default();
+            Ok(id)
+        }
+
+        async fn upload_file_with_progress(
+            &self,
+            local_path: &Path,
+            folder_id: &str,
+            file_name: &str,
+            on_progress: ProgressCallback<'_>,
+        ) -> Result<(), String> {
+            let data = std::fs::read(local_path).map_err(|e| e.to_string())?;
+            let total = data.len() as u64;
+
+            // Report in two halves like a chunked upload would
+            on_progress(file_name, total / 2, total);
+            on_progress(file_name, total, total);
+
+            self.folders
+                .lock()
+                .unwrap()
+                .get_mut(folder_id)
+                .ok_or_else(|| format!("No such folder: {folder_id}"))?
+                .insert(file_name.to_owned(), data);
+            Ok(())
+        }
+
+        async fn share_link(&self, folder_id: &str) -> Result<String, String> {
+            Ok(format!("memory://{folder_id}"))
+        }
+
+        async fn exists(&self, folder_id: &str, file_name: &str) -> Result<bool, String> {
+            Ok(self
+                .folders
+                .lock()
+                .unwrap()
+                .get(folder_id)
+                .is_some_and(|files| files.contains_key(file_name)))
+        }
+    }
+
+    /// Exercise the behaviour every provider must share.
+    async fn assert_provider_contract<P: StorageProvider>(provider: &P) {
+        let source_dir = TempDir::new().unwrap();
+        let source = source_dir.path().join("IMG_0001.jpg");
+        std::fs::write(&source, vec![7_u8; 1000]).unwrap();
+
+        let folder_id = provider.create_folder("Wedding", None).await.unwrap();
+        let nested_id = provider
+            .create_folder("Selects", Some(&folder_id))
+            .await
+            .unwrap();
+        assert_ne!(folder_id, nested_id);
+
+        assert!(!provider.exists(&folder_id, "photo_001.jpg").await.unwrap());
+
+        let events = Mutex::new(Vec::new());
+        provider
+            .upload_file_with_progress(
+                &source,
+                &folder_id,
+                "photo_001.jpg",
+                &|name, done, total| {
+                    events.lock().unwrap().push((name.to_owned(), done, total));
+                },
+            )
+            .await
+            .unwrap();
+
+        let events = events.into_inner().unwrap();
+        assert!(!events.is_empty());
+        assert!(events
+            .iter()
+            .all(|(name, _, total)| name == "photo_001.jpg" && *total == 1000));
+        assert!(events.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert_eq!(events.last().map(|(_, done, _)| *done), Some(1000));
+
+        assert!(provider.exists(&folder_id, "photo_001.jpg").await.unwrap());
+        assert!(!provider.exists(&nested_id, "photo_001.jpg").await.unwrap());
+        assert!(!provider.share_link(&folder_id).await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_memory_storage_contract() {
+        assert_provider_contract(&MemoryStorage::default()).await;
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_contract() {
+        let root = TempDir::new().unwrap();
+        assert_provider_contract(&LocalStorage::new(root.path())).await;
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_writes_under_root() {
+        let root = TempDir::new().unwrap();
+        let source_dir = TempDir::new().unwrap();
+        let source = source_dir.path().join("clip.mp4");
+        std::fs::write(&source, b"video bytes").unwrap();
+
+        let storage = LocalStorage::new(root.path());
+        let folder_id = storage.create_folder("Delivery", None).await.unwrap();
+        storage
+            .upload_file_with_progress(&source, &folder_id, "final.mp4", &|_, _, _| {})
+            .await
+            .unwrap();
+
+        assert_eq!(
+            std::fs::read(root.path().join("Delivery").join("final.mp4")).unwrap(),
+            b"video bytes"
+        );
+        assert_eq!(storage.share_link(&folder_id).await.unwrap(), folder_id);
+    }
+
+    #[tokio::test]
+    async fn test_local_storage_upload_missing_source() {
+        let root = TempDir::new().unwrap();
+        let storage = LocalStorage::new(root.path());
+        let folder_id = storage.create_folder("Delivery", None).await.unwrap();
+
+        let result = storage
+            .upload_file_with_progress(
+                &root.path().join("missing.jpg"),
+                &folder_id,
+                "missing.jpg",
+                &|_, _, _| {},
+            )
+            .await;
+        assert!(result.is_err());
+    }
+}
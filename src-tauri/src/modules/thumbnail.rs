@@ -0,0 +1,319 @@
+//! Thumbnail generation for project and SD card browsing.
+//!
+//! Decodes common image formats (falling back to the embedded EXIF preview for
+//! RAW files), applies the EXIF orientation, scales the long edge down to the
+//! requested size and caches the JPEG result under `~/CreatorOps/.thumbcache`,
+//! keyed by source path and modification time.
+
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use image::codecs::jpeg::JpegEncoder;
+use image::DynamicImage;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::modules::file_utils::get_home_dir;
+use crate::modules::metadata::{read_embedded_preview, read_exif_data};
+
+/// JPEG quality used for cached thumbnails.
+const THUMBNAIL_JPEG_QUALITY: u8 = 80;
+
+/// Generate a JPEG thumbnail for an image, returned base64-encoded for the frontend.
+#[tauri::command]
+pub async fn generate_thumbnail(path: String, max_edge: u32) -> Result<String, String> {
+    let cache_dir = get_thumbnail_cache_dir()?;
+    let bytes = tokio::task::spawn_blocking(move || {
+        generate_thumbnail_cached(Path::new(&path), max_edge, &cache_dir)
+    })
+    .await
+    .map_err(|e| format!("Thumbnail task failed: {e}"))??;
+
+    Ok(STANDARD.encode(bytes))
+}
+
+/// Directory holding cached thumbnails.
+fn get_thumbnail_cache_dir() -> Result<PathBuf, String> {
+    Ok(get_home_dir()?.join("CreatorOps").join(".thumbcache"))
+}
+
+/// Return the cached thumbnail for `path`, rendering and caching it on a miss.
+pub fn generate_thumbnail_cached(
+    path: &Path,
+    max_edge: u32,
+    cache_dir: &Path,
+) -> Result<Vec<u8>, String> {
+    if max_edge == 0 {
+        return Err("max_edge must be greater than zero".to_owned());
+    }
+
+    let metadata =
+        fs::metadata(path).map_err(|e| format!("Failed to read {}: {e}", path.display()))?;
+    let modified = metadata
+        .modified()
+        .ok()
+        .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+        .map_or(0, |d| d.as_nanos());
+
+    let cache_path = cache_dir.join(format!(
+        "{}.jpg",
+        cache_key(&path.to_string_lossy(), modified, max_edge)
+    ));
+    if let Ok(cached) = fs::read(&cache_path) {
+        return Ok(cached);
+    }
+
+    let bytes = render_thumbnail(path, max_edge)?;
+
+    // Caching is best-effort; a failed write only costs a re-render next time
+    if let Err(e) = fs::create_dir_all(cache_dir).and_then(|()| fs::write(&cache_path, &bytes)) {
+        log::warn!("Failed to cache thumbnail for {}: {e}", path.display());
+    }
+
+    Ok(bytes)
+}
+
+/// Hex SHA-256 over the inputs that determine a thumbnail's content.
+fn cache_key(path: &str, modified_nanos: u128, max_edge: u32) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(path.as_bytes());
+    hasher.update(modified_nanos.to_le_bytes());
+    hasher.update(max_edge.to_le_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .fold(String::with_capacity(64), |mut s, b| {
+            use std::fmt::Write as _;
+            write!(s, "{b:02x}").ok();
+            s
+        })
+}
+
+/// Decode, orient, scale and JPEG-encode an image.
+fn render_thumbnail(path: &Path, max_edge: u32) -> Result<Vec<u8>, String> {
+    encode_thumbnail_jpeg(&load_thumbnail_image(path, max_edge)?)
+}
+
+/// Decode and orient an image, scaling its long edge down to `max_edge`.
+pub fn load_thumbnail_image(path: &Path, max_edge: u32) -> Result<DynamicImage, String> {
+    let image = decode_image(path)?;
+    let image = apply_exif_orientation(image, read_exif_data(path).orientation);
+
+    Ok(if image.width().max(image.height()) > max_edge {
+        image.thumbnail(max_edge, max_edge)
+    } else {
+        image
+    })
+}
+
+/// Encode an image as an RGB JPEG at thumbnail quality.
+pub fn encode_thumbnail_jpeg(image: &DynamicImage) -> Result<Vec<u8>, String> {
+    encode_jpeg(image, THUMBNAIL_JPEG_QUALITY)
+}
+
+/// Encode an image as an RGB JPEG at the given quality (1-100).
+pub fn encode_jpeg(image: &DynamicImage, quality: u8) -> Result<Vec<u8>, String> {
+    let mut bytes = Vec::new();
+    JpegEncoder::new_with_quality(&mut bytes, quality)
+        .encode_image(&DynamicImage::ImageRgb8(image.to_rgb8()))
+        .map_err(|e| format!("Failed to encode JPEG: {e}"))?;
+
+    Ok(bytes)
+}
+
+/// Decode an image file, falling back to its embedded preview (RAW formats).
+fn decode_image(path: &Path) -> Result<DynamicImage, String> {
+    let decoded = image::ImageReader::open(path)
+        .map_err(|e| format!("Failed to open {}: {e}", path.display()))?
+        .with_guessed_format()
+        .map_err(|e| format!("Failed to read {}: {e}", path.display()))?
+        .decode();
+
+    match decoded {
+        Ok(image) => Ok(image),
+        Err(e) => read_embedded_preview(path)
+            .and_then(|preview| image::load_from_memory(&preview).ok())
+            .ok_or_else(|| format!("Failed to decode {}: {e}", path.display())),
+    }
+}
+
+/// Rotate and mirror the decoded pixels so the thumbnail displays upright.
+///
+/// Covers all eight EXIF `Orientation` values; 1, missing and unknown values
+/// leave the image as decoded.
+fn apply_exif_orientation(image: DynamicImage, orientation: Option<u32>) -> DynamicImage {
+    match orientation {
+        Some(2) => image.fliph(),
+        Some(3) => image.rotate180(),
+        Some(4) => image.flipv(),
+        // Transpose: mirrored across the top-left to bottom-right diagonal
+        Some(5) => image.rotate90().fliph(),
+        Some(6) => image.rotate90(),
+        // Transverse: mirrored across the top-right to bottom-left diagonal
+        Some(7) => image.rotate270().fliph(),
+        Some(8) => image.rotate270(),
+        _ => image,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use image::{ImageFormat, RgbImage};
+    use tempfile::TempDir;
+
+    fn write_test_image(path: &Path, width: u32, height: u32) {
+        RgbImage::from_fn(width, height, |x, y| {
+            image::Rgb([(x % 256) as u8, (y % 256) as u8, 128])
+        })
+        .save(path)
+        .unwrap();
+    }
+
+    #[test]
+    fn test_generate_thumbnail_scales_long_edge() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("landscape.png");
+        write_test_image(&source, 400, 200);
+
+        let bytes =
+            generate_thumbnail_cached(&source, 100, &temp_dir.path().join(".thumbcache")).unwrap();
+
+        let thumb = image::load_from_memory_with_format(&bytes, ImageFormat::Jpeg).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (100, 50));
+    }
+
+    #[test]
+    fn test_generate_thumbnail_does_not_upscale() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("small.png");
+        write_test_image(&source, 40, 30);
+
+        let bytes =
+            generate_thumbnail_cached(&source, 256, &temp_dir.path().join(".thumbcache")).unwrap();
+
+        let thumb = image::load_from_memory(&bytes).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (40, 30));
+    }
+
+    #[test]
+    fn test_generate_thumbnail_cache_hit() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path().join(".thumbcache");
+        let source = temp_dir.path().join("photo.png");
+        write_test_image(&source, 300, 300);
+
+        let first = generate_thumbnail_cached(&source, 64, &cache_dir).unwrap();
+        let cached: Vec<_> = fs::read_dir(&cache_dir).unwrap().flatten().collect();
+        assert_eq!(cached.len(), 1);
+
+        // Overwrite the cache entry so a hit is distinguishable from a re-render
+        fs::write(cached[0].path(), b"cached").unwrap();
+        let second = generate_thumbnail_cached(&source, 64, &cache_dir).unwrap();
+        assert_eq!(second, b"cached");
+        assert_ne!(first, second);
+
+        // A different size is a different cache entry
+        generate_thumbnail_cached(&source, 32, &cache_dir).unwrap();
+        assert_eq!(fs::read_dir(&cache_dir).unwrap().count(), 2);
+    }
+
+    #[test]
+    fn test_generate_thumbnail_invalid_input() {
+        let temp_dir = TempDir::new().unwrap();
+        let cache_dir = temp_dir.path().join(".thumbcache");
+        let not_image = temp_dir.path().join("notes.jpg");
+        fs::write(&not_image, b"not an image").unwrap();
+
+        assert!(generate_thumbnail_cached(&not_image, 64, &cache_dir).is_err());
+        assert!(generate_thumbnail_cached(Path::new("/nonexistent.jpg"), 64, &cache_dir).is_err());
+        assert_eq!(
+            generate_thumbnail_cached(&not_image, 0, &cache_dir).unwrap_err(),
+            "max_edge must be greater than zero"
+        );
+    }
+
+    #[test]
+    fn test_apply_exif_orientation_rotations() {
+        let image = DynamicImage::ImageRgb8(RgbImage::new(40, 20));
+
+        let rotated = apply_exif_orientation(image.clone(), Some(6));
+        assert_eq!((rotated.width(), rotated.height()), (20, 40));
+        let rotated = apply_exif_orientation(image.clone(), Some(8));
+        assert_eq!((rotated.width(), rotated.height()), (20, 40));
+        let rotated = apply_exif_orientation(image.clone(), Some(3));
+        assert_eq!((rotated.width(), rotated.height()), (40, 20));
+        let unchanged = apply_exif_orientation(image, None);
+        assert_eq!((unchanged.width(), unchanged.height()), (40, 20));
+    }
+
+    #[test]
+    fn test_apply_exif_orientation_all_values() {
+        // 3x2 image whose top-left pixel is marked; each orientation must move it
+        // to where that corner ends up when the photo is displayed upright
+        let mut pixels = RgbImage::new(3, 2);
+        pixels.put_pixel(0, 0, image::Rgb([255, 0, 0]));
+        let image = DynamicImage::ImageRgb8(pixels);
+        let expected = [
+            (1, (3, 2), (0, 0)),
+            (2, (3, 2), (2, 0)),
+            (3, (3, 2), (2, 1)),
+            (4, (3, 2), (0, 1)),
+            (5, (2, 3), (0, 0)),
+            (6, (2, 3), (1, 0)),
+            (7, (2, 3), (1, 2)),
+            (8, (2, 3), (0, 2)),
+        ];
+
+        for (orientation, size, (x, y)) in expected {
+            let oriented = apply_exif_orientation(image.clone(), Some(orientation)).to_rgb8();
+            assert_eq!(oriented.dimensions(), size, "orientation {orientation}");
+            assert_eq!(
+                oriented.get_pixel(x, y),
+                &image::Rgb([255, 0, 0]),
+                "orientation {orientation}"
+            );
+        }
+    }
+
+    /// Write a `width` x `height` JPEG tagged with EXIF `orientation`.
+    fn write_oriented_jpeg(path: &Path, width: u32, height: u32, orientation: u16) {
+        use exif::experimental::Writer;
+        use exif::{Field, In, Tag, Value};
+
+        let jpeg = encode_jpeg(&DynamicImage::ImageRgb8(RgbImage::new(width, height)), 90).unwrap();
+        let mut writer = Writer::new();
+        let field = Field {
+            tag: Tag::Orientation,
+            ifd_num: In::PRIMARY,
+            value: Value::Short(vec![orientation]),
+        };
+        writer.push_field(&field);
+        let mut tiff = std::io::Cursor::new(Vec::new());
+        writer.write(&mut tiff, false).unwrap();
+        let tiff = tiff.into_inner();
+
+        // SOI, then the APP1 Exif segment, then the rest of the encoded JPEG
+        let mut tagged = jpeg[..2].to_vec();
+        tagged.extend_from_slice(&[0xFF, 0xE1]);
+        tagged.extend_from_slice(&u16::try_from(tiff.len() + 8).unwrap().to_be_bytes());
+        tagged.extend_from_slice(b"Exif\0\0");
+        tagged.extend_from_slice(&tiff);
+        tagged.extend_from_slice(&jpeg[2..]);
+        fs::write(path, tagged).unwrap();
+    }
+
+    #[test]
+    fn test_generate_thumbnail_rotates_tagged_portrait() {
+        let temp_dir = TempDir::new().unwrap();
+        let source = temp_dir.path().join("portrait.jpg");
+        // Stored sideways, as cameras that only set the orientation flag do
+        write_oriented_jpeg(&source, 400, 200, 6);
+
+        let bytes =
+            generate_thumbnail_cached(&source, 100, &temp_dir.path().join(".thumbcache")).unwrap();
+
+        let thumb = image::load_from_memory_with_format(&bytes, ImageFormat::Jpeg).unwrap();
+        assert_eq!((thumb.width(), thumb.height()), (50, 100));
+    }
+}
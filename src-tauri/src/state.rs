@@ -5,12 +5,15 @@
 
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 use tokio::sync::Mutex;
 use tokio_util::sync::CancellationToken;
 
-use crate::modules::archive::ArchiveJob;
-use crate::modules::backup::BackupJob;
-use crate::modules::delivery::DeliveryJob;
+use crate::modules::archive::{ArchiveJob, ArchiveStatus};
+use crate::modules::backup::{BackupJob, BackupStatus};
+use crate::modules::delivery::{DeliveryJob, DeliveryStatus};
+use crate::modules::file_utils::get_timestamp;
+use crate::modules::google_drive::{DriveUploadJob, DriveUploadStatus, PauseGate};
 
 /// Type alias for backup job queue
 pub type BackupQueue = Arc<Mutex<HashMap<String, BackupJob>>>;
@@ -21,31 +24,149 @@ pub type DeliveryQueue = Arc<Mutex<HashMap<String, DeliveryJob>>>;
 /// Type alias for archive job queue
 pub type ArchiveQueue = Arc<Mutex<HashMap<String, ArchiveJob>>>;
 
+/// Type alias for Google Drive upload job queue
+pub type DriveUploadQueue = Arc<Mutex<HashMap<String, DriveUploadJob>>>;
+
+/// Type alias for running backup cancellation tokens
+pub type BackupTokens = Arc<Mutex<HashMap<String, CancellationToken>>>;
+
+/// Type alias for running delivery cancellation tokens
+pub type DeliveryTokens = Arc<Mutex<HashMap<String, CancellationToken>>>;
+
 /// Type alias for import cancellation tokens
 pub type ImportTokens = Arc<Mutex<HashMap<String, CancellationToken>>>;
 
+/// Type alias for Google Drive upload cancellation tokens
+pub type DriveUploadTokens = Arc<Mutex<HashMap<String, CancellationToken>>>;
+
+/// Type alias for Google Drive upload pause switches
+pub type DriveUploadPauses = Arc<Mutex<HashMap<String, PauseGate>>>;
+
+/// Type alias for archive cancellation tokens
+pub type ArchiveTokens = Arc<Mutex<HashMap<String, CancellationToken>>>;
+
+/// How often [`AppState::cancel_all_jobs`] checks whether tasks have stopped.
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
 /// Centralized application state managed by Tauri
 pub struct AppState {
     /// Backup job queue
     pub backup_queue: BackupQueue,
 
+    /// Running backup cancellation tokens
+    pub backup_tokens: BackupTokens,
+
     /// Delivery job queue
     pub delivery_queue: DeliveryQueue,
 
+    /// Running delivery cancellation tokens
+    pub delivery_tokens: DeliveryTokens,
+
     /// Archive job queue
     pub archive_queue: ArchiveQueue,
 
+    /// Archive cancellation tokens
+    pub archive_tokens: ArchiveTokens,
+
     /// Import operation cancellation tokens
     pub import_tokens: ImportTokens,
+
+    /// Google Drive upload job queue
+    pub drive_upload_queue: DriveUploadQueue,
+
+    /// Google Drive upload cancellation tokens
+    pub drive_upload_tokens: DriveUploadTokens,
+
+    /// Google Drive upload pause switches
+    pub drive_upload_pauses: DriveUploadPauses,
 }
 
 impl Default for AppState {
     fn default() -> Self {
         Self {
             backup_queue: Arc::new(Mutex::new(HashMap::new())),
+            backup_tokens: Arc::new(Mutex::new(HashMap::new())),
             delivery_queue: Arc::new(Mutex::new(HashMap::new())),
+            delivery_tokens: Arc::new(Mutex::new(HashMap::new())),
             archive_queue: Arc::new(Mutex::new(HashMap::new())),
+            archive_tokens: Arc::new(Mutex::new(HashMap::new())),
             import_tokens: Arc::new(Mutex::new(HashMap::new())),
+            drive_upload_queue: Arc::new(Mutex::new(HashMap::new())),
+            drive_upload_tokens: Arc::new(Mutex::new(HashMap::new())),
+            drive_upload_pauses: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+}
+
+impl AppState {
+    /// Cancel every registered job token, give the tasks up to `grace` to stop,
+    /// and mark jobs still in progress as cancelled.
+    ///
+    /// Tasks remove their own token when they finish, so the wait ends as soon as
+    /// every token map is empty. Returns the number of tokens cancelled.
+    pub async fn cancel_all_jobs(&self, grace: Duration) -> usize {
+        let token_maps = [
+            &self.backup_tokens,
+            &self.delivery_tokens,
+            &self.archive_tokens,
+            &self.import_tokens,
+            &self.drive_upload_tokens,
+        ];
+
+        let mut cancelled = 0;
+        for tokens in token_maps {
+            for token in tokens.lock().await.values() {
+                token.cancel();
+                cancelled += 1;
+            }
+        }
+
+        let deadline = tokio::time::Instant::now() + grace;
+        loop {
+            let mut running = 0;
+            for tokens in token_maps {
+                running += tokens.lock().await.len();
+            }
+            if running == 0 || tokio::time::Instant::now() >= deadline {
+                break;
+            }
+            tokio::time::sleep(SHUTDOWN_POLL_INTERVAL).await;
+        }
+
+        self.mark_running_jobs_cancelled().await;
+        cancelled
+    }
+
+    /// Mark every in-progress job in the queues as cancelled.
+    async fn mark_running_jobs_cancelled(&self) {
+        let now = get_timestamp();
+
+        for job in self.backup_queue.lock().await.values_mut() {
+            if job.status == BackupStatus::InProgress {
+                job.status = BackupStatus::Cancelled;
+                job.completed_at = Some(now.clone());
+            }
+        }
+        for job in self.delivery_queue.lock().await.values_mut() {
+            if job.status == DeliveryStatus::InProgress {
+                job.status = DeliveryStatus::Cancelled;
+                job.completed_at = Some(now.clone());
+            }
+        }
+        for job in self.archive_queue.lock().await.values_mut() {
+            if job.status == ArchiveStatus::InProgress {
+                job.status = ArchiveStatus::Cancelled;
+                job.completed_at = Some(now.clone());
+            }
+        }
+        for job in self.drive_upload_queue.lock().await.values_mut() {
+            if matches!(
+                job.status,
+                DriveUploadStatus::InProgress | DriveUploadStatus::Paused
+            ) {
+                job.status = DriveUploadStatus::Cancelled;
+                job.completed_at = Some(now.clone());
+            }
         }
     }
 }
@@ -77,6 +198,8 @@ mod tests {
             started_at: None,
             completed_at: None,
             error_message: None,
+            same_volume_warning: false,
+            group_id: None,
         };
 
         state
@@ -91,7 +214,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_delivery_queue_operations() {
-        use crate::modules::delivery::{DeliveryJob, DeliveryStatus};
+        use crate::modules::delivery::{DeliveryJob, DeliveryOptions, DeliveryStatus};
 
         let state = AppState::default();
         let job = DeliveryJob {
@@ -111,6 +234,10 @@ mod tests {
             completed_at: None,
             error_message: None,
             manifest_path: None,
+            output_path: None,
+            options: DeliveryOptions::default(),
+            files_skipped: 0,
+            files_unchanged: 0,
         };
 
         state
@@ -136,6 +263,8 @@ mod tests {
             archive_path: "/archives".to_owned(),
             compress: false,
             compression_format: None,
+            password: None,
+            and_remove_source: false,
             status: ArchiveStatus::Pending,
             total_files: 0,
             files_archived: 0,
@@ -157,6 +286,69 @@ mod tests {
         assert!(contains_key);
     }
 
+    #[tokio::test]
+    async fn test_backup_queue_through_state() {
+        use crate::modules::backup::{
+            cancel_backup_impl, get_backup_queue_impl, queue_backup_impl, BackupStatus,
+        };
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(temp_dir.path().join("IMG_0001.CR3"), b"raw").unwrap();
+
+        let state = AppState::default();
+        let job = queue_backup_impl(
+            &state.backup_queue,
+            "proj-1".to_owned(),
+            "Test Project".to_owned(),
+            temp_dir.path().to_string_lossy().to_string(),
+            "dest-1".to_owned(),
+            "Test Drive".to_owned(),
+            "/dest".to_owned(),
+        )
+        .await
+        .unwrap();
+        assert_eq!(job.total_files, 1);
+
+        cancel_backup_impl(&state.backup_queue, job.id.clone())
+            .await
+            .unwrap();
+
+        let jobs = get_backup_queue_impl(&state.backup_queue).await.unwrap();
+        assert_eq!(jobs.len(), 1);
+        assert_eq!(jobs[0].status, BackupStatus::Cancelled);
+
+        // A separate state shares nothing with the first
+        let other = AppState::default();
+        assert!(get_backup_queue_impl(&other.backup_queue)
+            .await
+            .unwrap()
+            .is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_running_job_tokens_operations() {
+        let state = AppState::default();
+        let backup = CancellationToken::new();
+        let delivery = CancellationToken::new();
+
+        state
+            .backup_tokens
+            .lock()
+            .await
+            .insert("backup-1".to_owned(), backup.clone());
+        state
+            .delivery_tokens
+            .lock()
+            .await
+            .insert("delivery-1".to_owned(), delivery.clone());
+
+        if let Some(token) = state.backup_tokens.lock().await.get("backup-1") {
+            token.cancel();
+        }
+        assert!(backup.is_cancelled());
+        assert!(!delivery.is_cancelled());
+    }
+
     #[tokio::test]
     async fn test_import_tokens_operations() {
         let state = AppState::default();
@@ -171,4 +363,101 @@ mod tests {
         let contains_key = state.import_tokens.lock().await.contains_key("import-1");
         assert!(contains_key);
     }
+
+    #[tokio::test]
+    async fn test_cancel_all_jobs_cancels_every_token() {
+        let state = AppState::default();
+        let token_maps = [
+            state.backup_tokens.clone(),
+            state.delivery_tokens.clone(),
+            state.archive_tokens.clone(),
+            state.import_tokens.clone(),
+            state.drive_upload_tokens.clone(),
+        ];
+
+        let mut tokens = Vec::new();
+        let mut tasks = Vec::new();
+        for (index, map) in token_maps.into_iter().enumerate() {
+            let id = format!("job-{index}");
+            let token = CancellationToken::new();
+            map.lock().await.insert(id.clone(), token.clone());
+            tokens.push(token.clone());
+
+            // Stand-in for a job task: stop once cancelled and drop its token
+            tasks.push(tokio::spawn(async move {
+                token.cancelled().await;
+                map.lock().await.remove(&id);
+            }));
+        }
+
+        let started = std::time::Instant::now();
+        let cancelled = state.cancel_all_jobs(Duration::from_secs(10)).await;
+
+        assert_eq!(cancelled, 5);
+        assert!(tokens.iter().all(CancellationToken::is_cancelled));
+        assert!(started.elapsed() < Duration::from_secs(10));
+        for task in tasks {
+            task.await.unwrap();
+        }
+        assert!(state.archive_tokens.lock().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_jobs_marks_running_jobs_cancelled() {
+        let state = AppState::default();
+        let archive_job = |id: &str, status: ArchiveStatus| ArchiveJob {
+            id: id.to_owned(),
+            project_id: "proj-1".to_owned(),
+            project_name: "Test Project".to_owned(),
+            source_path: "/source".to_owned(),
+            archive_path: "/archives".to_owned(),
+            compress: false,
+            compression_format: None,
+            password: None,
+            and_remove_source: false,
+            status,
+            total_files: 0,
+            files_archived: 0,
+            total_bytes: 0,
+            bytes_transferred: 0,
+            created_at: "2024-01-01".to_owned(),
+            started_at: None,
+            completed_at: None,
+            error_message: None,
+        };
+
+        {
+            let mut queue = state.archive_queue.lock().await;
+            queue.insert(
+                "running".to_owned(),
+                archive_job("running", ArchiveStatus::InProgress),
+            );
+            queue.insert(
+                "queued".to_owned(),
+                archive_job("queued", ArchiveStatus::Pending),
+            );
+        }
+        // A task that never acknowledges; the grace period runs out
+        let stuck = CancellationToken::new();
+        state
+            .archive_tokens
+            .lock()
+            .await
+            .insert("running".to_owned(), stuck.clone());
+
+        let cancelled = state.cancel_all_jobs(Duration::from_millis(100)).await;
+
+        assert_eq!(cancelled, 1);
+        assert!(stuck.is_cancelled());
+        let queue = state.archive_queue.lock().await.clone();
+        assert_eq!(queue["running"].status, ArchiveStatus::Cancelled);
+        assert!(queue["running"].completed_at.is_some());
+        assert_eq!(queue["queued"].status, ArchiveStatus::Pending);
+    }
+
+    #[tokio::test]
+    async fn test_cancel_all_jobs_without_jobs() {
+        let state = AppState::default();
+        assert_eq!(state.cancel_all_jobs(Duration::from_secs(10)).await, 0);
+    }
 }
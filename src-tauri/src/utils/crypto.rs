@@ -0,0 +1,60 @@
+//! AES-256-GCM helpers shared by token storage and password-protected archives
+//!
+//! Sealed data is laid out as `nonce || ciphertext`, with a fresh random nonce
+//! for every call, so the same plaintext never encrypts to the same bytes twice.
+
+/// Length of the AES-GCM nonce prepended to sealed data.
+const NONCE_LEN: usize = 12;
+
+/// Encrypt data using AES-256-GCM authenticated encryption
+pub fn encrypt_data(data: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, String> {
+    use aes_gcm::{
+        aead::{Aead, AeadCore, KeyInit, OsRng},
+        Aes256Gcm,
+    };
+
+    let cipher =
+        Aes256Gcm::new_from_slice(key).map_err(|e| format!("Failed to create cipher: {e}"))?;
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+    let ciphertext = cipher
+        .encrypt(&nonce, data)
+        .map_err(|e| format!("Failed to encrypt data: {e}"))?;
+
+    let mut result = nonce.to_vec();
+    result.extend_from_slice(&ciphertext);
+
+    Ok(result)
+}
+
+/// Decrypt data using AES-256-GCM authenticated encryption
+/// Validates authenticity and integrity before returning plaintext
+pub fn decrypt_data(encrypted: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, String> {
+    use aes_gcm::{
+        aead::{Aead, KeyInit},
+        Aes256Gcm, Nonce,
+    };
+
+    if encrypted.len() < NONCE_LEN {
+        return Err("Invalid encrypted data: too short".to_owned());
+    }
+
+    let (nonce_bytes, ciphertext) = encrypted.split_at(NONCE_LEN);
+    let nonce = Nonce::from_slice(nonce_bytes);
+
+    let cipher =
+        Aes256Gcm::new_from_slice(key).map_err(|e| format!("Failed to create cipher: {e}"))?;
+
+    cipher
+        .decrypt(nonce, ciphertext)
+        .map_err(|e| format!("Failed to decrypt data: {e}"))
+}
+
+/// Derive a 256-bit key from a user password with Argon2id
+pub fn derive_key_from_password(password: &str, salt: &[u8]) -> Result<[u8; 32], String> {
+    let mut key = [0_u8; 32];
+    argon2::Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| format!("Failed to derive key: {e}"))?;
+    Ok(key)
+}
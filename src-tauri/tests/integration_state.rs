@@ -6,7 +6,7 @@ use creatorops_lib::{
     cancel_backup_impl, cancel_import_impl, create_archive_impl, create_delivery_impl,
     error::ImportError, get_archive_queue_impl, get_backup_queue_impl, get_delivery_queue_impl,
     queue_backup_impl, remove_archive_job_impl, remove_backup_job_impl, remove_delivery_job_impl,
-    state::AppState,
+    state::AppState, ArchiveOptions, DeliveryOptions,
 };
 use tokio_util::sync::CancellationToken;
 
@@ -146,8 +146,7 @@ mod archive_integration_tests {
             "Archive Test".to_owned(),
             source.to_string_lossy().to_string(),
             archive_location.to_string_lossy().to_string(),
-            false,
-            None,
+            ArchiveOptions::default(),
         )
         .await;
 
@@ -176,8 +175,7 @@ mod archive_integration_tests {
             "Queue Test".to_owned(),
             source.to_string_lossy().to_string(),
             archive_location.to_string_lossy().to_string(),
-            false,
-            None,
+            ArchiveOptions::default(),
         )
         .await
         .unwrap();
@@ -206,8 +204,7 @@ mod archive_integration_tests {
             "Remove Test".to_owned(),
             source.to_string_lossy().to_string(),
             archive_location.to_string_lossy().to_string(),
-            false,
-            None,
+            ArchiveOptions::default(),
         )
         .await
         .unwrap();
@@ -235,6 +232,7 @@ mod delivery_integration_tests {
             vec!["file1.jpg".to_owned()],
             "/delivery".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await;
 
@@ -257,6 +255,7 @@ mod delivery_integration_tests {
             vec!["file1.jpg".to_owned()],
             "/delivery".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -281,6 +280,7 @@ mod delivery_integration_tests {
             vec!["file1.jpg".to_owned()],
             "/delivery".to_owned(),
             None,
+            DeliveryOptions::default(),
         )
         .await
         .unwrap();
@@ -31,12 +31,14 @@ export function CreateProject({ onProjectCreated, onCancel }: CreateProjectProps
 
     try {
       const project = await invoke<Project>('create_project', {
-        clientId: formData.clientId,
-        clientName: formData.clientName,
-        date: formData.date,
-        deadline: formData.deadline,
-        name: formData.name,
-        shootType: formData.shootType,
+        project: {
+          clientId: formData.clientId,
+          clientName: formData.clientName,
+          date: formData.date,
+          deadline: formData.deadline,
+          name: formData.name,
+          shootType: formData.shootType,
+        },
       })
       onProjectCreated?.(project)
     } catch (err) {
@@ -106,7 +106,7 @@ export function Delivery({ isActive }: DeliveryProps) {
             ? {
                 ...job,
                 bytesTransferred: progress.bytesTransferred,
-                filesCopied: progress.currentFile,
+                filesCopied: progress.filesCopied,
               }
             : job
         )
@@ -210,6 +210,7 @@ export function Delivery({ isActive }: DeliveryProps) {
 
       if (destination.type === 'local') {
         job = await invoke<DeliveryJob>('create_delivery', {
+          conflictMode: 'rename',
           deliveryPath: destination.path,
           namingTemplate: namingTemplate || undefined,
           projectId: selectedProject.id,
@@ -220,9 +221,9 @@ export function Delivery({ isActive }: DeliveryProps) {
       } else {
         const conflictMode = localStorage.getItem('drive_conflict_mode') || 'rename'
         job = await invoke<DeliveryJob>('upload_to_google_drive', {
-          conflictMode,
           files: [...selectedFiles],
           folderName: `${selectedProject.name}_${new Date().toISOString().split('T')[0]}`,
+          options: { conflictMode, reuseExisting: true },
           projectName: selectedProject.name,
         })
       }
@@ -444,7 +445,9 @@ export function Delivery({ isActive }: DeliveryProps) {
               className="naming-template-input"
             />
             <p className="hint">
-              Use {'{index}'} for file number, {'{name}'} for original name, {'{ext}'} for extension
+              Use {'{index}'} for file number ({'{index:4}'} to pad to 4 digits), {'{name}'} for
+              original name, {'{ext}'} for extension,
+              {'{date}'} for delivery date, {'{project}'} and {'{client}'} for project details
             </p>
           </section>
         )}
@@ -627,6 +627,7 @@ describe('import', () => {
 
   it('shows cancelled message when import is cancelled', async () => {
     const mockCopyResult: CopyResult = {
+      cancelled: true,
       error: 'Import cancelled by user',
       filesCopied: 5,
       filesSkipped: 0,
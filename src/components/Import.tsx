@@ -1,7 +1,7 @@
 import { useCallback, useEffect, useRef, useState } from 'react'
 import { invoke } from '@tauri-apps/api/core'
 import { listen } from '@tauri-apps/api/event'
-import type { CopyResult, ImportProgress, Project, SDCard } from '../types'
+import type { CopyResult, ImportProgress, Project, SDCard, SDCardFile } from '../types'
 import { ProjectStatus } from '../types'
 import { useNotification } from '../hooks/useNotification'
 import { CreateProject } from './CreateProject'
@@ -254,9 +254,10 @@ function SDCardItem({
 
     try {
       // Get all photo/video files from the SD card
-      const sourcePaths = await invoke<string[]>('list_sd_card_files', {
+      const cardFiles = await invoke<SDCardFile[]>('list_sd_card_files', {
         cardPath: card.path,
       })
+      const sourcePaths = cardFiles.map((file) => file.path)
 
       if (sourcePaths.length === 0) {
         const result = {
@@ -302,7 +303,7 @@ function SDCardItem({
       setImportResult(result)
 
       // Check if cancelled
-      const wasCancelled = result.error?.includes('cancelled') || false
+      const wasCancelled = result.cancelled ?? false
 
       // Save to history (unless cancelled)
       if (!wasCancelled) {
@@ -314,6 +315,7 @@ function SDCardItem({
           photosCopied: result.photosCopied,
           projectId: project.id,
           projectName: project.name,
+          skippedFiles: result.skipReasons,
           sourcePath: card.path,
           startedAt,
           totalBytes: result.totalBytes,
@@ -568,9 +570,10 @@ function SDCardItem({
   }
 
   if (isImporting) {
-    const percentage = importProgress
-      ? (importProgress.filesCopied / importProgress.totalFiles) * 100
+    const filesDone = importProgress
+      ? (importProgress.filesProcessed ?? importProgress.filesCopied)
       : 0
+    const percentage = importProgress ? (filesDone / importProgress.totalFiles) * 100 : 0
 
     return (
       <div className="project-list-item">
@@ -586,7 +589,7 @@ function SDCardItem({
                 <div className="progress-info">
                   <span className="progress-file">{importProgress.currentFile}</span>
                   <span className="progress-count">
-                    {importProgress.filesCopied} / {importProgress.totalFiles} files
+                    {filesDone} / {importProgress.totalFiles} files
                   </span>
                 </div>
 
@@ -606,7 +609,7 @@ function SDCardItem({
   }
 
   if (importResult) {
-    const wasCancelled = importResult.error?.includes('cancelled') || false
+    const wasCancelled = importResult.cancelled ?? false
 
     return (
       <div className="project-list-item">
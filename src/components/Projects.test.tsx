@@ -1393,7 +1393,8 @@ describe('projects', () => {
         if (cmd === 'get_project') return Promise.resolve(createMockProject())
         if (cmd === 'get_project_import_history') return Promise.resolve([])
         if (cmd === 'get_home_directory') return Promise.resolve('/Users/test')
-        if (cmd === 'list_sd_card_files') return Promise.resolve(['/Volumes/TESTSD/IMG_001.jpg'])
+        if (cmd === 'list_sd_card_files')
+          return Promise.resolve([{ path: '/Volumes/TESTSD/IMG_001.jpg' }])
         if (cmd === 'copy_files') return copyPromise
         return Promise.resolve([])
       })
@@ -1447,7 +1448,7 @@ describe('projects', () => {
         if (cmd === 'update_project_status')
           return Promise.resolve({ ...project, status: ProjectStatus.Editing })
         if (cmd === 'list_sd_card_files')
-          return Promise.resolve(['/card/IMG_001.jpg', '/card/IMG_002.jpg'])
+          return Promise.resolve([{ path: '/card/IMG_001.jpg' }, { path: '/card/IMG_002.jpg' }])
         if (cmd === 'copy_files') return Promise.resolve(copyResult)
         if (cmd === 'save_import_history') return Promise.resolve()
         return undefined
@@ -8,6 +8,7 @@ import {
   type ImportHistory,
   type Project,
   type SDCard,
+  type SDCardFile,
 } from '../types'
 import { CreateProject } from './CreateProject'
 import { useSDCardScanner } from '../hooks/useSDCardScanner'
@@ -255,8 +256,7 @@ export function Projects({ initialSelectedProjectId, isActive, onBackFromProject
     try {
       const job = await invoke<ArchiveJob>('create_archive', {
         archiveLocation,
-        compress: false,
-        compressionFormat: undefined,
+        options: { compress: false },
         projectId: project.id,
         projectName: project.name,
         sourcePath: project.folderPath,
@@ -385,9 +385,10 @@ export function Projects({ initialSelectedProjectId, isActive, onBackFromProject
     }
 
     try {
-      const sourcePaths = await invoke<string[]>('list_sd_card_files', {
+      const cardFiles = await invoke<SDCardFile[]>('list_sd_card_files', {
         cardPath: selectedSDCard.path,
       })
+      const sourcePaths = cardFiles.map((file) => file.path)
 
       if (sourcePaths.length === 0) {
         const result = createEmptyResult('No photo or video files found on SD card')
@@ -422,7 +423,7 @@ export function Projects({ initialSelectedProjectId, isActive, onBackFromProject
 
       setImportResult(result)
 
-      const wasCancelled = result.error?.includes('cancelled') ?? false
+      const wasCancelled = result.cancelled ?? false
 
       if (!wasCancelled) {
         await invoke('save_import_history', {
@@ -433,6 +434,7 @@ export function Projects({ initialSelectedProjectId, isActive, onBackFromProject
           photosCopied: result.photosCopied,
           projectId: selectedProject.id,
           projectName: selectedProject.name,
+          skippedFiles: result.skipReasons,
           sourcePath: selectedSDCard.path,
           startedAt,
           totalBytes: result.totalBytes,
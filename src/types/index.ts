@@ -25,6 +25,17 @@ interface SDCard {
   fileCount: number
   deviceType: string
   isRemovable: boolean
+  serial?: string
+}
+
+interface SDCardFile {
+  path: string
+  size: number
+  captureDate?: string
+  alreadyImported: boolean
+  pairKey: string
+  isRaw: boolean
+  isJpeg: boolean
 }
 
 interface Project {
@@ -39,6 +50,9 @@ interface Project {
   updatedAt: string
   deadline?: string
   clientId?: string
+  clientEmail?: string
+  clientPhone?: string
+  tags?: string[]
 }
 
 enum ProjectStatus {
@@ -51,6 +65,7 @@ enum ProjectStatus {
 
 interface ImportProgress {
   filesCopied: number
+  filesProcessed?: number
   totalFiles: number
   currentFile: string
 }
@@ -71,6 +86,7 @@ interface ImportHistory {
   destinationPath: string
   filesCopied: number
   filesSkipped: number
+  skippedFiles?: SkippedFile[]
   totalBytes: number
   photosCopied: number
   videosCopied: number
@@ -80,15 +96,51 @@ interface ImportHistory {
   errorMessage?: string
 }
 
+interface SkippedFile {
+  fileName: string
+  reason: string
+}
+
+interface ImportStats {
+  totalImports: number
+  totalFiles: number
+  totalBytes: number
+  photosCopied: number
+  videosCopied: number
+  projects: ProjectImportStats[]
+  busiestMonth?: MonthlyImportStats
+}
+
+interface ProjectImportStats {
+  projectId: string
+  projectName: string
+  imports: number
+  files: number
+  bytes: number
+}
+
+interface MonthlyImportStats {
+  month: string
+  imports: number
+  files: number
+}
+
 interface CopyResult {
   success: boolean
+  cancelled?: boolean
   error?: string
   filesCopied: number
   filesSkipped: number
+  duplicatesSkipped?: number
+  excludedSkipped?: number
   skippedFiles: string[]
+  skipReasons?: SkippedFile[]
   totalBytes: number
   photosCopied: number
   videosCopied: number
+  destinationRoot?: string
+  photosPath?: string
+  videosPath?: string
 }
 
 interface BackupDestination {
@@ -120,6 +172,8 @@ interface BackupJob {
   startedAt?: string
   completedAt?: string
   errorMessage?: string
+  sameVolumeWarning?: boolean
+  groupId?: string
 }
 
 interface BackupProgress {
@@ -133,6 +187,19 @@ interface BackupProgress {
   eta: number
 }
 
+interface VerifyReport {
+  matched: number
+  mismatched: string[]
+  missing: string[]
+  passed: boolean
+}
+
+interface VerifyProgress {
+  fileName: string
+  currentFile: number
+  totalFiles: number
+}
+
 interface BackupHistory {
   id: string
   projectId: string
@@ -146,10 +213,43 @@ interface BackupHistory {
   completedAt: string
   status: BackupStatus
   errorMessage?: string
+  durationSecs?: number
+  avgBytesPerSec?: number
+}
+
+interface DeliveryHistory {
+  id: string
+  projectId: string
+  projectName: string
+  deliveryPath: string
+  outputPath?: string
+  manifestPath?: string
+  filesCopied: number
+  filesSkipped: number
+  totalBytes: number
+  startedAt: string
+  completedAt: string
+}
+
+interface TemplateValidation {
+  valid: boolean
+  tokens: string[]
+  errors: string[]
+  sample?: string
 }
 
 type JobStatus = 'pending' | 'inprogress' | 'completed' | 'failed'
 
+type DeliveryStatus = JobStatus | 'cancelled'
+
+interface ExportPreset {
+  maxLongEdge: number
+  quality: number
+  format: 'jpeg' | 'png'
+}
+
+type ConflictMode = 'skip' | 'rename' | 'overwrite' | 'overwrite_keep_backup'
+
 interface DeliveryJob {
   id: string
   projectId: string
@@ -157,7 +257,7 @@ interface DeliveryJob {
   selectedFiles: string[]
   deliveryPath: string
   namingTemplate?: string
-  status: JobStatus
+  status: DeliveryStatus
   totalFiles: number
   filesCopied: number
   totalBytes: number
@@ -167,10 +267,83 @@ interface DeliveryJob {
   completedAt?: string
   errorMessage?: string
   manifestPath?: string
+  packageAsZip?: boolean
+  outputPath?: string
+  preserveStructure?: boolean
+  stripExif?: boolean
+  copyright?: string
+  exportPreset?: ExportPreset
+  conflictMode?: ConflictMode
+  filesSkipped?: number
+  skipExistingIdentical?: boolean
+  filesUnchanged?: number
   shareableLink?: string
   destinationType?: 'local' | 'google-drive'
 }
 
+type DriveUploadStatus = 'in_progress' | 'paused' | 'completed' | 'failed' | 'cancelled'
+
+interface DriveUploadJob {
+  id: string
+  projectName: string
+  folderName: string
+  folderId: string
+  shareableLink: string
+  totalFiles: number
+  uploadedFiles: number
+  failedFiles: number
+  totalBytes?: number
+  bytesUploaded?: number
+  status: DriveUploadStatus
+  createdAt: string
+  completedAt?: string
+}
+
+interface QueueCounts {
+  active: number
+  pending: number
+  failed: number
+}
+
+interface QueueSummary {
+  backups: QueueCounts
+  deliveries: QueueCounts
+  uploads: QueueCounts
+  archives: QueueCounts
+  total: QueueCounts
+  isBusy: boolean
+}
+
+interface WorkloadEstimate {
+  backupBytes: number
+  deliveryBytes: number
+  uploadBytes: number
+  remainingBytes: number
+  bytesPerSecond?: number
+  etaSeconds?: number
+}
+
+interface AppConfig {
+  baseDirectory: string
+  projectsDirectory: string
+  logsDirectory: string
+  importHistoryLimit: number
+  notificationsEnabled: boolean
+  defaultConflictMode: ConflictMode
+  hasDriveAccount: boolean
+}
+
+interface DriveFolder {
+  id: string
+  name: string
+}
+
+type ShareScope =
+  | { type: 'anyone' }
+  | { type: 'domainReader'; domain: string }
+  | { type: 'userReader'; email: string }
+  | { type: 'private' }
+
 interface GoogleDriveAccount {
   id: string
   email: string
@@ -205,12 +378,18 @@ interface DeliveryProgress {
   fileName: string
   currentFile: number
   totalFiles: number
+  filesCopied: number
   bytesTransferred: number
   totalBytes: number
+  jobPercent: number
   speed: number
   eta: number
 }
 
+type ArchiveFormat = 'zip' | 'tar_gz' | 'tar_zst'
+
+type ArchiveStatus = JobStatus | 'cancelled'
+
 interface ArchiveJob {
   id: string
   projectId: string
@@ -218,8 +397,9 @@ interface ArchiveJob {
   sourcePath: string
   archivePath: string
   compress: boolean
-  compressionFormat?: 'zip' | 'tar'
-  status: JobStatus
+  compressionFormat?: ArchiveFormat
+  andRemoveSource: boolean
+  status: ArchiveStatus
   totalFiles: number
   filesArchived: number
   totalBytes: number
@@ -230,6 +410,74 @@ interface ArchiveJob {
   errorMessage?: string
 }
 
+interface ArchiveProgress {
+  jobId: string
+  fileName: string
+  currentFile: number
+  totalFiles: number
+  bytesTransferred: number
+  totalBytes: number
+  speed: number
+  eta: number
+}
+
+interface RestoreResult {
+  destination: string
+  filesRestored: number
+  bytesRestored: number
+  project?: Project
+}
+
+interface RestoreProgress {
+  archivePath: string
+  fileName: string
+  filesRestored: number
+  bytesRestored: number
+}
+
+interface ManifestReport {
+  verified: number
+  mismatched: string[]
+  missing: string[]
+  extra: string[]
+  passed: boolean
+}
+
+type SortKey = 'name' | 'size' | 'modified'
+
+interface DuplicateGroup {
+  size: number
+  checksum: string
+  paths: string[]
+}
+
+interface VolumeUsage {
+  mountPoint: string
+  paths: string[]
+  totalBytes: number
+  usedBytes: number
+  freeBytes: number
+}
+
+interface VolumeInfo {
+  name: string
+  mountPath: string
+  totalBytes: number
+  freeBytes: number
+  isRemovable: boolean
+}
+
+interface SupportedExtensions {
+  photos: string[]
+  videos: string[]
+}
+
+interface FolderSize {
+  path: string
+  totalBytes: number
+  fileCount: number
+}
+
 interface ProjectFile {
   name: string
   path: string
@@ -244,23 +492,55 @@ export type {
   ClientStatus,
   ClientWithProjects,
   SDCard,
+  SDCardFile,
   Project,
   ImportProgress,
   FileInfo,
   ImportHistory,
   CopyResult,
+  SkippedFile,
+  ImportStats,
+  ProjectImportStats,
+  MonthlyImportStats,
   BackupDestination,
   BackupStatus,
   BackupJob,
   BackupProgress,
   BackupHistory,
+  VerifyReport,
+  VerifyProgress,
   JobStatus,
+  ConflictMode,
   DeliveryJob,
+  DeliveryStatus,
+  DeliveryHistory,
+  TemplateValidation,
+  ExportPreset,
   DeliveryDestination,
   DeliveryProgress,
+  ArchiveFormat,
+  ArchiveStatus,
   ArchiveJob,
+  ArchiveProgress,
+  RestoreResult,
+  RestoreProgress,
+  ManifestReport,
   ProjectFile,
+  DuplicateGroup,
+  SortKey,
+  VolumeUsage,
+  VolumeInfo,
+  FolderSize,
+  SupportedExtensions,
   GoogleDriveAccount,
+  DriveFolder,
+  DriveUploadStatus,
+  DriveUploadJob,
+  QueueCounts,
+  QueueSummary,
+  WorkloadEstimate,
+  AppConfig,
+  ShareScope,
 }
 
 export { ProjectStatus }